@@ -0,0 +1,96 @@
+use std::sync::OnceLock;
+use std::time::Instant;
+use std::time::SystemTime;
+use std::time::UNIX_EPOCH;
+
+/// The source of "now" for everything time based, such as expiry, in milliseconds since the
+/// Unix epoch.
+pub trait Clock: Send + Sync + 'static {
+    fn now_in_millis(&self) -> u128;
+}
+
+/// Reads the system time once and advances it with a monotonic timer afterwards, so jumps of
+/// the wall clock cannot expire keys early or keep them alive longer. All instances share the
+/// same origin and therefore always agree.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now_in_millis(&self) -> u128 {
+        static ORIGIN: OnceLock<(u128, Instant)> = OnceLock::new();
+        let (epoch, instant) = ORIGIN.get_or_init(|| {
+            let epoch = SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_millis();
+            (epoch, Instant::now())
+        });
+        epoch + instant.elapsed().as_millis()
+    }
+}
+
+#[cfg(test)]
+pub mod fixture {
+    use std::sync::atomic::AtomicU64;
+    use std::sync::atomic::Ordering;
+    use std::time::Duration;
+
+    use super::Clock;
+
+    /// A clock that stands still until a test advances it.
+    #[derive(Debug)]
+    pub struct FakeClock {
+        now: AtomicU64,
+    }
+
+    impl FakeClock {
+        pub fn new(now_in_millis: u128) -> Self {
+            Self {
+                now: AtomicU64::new(now_in_millis as u64),
+            }
+        }
+
+        pub fn advance(&self, duration: Duration) {
+            self.now
+                .fetch_add(duration.as_millis() as u64, Ordering::SeqCst);
+        }
+    }
+
+    impl Default for FakeClock {
+        fn default() -> Self {
+            Self::new(1_700_000_000_000)
+        }
+    }
+
+    impl Clock for FakeClock {
+        fn now_in_millis(&self) -> u128 {
+            self.now.load(Ordering::SeqCst) as u128
+        }
+    }
+}
+
+#[cfg(test)]
+mod specs_for_system_clock {
+    use std::time::SystemTime;
+    use std::time::UNIX_EPOCH;
+
+    use super::Clock;
+    use super::SystemClock;
+
+    #[test]
+    fn sut_is_close_to_system_time_and_never_goes_backwards() {
+        // Arrange
+        let system_time = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_millis();
+
+        // Act
+        let first = SystemClock.now_in_millis();
+        let second = SystemClock.now_in_millis();
+
+        // Assert
+        assert!(first.abs_diff(system_time) < 1000);
+        assert!(second >= first);
+    }
+}
@@ -0,0 +1,34 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::cluster::Cluster;
+use crate::link::Links;
+use crate::resp::Value;
+use crate::sentinel::split_address;
+
+/// How often the other nodes are asked for their view of the cluster.
+const PERIOD: Duration = Duration::from_millis(100);
+
+/// Keeps the view of the cluster up to date by asking every other node for its own, and
+/// introduces this node to those which do not know it yet.
+pub async fn run(cluster: Arc<Cluster>) {
+    let mut links = Links::new(Duration::from_secs(1));
+    let mut interval = tokio::time::interval(PERIOD);
+    loop {
+        interval.tick().await;
+        let others = cluster.nodes().into_iter().filter(|node| !node.myself);
+        for node in others.collect::<Vec<_>>() {
+            match links.request(&node.address, &["CLUSTER", "NODES"]).await {
+                Ok(Value::BulkString(description)) => {
+                    if !cluster.heard(&node.address, &description) {
+                        let (ip, port) = split_address(cluster.address());
+                        let _ = links
+                            .request(&node.address, &["CLUSTER", "MEET", ip, port])
+                            .await;
+                    }
+                }
+                _ => cluster.unreachable(&node.address),
+            }
+        }
+    }
+}
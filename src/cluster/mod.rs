@@ -0,0 +1,594 @@
+mod gossip;
+mod slot;
+
+use std::collections::BTreeMap;
+use std::net::Ipv4Addr;
+use std::sync::Mutex;
+use std::time::SystemTime;
+use std::time::UNIX_EPOCH;
+
+use crate::replication::random_id;
+use crate::sentinel::split_address;
+
+pub use gossip::run;
+pub use slot::SLOTS;
+pub use slot::key_slot;
+pub use slot::keys_of;
+
+/// Another node of the cluster.
+#[derive(Debug)]
+struct Node {
+    address: String,
+    /// Set until the node answered, its ID being made up until then.
+    handshake: bool,
+    /// Whether the node answered the last time it was asked for its view of the cluster.
+    connected: bool,
+    /// When the node last answered, in milliseconds since the Unix epoch.
+    pong: u128,
+}
+
+struct State {
+    /// Other nodes by their ID.
+    nodes: BTreeMap<String, Node>,
+    /// The ID of the node serving each slot, if any.
+    owners: Vec<Option<String>>,
+    /// Slots served by this node whose keys are being moved to another node.
+    migrating: BTreeMap<u16, String>,
+    /// Slots served by another node whose keys are being moved to this one.
+    importing: BTreeMap<u16, String>,
+}
+
+/// How CLUSTER SETSLOT changes a slot.
+#[derive(Debug, Clone, PartialEq)]
+pub enum SlotState {
+    Importing(String),
+    Migrating(String),
+    /// Assigns the slot to the node, ending any migration of its keys.
+    Node(String),
+    Stable,
+}
+
+/// Where the command on keys of a slot is served.
+#[derive(Debug, PartialEq)]
+pub enum Route {
+    Local,
+    /// Served here for the keys still present, by the node at the address otherwise.
+    Migrating(String),
+    Moved(String),
+    Unassigned,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct NodeInfo {
+    pub id: String,
+    pub address: String,
+    pub myself: bool,
+    pub handshake: bool,
+    pub connected: bool,
+    pub pong: u128,
+    /// The ranges of slots the node serves, bounds included.
+    pub slots: Vec<(u16, u16)>,
+    /// The slots being moved from or to the node, along with the other node, for this node only.
+    pub migrating: Vec<(u16, String)>,
+    pub importing: Vec<(u16, String)>,
+}
+
+/// A node of the cluster, with the slots it serves and what it knows of the other nodes.
+pub struct Cluster {
+    id: String,
+    address: String,
+    state: Mutex<State>,
+}
+
+impl Cluster {
+    pub fn new(port: usize) -> Self {
+        Self {
+            id: random_id(),
+            // The server only listens on the loopback interface.
+            address: format!("{}:{port}", Ipv4Addr::LOCALHOST),
+            state: Mutex::new(State {
+                nodes: BTreeMap::new(),
+                owners: vec![None; usize::from(SLOTS)],
+                migrating: BTreeMap::new(),
+                importing: BTreeMap::new(),
+            }),
+        }
+    }
+
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    pub fn address(&self) -> &str {
+        &self.address
+    }
+
+    /// Starts a handshake with the node at the address, unless it is known already.
+    pub fn meet(&self, address: &str) {
+        let mut state = self.state.lock().unwrap();
+        if address == self.address || state.nodes.values().any(|node| node.address == address) {
+            return;
+        }
+        let node = Node {
+            address: address.to_string(),
+            handshake: true,
+            connected: false,
+            pong: 0,
+        };
+        state.nodes.insert(random_id(), node);
+    }
+
+    pub fn add_slots(&self, slots: &[u16]) -> Result<(), String> {
+        let mut state = self.state.lock().unwrap();
+        if let Some(slot) = slots
+            .iter()
+            .find(|slot| state.owners[usize::from(**slot)].is_some())
+        {
+            return Err(format!("ERR Slot {slot} is already busy"));
+        }
+        for slot in slots {
+            state.owners[usize::from(*slot)] = Some(self.id.clone());
+        }
+        Ok(())
+    }
+
+    pub fn del_slots(&self, slots: &[u16]) -> Result<(), String> {
+        let mut state = self.state.lock().unwrap();
+        if let Some(slot) = slots
+            .iter()
+            .find(|slot| state.owners[usize::from(**slot)].is_none())
+        {
+            return Err(format!("ERR Slot {slot} is already unassigned"));
+        }
+        for slot in slots {
+            state.owners[usize::from(*slot)] = None;
+            state.migrating.remove(slot);
+            state.importing.remove(slot);
+        }
+        Ok(())
+    }
+
+    pub fn set_slot(&self, slot: u16, slot_state: SlotState) -> Result<(), String> {
+        let mut state = self.state.lock().unwrap();
+        let unknown = |id: &str| format!("ERR I don't know about node {id}");
+        let known = |state: &State, id: &str| id == self.id || state.nodes.contains_key(id);
+        let owned = state.owners[usize::from(slot)].as_deref() == Some(&self.id);
+        match slot_state {
+            SlotState::Migrating(id) => {
+                if !owned {
+                    return Err(format!("ERR I'm not the owner of hash slot {slot}"));
+                }
+                if id == self.id || !known(&state, &id) {
+                    return Err(unknown(&id));
+                }
+                state.migrating.insert(slot, id);
+            }
+            SlotState::Importing(id) => {
+                if owned {
+                    return Err(format!("ERR I'm already the owner of hash slot {slot}"));
+                }
+                if id == self.id || !known(&state, &id) {
+                    return Err(unknown(&id));
+                }
+                state.importing.insert(slot, id);
+            }
+            SlotState::Node(id) => {
+                if !known(&state, &id) {
+                    return Err(unknown(&id));
+                }
+                state.migrating.remove(&slot);
+                state.importing.remove(&slot);
+                state.owners[usize::from(slot)] = Some(id);
+            }
+            SlotState::Stable => {
+                state.migrating.remove(&slot);
+                state.importing.remove(&slot);
+            }
+        }
+        Ok(())
+    }
+
+    /// Where commands on keys of the slot are served, those being imported being served here if
+    /// the client is `asking`.
+    pub fn route(&self, slot: u16, asking: bool) -> Route {
+        let state = self.state.lock().unwrap();
+        if asking && state.importing.contains_key(&slot) {
+            return Route::Local;
+        }
+        let address = |id: &str| {
+            state
+                .nodes
+                .get(id)
+                .map(|node| node.address.clone())
+                .unwrap_or_default()
+        };
+        match &state.owners[usize::from(slot)] {
+            Some(owner) if *owner == self.id => match state.migrating.get(&slot) {
+                Some(target) => Route::Migrating(address(target)),
+                None => Route::Local,
+            },
+            Some(owner) => Route::Moved(address(owner)),
+            None => Route::Unassigned,
+        }
+    }
+
+    /// Every node known, this one first, followed by the others by ID.
+    pub fn nodes(&self) -> Vec<NodeInfo> {
+        let state = self.state.lock().unwrap();
+        let myself = NodeInfo {
+            id: self.id.clone(),
+            address: self.address.clone(),
+            myself: true,
+            handshake: false,
+            connected: true,
+            pong: 0,
+            slots: slots_of(&state, &self.id),
+            migrating: migrations(&state.migrating),
+            importing: migrations(&state.importing),
+        };
+        let others = state.nodes.iter().map(|(id, node)| NodeInfo {
+            id: id.clone(),
+            address: node.address.clone(),
+            myself: false,
+            handshake: node.handshake,
+            connected: node.connected,
+            pong: node.pong,
+            slots: slots_of(&state, id),
+            migrating: Vec::new(),
+            importing: Vec::new(),
+        });
+        std::iter::once(myself).chain(others).collect()
+    }
+
+    /// The view of the cluster other nodes ask for, as CLUSTER NODES describes it.
+    pub fn describe(&self) -> String {
+        self.nodes().iter().map(describe).collect()
+    }
+
+    /// Learns from the view of the node at the address, which is authoritative about the slots
+    /// it serves itself, along with the other nodes it knows. Tells whether the node knows this
+    /// one.
+    pub fn heard(&self, address: &str, description: &str) -> bool {
+        let described: Vec<Described> = description.lines().filter_map(Described::parse).collect();
+        let Some(speaker) = described.iter().find(|node| node.myself) else {
+            return false;
+        };
+        if speaker.id == self.id {
+            return true;
+        }
+        {
+            let mut state = self.state.lock().unwrap();
+            let previous = state
+                .nodes
+                .iter()
+                .find(|(_, node)| node.address == address)
+                .map(|(id, _)| id.clone());
+            if let Some(previous) = previous
+                && previous != speaker.id
+            {
+                state.nodes.remove(&previous);
+                rename(&mut state, &previous, &speaker.id);
+            }
+            let node = state.nodes.entry(speaker.id.clone()).or_insert(Node {
+                address: address.to_string(),
+                handshake: false,
+                connected: true,
+                pong: 0,
+            });
+            node.address = address.to_string();
+            node.handshake = false;
+            node.connected = true;
+            node.pong = now_in_millis();
+            for (slot, owner) in state.owners.iter_mut().enumerate() {
+                let claimed = speaker
+                    .slots
+                    .iter()
+                    .any(|(start, end)| (*start..=*end).contains(&(slot as u16)));
+                let served = owner.as_deref() == Some(&speaker.id);
+                if claimed && owner.as_deref() != Some(&self.id) {
+                    *owner = Some(speaker.id.clone());
+                } else if !claimed && served {
+                    *owner = None;
+                }
+            }
+        }
+        for node in &described {
+            if !node.myself && node.id != self.id {
+                self.meet(&node.address);
+            }
+        }
+        described.iter().any(|node| node.id == self.id)
+    }
+
+    /// Records the node at the address did not answer.
+    pub fn unreachable(&self, address: &str) {
+        let mut state = self.state.lock().unwrap();
+        for node in state.nodes.values_mut() {
+            if node.address == address {
+                node.connected = false;
+            }
+        }
+    }
+}
+
+/// Points whatever referred to the node by the ID made up during the handshake to its real ID.
+fn rename(state: &mut State, previous: &str, id: &str) {
+    for owner in state.owners.iter_mut().flatten() {
+        if owner == previous {
+            *owner = id.to_string();
+        }
+    }
+    for other in state
+        .migrating
+        .values_mut()
+        .chain(state.importing.values_mut())
+    {
+        if other == previous {
+            *other = id.to_string();
+        }
+    }
+}
+
+fn slots_of(state: &State, id: &str) -> Vec<(u16, u16)> {
+    let mut ranges: Vec<(u16, u16)> = Vec::new();
+    for (slot, owner) in state.owners.iter().enumerate() {
+        if owner.as_deref() != Some(id) {
+            continue;
+        }
+        let slot = slot as u16;
+        match ranges.last_mut() {
+            Some((_, end)) if *end + 1 == slot => *end = slot,
+            _ => ranges.push((slot, slot)),
+        }
+    }
+    ranges
+}
+
+fn migrations(migrations: &BTreeMap<u16, String>) -> Vec<(u16, String)> {
+    migrations
+        .iter()
+        .map(|(slot, id)| (*slot, id.clone()))
+        .collect()
+}
+
+fn now_in_millis() -> u128 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_millis())
+        .unwrap_or_default()
+}
+
+/// A line of CLUSTER NODES:
+/// `<id> <ip:port@cport> <flags> <master> <ping-sent> <pong-recv> <config-epoch> <link-state> <slot>...`
+fn describe(node: &NodeInfo) -> String {
+    let (ip, port) = split_address(&node.address);
+    let bus_port = port.parse::<u32>().map_or(0, |port| port + 10000);
+    let flags = match (node.myself, node.handshake) {
+        (true, _) => "myself,master",
+        (false, true) => "handshake",
+        (false, false) => "master",
+    };
+    let link = if node.connected {
+        "connected"
+    } else {
+        "disconnected"
+    };
+    let mut line = format!(
+        "{} {ip}:{port}@{bus_port} {flags} - 0 {} 0 {link}",
+        node.id, node.pong
+    );
+    for (start, end) in &node.slots {
+        match start == end {
+            true => line.push_str(&format!(" {start}")),
+            false => line.push_str(&format!(" {start}-{end}")),
+        }
+    }
+    for (slot, id) in &node.migrating {
+        line.push_str(&format!(" [{slot}->-{id}]"));
+    }
+    for (slot, id) in &node.importing {
+        line.push_str(&format!(" [{slot}-<-{id}]"));
+    }
+    line.push('\n');
+    line
+}
+
+/// A node as another node described it.
+struct Described {
+    id: String,
+    address: String,
+    myself: bool,
+    slots: Vec<(u16, u16)>,
+}
+
+impl Described {
+    fn parse(line: &str) -> Option<Self> {
+        let fields: Vec<&str> = line.split_whitespace().collect();
+        let [id, address, flags, _, _, _, _, _, slots @ ..] = &fields[..] else {
+            return None;
+        };
+        let (address, _) = address.split_once('@').unwrap_or((address, ""));
+        let slots = slots
+            .iter()
+            .filter(|slot| !slot.starts_with('['))
+            .map(|slot| {
+                let (start, end) = slot.split_once('-').unwrap_or((slot, slot));
+                Some((start.parse().ok()?, end.parse().ok()?))
+            })
+            .collect::<Option<_>>()?;
+        Some(Self {
+            id: id.to_string(),
+            address: address.to_string(),
+            myself: flags.split(',').any(|flag| flag == "myself"),
+            slots,
+        })
+    }
+}
+
+#[cfg(test)]
+mod specs_for_cluster {
+    use super::Cluster;
+    use super::Route;
+    use super::SlotState;
+
+    const OTHER: &str = "127.0.0.1:7001";
+
+    /// Has the cluster hear from another node serving the slots.
+    fn hear(sut: &Cluster, id: &str, slots: &str, knows: bool) -> bool {
+        let mut description =
+            format!("{id} {OTHER}@17001 myself,master - 0 0 0 connected {slots}\n");
+        if knows {
+            description.push_str(&format!(
+                "{} {}@17000 master - 0 0 0 connected\n",
+                sut.id(),
+                sut.address()
+            ));
+        }
+        sut.heard(OTHER, &description)
+    }
+
+    #[test]
+    fn sut_serves_slots_assigned_to_it() {
+        // Arrange
+        let sut = Cluster::new(7000);
+
+        // Act
+        sut.add_slots(&[0, 1, 2, 5]).unwrap();
+        let busy = sut.add_slots(&[3, 5]);
+        sut.del_slots(&[1]).unwrap();
+        let unassigned = sut.del_slots(&[1]);
+
+        // Assert
+        assert_eq!(busy, Err("ERR Slot 5 is already busy".to_string()));
+        assert_eq!(
+            unassigned,
+            Err("ERR Slot 1 is already unassigned".to_string())
+        );
+        assert_eq!(sut.nodes()[0].slots, vec![(0, 0), (2, 2), (5, 5)]);
+        assert_eq!(sut.route(0, false), Route::Local);
+        assert_eq!(sut.route(1, false), Route::Unassigned);
+    }
+
+    #[test]
+    fn sut_learns_about_nodes_met_once_they_answer() {
+        // Arrange
+        let sut = Cluster::new(7000);
+        sut.meet(OTHER);
+        sut.meet(OTHER);
+        sut.meet(sut.address());
+
+        // Act
+        let handshake = sut.nodes();
+        let knows = hear(&sut, "other", "100-200 300", false);
+
+        // Assert
+        assert_eq!(handshake.len(), 2);
+        assert!(handshake[1].handshake);
+        assert!(!knows);
+        let nodes = sut.nodes();
+        assert_eq!(nodes.len(), 2);
+        assert_eq!(nodes[1].id, "other");
+        assert!(!nodes[1].handshake);
+        assert_eq!(nodes[1].slots, vec![(100, 200), (300, 300)]);
+        assert_eq!(sut.route(150, false), Route::Moved(OTHER.to_string()));
+    }
+
+    #[test]
+    fn sut_forgets_slots_other_nodes_no_longer_serve() {
+        // Arrange
+        let sut = Cluster::new(7000);
+        sut.add_slots(&[5]).unwrap();
+        hear(&sut, "other", "0-10", true);
+
+        // Act
+        let knows = hear(&sut, "other", "0-3", true);
+
+        // Assert
+        assert!(knows);
+        assert_eq!(sut.route(2, false), Route::Moved(OTHER.to_string()));
+        assert_eq!(sut.route(5, false), Route::Local);
+        assert_eq!(sut.route(8, false), Route::Unassigned);
+    }
+
+    #[test]
+    fn sut_routes_slots_being_migrated() {
+        // Arrange
+        let sut = Cluster::new(7000);
+        hear(&sut, "other", "10", true);
+        sut.add_slots(&[5]).unwrap();
+
+        // Act
+        sut.set_slot(5, SlotState::Migrating("other".to_string()))
+            .unwrap();
+        sut.set_slot(10, SlotState::Importing("other".to_string()))
+            .unwrap();
+
+        // Assert
+        assert_eq!(sut.route(5, false), Route::Migrating(OTHER.to_string()));
+        assert_eq!(sut.route(10, false), Route::Moved(OTHER.to_string()));
+        assert_eq!(sut.route(10, true), Route::Local);
+        let description = sut.describe();
+        let myself = description.lines().next().unwrap();
+        assert!(
+            myself.ends_with(" 5 [5->-other] [10-<-other]"),
+            "{description}"
+        );
+    }
+
+    #[test]
+    fn sut_hands_slot_over_once_migrated() {
+        // Arrange
+        let sut = Cluster::new(7000);
+        hear(&sut, "other", "", true);
+        sut.add_slots(&[5]).unwrap();
+        sut.set_slot(5, SlotState::Migrating("other".to_string()))
+            .unwrap();
+
+        // Act
+        sut.set_slot(5, SlotState::Node("other".to_string()))
+            .unwrap();
+
+        // Assert
+        assert_eq!(sut.route(5, false), Route::Moved(OTHER.to_string()));
+        assert!(!sut.describe().contains('['));
+    }
+
+    #[rstest::rstest]
+    #[case(6, SlotState::Migrating("other".to_string()), "ERR I'm not the owner of hash slot 6")]
+    #[case(6, SlotState::Importing("unknown".to_string()), "ERR I don't know about node unknown")]
+    #[case(5, SlotState::Importing("other".to_string()), "ERR I'm already the owner of hash slot 5")]
+    #[case(5, SlotState::Node("unknown".to_string()), "ERR I don't know about node unknown")]
+    fn sut_rejects_impossible_slot_states(
+        #[case] slot: u16,
+        #[case] slot_state: SlotState,
+        #[case] expected: &str,
+    ) {
+        // Arrange
+        let sut = Cluster::new(7000);
+        hear(&sut, "other", "", true);
+        sut.add_slots(&[5]).unwrap();
+
+        // Act
+        let actual = sut.set_slot(slot, slot_state);
+
+        // Assert
+        assert_eq!(actual, Err(expected.to_string()));
+    }
+
+    #[test]
+    fn sut_describes_itself_the_way_it_parses_others() {
+        // Arrange
+        let sut = Cluster::new(7000);
+        let other = Cluster::new(7001);
+        sut.add_slots(&[0, 1, 2, 9]).unwrap();
+
+        // Act
+        let knows = other.heard(sut.address(), &sut.describe());
+
+        // Assert
+        assert!(!knows);
+        let nodes = other.nodes();
+        assert_eq!(nodes[1].id, sut.id());
+        assert_eq!(nodes[1].slots, vec![(0, 2), (9, 9)]);
+    }
+}
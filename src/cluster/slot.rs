@@ -0,0 +1,133 @@
+use crate::resp::Value;
+
+/// How many hash slots the keyspace of a cluster is split into.
+pub const SLOTS: u16 = 16384;
+
+/// The slot of the key, only the part between the first `{` and the next `}` being hashed if
+/// not empty, so related keys can be kept together.
+pub fn key_slot(key: &str) -> u16 {
+    let key = key.as_bytes();
+    let hashed = key
+        .iter()
+        .position(|byte| *byte == b'{')
+        .and_then(|open| {
+            let close = key[open + 1..].iter().position(|byte| *byte == b'}')?;
+            Some(&key[open + 1..open + 1 + close])
+        })
+        .filter(|tag| !tag.is_empty())
+        .unwrap_or(key);
+    crc16(hashed) % SLOTS
+}
+
+/// CRC16-CCITT (XMODEM), as Redis hashes keys.
+fn crc16(bytes: &[u8]) -> u16 {
+    bytes.iter().fold(0, |crc, byte| {
+        (0..8).fold(crc ^ (u16::from(*byte) << 8), |crc, _| {
+            if crc & 0x8000 != 0 {
+                (crc << 1) ^ 0x1021
+            } else {
+                crc << 1
+            }
+        })
+    })
+}
+
+/// The keys the request operates on, found where each command expects them, as Redis does with
+/// its key specifications.
+pub fn keys_of(request: &Value) -> Vec<&str> {
+    let Value::Array(array) = request else {
+        return Vec::new();
+    };
+    let arguments: Vec<&str> = array
+        .iter()
+        .filter_map(|argument| match argument {
+            Value::BulkString(argument) => Some(argument.as_str()),
+            _ => None,
+        })
+        .collect();
+    let Some((name, arguments)) = arguments.split_first() else {
+        return Vec::new();
+    };
+    let keys = match name.to_uppercase().as_str() {
+        "GET" | "SET" | "TYPE" | "PERSIST" | "EXPIRE" | "PEXPIRE" | "EXPIREAT" | "PEXPIREAT"
+        | "TTL" | "PTTL" | "EXPIRETIME" | "PEXPIRETIME" | "MOVE" | "SSCAN" | "HSCAN" | "ZSCAN"
+        | "GEOADD" | "GEODIST" | "GEOPOS" | "GEOHASH" | "GEOSEARCH" | "SPUBLISH" => {
+            arguments.get(..1)
+        }
+        "COPY" | "RENAME" | "RENAMENX" | "GEOSEARCHSTORE" => arguments.get(..2),
+        "DEL" | "UNLINK" | "EXISTS" | "TOUCH" | "WATCH" | "SSUBSCRIBE" => Some(arguments),
+        // The script or function, then how many keys follow.
+        "EVAL" | "EVAL_RO" | "EVALSHA" | "EVALSHA_RO" | "FCALL" | "FCALL_RO" => arguments
+            .get(1)
+            .and_then(|count| count.parse::<usize>().ok())
+            .and_then(|count| arguments.get(2..2 + count)),
+        _ => None,
+    };
+    keys.unwrap_or_default().to_vec()
+}
+
+#[cfg(test)]
+mod specs_for_key_slot {
+    use super::key_slot;
+
+    #[rstest::rstest]
+    #[case("123456789", 12739)]
+    #[case("foo", 12182)]
+    #[case("bar", 5061)]
+    #[case("", 0)]
+    fn sut_hashes_keys_into_slots(#[case] key: &str, #[case] expected: u16) {
+        // Act
+        let actual = key_slot(key);
+
+        // Assert
+        assert_eq!(actual, expected);
+    }
+
+    #[rstest::rstest]
+    #[case("{user1000}.following", "user1000")]
+    #[case("foo{bar}{zap}", "bar")]
+    #[case("foo{{bar}}zap", "{bar")]
+    #[case("foo{}{bar}", "foo{}{bar}")]
+    #[case("foo{bar", "foo{bar")]
+    fn sut_hashes_only_tag_of_keys_having_one(#[case] key: &str, #[case] hashed: &str) {
+        // Act
+        let actual = key_slot(key);
+
+        // Assert
+        assert_eq!(actual, key_slot(hashed));
+    }
+}
+
+#[cfg(test)]
+mod specs_for_keys_of {
+    use crate::resp::Value;
+
+    use super::keys_of;
+
+    #[rstest::rstest]
+    #[case(&["GET", "foo"], &["foo"])]
+    #[case(&["set", "foo", "bar", "PX", "100"], &["foo"])]
+    #[case(&["RENAME", "foo", "bar"], &["foo", "bar"])]
+    #[case(&["DEL", "foo", "bar", "baz"], &["foo", "bar", "baz"])]
+    #[case(&["HSCAN", "foo", "0", "COUNT", "10"], &["foo"])]
+    #[case(&["EVAL", "return 1", "2", "foo", "bar", "baz"], &["foo", "bar"])]
+    #[case(&["FCALL", "f", "0", "foo"], &[])]
+    #[case(&["EVAL", "return 1", "3", "foo"], &[])]
+    #[case(&["PING"], &[])]
+    #[case(&["KEYS", "*"], &[])]
+    fn sut_finds_keys_of_requests(#[case] request: &[&str], #[case] expected: &[&str]) {
+        // Arrange
+        let request = Value::Array(
+            request
+                .iter()
+                .map(|argument| Value::BulkString(argument.to_string()))
+                .collect(),
+        );
+
+        // Act
+        let actual = keys_of(&request);
+
+        // Assert
+        assert_eq!(actual, expected);
+    }
+}
@@ -0,0 +1,106 @@
+use crate::command::executor::Command;
+use crate::command::executor::CommandExecutor;
+use crate::command::executor::CommandExecutorContext;
+use crate::command::parser::extract_array;
+use crate::command::parser::validate_array_length;
+use crate::command::parser::validate_main_command;
+use crate::resp::Value;
+
+/// Lets the command that follows use a slot this node is importing, as clients do once
+/// redirected with ASK.
+#[derive(Debug, Default, PartialEq)]
+pub struct Asking;
+
+impl Command for Asking {
+    fn parse_from(value: &Value) -> Result<Self, anyhow::Error> {
+        let array = extract_array(value)?;
+        validate_array_length(array, 1)?;
+        validate_main_command(array, "ASKING")?;
+        Ok(Asking)
+    }
+}
+
+#[async_trait::async_trait]
+impl CommandExecutor for Asking {
+    async fn execute(&self, context: &CommandExecutorContext) -> Value {
+        if context.cluster.is_none() {
+            return Value::Error("ERR This instance has cluster support disabled".to_string());
+        }
+        context.session.set_asking();
+        Value::SimpleString("OK".to_string())
+    }
+}
+
+#[cfg(test)]
+mod specs_for_parse_from {
+    use crate::command::executor::Command;
+    use crate::resp::Value;
+
+    use super::Asking;
+
+    #[rstest::rstest]
+    #[case("ASKING")]
+    #[case("asking")]
+    fn sut_parses_asking_command_with_case_insensitive(#[case] asking: &str) {
+        // Arrange
+        let value = Value::Array(vec![Value::BulkString(asking.to_string())]);
+
+        // Act
+        let actual = Asking::parse_from(&value).unwrap();
+
+        // Assert
+        assert_eq!(actual, Asking);
+    }
+}
+
+#[cfg(test)]
+mod specs_for_execute {
+    use std::sync::Arc;
+
+    use crate::clock::SystemClock;
+    use crate::command::executor::CommandExecutor;
+    use crate::command::executor::CommandExecutorContext;
+    use crate::command::executor::fixture::command_executor_context;
+    use crate::config::Config;
+    use crate::config::Server;
+    use crate::repository::fixture::DummyRepository;
+    use crate::resp::Value;
+
+    use super::Asking;
+
+    #[tokio::test]
+    async fn sut_applies_to_the_next_command_only() {
+        // Arrange
+        let config = Config {
+            server: Server {
+                cluster_enabled: true,
+                ..Server::default()
+            },
+            ..Config::default()
+        };
+        let context = command_executor_context(DummyRepository, config, Arc::new(SystemClock));
+
+        // Act
+        let actual = Asking.execute(&context).await;
+
+        // Assert
+        assert_eq!(actual, Value::SimpleString("OK".to_string()));
+        assert!(context.session.take_asking());
+        assert!(!context.session.take_asking());
+    }
+
+    #[rstest::rstest]
+    #[tokio::test]
+    async fn sut_is_disabled_outside_cluster_mode(
+        #[from(command_executor_context)] context: CommandExecutorContext,
+    ) {
+        // Act
+        let actual = Asking.execute(&context).await;
+
+        // Assert
+        assert_eq!(
+            actual,
+            Value::Error("ERR This instance has cluster support disabled".to_string())
+        );
+    }
+}
@@ -0,0 +1,138 @@
+use crate::command::executor::Command;
+use crate::command::executor::CommandExecutor;
+use crate::command::executor::CommandExecutorContext;
+use crate::command::parser::extract_array;
+use crate::command::parser::extract_bulk_string;
+use crate::command::parser::validate_array_length;
+use crate::command::parser::validate_main_command;
+use crate::command::parser::validate_sub_command;
+use crate::resp::Value;
+
+/// CLIENT CACHING YES|NO, deciding whether the keys read by the next command are tracked.
+#[derive(Debug, Default, PartialEq)]
+pub struct ClientCaching {
+    caching: bool,
+}
+
+impl Command for ClientCaching {
+    fn parse_from(value: &Value) -> Result<Self, anyhow::Error> {
+        let array = extract_array(value)?;
+        validate_array_length(array, 3)?;
+        validate_main_command(array, "CLIENT")?;
+        validate_sub_command(array, "CACHING")?;
+        let caching = match extract_bulk_string(array, 2)?.to_uppercase().as_str() {
+            "YES" => true,
+            "NO" => false,
+            _ => return Err(anyhow::anyhow!("syntax error")),
+        };
+        Ok(ClientCaching { caching })
+    }
+}
+
+#[async_trait::async_trait]
+impl CommandExecutor for ClientCaching {
+    async fn execute(&self, context: &CommandExecutorContext) -> Value {
+        let options = context
+            .tracking
+            .options(context.session.id())
+            .unwrap_or_default();
+        if !options.opt_in && !options.opt_out {
+            return Value::Error(
+                "ERR CLIENT CACHING can be called only when the client is in tracking mode with OPTIN or OPTOUT mode enabled"
+                    .to_string(),
+            );
+        }
+        if self.caching && !options.opt_in {
+            return Value::Error(
+                "ERR CLIENT CACHING YES is only valid when tracking is enabled in OPTIN mode."
+                    .to_string(),
+            );
+        }
+        if !self.caching && !options.opt_out {
+            return Value::Error(
+                "ERR CLIENT CACHING NO is only valid when tracking is enabled in OPTOUT mode."
+                    .to_string(),
+            );
+        }
+        context.session.set_caching(self.caching);
+        Value::SimpleString("OK".to_string())
+    }
+}
+
+#[cfg(test)]
+mod specs_for_parse_from {
+    use crate::command::executor::Command;
+    use crate::resp::Value;
+
+    use super::ClientCaching;
+
+    #[rstest::rstest]
+    #[case("yes", true)]
+    #[case("NO", false)]
+    fn sut_parses_client_caching_command(#[case] argument: &str, #[case] caching: bool) {
+        // Arrange
+        let value = Value::Array(vec![
+            Value::BulkString("CLIENT".to_string()),
+            Value::BulkString("caching".to_string()),
+            Value::BulkString(argument.to_string()),
+        ]);
+
+        // Act
+        let actual = ClientCaching::parse_from(&value).unwrap();
+
+        // Assert
+        assert_eq!(actual, ClientCaching { caching });
+    }
+}
+
+#[cfg(test)]
+mod specs_for_execute {
+    use crate::command::executor::CommandExecutor;
+    use crate::command::executor::CommandExecutorContext;
+    use crate::command::executor::fixture::command_executor_context;
+    use crate::resp::Value;
+    use crate::tracking::TrackingOptions;
+
+    use super::ClientCaching;
+
+    #[rstest::rstest]
+    #[case(
+        None,
+        true,
+        Some(
+            "ERR CLIENT CACHING can be called only when the client is in tracking mode with OPTIN or OPTOUT mode enabled"
+        )
+    )]
+    #[case(Some(TrackingOptions { opt_out: true, ..TrackingOptions::default() }), true,
+        Some("ERR CLIENT CACHING YES is only valid when tracking is enabled in OPTIN mode."))]
+    #[case(Some(TrackingOptions { opt_in: true, ..TrackingOptions::default() }), false,
+        Some("ERR CLIENT CACHING NO is only valid when tracking is enabled in OPTOUT mode."))]
+    #[case(Some(TrackingOptions { opt_in: true, ..TrackingOptions::default() }), true, None)]
+    #[tokio::test]
+    async fn sut_sets_caching_only_in_matching_mode(
+        #[case] options: Option<TrackingOptions>,
+        #[case] caching: bool,
+        #[case] error: Option<&str>,
+        #[from(command_executor_context)] context: CommandExecutorContext,
+    ) {
+        // Arrange
+        if let Some(options) = options {
+            context.tracking.enable(&context.session, options);
+        }
+
+        // Act
+        let actual = ClientCaching { caching }.execute(&context).await;
+
+        // Assert
+        match error {
+            Some(error) => {
+                assert_eq!(actual, Value::Error(error.to_string()));
+                assert_eq!(context.session.caching(), None);
+            }
+            None => {
+                assert_eq!(actual, Value::SimpleString("OK".to_string()));
+                assert_eq!(context.session.caching(), Some(caching));
+            }
+        }
+    }
+}
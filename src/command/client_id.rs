@@ -0,0 +1,80 @@
+use crate::command::executor::Command;
+use crate::command::executor::CommandExecutor;
+use crate::command::executor::CommandExecutorContext;
+use crate::command::parser::extract_array;
+use crate::command::parser::validate_array_length;
+use crate::command::parser::validate_main_command;
+use crate::command::parser::validate_sub_command;
+use crate::resp::Value;
+
+#[derive(Debug, Default, PartialEq)]
+pub struct ClientId;
+
+impl Command for ClientId {
+    fn parse_from(value: &Value) -> Result<Self, anyhow::Error> {
+        let array = extract_array(value)?;
+        validate_array_length(array, 2)?;
+        validate_main_command(array, "CLIENT")?;
+        validate_sub_command(array, "ID")?;
+        Ok(ClientId)
+    }
+}
+
+#[async_trait::async_trait]
+impl CommandExecutor for ClientId {
+    async fn execute(&self, context: &CommandExecutorContext) -> Value {
+        Value::Integer(context.session.id() as i64)
+    }
+}
+
+#[cfg(test)]
+mod specs_for_parse_from {
+    use crate::command::executor::Command;
+    use crate::resp::Value;
+
+    use super::ClientId;
+
+    #[rstest::rstest]
+    #[case("CLIENT", "ID")]
+    #[case("client", "id")]
+    fn sut_parses_client_id_command_with_case_insensitive(#[case] client: &str, #[case] id: &str) {
+        // Arrange
+        let value = Value::Array(vec![
+            Value::BulkString(client.to_string()),
+            Value::BulkString(id.to_string()),
+        ]);
+
+        // Act
+        let actual = ClientId::parse_from(&value).unwrap();
+
+        // Assert
+        assert_eq!(actual, ClientId);
+    }
+}
+
+#[cfg(test)]
+mod specs_for_execute {
+    use crate::command::executor::CommandExecutor;
+    use crate::command::executor::CommandExecutorContext;
+    use crate::command::executor::fixture::command_executor_context;
+    use crate::resp::Value;
+
+    use super::ClientId;
+
+    #[rstest::rstest]
+    #[tokio::test]
+    async fn sut_returns_distinct_ids_for_each_client(
+        #[from(command_executor_context)] context: CommandExecutorContext,
+    ) {
+        // Arrange
+        let other = context.new_session();
+
+        // Act
+        let first = ClientId.execute(&context).await;
+        let second = ClientId.execute(&other).await;
+
+        // Assert
+        assert_ne!(first, second);
+        assert_eq!(first, Value::Integer(context.session.id() as i64));
+    }
+}
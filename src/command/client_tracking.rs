@@ -0,0 +1,274 @@
+use crate::command::executor::Command;
+use crate::command::executor::CommandExecutor;
+use crate::command::executor::CommandExecutorContext;
+use crate::command::parser::extract_array;
+use crate::command::parser::extract_bulk_string;
+use crate::command::parser::validate_main_command;
+use crate::command::parser::validate_min_array_length;
+use crate::command::parser::validate_sub_command;
+use crate::resp::Value;
+use crate::tracking::TrackingOptions;
+
+/// CLIENT TRACKING ON|OFF with its options, which are only checked against each other once
+/// executed.
+#[derive(Debug, Default, PartialEq)]
+pub struct ClientTracking {
+    enabled: bool,
+    options: TrackingOptions,
+}
+
+impl Command for ClientTracking {
+    fn parse_from(value: &Value) -> Result<Self, anyhow::Error> {
+        let array = extract_array(value)?;
+        validate_min_array_length(array, 3)?;
+        validate_main_command(array, "CLIENT")?;
+        validate_sub_command(array, "TRACKING")?;
+        let enabled = match extract_bulk_string(array, 2)?.to_uppercase().as_str() {
+            "ON" => true,
+            "OFF" => false,
+            _ => return Err(anyhow::anyhow!("syntax error")),
+        };
+        let mut options = TrackingOptions::default();
+        let mut index = 3;
+        while index < array.len() {
+            match extract_bulk_string(array, index)?.to_uppercase().as_str() {
+                "REDIRECT" => {
+                    index += 1;
+                    let id = extract_bulk_string(array, index)?
+                        .parse()
+                        .map_err(|_| anyhow::anyhow!("value is not an integer or out of range"))?;
+                    options.redirect = Some(id);
+                }
+                "PREFIX" => {
+                    index += 1;
+                    options
+                        .prefixes
+                        .push(extract_bulk_string(array, index)?.to_string());
+                }
+                "BCAST" => options.broadcast = true,
+                "OPTIN" => options.opt_in = true,
+                "OPTOUT" => options.opt_out = true,
+                "NOLOOP" => options.no_loop = true,
+                _ => return Err(anyhow::anyhow!("syntax error")),
+            }
+            index += 1;
+        }
+        Ok(ClientTracking { enabled, options })
+    }
+}
+
+#[async_trait::async_trait]
+impl CommandExecutor for ClientTracking {
+    async fn execute(&self, context: &CommandExecutorContext) -> Value {
+        let tracking = &context.tracking;
+        let id = context.session.id();
+        if !self.enabled {
+            tracking.disable(id);
+            return Value::SimpleString("OK".to_string());
+        }
+
+        let options = &self.options;
+        if options.opt_in && options.opt_out {
+            return Value::Error("ERR You can't use both OPTIN and OPTOUT".to_string());
+        }
+        if options.broadcast && (options.opt_in || options.opt_out) {
+            return Value::Error("ERR OPTIN and OPTOUT are not compatible with BCAST".to_string());
+        }
+        if !options.broadcast && !options.prefixes.is_empty() {
+            return Value::Error("ERR PREFIX option requires BCAST mode to be enabled".to_string());
+        }
+        if let Some(redirect) = options.redirect
+            && !tracking.exists(redirect)
+        {
+            return Value::Error(
+                "ERR The client ID you want redirect to does not exist".to_string(),
+            );
+        }
+
+        let current = tracking.options(id);
+        if let Some(current) = &current
+            && current.broadcast != options.broadcast
+        {
+            return Value::Error(
+                "ERR You can't switch BCAST mode on/off before disabling tracking for this client, and then re-enabling it with a different mode."
+                    .to_string(),
+            );
+        }
+        let mut prefixes = current.map(|current| current.prefixes).unwrap_or_default();
+        for prefix in &options.prefixes {
+            if prefixes.contains(prefix) {
+                continue;
+            }
+            if let Some(other) = prefixes
+                .iter()
+                .find(|other| prefix.starts_with(other.as_str()) || other.starts_with(prefix))
+            {
+                return Value::Error(format!(
+                    "ERR Prefix '{prefix}' overlaps with an existing prefix '{other}'. Prefixes for a single client must not overlap."
+                ));
+            }
+            prefixes.push(prefix.clone());
+        }
+
+        let options = TrackingOptions {
+            prefixes,
+            ..options.clone()
+        };
+        tracking.enable(&context.session, options);
+        Value::SimpleString("OK".to_string())
+    }
+}
+
+#[cfg(test)]
+mod specs_for_parse_from {
+    use crate::command::executor::Command;
+    use crate::resp::Value;
+    use crate::tracking::TrackingOptions;
+
+    use super::ClientTracking;
+
+    fn array(arguments: &[&str]) -> Value {
+        Value::Array(
+            arguments
+                .iter()
+                .map(|argument| Value::BulkString(argument.to_string()))
+                .collect(),
+        )
+    }
+
+    #[rstest::rstest]
+    #[case(&["CLIENT", "TRACKING", "off"], ClientTracking::default())]
+    #[case(&["client", "tracking", "ON"], ClientTracking {
+        enabled: true,
+        options: TrackingOptions::default(),
+    })]
+    #[case(&["CLIENT", "TRACKING", "ON", "REDIRECT", "7", "NOLOOP"], ClientTracking {
+        enabled: true,
+        options: TrackingOptions { redirect: Some(7), no_loop: true, ..TrackingOptions::default() },
+    })]
+    #[case(&["CLIENT", "TRACKING", "ON", "bcast", "PREFIX", "a:", "PREFIX", "b:"], ClientTracking {
+        enabled: true,
+        options: TrackingOptions {
+            broadcast: true,
+            prefixes: vec!["a:".to_string(), "b:".to_string()],
+            ..TrackingOptions::default()
+        },
+    })]
+    #[case(&["CLIENT", "TRACKING", "ON", "OPTIN"], ClientTracking {
+        enabled: true,
+        options: TrackingOptions { opt_in: true, ..TrackingOptions::default() },
+    })]
+    fn sut_parses_client_tracking_options(
+        #[case] arguments: &[&str],
+        #[case] expected: ClientTracking,
+    ) {
+        // Act
+        let actual = ClientTracking::parse_from(&array(arguments)).unwrap();
+
+        // Assert
+        assert_eq!(actual, expected);
+    }
+
+    #[rstest::rstest]
+    #[case(&["CLIENT", "TRACKING"])]
+    #[case(&["CLIENT", "TRACKING", "MAYBE"])]
+    #[case(&["CLIENT", "TRACKING", "ON", "REDIRECT"])]
+    #[case(&["CLIENT", "TRACKING", "ON", "REDIRECT", "me"])]
+    #[case(&["CLIENT", "TRACKING", "ON", "FAST"])]
+    fn sut_rejects_malformed_client_tracking(#[case] arguments: &[&str]) {
+        // Act
+        let actual = ClientTracking::parse_from(&array(arguments));
+
+        // Assert
+        assert!(actual.is_err());
+    }
+}
+
+#[cfg(test)]
+mod specs_for_execute {
+    use crate::command::executor::CommandExecutor;
+    use crate::command::executor::CommandExecutorContext;
+    use crate::command::executor::fixture::command_executor_context;
+    use crate::resp::Value;
+    use crate::tracking::TrackingOptions;
+
+    use super::ClientTracking;
+
+    fn on(options: TrackingOptions) -> ClientTracking {
+        ClientTracking {
+            enabled: true,
+            options,
+        }
+    }
+
+    #[rstest::rstest]
+    #[case(TrackingOptions { opt_in: true, opt_out: true, ..TrackingOptions::default() },
+        "ERR You can't use both OPTIN and OPTOUT")]
+    #[case(TrackingOptions { broadcast: true, opt_in: true, ..TrackingOptions::default() },
+        "ERR OPTIN and OPTOUT are not compatible with BCAST")]
+    #[case(TrackingOptions { prefixes: vec!["a".to_string()], ..TrackingOptions::default() },
+        "ERR PREFIX option requires BCAST mode to be enabled")]
+    #[case(TrackingOptions { redirect: Some(u64::MAX), ..TrackingOptions::default() },
+        "ERR The client ID you want redirect to does not exist")]
+    #[case(TrackingOptions {
+        broadcast: true,
+        prefixes: vec!["user".to_string(), "user:".to_string()],
+        ..TrackingOptions::default()
+    }, "ERR Prefix 'user:' overlaps with an existing prefix 'user'. Prefixes for a single client must not overlap.")]
+    #[tokio::test]
+    async fn sut_rejects_incompatible_options(
+        #[case] options: TrackingOptions,
+        #[case] expected: &str,
+        #[from(command_executor_context)] context: CommandExecutorContext,
+    ) {
+        // Act
+        let actual = on(options).execute(&context).await;
+
+        // Assert
+        assert_eq!(actual, Value::Error(expected.to_string()));
+        assert_eq!(context.tracking.options(context.session.id()), None);
+    }
+
+    #[rstest::rstest]
+    #[tokio::test]
+    async fn sut_refuses_to_switch_broadcast_mode_while_enabled(
+        #[from(command_executor_context)] context: CommandExecutorContext,
+    ) {
+        // Arrange
+        on(TrackingOptions::default()).execute(&context).await;
+        let broadcast = TrackingOptions {
+            broadcast: true,
+            ..TrackingOptions::default()
+        };
+
+        // Act
+        let actual = on(broadcast).execute(&context).await;
+
+        // Assert
+        assert!(matches!(actual, Value::Error(e) if e.starts_with("ERR You can't switch BCAST")));
+    }
+
+    #[rstest::rstest]
+    #[tokio::test]
+    async fn sut_enables_and_disables_tracking(
+        #[from(command_executor_context)] context: CommandExecutorContext,
+    ) {
+        // Arrange
+        let target = context.new_session();
+        let options = TrackingOptions {
+            redirect: Some(target.session.id()),
+            ..TrackingOptions::default()
+        };
+
+        // Act
+        let enabled = on(options.clone()).execute(&context).await;
+        let tracked = context.tracking.options(context.session.id());
+        let disabled = ClientTracking::default().execute(&context).await;
+
+        // Assert
+        assert_eq!(enabled, Value::SimpleString("OK".to_string()));
+        assert_eq!(tracked, Some(options));
+        assert_eq!(disabled, Value::SimpleString("OK".to_string()));
+        assert_eq!(context.tracking.options(context.session.id()), None);
+    }
+}
@@ -0,0 +1,471 @@
+use crate::cluster::NodeInfo;
+use crate::cluster::SLOTS;
+use crate::cluster::SlotState;
+use crate::cluster::key_slot;
+use crate::command::executor::Command;
+use crate::command::executor::CommandExecutor;
+use crate::command::executor::CommandExecutorContext;
+use crate::command::parser::extract_array;
+use crate::command::parser::extract_bulk_string;
+use crate::command::parser::validate_main_command;
+use crate::command::parser::validate_min_array_length;
+use crate::resp::Value;
+use crate::sentinel::split_address;
+
+/// CLUSTER, describing the nodes of the cluster and the slots they serve, and changing those
+/// served by this node.
+#[derive(Debug, PartialEq)]
+pub enum ClusterCommand {
+    Info,
+    MyId,
+    Nodes,
+    Slots,
+    Shards,
+    Meet(String),
+    AddSlots(Vec<u16>),
+    DelSlots(Vec<u16>),
+    SetSlot(u16, SlotState),
+    KeySlot(String),
+    CountKeysInSlot(u16),
+    GetKeysInSlot(u16, usize),
+}
+
+fn slot(argument: &str) -> Result<u16, anyhow::Error> {
+    argument
+        .parse()
+        .ok()
+        .filter(|slot| *slot < SLOTS)
+        .ok_or_else(|| anyhow::anyhow!("Invalid or out of range slot"))
+}
+
+fn slots(arguments: &[&str]) -> Result<Vec<u16>, anyhow::Error> {
+    arguments.iter().map(|argument| slot(argument)).collect()
+}
+
+/// The slots of the ranges given as pairs of bounds, both included.
+fn slot_ranges(arguments: &[&str]) -> Result<Vec<u16>, anyhow::Error> {
+    if !arguments.len().is_multiple_of(2) {
+        anyhow::bail!("wrong number of arguments");
+    }
+    let mut slots = Vec::new();
+    for range in arguments.chunks(2) {
+        let (start, end) = (slot(range[0])?, slot(range[1])?);
+        if start > end {
+            anyhow::bail!("start slot number {start} is greater than end slot number {end}");
+        }
+        slots.extend(start..=end);
+    }
+    Ok(slots)
+}
+
+impl Command for ClusterCommand {
+    fn parse_from(value: &Value) -> Result<Self, anyhow::Error> {
+        let array = extract_array(value)?;
+        validate_min_array_length(array, 2)?;
+        validate_main_command(array, "CLUSTER")?;
+        let arguments = (1..array.len())
+            .map(|index| extract_bulk_string(array, index))
+            .collect::<Result<Vec<_>, _>>()?;
+        let subcommand = arguments[0].to_uppercase();
+        match (subcommand.as_str(), &arguments[1..]) {
+            ("INFO", []) => Ok(ClusterCommand::Info),
+            ("MYID", []) => Ok(ClusterCommand::MyId),
+            ("NODES", []) => Ok(ClusterCommand::Nodes),
+            ("SLOTS", []) => Ok(ClusterCommand::Slots),
+            ("SHARDS", []) => Ok(ClusterCommand::Shards),
+            ("MEET", [ip, port]) => {
+                let port: u16 = port.parse()?;
+                Ok(ClusterCommand::Meet(format!("{ip}:{port}")))
+            }
+            ("ADDSLOTS", [_, ..]) => Ok(ClusterCommand::AddSlots(slots(&arguments[1..])?)),
+            ("DELSLOTS", [_, ..]) => Ok(ClusterCommand::DelSlots(slots(&arguments[1..])?)),
+            ("ADDSLOTSRANGE", [_, ..]) => {
+                Ok(ClusterCommand::AddSlots(slot_ranges(&arguments[1..])?))
+            }
+            ("DELSLOTSRANGE", [_, ..]) => {
+                Ok(ClusterCommand::DelSlots(slot_ranges(&arguments[1..])?))
+            }
+            ("SETSLOT", [number, state, rest @ ..]) => {
+                let state = match (state.to_uppercase().as_str(), rest) {
+                    ("IMPORTING", [id]) => SlotState::Importing(id.to_string()),
+                    ("MIGRATING", [id]) => SlotState::Migrating(id.to_string()),
+                    ("NODE", [id]) => SlotState::Node(id.to_string()),
+                    ("STABLE", []) => SlotState::Stable,
+                    _ => anyhow::bail!("Invalid CLUSTER SETSLOT action or number of arguments"),
+                };
+                Ok(ClusterCommand::SetSlot(slot(number)?, state))
+            }
+            ("KEYSLOT", [key]) => Ok(ClusterCommand::KeySlot(key.to_string())),
+            ("COUNTKEYSINSLOT", [number]) => Ok(ClusterCommand::CountKeysInSlot(slot(number)?)),
+            ("GETKEYSINSLOT", [number, count]) => {
+                Ok(ClusterCommand::GetKeysInSlot(slot(number)?, count.parse()?))
+            }
+            _ => Err(anyhow::anyhow!("Unknown cluster subcommand '{subcommand}'")),
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl CommandExecutor for ClusterCommand {
+    async fn execute(&self, context: &CommandExecutorContext) -> Value {
+        let Some(cluster) = &context.cluster else {
+            return Value::Error("ERR This instance has cluster support disabled".to_string());
+        };
+        let ok = |result: Result<(), String>| match result {
+            Ok(()) => Value::SimpleString("OK".to_string()),
+            Err(e) => Value::Error(e),
+        };
+        match self {
+            ClusterCommand::Info => Value::BulkString(info(&cluster.nodes())),
+            ClusterCommand::MyId => Value::BulkString(cluster.id().to_string()),
+            ClusterCommand::Nodes => Value::BulkString(cluster.describe()),
+            ClusterCommand::Slots => slots_of(&cluster.nodes()),
+            ClusterCommand::Shards => shards(&cluster.nodes()),
+            ClusterCommand::Meet(address) => {
+                cluster.meet(address);
+                Value::SimpleString("OK".to_string())
+            }
+            ClusterCommand::AddSlots(slots) => ok(cluster.add_slots(slots)),
+            ClusterCommand::DelSlots(slots) => ok(cluster.del_slots(slots)),
+            ClusterCommand::SetSlot(slot, state) => ok(cluster.set_slot(*slot, state.clone())),
+            ClusterCommand::KeySlot(key) => Value::Integer(i64::from(key_slot(key))),
+            ClusterCommand::CountKeysInSlot(slot) => {
+                Value::Integer(keys_in_slot(context, *slot).await.len() as i64)
+            }
+            ClusterCommand::GetKeysInSlot(slot, count) => Value::Array(
+                keys_in_slot(context, *slot)
+                    .await
+                    .into_iter()
+                    .take(*count)
+                    .map(Value::BulkString)
+                    .collect(),
+            ),
+        }
+    }
+}
+
+/// The live keys hashing to the slot, in order.
+async fn keys_in_slot(context: &CommandExecutorContext, slot: u16) -> Vec<String> {
+    let now_in_millis = context.clock.now_in_millis();
+    let mut keys: Vec<String> = context
+        .repository()
+        .entries()
+        .await
+        .into_iter()
+        .filter(|entry| !entry.is_expired(now_in_millis) && key_slot(&entry.key) == slot)
+        .map(|entry| entry.key.to_string())
+        .collect();
+    keys.sort();
+    keys
+}
+
+fn info(nodes: &[NodeInfo]) -> String {
+    let assigned: usize = nodes
+        .iter()
+        .flat_map(|node| &node.slots)
+        .map(|(start, end)| usize::from(end - start) + 1)
+        .sum();
+    let size = nodes.iter().filter(|node| !node.slots.is_empty()).count();
+    let state = match assigned == usize::from(SLOTS) {
+        true => "ok",
+        false => "fail",
+    };
+    [
+        "cluster_enabled:1".to_string(),
+        format!("cluster_state:{state}"),
+        format!("cluster_slots_assigned:{assigned}"),
+        format!("cluster_slots_ok:{assigned}"),
+        "cluster_slots_pfail:0".to_string(),
+        "cluster_slots_fail:0".to_string(),
+        format!("cluster_known_nodes:{}", nodes.len()),
+        format!("cluster_size:{size}"),
+        "cluster_current_epoch:0".to_string(),
+        "cluster_my_epoch:0".to_string(),
+    ]
+    .iter()
+    .map(|line| format!("{line}\r\n"))
+    .collect()
+}
+
+/// The ID, IP and port of the node, as CLUSTER SLOTS lists them, with no further metadata.
+fn endpoint(node: &NodeInfo) -> Value {
+    let (ip, port) = split_address(&node.address);
+    Value::Array(vec![
+        Value::BulkString(ip.to_string()),
+        Value::Integer(port.parse().unwrap_or_default()),
+        Value::BulkString(node.id.clone()),
+        Value::Array(Vec::new()),
+    ])
+}
+
+fn slots_of(nodes: &[NodeInfo]) -> Value {
+    let mut ranges: Vec<(u16, u16, &NodeInfo)> = nodes
+        .iter()
+        .flat_map(|node| {
+            node.slots
+                .iter()
+                .map(move |(start, end)| (*start, *end, node))
+        })
+        .collect();
+    ranges.sort_by_key(|(start, _, _)| *start);
+    Value::Array(
+        ranges
+            .into_iter()
+            .map(|(start, end, node)| {
+                Value::Array(vec![
+                    Value::Integer(i64::from(start)),
+                    Value::Integer(i64::from(end)),
+                    endpoint(node),
+                ])
+            })
+            .collect(),
+    )
+}
+
+fn shards(nodes: &[NodeInfo]) -> Value {
+    let bulk = |value: &str| Value::BulkString(value.to_string());
+    Value::Array(
+        nodes
+            .iter()
+            .filter(|node| !node.handshake)
+            .map(|node| {
+                let (ip, port) = split_address(&node.address);
+                let slots = node
+                    .slots
+                    .iter()
+                    .flat_map(|(start, end)| [*start, *end])
+                    .map(|slot| Value::Integer(i64::from(slot)))
+                    .collect();
+                let health = match node.connected {
+                    true => "online",
+                    false => "fail",
+                };
+                let description = vec![
+                    bulk("id"),
+                    bulk(&node.id),
+                    bulk("port"),
+                    Value::Integer(port.parse().unwrap_or_default()),
+                    bulk("ip"),
+                    bulk(ip),
+                    bulk("endpoint"),
+                    bulk(ip),
+                    bulk("role"),
+                    bulk("master"),
+                    bulk("replication-offset"),
+                    Value::Integer(0),
+                    bulk("health"),
+                    bulk(health),
+                ];
+                Value::Array(vec![
+                    bulk("slots"),
+                    Value::Array(slots),
+                    bulk("nodes"),
+                    Value::Array(vec![Value::Array(description)]),
+                ])
+            })
+            .collect(),
+    )
+}
+
+#[cfg(test)]
+mod specs_for_parse_from {
+    use crate::cluster::SlotState;
+    use crate::command::executor::Command;
+    use crate::resp::Value;
+
+    use super::ClusterCommand;
+
+    fn array(arguments: &[&str]) -> Value {
+        Value::Array(
+            arguments
+                .iter()
+                .map(|argument| Value::BulkString(argument.to_string()))
+                .collect(),
+        )
+    }
+
+    #[rstest::rstest]
+    #[case(&["CLUSTER", "info"], ClusterCommand::Info)]
+    #[case(&["cluster", "MYID"], ClusterCommand::MyId)]
+    #[case(&["CLUSTER", "MEET", "127.0.0.1", "7001"], ClusterCommand::Meet("127.0.0.1:7001".to_string()))]
+    #[case(&["CLUSTER", "ADDSLOTS", "1", "5"], ClusterCommand::AddSlots(vec![1, 5]))]
+    #[case(&["CLUSTER", "ADDSLOTSRANGE", "1", "3", "7", "7"], ClusterCommand::AddSlots(vec![1, 2, 3, 7]))]
+    #[case(&["CLUSTER", "DELSLOTSRANGE", "16382", "16383"], ClusterCommand::DelSlots(vec![16382, 16383]))]
+    #[case(&["CLUSTER", "SETSLOT", "5", "migrating", "abc"],
+        ClusterCommand::SetSlot(5, SlotState::Migrating("abc".to_string())))]
+    #[case(&["CLUSTER", "SETSLOT", "5", "STABLE"], ClusterCommand::SetSlot(5, SlotState::Stable))]
+    #[case(&["CLUSTER", "KEYSLOT", "foo"], ClusterCommand::KeySlot("foo".to_string()))]
+    #[case(&["CLUSTER", "COUNTKEYSINSLOT", "12182"], ClusterCommand::CountKeysInSlot(12182))]
+    #[case(&["CLUSTER", "GETKEYSINSLOT", "12182", "10"], ClusterCommand::GetKeysInSlot(12182, 10))]
+    fn sut_parses_cluster_subcommands(
+        #[case] arguments: &[&str],
+        #[case] expected: ClusterCommand,
+    ) {
+        // Act
+        let actual = ClusterCommand::parse_from(&array(arguments)).unwrap();
+
+        // Assert
+        assert_eq!(actual, expected);
+    }
+
+    #[rstest::rstest]
+    #[case(&["CLUSTER"])]
+    #[case(&["CLUSTER", "ADDSLOTS", "16384"])]
+    #[case(&["CLUSTER", "ADDSLOTSRANGE", "5", "1"])]
+    #[case(&["CLUSTER", "ADDSLOTSRANGE", "1", "2", "3"])]
+    #[case(&["CLUSTER", "SETSLOT", "5", "NODE"])]
+    #[case(&["CLUSTER", "RESET"])]
+    fn sut_rejects_malformed_cluster_commands(#[case] arguments: &[&str]) {
+        // Act
+        let actual = ClusterCommand::parse_from(&array(arguments));
+
+        // Assert
+        assert!(actual.is_err());
+    }
+}
+
+#[cfg(test)]
+mod specs_for_execute {
+    use std::sync::Arc;
+
+    use crate::clock::SystemClock;
+    use crate::cluster::key_slot;
+    use crate::command::executor::CommandExecutor;
+    use crate::command::executor::CommandExecutorContext;
+    use crate::command::executor::fixture::command_executor_context;
+    use crate::config::Config;
+    use crate::config::Server;
+    use crate::repository::Data;
+    use crate::repository::Entry;
+    use crate::repository::InMemoryRepository;
+    use crate::repository::Repository;
+    use crate::repository::fixture::DummyRepository;
+    use crate::resp::Value;
+
+    use super::ClusterCommand;
+
+    fn node(repository: impl Repository) -> CommandExecutorContext {
+        let config = Config {
+            server: Server {
+                port: 7000,
+                cluster_enabled: true,
+                ..Server::default()
+            },
+            ..Config::default()
+        };
+        command_executor_context(repository, config, Arc::new(SystemClock))
+    }
+
+    #[tokio::test]
+    async fn sut_reports_cluster_state_once_every_slot_is_assigned() {
+        // Arrange
+        let context = node(DummyRepository);
+
+        // Act
+        let before = ClusterCommand::Info.execute(&context).await;
+        ClusterCommand::AddSlots((0..16384).collect())
+            .execute(&context)
+            .await;
+        let after = ClusterCommand::Info.execute(&context).await;
+
+        // Assert
+        let Value::BulkString(before) = before else {
+            panic!("{before:?}");
+        };
+        assert!(before.contains("cluster_state:fail\r\ncluster_slots_assigned:0\r\n"));
+        let Value::BulkString(after) = after else {
+            panic!("{after:?}");
+        };
+        assert!(after.contains("cluster_state:ok\r\ncluster_slots_assigned:16384\r\n"));
+        assert!(after.contains("cluster_known_nodes:1\r\ncluster_size:1\r\n"));
+    }
+
+    #[tokio::test]
+    async fn sut_lists_slot_ranges_with_the_node_serving_them() {
+        // Arrange
+        let context = node(DummyRepository);
+        ClusterCommand::AddSlots(vec![0, 1, 2, 10])
+            .execute(&context)
+            .await;
+        let id = context.cluster.as_ref().unwrap().id().to_string();
+
+        // Act
+        let actual = ClusterCommand::Slots.execute(&context).await;
+
+        // Assert
+        let endpoint = Value::Array(vec![
+            Value::BulkString("127.0.0.1".to_string()),
+            Value::Integer(7000),
+            Value::BulkString(id),
+            Value::Array(Vec::new()),
+        ]);
+        let expected = Value::Array(vec![
+            Value::Array(vec![Value::Integer(0), Value::Integer(2), endpoint.clone()]),
+            Value::Array(vec![Value::Integer(10), Value::Integer(10), endpoint]),
+        ]);
+        assert_eq!(actual, expected);
+    }
+
+    #[tokio::test]
+    async fn sut_counts_and_lists_keys_of_slot() {
+        // Arrange
+        let repository = InMemoryRepository::new();
+        for key in ["{user}.name", "{user}.email", "foo"] {
+            repository
+                .set(Entry {
+                    key: key.into(),
+                    value: Data::String("value".to_string()),
+                    expiry: None,
+                })
+                .await;
+        }
+        let context = node(repository);
+        let slot = key_slot("user");
+
+        // Act
+        let count = ClusterCommand::CountKeysInSlot(slot)
+            .execute(&context)
+            .await;
+        let keys = ClusterCommand::GetKeysInSlot(slot, 1)
+            .execute(&context)
+            .await;
+
+        // Assert
+        assert_eq!(count, Value::Integer(2));
+        assert_eq!(
+            keys,
+            Value::Array(vec![Value::BulkString("{user}.email".to_string())])
+        );
+    }
+
+    #[tokio::test]
+    async fn sut_replies_errors_of_slot_changes() {
+        // Arrange
+        let context = node(DummyRepository);
+        ClusterCommand::AddSlots(vec![5]).execute(&context).await;
+
+        // Act
+        let actual = ClusterCommand::AddSlots(vec![5]).execute(&context).await;
+
+        // Assert
+        assert_eq!(
+            actual,
+            Value::Error("ERR Slot 5 is already busy".to_string())
+        );
+    }
+
+    #[rstest::rstest]
+    #[tokio::test]
+    async fn sut_is_disabled_outside_cluster_mode(
+        #[from(command_executor_context)] context: CommandExecutorContext,
+    ) {
+        // Act
+        let actual = ClusterCommand::MyId.execute(&context).await;
+
+        // Assert
+        assert_eq!(
+            actual,
+            Value::Error("ERR This instance has cluster support disabled".to_string())
+        );
+    }
+}
@@ -0,0 +1,242 @@
+use crate::command::executor::Command;
+use crate::command::executor::CommandExecutor;
+use crate::command::executor::CommandExecutorContext;
+use crate::command::parser::extract_array;
+use crate::command::parser::extract_bulk_string;
+use crate::command::parser::validate_main_command;
+use crate::command::parser::validate_min_array_length;
+use crate::command::scan::ScanOptions;
+use crate::command::scan::parse_cursor;
+use crate::command::scan::scan_reply;
+use crate::repository::RepositoryError;
+use crate::resp::Value;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Collection {
+    Set,
+    Hash,
+    SortedSet,
+}
+
+const VARIANTS: [(&str, Collection); 3] = [
+    ("SSCAN", Collection::Set),
+    ("HSCAN", Collection::Hash),
+    ("ZSCAN", Collection::SortedSet),
+];
+
+/// SSCAN, HSCAN and ZSCAN, iterating over the elements of a single collection.
+#[derive(Debug, PartialEq)]
+pub struct CollectionScan {
+    collection: Collection,
+    key: String,
+    cursor: u64,
+    options: ScanOptions,
+}
+
+impl Command for CollectionScan {
+    fn parse_from(value: &Value) -> Result<Self, anyhow::Error> {
+        let array = extract_array(value)?;
+        validate_min_array_length(array, 3)?;
+        let (_, collection) = VARIANTS
+            .iter()
+            .find(|(name, _)| validate_main_command(array, name).is_ok())
+            .ok_or_else(|| anyhow::anyhow!("expected SSCAN, HSCAN or ZSCAN main command"))?;
+        let key = extract_bulk_string(array, 1)?;
+        Ok(CollectionScan {
+            collection: *collection,
+            key: key.to_string(),
+            cursor: parse_cursor(array, 2)?,
+            options: ScanOptions::parse(array, 3, false)?,
+        })
+    }
+}
+
+#[async_trait::async_trait]
+impl CommandExecutor for CollectionScan {
+    async fn execute(&self, context: &CommandExecutorContext) -> Value {
+        context.track(&self.key);
+        let repository = &context.repository();
+        match self.collection {
+            Collection::SortedSet => {
+                match repository
+                    .zscan(&self.key, self.cursor, self.options.count)
+                    .await
+                {
+                    Ok((cursor, members)) => {
+                        let elements = members
+                            .into_iter()
+                            .filter(|(member, _)| self.options.matches(member))
+                            .flat_map(|(member, score)| [member, score.to_string()])
+                            .collect();
+                        scan_reply(cursor, elements)
+                    }
+                    Err(e) => Value::Error(e.to_string()),
+                }
+            }
+            // Sets and hashes are not supported as values yet, so any existing key has the
+            // wrong type.
+            Collection::Set | Collection::Hash => match repository.type_of(&self.key).await {
+                Some(_) => Value::Error(RepositoryError::WrongType.to_string()),
+                None => scan_reply(0, vec![]),
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod specs_for_parse_from {
+    use crate::command::executor::Command;
+    use crate::resp::Value;
+
+    use super::Collection;
+    use super::CollectionScan;
+    use super::ScanOptions;
+
+    fn array(arguments: &[&str]) -> Value {
+        Value::Array(
+            arguments
+                .iter()
+                .map(|argument| Value::BulkString(argument.to_string()))
+                .collect(),
+        )
+    }
+
+    #[rstest::rstest]
+    #[case("SSCAN", Collection::Set)]
+    #[case("hscan", Collection::Hash)]
+    #[case("ZScan", Collection::SortedSet)]
+    fn sut_parses_collection_scan_commands_correctly(
+        #[case] command: &str,
+        #[case] collection: Collection,
+    ) {
+        // Arrange
+        let value = array(&[command, "foo", "7", "MATCH", "a*", "COUNT", "5"]);
+
+        // Act
+        let actual = CollectionScan::parse_from(&value).unwrap();
+
+        // Assert
+        let expected = CollectionScan {
+            collection,
+            key: "foo".to_string(),
+            cursor: 7,
+            options: ScanOptions {
+                pattern: Some("a*".to_string()),
+                count: 5,
+                type_name: None,
+            },
+        };
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn sut_raises_error_if_type_option_is_given() {
+        // Arrange
+        let value = array(&["ZSCAN", "foo", "0", "TYPE", "string"]);
+
+        // Act
+        let actual = CollectionScan::parse_from(&value);
+
+        // Assert
+        assert!(actual.is_err());
+    }
+}
+
+#[cfg(test)]
+mod specs_for_execute {
+    use crate::command::executor::CommandExecutor;
+    use crate::command::executor::CommandExecutorContext;
+    use crate::command::executor::fixture::command_executor_context;
+    use crate::repository::AddCondition;
+    use crate::repository::Data;
+    use crate::repository::Entry;
+    use crate::repository::InMemoryRepository;
+    use crate::resp::Value;
+
+    use super::Collection;
+    use super::CollectionScan;
+    use super::ScanOptions;
+
+    fn command(collection: Collection, key: &str, pattern: Option<&str>) -> CollectionScan {
+        CollectionScan {
+            collection,
+            key: key.to_string(),
+            cursor: 0,
+            options: ScanOptions {
+                pattern: pattern.map(str::to_string),
+                count: 10,
+                type_name: None,
+            },
+        }
+    }
+
+    #[rstest::rstest]
+    #[tokio::test]
+    async fn sut_responds_matching_members_with_scores_of_sorted_set(
+        #[from(command_executor_context)]
+        #[with(InMemoryRepository::new())]
+        context: CommandExecutorContext,
+    ) {
+        // Arrange
+        context
+            .repository()
+            .zadd(
+                "zset",
+                vec![("apple".to_string(), 1.5), ("banana".to_string(), 2.0)],
+                AddCondition::Always,
+            )
+            .await
+            .unwrap();
+        let command = command(Collection::SortedSet, "zset", Some("a*"));
+
+        // Act
+        let actual = command.execute(&context).await;
+
+        // Assert
+        let expected = Value::Array(vec![
+            Value::BulkString("0".to_string()),
+            Value::Array(vec![
+                Value::BulkString("apple".to_string()),
+                Value::BulkString("1.5".to_string()),
+            ]),
+        ]);
+        assert_eq!(actual, expected);
+    }
+
+    #[rstest::rstest]
+    #[case(Collection::Set, "missing", "0")]
+    #[case(Collection::Hash, "foo", "WRONGTYPE")]
+    #[case(Collection::SortedSet, "foo", "WRONGTYPE")]
+    #[tokio::test]
+    async fn sut_responds_empty_page_or_error_for_keys_of_other_types(
+        #[case] collection: Collection,
+        #[case] key: &str,
+        #[case] expected_prefix: &str,
+        #[from(command_executor_context)]
+        #[with(InMemoryRepository::new())]
+        context: CommandExecutorContext,
+    ) {
+        // Arrange
+        context
+            .repository()
+            .set(Entry {
+                key: "foo".into(),
+                value: Data::String("bar".to_string()),
+                expiry: None,
+            })
+            .await;
+        let command = command(collection, key, None);
+
+        // Act
+        let actual = command.execute(&context).await;
+
+        // Assert
+        match actual {
+            Value::Error(e) => assert!(e.starts_with(expected_prefix)),
+            Value::Array(reply) => {
+                assert_eq!(reply[0], Value::BulkString(expected_prefix.to_string()))
+            }
+            _ => panic!("unexpected reply"),
+        }
+    }
+}
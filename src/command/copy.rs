@@ -0,0 +1,221 @@
+use crate::command::executor::Command;
+use crate::command::executor::CommandExecutor;
+use crate::command::executor::CommandExecutorContext;
+use crate::command::parser::extract_array;
+use crate::command::parser::extract_bulk_string;
+use crate::command::parser::validate_main_command;
+use crate::command::parser::validate_min_array_length;
+use crate::command::select::database_index;
+use crate::notification::Class;
+use crate::repository::Entry;
+use crate::resp::Value;
+
+#[derive(Debug, Default, PartialEq)]
+pub struct Copy {
+    source: String,
+    destination: String,
+    database: Option<i64>,
+    replace: bool,
+}
+
+impl Command for Copy {
+    fn parse_from(value: &Value) -> Result<Self, anyhow::Error> {
+        let array = extract_array(value)?;
+        validate_min_array_length(array, 3)?;
+        validate_main_command(array, "COPY")?;
+        let source = extract_bulk_string(array, 1)?;
+        let destination = extract_bulk_string(array, 2)?;
+
+        let mut database = None;
+        let mut replace = false;
+        let mut index = 3;
+        while index < array.len() {
+            match extract_bulk_string(array, index)?.to_uppercase().as_str() {
+                "REPLACE" => replace = true,
+                "DB" if index + 1 < array.len() => {
+                    index += 1;
+                    database = Some(extract_bulk_string(array, index)?.parse()?);
+                }
+                _ => return Err(anyhow::anyhow!("syntax error")),
+            }
+            index += 1;
+        }
+
+        Ok(Copy {
+            source: source.to_string(),
+            destination: destination.to_string(),
+            database,
+            replace,
+        })
+    }
+}
+
+#[async_trait::async_trait]
+impl CommandExecutor for Copy {
+    async fn execute(&self, context: &CommandExecutorContext) -> Value {
+        let database = match self.database.map(|index| database_index(context, index)) {
+            Some(Ok(database)) => database,
+            Some(Err(e)) => return e,
+            None => context.session.database(),
+        };
+        if database != context.session.database() {
+            return self.copy_across(context, database).await;
+        }
+        if self.source == self.destination {
+            return Value::Error("ERR source and destination objects are the same".to_string());
+        }
+        let copied = context
+            .repository()
+            .copy(&self.source, &self.destination, self.replace)
+            .await;
+        if copied {
+            context.notify(Class::Generic, "copy_to", &self.destination);
+        }
+        Value::Integer(copied as i64)
+    }
+}
+
+impl Copy {
+    async fn copy_across(&self, context: &CommandExecutorContext, database: usize) -> Value {
+        let Some(entry) = context.repository().entry(&self.source).await else {
+            return Value::Integer(0);
+        };
+        let target = context.databases.get(database).unwrap();
+        if !self.replace && target.exists(&self.destination).await {
+            return Value::Integer(0);
+        }
+        target
+            .set(Entry {
+                key: self.destination.as_str().into(),
+                ..entry
+            })
+            .await;
+        context.notify_in(database, Class::Generic, "copy_to", &self.destination);
+        Value::Integer(1)
+    }
+}
+
+#[cfg(test)]
+mod specs_for_parse_from {
+    use crate::command::executor::Command;
+    use crate::resp::Value;
+
+    use super::Copy;
+
+    #[rstest::rstest]
+    #[case(vec![], None, false)]
+    #[case(vec!["replace"], None, true)]
+    #[case(vec!["DB", "3", "REPLACE"], Some(3), true)]
+    fn sut_parses_copy_command_correctly(
+        #[case] options: Vec<&str>,
+        #[case] database: Option<i64>,
+        #[case] replace: bool,
+    ) {
+        // Arrange
+        let mut array = vec![
+            Value::BulkString("COPY".to_string()),
+            Value::BulkString("foo".to_string()),
+            Value::BulkString("bar".to_string()),
+        ];
+        array.extend(
+            options
+                .into_iter()
+                .map(|o| Value::BulkString(o.to_string())),
+        );
+        let value = Value::Array(array);
+
+        // Act
+        let actual = Copy::parse_from(&value).unwrap();
+
+        // Assert
+        let expected = Copy {
+            source: "foo".to_string(),
+            destination: "bar".to_string(),
+            database,
+            replace,
+        };
+        assert_eq!(actual, expected);
+    }
+}
+
+#[cfg(test)]
+mod specs_for_execute {
+    use crate::command::executor::CommandExecutor;
+    use crate::command::executor::CommandExecutorContext;
+    use crate::command::executor::fixture::command_executor_context;
+    use crate::command::executor::fixture::command_executor_context_with_databases;
+    use crate::repository::Data;
+    use crate::repository::Entry;
+    use crate::repository::InMemoryRepository;
+    use crate::resp::Value;
+
+    use super::Copy;
+
+    #[rstest::rstest]
+    #[case(false, 0, "2")]
+    #[case(true, 1, "1")]
+    #[tokio::test]
+    async fn sut_copies_value_respecting_replace_option(
+        #[case] replace: bool,
+        #[case] expected: i64,
+        #[case] expected_value: &str,
+        #[from(command_executor_context)]
+        #[with(InMemoryRepository::new())]
+        context: CommandExecutorContext,
+    ) {
+        // Arrange
+        for (key, value) in [("foo", "1"), ("bar", "2")] {
+            context
+                .repository()
+                .set(Entry {
+                    key: key.into(),
+                    value: Data::String(value.to_string()),
+                    expiry: None,
+                })
+                .await;
+        }
+        let command = Copy {
+            source: "foo".to_string(),
+            destination: "bar".to_string(),
+            database: None,
+            replace,
+        };
+
+        // Act
+        let actual = command.execute(&context).await;
+
+        // Assert
+        assert_eq!(actual, Value::Integer(expected));
+        let value = context.repository().get("bar").await;
+        assert_eq!(value, Ok(Some(expected_value.to_string())));
+    }
+
+    #[tokio::test]
+    async fn sut_copies_value_to_other_database_even_with_same_key() {
+        // Arrange
+        let context = command_executor_context_with_databases(16);
+        context
+            .repository()
+            .set(Entry {
+                key: "foo".into(),
+                value: Data::String("1".to_string()),
+                expiry: None,
+            })
+            .await;
+        let command = Copy {
+            source: "foo".to_string(),
+            destination: "foo".to_string(),
+            database: Some(2),
+            replace: false,
+        };
+
+        // Act
+        let actual = command.execute(&context).await;
+
+        // Assert
+        assert_eq!(actual, Value::Integer(1));
+        let value = context.databases.get(2).unwrap().get("foo").await;
+        assert_eq!(value, Ok(Some("1".to_string())));
+        assert!(context.repository().exists("foo").await);
+    }
+}
@@ -0,0 +1,65 @@
+use crate::command::executor::Command;
+use crate::command::executor::CommandExecutor;
+use crate::command::executor::CommandExecutorContext;
+use crate::command::parser::extract_array;
+use crate::command::parser::validate_array_length;
+use crate::command::parser::validate_main_command;
+use crate::resp::Value;
+
+#[derive(Debug, Default, PartialEq)]
+pub struct DbSize;
+
+impl Command for DbSize {
+    fn parse_from(value: &Value) -> Result<Self, anyhow::Error> {
+        let array = extract_array(value)?;
+        validate_array_length(array, 1)?;
+        validate_main_command(array, "DBSIZE")?;
+        Ok(DbSize)
+    }
+}
+
+#[async_trait::async_trait]
+impl CommandExecutor for DbSize {
+    async fn execute(&self, context: &CommandExecutorContext) -> Value {
+        Value::Integer(context.repository().size().await as i64)
+    }
+}
+
+#[cfg(test)]
+mod specs_for_execute {
+    use crate::command::executor::CommandExecutor;
+    use crate::command::executor::CommandExecutorContext;
+    use crate::command::executor::fixture::command_executor_context;
+    use crate::repository::Data;
+    use crate::repository::Entry;
+    use crate::repository::InMemoryRepository;
+    use crate::resp::Value;
+
+    use super::DbSize;
+
+    #[rstest::rstest]
+    #[tokio::test]
+    async fn sut_responds_number_of_keys(
+        #[from(command_executor_context)]
+        #[with(InMemoryRepository::new())]
+        context: CommandExecutorContext,
+    ) {
+        // Arrange
+        for key in ["foo", "bar"] {
+            context
+                .repository()
+                .set(Entry {
+                    key: key.into(),
+                    value: Data::String(key.to_string()),
+                    expiry: None,
+                })
+                .await;
+        }
+
+        // Act
+        let actual = DbSize.execute(&context).await;
+
+        // Assert
+        assert_eq!(actual, Value::Integer(2));
+    }
+}
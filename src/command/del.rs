@@ -0,0 +1,113 @@
+use crate::command::executor::Command;
+use crate::command::executor::CommandExecutor;
+use crate::command::executor::CommandExecutorContext;
+use crate::command::parser::extract_array;
+use crate::command::parser::extract_bulk_strings;
+use crate::command::parser::validate_main_command;
+use crate::command::parser::validate_min_array_length;
+use crate::notification::Class;
+use crate::resp::Value;
+
+#[derive(Debug, Default, PartialEq)]
+pub struct Del {
+    keys: Vec<String>,
+}
+
+impl Command for Del {
+    fn parse_from(value: &Value) -> Result<Self, anyhow::Error> {
+        let array = extract_array(value)?;
+        validate_min_array_length(array, 2)?;
+        validate_main_command(array, "DEL")?;
+        let keys = extract_bulk_strings(array, 1)?;
+        Ok(Del { keys })
+    }
+}
+
+#[async_trait::async_trait]
+impl CommandExecutor for Del {
+    async fn execute(&self, context: &CommandExecutorContext) -> Value {
+        let mut count = 0;
+        for key in self.keys.iter() {
+            if context.repository().delete(key).await.is_some() {
+                context.notify(Class::Generic, "del", key);
+                count += 1;
+            }
+        }
+        Value::Integer(count)
+    }
+}
+
+#[cfg(test)]
+mod specs_for_parse_from {
+    use crate::command::executor::Command;
+    use crate::resp::Value;
+
+    use super::Del;
+
+    #[rstest::rstest]
+    #[case("DEL")]
+    #[case("del")]
+    #[case("DeL")]
+    fn sut_parses_del_command_with_case_insensitive(#[case] del: &str) {
+        // Arrange
+        let value = Value::Array(vec![
+            Value::BulkString(del.to_string()),
+            Value::BulkString("foo".to_string()),
+            Value::BulkString("bar".to_string()),
+        ]);
+
+        // Act
+        let actual = Del::parse_from(&value).unwrap();
+
+        // Assert
+        let expected = Del {
+            keys: vec!["foo".to_string(), "bar".to_string()],
+        };
+        assert_eq!(actual, expected);
+    }
+}
+
+#[cfg(test)]
+mod specs_for_execute {
+    use fake::Fake;
+    use fake::faker::lorem::en::Word;
+
+    use crate::command::executor::CommandExecutor;
+    use crate::command::executor::CommandExecutorContext;
+    use crate::command::executor::fixture::command_executor_context;
+    use crate::repository::Data;
+    use crate::repository::Entry;
+    use crate::repository::InMemoryRepository;
+    use crate::resp::Value;
+
+    use super::Del;
+
+    #[rstest::rstest]
+    #[tokio::test]
+    async fn sut_responds_number_of_deleted_keys(
+        #[from(command_executor_context)]
+        #[with(InMemoryRepository::new())]
+        context: CommandExecutorContext,
+    ) {
+        // Arrange
+        let key: String = Word().fake();
+        context
+            .repository()
+            .set(Entry {
+                key: key.as_str().into(),
+                value: Data::String(Word().fake()),
+                expiry: None,
+            })
+            .await;
+        let command = Del {
+            keys: vec![key.clone(), format!("{key}-missing")],
+        };
+
+        // Act
+        let actual = command.execute(&context).await;
+
+        // Assert
+        assert_eq!(actual, Value::Integer(1));
+        assert!(!context.repository().exists(&key).await);
+    }
+}
@@ -0,0 +1,91 @@
+use crate::command::executor::Command;
+use crate::command::executor::CommandExecutor;
+use crate::command::executor::CommandExecutorContext;
+use crate::command::parser::extract_array;
+use crate::command::parser::validate_array_length;
+use crate::command::parser::validate_main_command;
+use crate::resp::Value;
+
+#[derive(Debug, Default, PartialEq)]
+pub struct Discard;
+
+impl Command for Discard {
+    fn parse_from(value: &Value) -> Result<Self, anyhow::Error> {
+        let array = extract_array(value)?;
+        validate_array_length(array, 1)?;
+        validate_main_command(array, "DISCARD")?;
+        Ok(Discard)
+    }
+}
+
+#[async_trait::async_trait]
+impl CommandExecutor for Discard {
+    async fn execute(&self, context: &CommandExecutorContext) -> Value {
+        if context.session.finish().is_none() {
+            return Value::Error("ERR DISCARD without MULTI".to_string());
+        }
+        context.session.unwatch();
+        Value::SimpleString("OK".to_string())
+    }
+}
+
+#[cfg(test)]
+mod specs_for_parse_from {
+    use crate::command::executor::Command;
+    use crate::resp::Value;
+
+    use super::Discard;
+
+    #[rstest::rstest]
+    #[case("DISCARD")]
+    #[case("discard")]
+    fn sut_parses_discard_command_with_case_insensitive(#[case] discard: &str) {
+        // Arrange
+        let value = Value::Array(vec![Value::BulkString(discard.to_string())]);
+
+        // Act
+        let actual = Discard::parse_from(&value).unwrap();
+
+        // Assert
+        assert_eq!(actual, Discard);
+    }
+}
+
+#[cfg(test)]
+mod specs_for_execute {
+    use crate::command::executor::CommandExecutor;
+    use crate::command::executor::CommandExecutorContext;
+    use crate::command::executor::fixture::command_executor_context;
+    use crate::resp::Value;
+
+    use super::Discard;
+
+    #[rstest::rstest]
+    #[tokio::test]
+    async fn sut_ends_transaction(
+        #[from(command_executor_context)] context: CommandExecutorContext,
+    ) {
+        // Arrange
+        context.session.begin();
+
+        // Act
+        let actual = Discard.execute(&context).await;
+
+        // Assert
+        assert_eq!(actual, Value::SimpleString("OK".to_string()));
+        assert!(!context.session.in_transaction());
+    }
+
+    #[rstest::rstest]
+    #[tokio::test]
+    async fn sut_responds_error_if_transaction_is_not_started(
+        #[from(command_executor_context)] context: CommandExecutorContext,
+    ) {
+        // Act
+        let actual = Discard.execute(&context).await;
+
+        // Assert
+        let expected = Value::Error("ERR DISCARD without MULTI".to_string());
+        assert_eq!(actual, expected);
+    }
+}
@@ -0,0 +1,469 @@
+use crate::command::executor::Command;
+use crate::command::executor::CommandExecutor;
+use crate::command::executor::CommandExecutorContext;
+use crate::command::parser::extract_array;
+use crate::command::parser::extract_bulk_string;
+use crate::command::parser::extract_bulk_strings;
+use crate::command::parser::validate_main_command;
+use crate::command::parser::validate_min_array_length;
+use crate::resp::Value;
+use crate::scripting::Program;
+
+#[derive(Debug, PartialEq)]
+enum Script {
+    Body(String),
+    Sha(String),
+}
+
+/// EVAL and EVALSHA only differ in whether the script is given or looked up by its digest, and
+/// their _RO variants in that the script may not write.
+#[derive(Debug, PartialEq)]
+pub struct Eval {
+    script: Script,
+    keys: Vec<String>,
+    args: Vec<String>,
+    read_only: bool,
+}
+
+impl Command for Eval {
+    fn parse_from(value: &Value) -> Result<Self, anyhow::Error> {
+        let array = extract_array(value)?;
+        validate_min_array_length(array, 3)?;
+        let script = extract_bulk_string(array, 1)?.to_string();
+        let is = |name| validate_main_command(array, name).is_ok();
+        let (script, read_only) = if is("EVAL") || is("EVAL_RO") {
+            (Script::Body(script), is("EVAL_RO"))
+        } else if is("EVALSHA") || is("EVALSHA_RO") {
+            (Script::Sha(script), is("EVALSHA_RO"))
+        } else {
+            return Err(anyhow::anyhow!("expected EVAL or EVALSHA main command"));
+        };
+        let (keys, args) = parse_keys_and_args(array, 2)?;
+        Ok(Eval {
+            script,
+            keys,
+            args,
+            read_only,
+        })
+    }
+}
+
+/// Parses `numkeys key [key ...] arg [arg ...]` starting at `start`, shared with FCALL.
+pub fn parse_keys_and_args(
+    array: &[Value],
+    start: usize,
+) -> Result<(Vec<String>, Vec<String>), anyhow::Error> {
+    let numkeys: i64 = extract_bulk_string(array, start)?.parse()?;
+    let numkeys = usize::try_from(numkeys)
+        .map_err(|_| anyhow::anyhow!("Number of keys can't be negative"))?;
+    if numkeys > array.len() - start - 1 {
+        return Err(anyhow::anyhow!(
+            "Number of keys can't be greater than number of args"
+        ));
+    }
+    let mut keys = extract_bulk_strings(array, start + 1)?;
+    let args = keys.split_off(numkeys);
+    Ok((keys, args))
+}
+
+impl Eval {
+    pub fn is_read_only(&self) -> bool {
+        self.read_only
+    }
+
+    /// Runs the script, the caller holding the execution lock exclusively as EXEC does. Its
+    /// writes are sent to replicas as a transaction of their own if `wrap` is set.
+    pub async fn run(&self, context: &CommandExecutorContext, wrap: bool) -> Value {
+        let body = match &self.script {
+            Script::Body(body) => {
+                context.scripts.load(body);
+                body.clone()
+            }
+            Script::Sha(sha) => match context.scripts.get(sha) {
+                Some(body) => body,
+                None => {
+                    return Value::Error(
+                        "NOSCRIPT No matching script. Please use EVAL.".to_string(),
+                    );
+                }
+            },
+        };
+        let program = Program::Script(body);
+        let (keys, args) = (self.keys.clone(), self.args.clone());
+        context
+            .scripts
+            .run(context, program, keys, args, self.read_only, wrap)
+            .await
+    }
+}
+
+#[async_trait::async_trait]
+impl CommandExecutor for Eval {
+    async fn execute(&self, context: &CommandExecutorContext) -> Value {
+        let _exclusive = match context.lock_exclusive().await {
+            Ok(exclusive) => exclusive,
+            Err(e) => return e,
+        };
+        self.run(context, true).await
+    }
+}
+
+#[cfg(test)]
+mod specs_for_parse_from {
+    use crate::command::executor::Command;
+    use crate::resp::Value;
+
+    use super::Eval;
+    use super::Script;
+
+    fn array(arguments: &[&str]) -> Value {
+        Value::Array(
+            arguments
+                .iter()
+                .map(|argument| Value::BulkString(argument.to_string()))
+                .collect(),
+        )
+    }
+
+    #[rstest::rstest]
+    #[case("EVAL", Script::Body("return 1".to_string()), false)]
+    #[case("eval", Script::Body("return 1".to_string()), false)]
+    #[case("EVALSHA", Script::Sha("return 1".to_string()), false)]
+    #[case("EVAL_RO", Script::Body("return 1".to_string()), true)]
+    #[case("evalsha_ro", Script::Sha("return 1".to_string()), true)]
+    fn sut_parses_keys_and_args_correctly(
+        #[case] command: &str,
+        #[case] script: Script,
+        #[case] read_only: bool,
+    ) {
+        // Arrange
+        let value = array(&[command, "return 1", "2", "foo", "bar", "baz"]);
+
+        // Act
+        let actual = Eval::parse_from(&value).unwrap();
+
+        // Assert
+        let expected = Eval {
+            script,
+            keys: vec!["foo".to_string(), "bar".to_string()],
+            args: vec!["baz".to_string()],
+            read_only,
+        };
+        assert_eq!(actual, expected);
+    }
+
+    #[rstest::rstest]
+    #[case(&["EVAL", "return 1", "-1"])]
+    #[case(&["EVAL", "return 1", "2", "foo"])]
+    #[case(&["EVAL", "return 1", "one"])]
+    fn sut_raises_error_if_number_of_keys_is_invalid(#[case] arguments: &[&str]) {
+        // Act
+        let actual = Eval::parse_from(&array(arguments));
+
+        // Assert
+        assert!(actual.is_err());
+    }
+}
+
+#[cfg(test)]
+mod specs_for_execute {
+    use std::sync::Arc;
+
+    use crate::clock::SystemClock;
+    use crate::command::executor::Command;
+    use crate::command::executor::CommandExecutor;
+    use crate::command::executor::CommandExecutorContext;
+    use crate::command::executor::fixture::command_executor_context;
+    use crate::command::executor::fixture::command_executor_context_with_databases;
+    use crate::config::Config;
+    use crate::config::ReplicationSlave;
+    use crate::repository::InMemoryRepository;
+    use crate::resp::Value;
+
+    use super::Eval;
+
+    fn eval(arguments: &[&str]) -> Eval {
+        let mut array = vec![Value::BulkString("EVAL".to_string())];
+        array.extend(
+            arguments
+                .iter()
+                .map(|argument| Value::BulkString(argument.to_string())),
+        );
+        Eval::parse_from(&Value::Array(array)).unwrap()
+    }
+
+    #[rstest::rstest]
+    #[case(&["return 1", "0"], Value::Integer(1))]
+    #[case(&["return 3.99", "0"], Value::Integer(3))]
+    #[case(&["return true", "0"], Value::Integer(1))]
+    #[case(&["return false", "0"], Value::Null)]
+    #[case(&["return nil", "0"], Value::Null)]
+    #[case(&["return 'foo'", "0"], Value::BulkString("foo".to_string()))]
+    #[case(&["return {1, 'two', {3}, nil, 5}", "0"], Value::Array(vec![
+        Value::Integer(1),
+        Value::BulkString("two".to_string()),
+        Value::Array(vec![Value::Integer(3)]),
+    ]))]
+    #[case(&["return redis.status_reply('FINE')", "0"], Value::SimpleString("FINE".to_string()))]
+    #[case(&["return redis.error_reply('MY error')", "0"], Value::Error("MY error".to_string()))]
+    #[case(&["return {KEYS[1], ARGV[1]}", "1", "foo", "bar"], Value::Array(vec![
+        Value::BulkString("foo".to_string()),
+        Value::BulkString("bar".to_string()),
+    ]))]
+    #[tokio::test]
+    async fn sut_converts_lua_values_to_replies(
+        #[case] arguments: &[&str],
+        #[case] expected: Value,
+        #[from(command_executor_context)] context: CommandExecutorContext,
+    ) {
+        // Act
+        let actual = eval(arguments).execute(&context).await;
+
+        // Assert
+        assert_eq!(actual, expected);
+    }
+
+    #[rstest::rstest]
+    #[tokio::test]
+    async fn sut_calls_commands_converting_replies_to_lua_values(
+        #[from(command_executor_context)]
+        #[with(InMemoryRepository::new())]
+        context: CommandExecutorContext,
+    ) {
+        // Arrange
+        let script = "
+            local status = redis.call('SET', KEYS[1], ARGV[1])
+            local missing = redis.call('GET', 'missing')
+            return {status.ok, redis.call('GET', KEYS[1]), tostring(missing), redis.call('EXISTS', KEYS[1])}
+        ";
+        let command = eval(&[script, "1", "foo", "bar"]);
+
+        // Act
+        let actual = command.execute(&context).await;
+
+        // Assert
+        let expected = Value::Array(vec![
+            Value::BulkString("OK".to_string()),
+            Value::BulkString("bar".to_string()),
+            Value::BulkString("false".to_string()),
+            Value::Integer(1),
+        ]);
+        assert_eq!(actual, expected);
+    }
+
+    #[rstest::rstest]
+    #[case(
+        "return redis.call('NOSUCHCOMMAND')",
+        "ERR Unknown Redis command called from script"
+    )]
+    #[case(
+        "return redis.call('MULTI')",
+        "ERR This Redis command is not allowed from script"
+    )]
+    #[case("return redis.call('SELECT', '100')", "ERR DB index is out of range")]
+    #[tokio::test]
+    async fn sut_raises_error_replies_of_redis_call(
+        #[case] script: &str,
+        #[case] expected: &str,
+        #[from(command_executor_context)]
+        #[with(InMemoryRepository::new())]
+        context: CommandExecutorContext,
+    ) {
+        // Act
+        let actual = eval(&[script, "0"]).execute(&context).await;
+
+        // Assert
+        assert_eq!(actual, Value::Error(expected.to_string()));
+    }
+
+    #[rstest::rstest]
+    #[case(
+        "redis.call('SET', 'foo', 'bar') redis.call('SET', 'baz', 'qux')",
+        "*1\r\n$5\r\nMULTI\r\n*3\r\n$3\r\nSET\r\n$3\r\nfoo\r\n$3\r\nbar\r\n*3\r\n$3\r\nSET\r\n$3\r\nbaz\r\n$3\r\nqux\r\n*1\r\n$4\r\nEXEC\r\n"
+    )]
+    #[case("return redis.call('GET', 'foo')", "")]
+    #[tokio::test]
+    async fn sut_sends_writes_of_script_to_replicas_as_one_transaction(
+        #[case] script: &str,
+        #[case] expected: &str,
+        #[from(command_executor_context)]
+        #[with(InMemoryRepository::new())]
+        context: CommandExecutorContext,
+    ) {
+        // Arrange
+        let replica = context.new_session();
+        context.replication.attach(&replica.session);
+
+        // Act
+        eval(&[script, "0"]).execute(&context).await;
+
+        // Assert
+        let mut messages = replica.session.subscriber.messages().await;
+        let mut actual = vec![];
+        while let Ok(Value::Raw(bytes)) = messages.try_recv() {
+            actual.extend(bytes);
+        }
+        let actual = String::from_utf8(actual).unwrap();
+        assert!(actual.ends_with(expected), "{actual}");
+        assert_eq!(
+            actual.matches("MULTI").count(),
+            usize::from(!expected.is_empty())
+        );
+    }
+
+    #[rstest::rstest]
+    #[tokio::test]
+    async fn sut_refuses_writes_of_read_only_scripts(
+        #[from(command_executor_context)]
+        #[with(InMemoryRepository::new())]
+        context: CommandExecutorContext,
+    ) {
+        // Arrange
+        let value = Value::Array(
+            [
+                "EVAL_RO",
+                "return redis.call('SET', KEYS[1], 'bar')",
+                "1",
+                "foo",
+            ]
+            .iter()
+            .map(|argument| Value::BulkString(argument.to_string()))
+            .collect(),
+        );
+        let command = Eval::parse_from(&value).unwrap();
+
+        // Act
+        let actual = command.execute(&context).await;
+
+        // Assert
+        let expected = "ERR Write commands are not allowed from read-only scripts.";
+        assert_eq!(actual, Value::Error(expected.to_string()));
+        assert!(!context.repository().exists("foo").await);
+    }
+
+    #[rstest::rstest]
+    #[case("return redis.call('GET', KEYS[1])", Value::Null)]
+    #[case(
+        "return redis.call('SET', KEYS[1], 'bar')",
+        Value::Error("READONLY You can't write against a read only replica.".to_string())
+    )]
+    #[tokio::test]
+    async fn sut_runs_script_on_read_only_replica_refusing_its_writes(
+        #[case] script: &str,
+        #[case] expected: Value,
+    ) {
+        // Arrange
+        let mut config = Config::default();
+        config.replication.slave = Some(ReplicationSlave {
+            master_address: "127.0.0.1:6379".to_string(),
+        });
+        let context =
+            command_executor_context(InMemoryRepository::new(), config, Arc::new(SystemClock));
+        context
+            .replication
+            .follow("8371b4fb1155b71f4a04d3e1bc3e18c4a990aeeb".to_string(), 0);
+
+        // Act
+        let actual = eval(&[script, "1", "foo"]).execute(&context).await;
+
+        // Assert
+        assert_eq!(actual, expected);
+        assert!(!context.repository().exists("foo").await);
+    }
+
+    #[rstest::rstest]
+    #[tokio::test]
+    async fn sut_returns_error_replies_of_redis_pcall(
+        #[from(command_executor_context)]
+        #[with(InMemoryRepository::new())]
+        context: CommandExecutorContext,
+    ) {
+        // Arrange
+        let script = "return redis.pcall('NOSUCHCOMMAND').err";
+
+        // Act
+        let actual = eval(&[script, "0"]).execute(&context).await;
+
+        // Assert
+        let expected = "ERR Unknown Redis command called from script";
+        assert_eq!(actual, Value::BulkString(expected.to_string()));
+    }
+
+    #[rstest::rstest]
+    #[case("return +", "ERR Error compiling script")]
+    #[case("error('boom')", "ERR user_script:1: boom")]
+    #[tokio::test]
+    async fn sut_responds_error_if_script_fails(
+        #[case] script: &str,
+        #[case] expected: &str,
+        #[from(command_executor_context)] context: CommandExecutorContext,
+    ) {
+        // Act
+        let actual = eval(&[script, "0"]).execute(&context).await;
+
+        // Assert
+        let Value::Error(actual) = actual else {
+            panic!("expected an error but got {actual:?}");
+        };
+        assert!(actual.starts_with(expected), "{actual}");
+    }
+
+    #[rstest::rstest]
+    #[tokio::test]
+    async fn sut_runs_cached_script_by_its_digest(
+        #[from(command_executor_context)] context: CommandExecutorContext,
+    ) {
+        // Arrange
+        eval(&["return 'cached'", "0"]).execute(&context).await;
+        let sha = crate::scripting::sha1hex("return 'cached'");
+        let value = Value::Array(vec![
+            Value::BulkString("EVALSHA".to_string()),
+            Value::BulkString(sha.to_uppercase()),
+            Value::BulkString("0".to_string()),
+        ]);
+        let command = Eval::parse_from(&value).unwrap();
+
+        // Act
+        let actual = command.execute(&context).await;
+
+        // Assert
+        assert_eq!(actual, Value::BulkString("cached".to_string()));
+    }
+
+    #[rstest::rstest]
+    #[tokio::test]
+    async fn sut_responds_noscript_if_digest_is_unknown(
+        #[from(command_executor_context)] context: CommandExecutorContext,
+    ) {
+        // Arrange
+        let value = Value::Array(vec![
+            Value::BulkString("EVALSHA".to_string()),
+            Value::BulkString("ffffffffffffffffffffffffffffffffffffffff".to_string()),
+            Value::BulkString("0".to_string()),
+        ]);
+        let command = Eval::parse_from(&value).unwrap();
+
+        // Act
+        let actual = command.execute(&context).await;
+
+        // Assert
+        let expected = Value::Error("NOSCRIPT No matching script. Please use EVAL.".to_string());
+        assert_eq!(actual, expected);
+    }
+
+    #[tokio::test]
+    async fn sut_selects_database_of_caller_again_once_script_ran() {
+        // Arrange
+        let context = command_executor_context_with_databases(16);
+        let script = "redis.call('SELECT', 1) redis.call('SET', KEYS[1], 'bar')";
+        let command = eval(&[script, "1", "foo"]);
+
+        // Act
+        command.execute(&context).await;
+
+        // Assert
+        assert_eq!(context.session.database(), 0);
+        assert!(!context.repository().exists("foo").await);
+        assert!(context.databases.get(1).unwrap().exists("foo").await);
+    }
+}
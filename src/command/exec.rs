@@ -0,0 +1,303 @@
+use std::sync::atomic::Ordering;
+
+use crate::command::executor::Command;
+use crate::command::executor::CommandExecutor;
+use crate::command::executor::CommandExecutorContext;
+use crate::command::executor::CommandSet;
+use crate::command::executor::dispatch;
+use crate::command::parser::extract_array;
+use crate::command::parser::validate_array_length;
+use crate::command::parser::validate_main_command;
+use crate::resp::Value;
+
+#[derive(Debug, Default, PartialEq)]
+pub struct Exec;
+
+impl Command for Exec {
+    fn parse_from(value: &Value) -> Result<Self, anyhow::Error> {
+        let array = extract_array(value)?;
+        validate_array_length(array, 1)?;
+        validate_main_command(array, "EXEC")?;
+        Ok(Exec)
+    }
+}
+
+#[async_trait::async_trait]
+impl CommandExecutor for Exec {
+    async fn execute(&self, context: &CommandExecutorContext) -> Value {
+        let Some(transaction) = context.session.finish() else {
+            return Value::Error("ERR EXEC without MULTI".to_string());
+        };
+        let dirty = context.session.unwatch();
+        if transaction.aborted {
+            return Value::Error(
+                "EXECABORT Transaction discarded because of previous errors.".to_string(),
+            );
+        }
+        for (command, _) in &transaction.commands {
+            if let Some(refusal) = context.admit(command).await {
+                return refusal;
+            }
+        }
+
+        let _exclusive = match context.lock_exclusive().await {
+            Ok(exclusive) => exclusive,
+            Err(e) => return e,
+        };
+        if dirty.load(Ordering::SeqCst) {
+            return Value::NullArray;
+        }
+        let mut replies = Vec::with_capacity(transaction.commands.len());
+        let mut propagated = false;
+        for (command, request) in transaction.commands {
+            let write = command.is_write();
+            // Scripts propagate the writes they make themselves rather than being replayed.
+            let script = matches!(command, CommandSet::Eval(_) | CommandSet::FCall(_));
+            if write && script && !propagated {
+                context.propagate(&Value::Array(vec![Value::BulkString("MULTI".to_string())]));
+                propagated = true;
+            }
+            let reply = dispatch(command, context).await;
+            if write && !script && !matches!(reply, Value::Error(_)) {
+                // Replicas apply the writes of the transaction at once as well.
+                if !propagated {
+                    context.propagate(&Value::Array(vec![Value::BulkString("MULTI".to_string())]));
+                    propagated = true;
+                }
+                context.propagate(&request);
+            }
+            replies.push(reply);
+        }
+        if propagated {
+            context.propagate(&Value::Array(vec![Value::BulkString("EXEC".to_string())]));
+        }
+        Value::Array(replies)
+    }
+}
+
+#[cfg(test)]
+mod specs_for_parse_from {
+    use crate::command::executor::Command;
+    use crate::resp::Value;
+
+    use super::Exec;
+
+    #[rstest::rstest]
+    #[case("EXEC")]
+    #[case("exec")]
+    fn sut_parses_exec_command_with_case_insensitive(#[case] exec: &str) {
+        // Arrange
+        let value = Value::Array(vec![Value::BulkString(exec.to_string())]);
+
+        // Act
+        let actual = Exec::parse_from(&value).unwrap();
+
+        // Assert
+        assert_eq!(actual, Exec);
+    }
+}
+
+#[cfg(test)]
+mod specs_for_execute {
+    use std::time::Duration;
+
+    use crate::command::executor::CommandExecutor;
+    use crate::command::executor::CommandExecutorContext;
+    use crate::command::executor::fixture::command_executor_context;
+    use crate::command::executor::parse;
+    use crate::repository::Data;
+    use crate::repository::Entry;
+    use crate::repository::InMemoryRepository;
+    use crate::resp::Value;
+
+    use super::Exec;
+
+    fn queue(context: &CommandExecutorContext, arguments: &[&str]) {
+        let value = Value::Array(
+            arguments
+                .iter()
+                .map(|argument| Value::BulkString(argument.to_string()))
+                .collect(),
+        );
+        let command = parse(&value).unwrap();
+        assert!(context.session.queue(command, &value).is_none());
+    }
+
+    async fn set_by_other_client(context: &CommandExecutorContext) {
+        context
+            .new_session()
+            .repository()
+            .set(Entry {
+                key: "foo".into(),
+                value: Data::String("baz".to_string()),
+                expiry: None,
+            })
+            .await;
+    }
+
+    #[rstest::rstest]
+    #[tokio::test]
+    async fn sut_executes_queued_commands_in_order(
+        #[from(command_executor_context)]
+        #[with(InMemoryRepository::new())]
+        context: CommandExecutorContext,
+    ) {
+        // Arrange
+        context.session.begin();
+        queue(&context, &["SET", "foo", "bar"]);
+        queue(&context, &["GET", "foo"]);
+
+        // Act
+        let actual = Exec.execute(&context).await;
+
+        // Assert
+        let expected = Value::Array(vec![
+            Value::SimpleString("OK".to_string()),
+            Value::BulkString("bar".to_string()),
+        ]);
+        assert_eq!(actual, expected);
+        assert!(!context.session.in_transaction());
+    }
+
+    #[rstest::rstest]
+    #[tokio::test]
+    async fn sut_responds_null_array_if_watched_key_was_modified(
+        #[from(command_executor_context)]
+        #[with(InMemoryRepository::new())]
+        context: CommandExecutorContext,
+    ) {
+        // Arrange
+        let dirty = context.session.watching();
+        context.repository().watch("foo", &dirty).await;
+        context.session.begin();
+        queue(&context, &["SET", "foo", "bar"]);
+        set_by_other_client(&context).await;
+
+        // Act
+        let actual = Exec.execute(&context).await;
+
+        // Assert
+        assert_eq!(actual, Value::NullArray);
+        assert_eq!(
+            context.repository().get("foo").await,
+            Ok(Some("baz".to_string()))
+        );
+    }
+
+    #[rstest::rstest]
+    #[tokio::test]
+    async fn sut_forgets_watched_keys_once_executed(
+        #[from(command_executor_context)]
+        #[with(InMemoryRepository::new())]
+        context: CommandExecutorContext,
+    ) {
+        // Arrange
+        let dirty = context.session.watching();
+        context.repository().watch("foo", &dirty).await;
+        context.session.begin();
+        Exec.execute(&context).await;
+        set_by_other_client(&context).await;
+        context.session.begin();
+
+        // Act
+        let actual = Exec.execute(&context).await;
+
+        // Assert
+        assert_eq!(actual, Value::Array(vec![]));
+    }
+
+    #[rstest::rstest]
+    #[tokio::test]
+    async fn sut_discards_transaction_if_it_was_aborted(
+        #[from(command_executor_context)]
+        #[with(InMemoryRepository::new())]
+        context: CommandExecutorContext,
+    ) {
+        // Arrange
+        context.session.begin();
+        queue(&context, &["SET", "foo", "bar"]);
+        context.session.abort();
+
+        // Act
+        let actual = Exec.execute(&context).await;
+
+        // Assert
+        let expected =
+            Value::Error("EXECABORT Transaction discarded because of previous errors.".to_string());
+        assert_eq!(actual, expected);
+        assert!(!context.repository().exists("foo").await);
+    }
+
+    #[rstest::rstest]
+    #[tokio::test]
+    async fn sut_propagates_writes_of_queued_script_once_within_transaction(
+        #[from(command_executor_context)]
+        #[with(InMemoryRepository::new())]
+        context: CommandExecutorContext,
+    ) {
+        // Arrange
+        let replica = context.new_session();
+        context.replication.attach(&replica.session);
+        context.session.begin();
+        queue(
+            &context,
+            &[
+                "EVAL",
+                "return redis.call('SET', KEYS[1], 'bar')",
+                "1",
+                "foo",
+            ],
+        );
+
+        // Act
+        Exec.execute(&context).await;
+
+        // Assert
+        let mut messages = replica.session.subscriber.messages().await;
+        let mut actual = vec![];
+        while let Ok(Value::Raw(bytes)) = messages.try_recv() {
+            actual.extend(bytes);
+        }
+        let actual = String::from_utf8(actual).unwrap();
+        assert!(actual.contains("MULTI\r\n*3\r\n$3\r\nSET\r\n"), "{actual}");
+        assert!(actual.ends_with("EXEC\r\n"), "{actual}");
+        assert!(!actual.contains("EVAL"), "{actual}");
+    }
+
+    #[rstest::rstest]
+    #[tokio::test]
+    async fn sut_holds_back_queued_writes_while_master_role_is_handed_over(
+        #[from(command_executor_context)]
+        #[with(InMemoryRepository::new())]
+        context: CommandExecutorContext,
+    ) {
+        // Arrange
+        context.replication.begin_failover();
+        context.session.begin();
+        queue(&context, &["SET", "foo", "bar"]);
+
+        // Act
+        let (_, written_while_paused) = tokio::join!(Exec.execute(&context), async {
+            tokio::time::sleep(Duration::from_millis(50)).await;
+            let written = context.repository().exists("foo").await;
+            context.replication.end_failover(false);
+            written
+        });
+
+        // Assert
+        assert!(!written_while_paused);
+        assert!(context.repository().exists("foo").await);
+    }
+
+    #[rstest::rstest]
+    #[tokio::test]
+    async fn sut_responds_error_if_transaction_is_not_started(
+        #[from(command_executor_context)] context: CommandExecutorContext,
+    ) {
+        // Act
+        let actual = Exec.execute(&context).await;
+
+        // Assert
+        assert_eq!(actual, Value::Error("ERR EXEC without MULTI".to_string()));
+    }
+}
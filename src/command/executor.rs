@@ -1,15 +1,91 @@
 use std::sync::Arc;
+use std::time::Duration;
 
+use tokio::sync::RwLock;
+use tokio::sync::RwLockReadGuard;
+use tokio::sync::RwLockWriteGuard;
+
+use crate::clock::Clock;
+use crate::cluster::Cluster;
+use crate::cluster::Route;
+use crate::cluster::key_slot;
+use crate::cluster::keys_of;
+use crate::command::asking::Asking;
+use crate::command::client_caching::ClientCaching;
+use crate::command::client_id::ClientId;
+use crate::command::client_tracking::ClientTracking;
+use crate::command::cluster::ClusterCommand;
+use crate::command::collection_scan::CollectionScan;
 use crate::command::config_get::ConfigGet;
+use crate::command::copy::Copy;
+use crate::command::db_size::DbSize;
+use crate::command::del::Del;
+use crate::command::discard::Discard;
 use crate::command::echo::Echo;
+use crate::command::eval::Eval;
+use crate::command::exec::Exec;
+use crate::command::exists::Exists;
+use crate::command::expire::Expire;
+use crate::command::expire_time::ExpireTime;
+use crate::command::failover::Failover;
+use crate::command::fcall::FCall;
+use crate::command::flush_db::FlushDb;
+use crate::command::function::Function;
+use crate::command::geo_add::GeoAdd;
+use crate::command::geo_dist::GeoDist;
+use crate::command::geo_hash::GeoHash;
+use crate::command::geo_pos::GeoPos;
+use crate::command::geo_search::GeoSearch;
+use crate::command::geo_search_store::GeoSearchStore;
 use crate::command::get::Get;
-use crate::command::info_replication::InfoReplication;
+use crate::command::hello::Hello;
+use crate::command::info::Info;
+use crate::command::key_type::KeyType;
 use crate::command::keys::Keys;
+use crate::command::move_key::MoveKey;
+use crate::command::multi::Multi;
+use crate::command::persist::Persist;
 use crate::command::ping::Ping;
+use crate::command::psync::Psync;
+use crate::command::pub_sub::PubSub;
+use crate::command::publish::Publish;
+use crate::command::quit::Quit;
+use crate::command::random_key::RandomKey;
+use crate::command::rename::Rename;
+use crate::command::rename_nx::RenameNx;
+use crate::command::replconf::Replconf;
+use crate::command::replica_of::ReplicaOf;
+use crate::command::save::Save;
+use crate::command::scan::Scan;
+use crate::command::script::Script;
+use crate::command::script_kill::ScriptKill;
+use crate::command::select::Select;
+use crate::command::sentinel::SentinelCommand;
+use crate::command::session::Session;
 use crate::command::set::Set;
+use crate::command::subscribe::Subscribe;
+use crate::command::swap_db::SwapDb;
+use crate::command::touch::Touch;
+use crate::command::ttl::Ttl;
+use crate::command::unlink::Unlink;
+use crate::command::unsubscribe::Unsubscribe;
+use crate::command::unwatch::Unwatch;
+use crate::command::wait::Wait;
+use crate::command::wait_aof::WaitAof;
+use crate::command::watch::Watch;
 use crate::config::Config;
+use crate::notification::Class;
+use crate::notification::Notifier;
+use crate::pubsub::Broker;
+use crate::replication::Replication;
+use crate::repository::Databases;
 use crate::repository::Repository;
 use crate::resp::Value;
+use crate::scripting::BUSY;
+use crate::scripting::Scripts;
+use crate::sentinel::Sentinel;
+use crate::stats::Stats;
+use crate::tracking::Tracking;
 
 pub trait Command: Sized {
     fn parse_from(value: &Value) -> Result<Self, anyhow::Error>;
@@ -22,18 +98,375 @@ pub enum CommandSet {
     Get(Get),
     Keys(Keys),
     ConfigGet(ConfigGet),
-    InfoReplication(InfoReplication),
+    Info(Info),
+    GeoAdd(GeoAdd),
+    GeoDist(GeoDist),
+    GeoPos(GeoPos),
+    GeoHash(GeoHash),
+    GeoSearch(GeoSearch),
+    GeoSearchStore(GeoSearchStore),
+    Copy(Copy),
+    DbSize(DbSize),
+    Del(Del),
+    Exists(Exists),
+    KeyType(KeyType),
+    RandomKey(RandomKey),
+    Rename(Rename),
+    RenameNx(RenameNx),
+    Touch(Touch),
+    Unlink(Unlink),
+    Expire(Expire),
+    Ttl(Ttl),
+    ExpireTime(ExpireTime),
+    Persist(Persist),
+    Scan(Scan),
+    CollectionScan(CollectionScan),
+    Select(Select),
+    SwapDb(SwapDb),
+    MoveKey(MoveKey),
+    FlushDb(FlushDb),
+    Multi(Multi),
+    Exec(Exec),
+    Discard(Discard),
+    Watch(Watch),
+    Unwatch(Unwatch),
+    Eval(Eval),
+    Script(Script),
+    ScriptKill(ScriptKill),
+    Function(Function),
+    FCall(FCall),
+    Save(Save),
+    Subscribe(Subscribe),
+    Unsubscribe(Unsubscribe),
+    Publish(Publish),
+    PubSub(PubSub),
+    Quit(Quit),
+    Hello(Hello),
+    ClientId(ClientId),
+    ClientTracking(ClientTracking),
+    ClientCaching(ClientCaching),
+    Psync(Psync),
+    Replconf(Replconf),
+    Wait(Wait),
+    WaitAof(WaitAof),
+    ReplicaOf(ReplicaOf),
+    Failover(Failover),
+    SentinelCommand(SentinelCommand),
+    Asking(Asking),
+    ClusterCommand(ClusterCommand),
+}
+
+impl CommandSet {
+    /// Whether the command may modify the keyspace or the libraries, which read-only scripts
+    /// must not do.
+    pub fn is_write(&self) -> bool {
+        match self {
+            CommandSet::Eval(command) => return !command.is_read_only(),
+            CommandSet::FCall(command) => return !command.is_read_only(),
+            CommandSet::Function(command) => return command.is_write(),
+            _ => {}
+        }
+        matches!(
+            self,
+            CommandSet::Set(_)
+                | CommandSet::GeoAdd(_)
+                | CommandSet::GeoSearchStore(_)
+                | CommandSet::Copy(_)
+                | CommandSet::Del(_)
+                | CommandSet::Rename(_)
+                | CommandSet::RenameNx(_)
+                | CommandSet::Unlink(_)
+                | CommandSet::Expire(_)
+                | CommandSet::Persist(_)
+                | CommandSet::SwapDb(_)
+                | CommandSet::MoveKey(_)
+                | CommandSet::FlushDb(_)
+        )
+    }
+
+    /// Whether the command may be sent by a client subscribed to any channel or pattern.
+    pub fn is_allowed_when_subscribed(&self) -> bool {
+        matches!(
+            self,
+            CommandSet::Subscribe(_)
+                | CommandSet::Unsubscribe(_)
+                | CommandSet::Ping(_)
+                | CommandSet::Quit(_)
+        )
+    }
+
+    /// Whether a sentinel serves the command, as it holds no data.
+    pub fn is_allowed_in_sentinel(&self) -> bool {
+        matches!(
+            self,
+            CommandSet::Ping(_)
+                | CommandSet::Info(_)
+                | CommandSet::SentinelCommand(_)
+                | CommandSet::Subscribe(_)
+                | CommandSet::Unsubscribe(_)
+                | CommandSet::Publish(_)
+                | CommandSet::PubSub(_)
+                | CommandSet::Hello(_)
+                | CommandSet::ClientId(_)
+                | CommandSet::Quit(_)
+        )
+    }
+
+    /// Whether a replica serves the command while the link to its master is down and
+    /// replica-serve-stale-data is off.
+    pub fn is_allowed_when_stale(&self) -> bool {
+        matches!(
+            self,
+            CommandSet::Ping(_)
+                | CommandSet::ConfigGet(_)
+                | CommandSet::Info(_)
+                | CommandSet::Select(_)
+                | CommandSet::Multi(_)
+                | CommandSet::Exec(_)
+                | CommandSet::Discard(_)
+                | CommandSet::Watch(_)
+                | CommandSet::Unwatch(_)
+                | CommandSet::Subscribe(_)
+                | CommandSet::Unsubscribe(_)
+                | CommandSet::Publish(_)
+                | CommandSet::PubSub(_)
+                | CommandSet::Quit(_)
+                | CommandSet::Hello(_)
+                | CommandSet::ClientId(_)
+                | CommandSet::ClientTracking(_)
+                | CommandSet::ClientCaching(_)
+                | CommandSet::Psync(_)
+                | CommandSet::Replconf(_)
+                | CommandSet::ReplicaOf(_)
+                | CommandSet::Failover(_)
+                | CommandSet::ClusterCommand(_)
+        )
+    }
 }
 
 #[derive(Clone)]
 pub struct CommandExecutorContext {
-    pub repository: Arc<dyn Repository>,
+    pub databases: Arc<Databases>,
     pub config: Arc<Config>,
+    pub clock: Arc<dyn Clock>,
+    pub session: Arc<Session>,
+    /// Held shared by every command and exclusively by EXEC, so a transaction is never
+    /// interleaved with commands of other clients.
+    pub execution: Arc<RwLock<()>>,
+    pub scripts: Arc<Scripts>,
+    pub pubsub: Arc<Broker>,
+    pub notifier: Arc<Notifier>,
+    pub tracking: Arc<Tracking>,
+    pub replication: Arc<Replication>,
+    pub stats: Arc<Stats>,
+    /// Set when the server runs as a sentinel.
+    pub sentinel: Option<Arc<Sentinel>>,
+    /// Set when the server runs as a node of a cluster.
+    pub cluster: Option<Arc<Cluster>>,
 }
 
 impl CommandExecutorContext {
-    pub fn new(repository: Arc<dyn Repository>, config: Arc<Config>) -> Self {
-        Self { repository, config }
+    pub fn new(databases: Arc<Databases>, config: Arc<Config>, clock: Arc<dyn Clock>) -> Self {
+        let session = Arc::new(Session::new(config.server.pubsub_buffer_limit));
+        let pubsub = Arc::new(Broker::default());
+        let tracking = Arc::new(Tracking::default());
+        tracking.register(&session);
+        let master = config
+            .replication
+            .slave
+            .as_ref()
+            .map(|slave| slave.master_address.clone());
+        let replication = Arc::new(
+            Replication::new(master, config.replication.backlog_size)
+                .with_role(databases.replica()),
+        );
+        let sentinel = config
+            .sentinel
+            .as_ref()
+            .map(|sentinel| Arc::new(Sentinel::new(sentinel, config.server.port)));
+        let cluster = config
+            .server
+            .cluster_enabled
+            .then(|| Arc::new(Cluster::new(config.server.port)));
+        let notifier = Notifier::new(
+            config.server.notify_keyspace_events,
+            pubsub.clone(),
+            tracking.clone(),
+            replication.clone(),
+        );
+        Self {
+            databases,
+            config,
+            clock,
+            session,
+            execution: Arc::new(RwLock::new(())),
+            scripts: Arc::new(Scripts::default()),
+            pubsub,
+            notifier: Arc::new(notifier),
+            tracking,
+            replication,
+            stats: Arc::new(Stats::default()),
+            sentinel,
+            cluster,
+        }
+    }
+
+    /// Shares everything but the session, for a newly connected client.
+    pub fn new_session(&self) -> Self {
+        let session = Arc::new(Session::new(self.config.server.pubsub_buffer_limit));
+        self.tracking.register(&session);
+        Self {
+            session,
+            ..self.clone()
+        }
+    }
+
+    /// The error a replica replies with instead of running the command for a client other than
+    /// its master, if any.
+    pub fn refuse(&self, command_set: &CommandSet) -> Option<Value> {
+        if self.session.is_master() || !self.replication.is_replica() {
+            return None;
+        }
+        let replication = &self.config.replication;
+        // Scripts may only read here, which `redis.call` enforces command by command.
+        let script = matches!(command_set, CommandSet::Eval(_) | CommandSet::FCall(_));
+        if replication.replica_read_only && command_set.is_write() && !script {
+            return Some(Value::Error(
+                "READONLY You can't write against a read only replica.".to_string(),
+            ));
+        }
+        if !replication.replica_serve_stale_data
+            && !self.replication.is_linked()
+            && !command_set.is_allowed_when_stale()
+        {
+            return Some(Value::Error(
+                "MASTERDOWN Link with MASTER is down and replica-serve-stale-data is set to 'no'."
+                    .to_string(),
+            ));
+        }
+        None
+    }
+
+    /// Holds back a write of a client while the master role is handed over, refusing it once
+    /// a failover left this server a replica meanwhile.
+    pub async fn admit(&self, command_set: &CommandSet) -> Option<Value> {
+        if self.session.is_master() || !command_set.is_write() {
+            return None;
+        }
+        self.replication.writable().await;
+        self.refuse(command_set)
+    }
+
+    /// The redirection a cluster node replies with instead of running the request, when its keys
+    /// hash to different slots or are served by another node, if any.
+    pub async fn redirect(&self, command_set: &CommandSet, request: &Value) -> Option<Value> {
+        let cluster = self.cluster.as_ref()?;
+        if matches!(command_set, CommandSet::Asking(_)) {
+            return None;
+        }
+        let asking = self.session.take_asking();
+        let keys = keys_of(request);
+        let (first, others) = keys.split_first()?;
+        let slot = key_slot(first);
+        if others.iter().any(|key| key_slot(key) != slot) {
+            return Some(Value::Error(
+                "CROSSSLOT Keys in request don't hash to the same slot".to_string(),
+            ));
+        }
+        match cluster.route(slot, asking) {
+            Route::Local => None,
+            Route::Migrating(address) => {
+                let repository = self.repository();
+                let mut present = 0;
+                for key in &keys {
+                    if repository.exists(key).await {
+                        present += 1;
+                    }
+                }
+                // Keys split between both nodes can be served by neither until the move ends.
+                match present {
+                    0 => Some(Value::Error(format!("ASK {slot} {address}"))),
+                    present if present < keys.len() => Some(Value::Error(
+                        "TRYAGAIN Multiple keys request during rehashing of slot".to_string(),
+                    )),
+                    _ => None,
+                }
+            }
+            Route::Moved(address) => Some(Value::Error(format!("MOVED {slot} {address}"))),
+            Route::Unassigned => Some(Value::Error("CLUSTERDOWN Hash slot not served".to_string())),
+        }
+    }
+
+    /// Waits for the execution lock to be shared, giving up with BUSY once a script has been
+    /// running for too long.
+    pub async fn lock_shared(&self) -> Result<RwLockReadGuard<'_, ()>, Value> {
+        tokio::select! {
+            biased;
+            shared = self.execution.read() => Ok(shared),
+            _ = self.scripts.busy(self.busy_reply_threshold()) => {
+                Err(Value::Error(BUSY.to_string()))
+            }
+        }
+    }
+
+    /// Waits for the execution lock to be exclusive, giving up with BUSY once a script has been
+    /// running for too long.
+    pub async fn lock_exclusive(&self) -> Result<RwLockWriteGuard<'_, ()>, Value> {
+        tokio::select! {
+            biased;
+            exclusive = self.execution.write() => Ok(exclusive),
+            _ = self.scripts.busy(self.busy_reply_threshold()) => {
+                Err(Value::Error(BUSY.to_string()))
+            }
+        }
+    }
+
+    fn busy_reply_threshold(&self) -> Duration {
+        Duration::from_millis(self.config.server.busy_reply_threshold)
+    }
+
+    /// Notifies an event of the key in the database selected by the session.
+    pub fn notify(&self, class: Class, event: &str, key: &str) {
+        self.notify_in(self.session.database(), class, event, key);
+    }
+
+    /// Notifies an event of the key in the given database, after whatever the repositories
+    /// recorded meanwhile.
+    pub fn notify_in(&self, database: usize, class: Class, event: &str, key: &str) {
+        self.notifier.flush(&self.databases);
+        self.notifier
+            .notify(database, class, event, key, Some(self.session.id()));
+    }
+
+    /// Counts a write as a change to save, and sends it to the replicas unless it came from the
+    /// master of this server. Relative expiry times are sent as absolute ones, so replicas applying
+    /// the write late do not keep the key longer.
+    pub fn propagate(&self, request: &Value) {
+        self.stats.changed();
+        if !self.session.is_master() {
+            let now_in_millis = self.clock.now_in_millis();
+            let request = if let Ok(command) = Expire::parse_from(request) {
+                command.absolute(now_in_millis)
+            } else if let Ok(command) = Set::parse_from(request) {
+                command.absolute(now_in_millis)
+            } else {
+                request.clone()
+            };
+            self.replication
+                .propagate(self.session.database(), &request);
+        }
+    }
+
+    /// Remembers the key was read by the client, for it to be invalidated once modified.
+    pub fn track(&self, key: &str) {
+        self.tracking.remember(&self.session, key);
+    }
+
+    /// The repository of the database selected by the session.
+    pub fn repository(&self) -> Arc<dyn Repository> {
+        self.databases
+            .get(self.session.database())
+            .expect("selected database always exists")
     }
 }
 
@@ -61,23 +494,678 @@ pub fn parse(value: &Value) -> Result<CommandSet, anyhow::Error> {
     if let Ok(command) = ConfigGet::parse_from(value) {
         return Ok(CommandSet::ConfigGet(command));
     }
-    if let Ok(command) = InfoReplication::parse_from(value) {
-        return Ok(CommandSet::InfoReplication(command));
+    if let Ok(command) = Info::parse_from(value) {
+        return Ok(CommandSet::Info(command));
+    }
+    if let Ok(command) = GeoAdd::parse_from(value) {
+        return Ok(CommandSet::GeoAdd(command));
+    }
+    if let Ok(command) = GeoDist::parse_from(value) {
+        return Ok(CommandSet::GeoDist(command));
+    }
+    if let Ok(command) = GeoPos::parse_from(value) {
+        return Ok(CommandSet::GeoPos(command));
+    }
+    if let Ok(command) = GeoHash::parse_from(value) {
+        return Ok(CommandSet::GeoHash(command));
+    }
+    if let Ok(command) = GeoSearch::parse_from(value) {
+        return Ok(CommandSet::GeoSearch(command));
+    }
+    if let Ok(command) = GeoSearchStore::parse_from(value) {
+        return Ok(CommandSet::GeoSearchStore(command));
+    }
+    if let Ok(command) = Copy::parse_from(value) {
+        return Ok(CommandSet::Copy(command));
+    }
+    if let Ok(command) = DbSize::parse_from(value) {
+        return Ok(CommandSet::DbSize(command));
+    }
+    if let Ok(command) = Del::parse_from(value) {
+        return Ok(CommandSet::Del(command));
+    }
+    if let Ok(command) = Exists::parse_from(value) {
+        return Ok(CommandSet::Exists(command));
+    }
+    if let Ok(command) = KeyType::parse_from(value) {
+        return Ok(CommandSet::KeyType(command));
+    }
+    if let Ok(command) = RandomKey::parse_from(value) {
+        return Ok(CommandSet::RandomKey(command));
+    }
+    if let Ok(command) = Rename::parse_from(value) {
+        return Ok(CommandSet::Rename(command));
+    }
+    if let Ok(command) = RenameNx::parse_from(value) {
+        return Ok(CommandSet::RenameNx(command));
+    }
+    if let Ok(command) = Touch::parse_from(value) {
+        return Ok(CommandSet::Touch(command));
+    }
+    if let Ok(command) = Unlink::parse_from(value) {
+        return Ok(CommandSet::Unlink(command));
+    }
+    if let Ok(command) = Expire::parse_from(value) {
+        return Ok(CommandSet::Expire(command));
+    }
+    if let Ok(command) = Ttl::parse_from(value) {
+        return Ok(CommandSet::Ttl(command));
+    }
+    if let Ok(command) = ExpireTime::parse_from(value) {
+        return Ok(CommandSet::ExpireTime(command));
+    }
+    if let Ok(command) = Persist::parse_from(value) {
+        return Ok(CommandSet::Persist(command));
+    }
+    if let Ok(command) = Scan::parse_from(value) {
+        return Ok(CommandSet::Scan(command));
+    }
+    if let Ok(command) = CollectionScan::parse_from(value) {
+        return Ok(CommandSet::CollectionScan(command));
+    }
+    if let Ok(command) = Select::parse_from(value) {
+        return Ok(CommandSet::Select(command));
+    }
+    if let Ok(command) = SwapDb::parse_from(value) {
+        return Ok(CommandSet::SwapDb(command));
+    }
+    if let Ok(command) = MoveKey::parse_from(value) {
+        return Ok(CommandSet::MoveKey(command));
+    }
+    if let Ok(command) = FlushDb::parse_from(value) {
+        return Ok(CommandSet::FlushDb(command));
+    }
+    if let Ok(command) = Multi::parse_from(value) {
+        return Ok(CommandSet::Multi(command));
+    }
+    if let Ok(command) = Exec::parse_from(value) {
+        return Ok(CommandSet::Exec(command));
+    }
+    if let Ok(command) = Discard::parse_from(value) {
+        return Ok(CommandSet::Discard(command));
+    }
+    if let Ok(command) = Watch::parse_from(value) {
+        return Ok(CommandSet::Watch(command));
+    }
+    if let Ok(command) = Unwatch::parse_from(value) {
+        return Ok(CommandSet::Unwatch(command));
+    }
+    if let Ok(command) = Eval::parse_from(value) {
+        return Ok(CommandSet::Eval(command));
+    }
+    if let Ok(command) = Script::parse_from(value) {
+        return Ok(CommandSet::Script(command));
+    }
+    if let Ok(command) = ScriptKill::parse_from(value) {
+        return Ok(CommandSet::ScriptKill(command));
+    }
+    if let Ok(command) = Function::parse_from(value) {
+        return Ok(CommandSet::Function(command));
+    }
+    if let Ok(command) = FCall::parse_from(value) {
+        return Ok(CommandSet::FCall(command));
+    }
+    if let Ok(command) = Save::parse_from(value) {
+        return Ok(CommandSet::Save(command));
+    }
+    if let Ok(command) = Subscribe::parse_from(value) {
+        return Ok(CommandSet::Subscribe(command));
+    }
+    if let Ok(command) = Unsubscribe::parse_from(value) {
+        return Ok(CommandSet::Unsubscribe(command));
+    }
+    if let Ok(command) = Publish::parse_from(value) {
+        return Ok(CommandSet::Publish(command));
+    }
+    if let Ok(command) = PubSub::parse_from(value) {
+        return Ok(CommandSet::PubSub(command));
+    }
+    if let Ok(command) = Quit::parse_from(value) {
+        return Ok(CommandSet::Quit(command));
+    }
+    if let Ok(command) = Hello::parse_from(value) {
+        return Ok(CommandSet::Hello(command));
+    }
+    if let Ok(command) = ClientId::parse_from(value) {
+        return Ok(CommandSet::ClientId(command));
+    }
+    if let Ok(command) = ClientTracking::parse_from(value) {
+        return Ok(CommandSet::ClientTracking(command));
+    }
+    if let Ok(command) = ClientCaching::parse_from(value) {
+        return Ok(CommandSet::ClientCaching(command));
+    }
+    if let Ok(command) = Psync::parse_from(value) {
+        return Ok(CommandSet::Psync(command));
+    }
+    if let Ok(command) = Replconf::parse_from(value) {
+        return Ok(CommandSet::Replconf(command));
+    }
+    if let Ok(command) = Wait::parse_from(value) {
+        return Ok(CommandSet::Wait(command));
+    }
+    if let Ok(command) = WaitAof::parse_from(value) {
+        return Ok(CommandSet::WaitAof(command));
+    }
+    if let Ok(command) = ReplicaOf::parse_from(value) {
+        return Ok(CommandSet::ReplicaOf(command));
+    }
+    if let Ok(command) = Failover::parse_from(value) {
+        return Ok(CommandSet::Failover(command));
+    }
+    if let Ok(command) = SentinelCommand::parse_from(value) {
+        return Ok(CommandSet::SentinelCommand(command));
+    }
+    if let Ok(command) = Asking::parse_from(value) {
+        return Ok(CommandSet::Asking(command));
+    }
+    if let Ok(command) = ClusterCommand::parse_from(value) {
+        return Ok(CommandSet::ClusterCommand(command));
     }
     Err(anyhow::anyhow!(
         "unable to parse value as any supported command"
     ))
 }
 
-pub async fn execute(command_set: CommandSet, context: &CommandExecutorContext) -> Value {
-    match command_set {
+/// Executes a command sent by a client, propagating it as `request` once it wrote anything.
+pub async fn execute(
+    command_set: CommandSet,
+    request: &Value,
+    context: &CommandExecutorContext,
+) -> Value {
+    // Scripts may write as well, EXEC holding back its writes by itself.
+    let refusal = match command_set {
+        CommandSet::Eval(_) | CommandSet::FCall(_) => context.admit(&command_set).await,
+        _ => None,
+    };
+    if let Some(refusal) = refusal {
+        return refusal;
+    }
+    match &command_set {
+        // Take the execution lock exclusively, or not at all to stop a running script.
+        CommandSet::Exec(command) => return command.execute(context).await,
+        CommandSet::Eval(command) => return command.execute(context).await,
+        CommandSet::ScriptKill(command) => return command.execute(context).await,
+        CommandSet::FCall(command) => return command.execute(context).await,
+        CommandSet::Psync(command) => return command.execute(context).await,
+        // Block without the execution lock, so writes go on meanwhile.
+        CommandSet::Wait(command) => return command.execute(context).await,
+        CommandSet::WaitAof(command) => return command.execute(context).await,
+        _ => {}
+    }
+    if command_set.is_write() {
+        if let Some(refusal) = context.admit(&command_set).await {
+            return refusal;
+        }
+        // Writes never interleave, so replicas apply them in the order they were.
+        let _exclusive = match context.lock_exclusive().await {
+            Ok(exclusive) => exclusive,
+            Err(e) => return e,
+        };
+        let reply = dispatch(command_set, context).await;
+        if !matches!(reply, Value::Error(_)) {
+            context.propagate(request);
+        }
+        return reply;
+    }
+    let _shared = match context.lock_shared().await {
+        Ok(shared) => shared,
+        Err(e) => return e,
+    };
+    dispatch(command_set, context).await
+}
+
+pub async fn dispatch(command_set: CommandSet, context: &CommandExecutorContext) -> Value {
+    let reply = match command_set {
         CommandSet::Ping(command) => command.execute(context).await,
         CommandSet::Echo(command) => command.execute(context).await,
         CommandSet::Set(command) => command.execute(context).await,
         CommandSet::Get(command) => command.execute(context).await,
         CommandSet::Keys(command) => command.execute(context).await,
         CommandSet::ConfigGet(command) => command.execute(context).await,
-        CommandSet::InfoReplication(command) => command.execute(context).await,
+        CommandSet::Info(command) => command.execute(context).await,
+        CommandSet::GeoAdd(command) => command.execute(context).await,
+        CommandSet::GeoDist(command) => command.execute(context).await,
+        CommandSet::GeoPos(command) => command.execute(context).await,
+        CommandSet::GeoHash(command) => command.execute(context).await,
+        CommandSet::GeoSearch(command) => command.execute(context).await,
+        CommandSet::GeoSearchStore(command) => command.execute(context).await,
+        CommandSet::Copy(command) => command.execute(context).await,
+        CommandSet::DbSize(command) => command.execute(context).await,
+        CommandSet::Del(command) => command.execute(context).await,
+        CommandSet::Exists(command) => command.execute(context).await,
+        CommandSet::KeyType(command) => command.execute(context).await,
+        CommandSet::RandomKey(command) => command.execute(context).await,
+        CommandSet::Rename(command) => command.execute(context).await,
+        CommandSet::RenameNx(command) => command.execute(context).await,
+        CommandSet::Touch(command) => command.execute(context).await,
+        CommandSet::Unlink(command) => command.execute(context).await,
+        CommandSet::Expire(command) => command.execute(context).await,
+        CommandSet::Ttl(command) => command.execute(context).await,
+        CommandSet::ExpireTime(command) => command.execute(context).await,
+        CommandSet::Persist(command) => command.execute(context).await,
+        CommandSet::Scan(command) => command.execute(context).await,
+        CommandSet::CollectionScan(command) => command.execute(context).await,
+        CommandSet::Select(command) => command.execute(context).await,
+        CommandSet::SwapDb(command) => command.execute(context).await,
+        CommandSet::MoveKey(command) => command.execute(context).await,
+        CommandSet::FlushDb(command) => command.execute(context).await,
+        CommandSet::Multi(command) => command.execute(context).await,
+        CommandSet::Exec(command) => command.execute(context).await,
+        CommandSet::Discard(command) => command.execute(context).await,
+        CommandSet::Watch(command) => command.execute(context).await,
+        CommandSet::Unwatch(command) => command.execute(context).await,
+        // Within a transaction, the execution lock is held already.
+        CommandSet::Eval(command) => command.run(context, false).await,
+        CommandSet::Script(command) => command.execute(context).await,
+        CommandSet::ScriptKill(command) => command.execute(context).await,
+        CommandSet::Function(command) => command.execute(context).await,
+        CommandSet::FCall(command) => command.run(context, false).await,
+        CommandSet::Save(command) => command.execute(context).await,
+        CommandSet::Subscribe(command) => command.execute(context).await,
+        CommandSet::Unsubscribe(command) => command.execute(context).await,
+        CommandSet::Publish(command) => command.execute(context).await,
+        CommandSet::PubSub(command) => command.execute(context).await,
+        CommandSet::Quit(command) => command.execute(context).await,
+        CommandSet::Hello(command) => command.execute(context).await,
+        CommandSet::ClientId(command) => command.execute(context).await,
+        CommandSet::ClientTracking(command) => command.execute(context).await,
+        CommandSet::ClientCaching(command) => command.execute(context).await,
+        CommandSet::Psync(command) => command.execute(context).await,
+        CommandSet::Replconf(command) => command.execute(context).await,
+        // Within a transaction, never block while holding the execution lock.
+        CommandSet::Wait(command) => command.count(context),
+        CommandSet::WaitAof(command) => command.count(context),
+        CommandSet::ReplicaOf(command) => command.execute(context).await,
+        CommandSet::Failover(command) => command.execute(context).await,
+        CommandSet::SentinelCommand(command) => command.execute(context).await,
+        CommandSet::Asking(command) => command.execute(context).await,
+        CommandSet::ClusterCommand(command) => command.execute(context).await,
+    };
+    context.notifier.flush(&context.databases);
+    reply
+}
+
+#[cfg(test)]
+mod specs_for_refuse {
+    use std::sync::Arc;
+
+    use crate::clock::SystemClock;
+    use crate::command::executor::CommandExecutorContext;
+    use crate::command::executor::CommandSet;
+    use crate::command::executor::fixture::command_executor_context;
+    use crate::command::executor::parse;
+    use crate::config::Config;
+    use crate::config::ReplicationSlave;
+    use crate::repository::fixture::DummyRepository;
+    use crate::resp::Value;
+
+    const READONLY: &str = "READONLY You can't write against a read only replica.";
+    const MASTERDOWN: &str =
+        "MASTERDOWN Link with MASTER is down and replica-serve-stale-data is set to 'no'.";
+
+    fn command(arguments: &[&str]) -> CommandSet {
+        let value = Value::Array(
+            arguments
+                .iter()
+                .map(|argument| Value::BulkString(argument.to_string()))
+                .collect(),
+        );
+        parse(&value).unwrap()
+    }
+
+    fn replica(read_only: bool, serve_stale_data: bool) -> CommandExecutorContext {
+        let mut config = Config::default();
+        config.replication.slave = Some(ReplicationSlave {
+            master_address: "127.0.0.1:6379".to_string(),
+        });
+        config.replication.replica_read_only = read_only;
+        config.replication.replica_serve_stale_data = serve_stale_data;
+        command_executor_context(DummyRepository, config, Arc::new(SystemClock))
+    }
+
+    #[rstest::rstest]
+    #[case(&["SET", "foo", "bar"])]
+    #[case(&["GET", "foo"])]
+    fn sut_runs_every_command_on_a_master(
+        #[case] arguments: &[&str],
+        #[from(command_executor_context)] context: CommandExecutorContext,
+    ) {
+        // Act
+        let actual = context.refuse(&command(arguments));
+
+        // Assert
+        assert_eq!(actual, None);
+    }
+
+    #[rstest::rstest]
+    #[case(true, &["SET", "foo", "bar"], Some(READONLY))]
+    #[case(true, &["DEL", "foo"], Some(READONLY))]
+    #[case(true, &["GET", "foo"], None)]
+    #[case(true, &["EVAL", "return 1", "0"], None)]
+    #[case(true, &["EVALSHA", "0123", "0"], None)]
+    #[case(true, &["EVAL_RO", "return 1", "0"], None)]
+    #[case(true, &["FCALL", "f", "0"], None)]
+    #[case(true, &["FCALL_RO", "f", "0"], None)]
+    #[case(true, &["FUNCTION", "LOAD", "code"], Some(READONLY))]
+    #[case(true, &["FUNCTION", "DELETE", "mylib"], Some(READONLY))]
+    #[case(true, &["FUNCTION", "RESTORE", "payload"], Some(READONLY))]
+    #[case(true, &["FUNCTION", "FLUSH"], Some(READONLY))]
+    #[case(true, &["FUNCTION", "LIST"], None)]
+    #[case(false, &["SET", "foo", "bar"], None)]
+    fn sut_refuses_writes_of_clients_on_a_read_only_replica(
+        #[case] read_only: bool,
+        #[case] arguments: &[&str],
+        #[case] expected: Option<&str>,
+    ) {
+        // Arrange
+        let context = replica(read_only, true);
+        context
+            .replication
+            .follow("8371b4fb1155b71f4a04d3e1bc3e18c4a990aeeb".to_string(), 0);
+
+        // Act
+        let actual = context.refuse(&command(arguments));
+
+        // Assert
+        assert_eq!(actual, expected.map(|e| Value::Error(e.to_string())));
+    }
+
+    #[rstest::rstest]
+    fn sut_accepts_writes_of_its_master() {
+        // Arrange
+        let context = replica(true, false);
+        context.session.mark_master();
+
+        // Act
+        let actual = context.refuse(&command(&["SET", "foo", "bar"]));
+
+        // Assert
+        assert_eq!(actual, None);
+    }
+
+    #[rstest::rstest]
+    #[case(false, false, &["GET", "foo"], Some(MASTERDOWN))]
+    #[case(false, false, &["PING"], None)]
+    #[case(false, false, &["REPLICAOF", "NO", "ONE"], None)]
+    #[case(false, true, &["GET", "foo"], None)]
+    #[case(true, false, &["GET", "foo"], None)]
+    fn sut_refuses_stale_data_while_link_is_down(
+        #[case] linked: bool,
+        #[case] serve_stale_data: bool,
+        #[case] arguments: &[&str],
+        #[case] expected: Option<&str>,
+    ) {
+        // Arrange
+        let context = replica(false, serve_stale_data);
+        if linked {
+            context
+                .replication
+                .follow("8371b4fb1155b71f4a04d3e1bc3e18c4a990aeeb".to_string(), 0);
+        }
+
+        // Act
+        let actual = context.refuse(&command(arguments));
+
+        // Assert
+        assert_eq!(actual, expected.map(|e| Value::Error(e.to_string())));
+    }
+}
+
+#[cfg(test)]
+mod specs_for_execute {
+    use std::time::Duration;
+
+    use crate::command::executor::CommandExecutorContext;
+    use crate::command::executor::execute;
+    use crate::command::executor::fixture::command_executor_context;
+    use crate::command::executor::parse;
+    use crate::repository::InMemoryRepository;
+    use crate::resp::Value;
+
+    #[rstest::rstest]
+    #[case(&["SET", "foo", "bar"])]
+    #[case(&["EVAL", "return redis.call('SET', KEYS[1], 'bar')", "1", "foo"])]
+    #[tokio::test]
+    async fn sut_holds_back_writes_while_master_role_is_handed_over(
+        #[case] arguments: &[&str],
+        #[from(command_executor_context)]
+        #[with(InMemoryRepository::new())]
+        context: CommandExecutorContext,
+    ) {
+        // Arrange
+        let request = Value::Array(
+            arguments
+                .iter()
+                .map(|argument| Value::BulkString(argument.to_string()))
+                .collect(),
+        );
+        context.replication.begin_failover();
+
+        // Act
+        let (_, written_while_paused) = tokio::join!(
+            execute(parse(&request).unwrap(), &request, &context),
+            async {
+                tokio::time::sleep(Duration::from_millis(50)).await;
+                let written = context.repository().exists("foo").await;
+                context.replication.end_failover(false);
+                written
+            }
+        );
+
+        // Assert
+        assert!(!written_while_paused);
+        assert!(context.repository().exists("foo").await);
+    }
+}
+
+#[cfg(test)]
+mod specs_for_propagate {
+    use crate::clock::Clock;
+    use crate::command::executor::fixture::command_executor_context_with_fake_clock;
+    use crate::command::session::Session;
+    use crate::resp::Value;
+
+    fn request(arguments: &[&str]) -> Value {
+        Value::Array(
+            arguments
+                .iter()
+                .map(|argument| Value::BulkString(argument.to_string()))
+                .collect(),
+        )
+    }
+
+    #[rstest::rstest]
+    #[case(&["EXPIRE", "foo", "10"], &["PEXPIREAT", "foo", "+10000"])]
+    #[case(&["PEXPIRE", "foo", "10", "NX"], &["PEXPIREAT", "foo", "+10", "NX"])]
+    #[case(&["PEXPIREAT", "foo", "10"], &["PEXPIREAT", "foo", "10"])]
+    #[case(&["SET", "foo", "bar", "EX", "10"], &["SET", "foo", "bar", "PXAT", "+10000"])]
+    #[case(&["SET", "foo", "bar", "PX", "10"], &["SET", "foo", "bar", "PXAT", "+10"])]
+    #[case(&["SET", "foo", "bar"], &["SET", "foo", "bar"])]
+    #[case(&["DEL", "foo"], &["DEL", "foo"])]
+    #[tokio::test]
+    async fn sut_sends_replicas_expiry_times_as_absolute_ones(
+        #[case] arguments: &[&str],
+        #[case] expected: &[&str],
+    ) {
+        // Arrange
+        let (context, clock) = command_executor_context_with_fake_clock();
+        let now_in_millis = clock.now_in_millis();
+        let replica = Session::new(8);
+        context.replication.attach(&replica);
+
+        // Act
+        context.propagate(&request(arguments));
+
+        // Assert
+        let mut messages = replica.subscriber.messages().await;
+        let Ok(Value::Raw(actual)) = messages.try_recv() else {
+            panic!("expected the write");
+        };
+        // Times prefixed with + are relative to now.
+        let expected: Vec<String> = expected
+            .iter()
+            .map(|argument| match argument.strip_prefix('+') {
+                Some(millis) => (now_in_millis + millis.parse::<u128>().unwrap()).to_string(),
+                None => argument.to_string(),
+            })
+            .collect();
+        let expected: Vec<&str> = expected.iter().map(String::as_str).collect();
+        let expected = request(&expected).serialize();
+        assert!(actual.ends_with(&expected), "{actual:?}");
+    }
+}
+
+#[cfg(test)]
+mod specs_for_redirect {
+    use std::sync::Arc;
+
+    use crate::clock::SystemClock;
+    use crate::cluster::Cluster;
+    use crate::cluster::SlotState;
+    use crate::cluster::key_slot;
+    use crate::command::executor::CommandExecutorContext;
+    use crate::command::executor::fixture::command_executor_context;
+    use crate::command::executor::parse;
+    use crate::config::Config;
+    use crate::config::Server;
+    use crate::repository::Data;
+    use crate::repository::Entry;
+    use crate::repository::InMemoryRepository;
+    use crate::repository::Repository;
+    use crate::resp::Value;
+
+    const OTHER: &str = "127.0.0.1:7001";
+
+    async fn redirect(context: &CommandExecutorContext, arguments: &[&str]) -> Option<Value> {
+        let value = Value::Array(
+            arguments
+                .iter()
+                .map(|argument| Value::BulkString(argument.to_string()))
+                .collect(),
+        );
+        context.redirect(&parse(&value).unwrap(), &value).await
+    }
+
+    /// A node serving the slot of `foo`, which holds that key, along with another node serving
+    /// the slot of `bar`.
+    async fn node() -> (CommandExecutorContext, Arc<Cluster>) {
+        let repository = InMemoryRepository::new();
+        repository
+            .set(Entry {
+                key: "foo".into(),
+                value: Data::String("bar".to_string()),
+                expiry: None,
+            })
+            .await;
+        let config = Config {
+            server: Server {
+                cluster_enabled: true,
+                ..Server::default()
+            },
+            ..Config::default()
+        };
+        let context = command_executor_context(repository, config, Arc::new(SystemClock));
+        let cluster = context.cluster.clone().unwrap();
+        cluster.add_slots(&[key_slot("foo")]).unwrap();
+        let bar = key_slot("bar");
+        let description = format!("other {OTHER}@17001 myself,master - 0 0 0 connected {bar}\n");
+        cluster.heard(OTHER, &description);
+        (context, cluster)
+    }
+
+    fn error(message: &str) -> Option<Value> {
+        Some(Value::Error(message.to_string()))
+    }
+
+    #[rstest::rstest]
+    #[tokio::test]
+    async fn sut_runs_every_command_outside_cluster_mode(
+        #[from(command_executor_context)] context: CommandExecutorContext,
+    ) {
+        // Act
+        let actual = redirect(&context, &["DEL", "foo", "bar"]).await;
+
+        // Assert
+        assert_eq!(actual, None);
+    }
+
+    #[rstest::rstest]
+    #[case(&["GET", "foo"], None)]
+    #[case(&["DEL", "foo", "{foo}.other"], None)]
+    #[case(&["PING"], None)]
+    #[case(&["GET", "bar"], error("MOVED 5061 127.0.0.1:7001"))]
+    #[case(&["GET", "baz"], error("CLUSTERDOWN Hash slot not served"))]
+    #[case(&["DEL", "foo", "bar"], error("CROSSSLOT Keys in request don't hash to the same slot"))]
+    #[case(&["EVAL", "return 1", "2", "foo", "bar"], error("CROSSSLOT Keys in request don't hash to the same slot"))]
+    #[tokio::test]
+    async fn sut_redirects_commands_on_keys_served_elsewhere(
+        #[case] arguments: &[&str],
+        #[case] expected: Option<Value>,
+    ) {
+        // Arrange
+        let (context, _) = node().await;
+
+        // Act
+        let actual = redirect(&context, arguments).await;
+
+        // Assert
+        assert_eq!(actual, expected);
+    }
+
+    #[tokio::test]
+    async fn sut_asks_to_try_missing_keys_of_migrating_slot_on_its_target() {
+        // Arrange
+        let (context, cluster) = node().await;
+        let slot = key_slot("foo");
+        cluster
+            .set_slot(slot, SlotState::Migrating("other".to_string()))
+            .unwrap();
+
+        // Act
+        let present = redirect(&context, &["GET", "foo"]).await;
+        let missing = redirect(&context, &["GET", "{foo}.missing"]).await;
+        let all_missing = redirect(&context, &["DEL", "{foo}.a", "{foo}.b"]).await;
+
+        // Assert
+        assert_eq!(present, None);
+        assert_eq!(missing, error(&format!("ASK {slot} {OTHER}")));
+        assert_eq!(all_missing, error(&format!("ASK {slot} {OTHER}")));
+    }
+
+    #[tokio::test]
+    async fn sut_asks_to_try_again_keys_of_migrating_slot_split_between_nodes() {
+        // Arrange
+        let (context, cluster) = node().await;
+        let slot = key_slot("foo");
+        cluster
+            .set_slot(slot, SlotState::Migrating("other".to_string()))
+            .unwrap();
+
+        // Act
+        let actual = redirect(&context, &["DEL", "foo", "{foo}.missing"]).await;
+
+        // Assert
+        let expected = error("TRYAGAIN Multiple keys request during rehashing of slot");
+        assert_eq!(actual, expected);
+    }
+
+    #[tokio::test]
+    async fn sut_serves_importing_slot_for_the_command_following_asking() {
+        // Arrange
+        let (context, cluster) = node().await;
+        cluster
+            .set_slot(key_slot("bar"), SlotState::Importing("other".to_string()))
+            .unwrap();
+
+        // Act
+        context.session.set_asking();
+        let asking = redirect(&context, &["GET", "bar"]).await;
+        let following = redirect(&context, &["GET", "bar"]).await;
+
+        // Assert
+        assert_eq!(asking, None);
+        assert_eq!(following, error("MOVED 5061 127.0.0.1:7001"));
     }
 }
 
@@ -85,16 +1173,39 @@ pub async fn execute(command_set: CommandSet, context: &CommandExecutorContext)
 pub mod fixture {
     use std::sync::Arc;
 
+    use crate::clock::Clock;
+    use crate::clock::SystemClock;
+    use crate::clock::fixture::FakeClock;
     use crate::command::executor::CommandExecutorContext;
     use crate::config::Config;
+    use crate::repository::Databases;
+    use crate::repository::InMemoryRepository;
     use crate::repository::Repository;
     use crate::repository::fixture::DummyRepository;
 
+    /// A context with a single database backed by `repository`.
     #[rstest::fixture]
     pub fn command_executor_context(
         #[default(DummyRepository)] repository: impl Repository,
         #[default(Config::default())] config: Config,
+        #[default(Arc::new(SystemClock))] clock: Arc<dyn Clock>,
     ) -> CommandExecutorContext {
-        CommandExecutorContext::new(Arc::new(repository), Arc::new(config))
+        let databases = Databases::new(vec![Arc::new(repository)]);
+        CommandExecutorContext::new(Arc::new(databases), Arc::new(config), clock)
+    }
+
+    /// A context with `count` in-memory databases.
+    pub fn command_executor_context_with_databases(count: usize) -> CommandExecutorContext {
+        let clock = Arc::new(SystemClock);
+        let databases = Databases::in_memory(count, clock.clone());
+        CommandExecutorContext::new(Arc::new(databases), Arc::new(Config::default()), clock)
+    }
+
+    /// A context whose repository and commands share a clock that only moves when advanced.
+    pub fn command_executor_context_with_fake_clock() -> (CommandExecutorContext, Arc<FakeClock>) {
+        let clock = Arc::new(FakeClock::default());
+        let repository = InMemoryRepository::with_clock(clock.clone());
+        let context = command_executor_context(repository, Config::default(), clock.clone());
+        (context, clock)
     }
 }
@@ -0,0 +1,110 @@
+use crate::command::executor::Command;
+use crate::command::executor::CommandExecutor;
+use crate::command::executor::CommandExecutorContext;
+use crate::command::parser::extract_array;
+use crate::command::parser::extract_bulk_strings;
+use crate::command::parser::validate_main_command;
+use crate::command::parser::validate_min_array_length;
+use crate::resp::Value;
+
+#[derive(Debug, Default, PartialEq)]
+pub struct Exists {
+    keys: Vec<String>,
+}
+
+impl Command for Exists {
+    fn parse_from(value: &Value) -> Result<Self, anyhow::Error> {
+        let array = extract_array(value)?;
+        validate_min_array_length(array, 2)?;
+        validate_main_command(array, "EXISTS")?;
+        let keys = extract_bulk_strings(array, 1)?;
+        Ok(Exists { keys })
+    }
+}
+
+#[async_trait::async_trait]
+impl CommandExecutor for Exists {
+    async fn execute(&self, context: &CommandExecutorContext) -> Value {
+        let mut count = 0;
+        for key in self.keys.iter() {
+            context.track(key);
+            if context.repository().exists(key).await {
+                count += 1;
+            }
+        }
+        Value::Integer(count)
+    }
+}
+
+#[cfg(test)]
+mod specs_for_parse_from {
+    use crate::command::executor::Command;
+    use crate::resp::Value;
+
+    use super::Exists;
+
+    #[rstest::rstest]
+    #[case("EXISTS")]
+    #[case("exists")]
+    #[case("ExIsTs")]
+    fn sut_parses_exists_command_with_case_insensitive(#[case] exists: &str) {
+        // Arrange
+        let value = Value::Array(vec![
+            Value::BulkString(exists.to_string()),
+            Value::BulkString("foo".to_string()),
+        ]);
+
+        // Act
+        let actual = Exists::parse_from(&value).unwrap();
+
+        // Assert
+        let expected = Exists {
+            keys: vec!["foo".to_string()],
+        };
+        assert_eq!(actual, expected);
+    }
+}
+
+#[cfg(test)]
+mod specs_for_execute {
+    use fake::Fake;
+    use fake::faker::lorem::en::Word;
+
+    use crate::command::executor::CommandExecutor;
+    use crate::command::executor::CommandExecutorContext;
+    use crate::command::executor::fixture::command_executor_context;
+    use crate::repository::Data;
+    use crate::repository::Entry;
+    use crate::repository::InMemoryRepository;
+    use crate::resp::Value;
+
+    use super::Exists;
+
+    #[rstest::rstest]
+    #[tokio::test]
+    async fn sut_counts_existing_keys_including_duplicates(
+        #[from(command_executor_context)]
+        #[with(InMemoryRepository::new())]
+        context: CommandExecutorContext,
+    ) {
+        // Arrange
+        let key: String = Word().fake();
+        context
+            .repository()
+            .set(Entry {
+                key: key.as_str().into(),
+                value: Data::String(Word().fake()),
+                expiry: None,
+            })
+            .await;
+        let command = Exists {
+            keys: vec![key.clone(), key.clone(), format!("{key}-missing")],
+        };
+
+        // Act
+        let actual = command.execute(&context).await;
+
+        // Assert
+        assert_eq!(actual, Value::Integer(2));
+    }
+}
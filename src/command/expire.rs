@@ -0,0 +1,312 @@
+use crate::command::executor::Command;
+use crate::command::executor::CommandExecutor;
+use crate::command::executor::CommandExecutorContext;
+use crate::command::parser::extract_array;
+use crate::command::parser::extract_bulk_string;
+use crate::command::parser::validate_main_command;
+use crate::command::parser::validate_min_array_length;
+use crate::notification::Class;
+use crate::repository::ExpireCondition;
+use crate::repository::Expiry;
+use crate::repository::TimeUnit;
+use crate::resp::Value;
+
+/// EXPIRE, PEXPIRE, EXPIREAT and PEXPIREAT only differ in how the given time is interpreted.
+const VARIANTS: [(&str, TimeUnit, bool); 4] = [
+    ("EXPIRE", TimeUnit::Second, false),
+    ("PEXPIRE", TimeUnit::Millisecond, false),
+    ("EXPIREAT", TimeUnit::Second, true),
+    ("PEXPIREAT", TimeUnit::Millisecond, true),
+];
+
+#[derive(Debug, PartialEq)]
+pub struct Expire {
+    key: String,
+    time: i64,
+    unit: TimeUnit,
+    absolute: bool,
+    condition: ExpireCondition,
+}
+
+impl Command for Expire {
+    fn parse_from(value: &Value) -> Result<Self, anyhow::Error> {
+        let array = extract_array(value)?;
+        validate_min_array_length(array, 3)?;
+        let (_, unit, absolute) = VARIANTS
+            .iter()
+            .find(|(name, _, _)| validate_main_command(array, name).is_ok())
+            .ok_or_else(|| anyhow::anyhow!("expected EXPIRE family main command"))?;
+        let key = extract_bulk_string(array, 1)?;
+        let time = extract_bulk_string(array, 2)?.parse()?;
+
+        let mut condition = ExpireCondition::default();
+        for index in 3..array.len() {
+            match extract_bulk_string(array, index)?.to_uppercase().as_str() {
+                "NX" => condition.nx = true,
+                "XX" => condition.xx = true,
+                "GT" => condition.gt = true,
+                "LT" => condition.lt = true,
+                option => return Err(anyhow::anyhow!("Unsupported option {option}")),
+            }
+        }
+        if condition.nx && (condition.xx || condition.gt || condition.lt) {
+            return Err(anyhow::anyhow!(
+                "NX and XX, GT or LT options at the same time are not compatible"
+            ));
+        }
+        if condition.gt && condition.lt {
+            return Err(anyhow::anyhow!(
+                "GT and LT options at the same time are not compatible"
+            ));
+        }
+
+        Ok(Expire {
+            key: key.to_string(),
+            time,
+            unit: unit.clone(),
+            absolute: *absolute,
+            condition,
+        })
+    }
+}
+
+impl Expire {
+    /// Negative or past times are kept as the epoch so the repository deletes the key.
+    fn expiry_in_millis(&self, now_in_millis: u128) -> u128 {
+        let millis = match self.unit {
+            TimeUnit::Second => self.time as i128 * 1000,
+            TimeUnit::Millisecond => self.time as i128,
+        };
+        let millis = if self.absolute {
+            millis
+        } else {
+            now_in_millis as i128 + millis
+        };
+        millis.max(0) as u128
+    }
+
+    /// The PEXPIREAT request setting the same expiry as of `now_in_millis` at any later time,
+    /// for replicas.
+    pub fn absolute(&self, now_in_millis: u128) -> Value {
+        let mut arguments = vec![
+            "PEXPIREAT".to_string(),
+            self.key.clone(),
+            self.expiry_in_millis(now_in_millis).to_string(),
+        ];
+        let flags = [
+            (self.condition.nx, "NX"),
+            (self.condition.xx, "XX"),
+            (self.condition.gt, "GT"),
+            (self.condition.lt, "LT"),
+        ];
+        arguments.extend(
+            flags
+                .iter()
+                .filter(|(set, _)| *set)
+                .map(|(_, flag)| flag.to_string()),
+        );
+        Value::Array(arguments.into_iter().map(Value::BulkString).collect())
+    }
+}
+
+#[async_trait::async_trait]
+impl CommandExecutor for Expire {
+    async fn execute(&self, context: &CommandExecutorContext) -> Value {
+        let expiry = Expiry {
+            epoch: self.expiry_in_millis(context.clock.now_in_millis()),
+            unit: TimeUnit::Millisecond,
+        };
+        let deleted = expiry.epoch <= context.clock.now_in_millis();
+        let updated = context
+            .repository()
+            .expire(&self.key, expiry, self.condition)
+            .await;
+        if updated {
+            let event = if deleted { "del" } else { "expire" };
+            context.notify(Class::Generic, event, &self.key);
+        }
+        Value::Integer(updated as i64)
+    }
+}
+
+#[cfg(test)]
+mod specs_for_parse_from {
+    use crate::command::executor::Command;
+    use crate::repository::ExpireCondition;
+    use crate::repository::TimeUnit;
+    use crate::resp::Value;
+
+    use super::Expire;
+
+    fn array(arguments: &[&str]) -> Value {
+        Value::Array(
+            arguments
+                .iter()
+                .map(|argument| Value::BulkString(argument.to_string()))
+                .collect(),
+        )
+    }
+
+    #[rstest::rstest]
+    #[case("EXPIRE", TimeUnit::Second, false)]
+    #[case("pexpire", TimeUnit::Millisecond, false)]
+    #[case("ExpireAt", TimeUnit::Second, true)]
+    #[case("PEXPIREAT", TimeUnit::Millisecond, true)]
+    fn sut_parses_expire_family_commands_correctly(
+        #[case] command: &str,
+        #[case] unit: TimeUnit,
+        #[case] absolute: bool,
+    ) {
+        // Arrange
+        let value = array(&[command, "foo", "-10", "xx", "GT"]);
+
+        // Act
+        let actual = Expire::parse_from(&value).unwrap();
+
+        // Assert
+        let expected = Expire {
+            key: "foo".to_string(),
+            time: -10,
+            unit,
+            absolute,
+            condition: ExpireCondition {
+                xx: true,
+                gt: true,
+                ..Default::default()
+            },
+        };
+        assert_eq!(actual, expected);
+    }
+
+    #[rstest::rstest]
+    #[case(&["EXPIRE", "foo", "10", "NX", "XX"])]
+    #[case(&["EXPIRE", "foo", "10", "GT", "LT"])]
+    #[case(&["EXPIRE", "foo", "10", "YY"])]
+    #[case(&["EXPIRE", "foo", "ten"])]
+    fn sut_raises_error_if_arguments_are_invalid(#[case] arguments: &[&str]) {
+        // Act
+        let actual = Expire::parse_from(&array(arguments));
+
+        // Assert
+        assert!(actual.is_err());
+    }
+}
+
+#[cfg(test)]
+mod specs_for_execute {
+    use crate::clock::Clock;
+    use crate::command::executor::CommandExecutor;
+    use crate::command::executor::CommandExecutorContext;
+    use crate::command::executor::fixture::command_executor_context_with_fake_clock;
+    use crate::repository::Data;
+    use crate::repository::Entry;
+    use crate::repository::ExpireCondition;
+    use crate::repository::Expiry;
+    use crate::repository::TimeUnit;
+    use crate::resp::Value;
+
+    use super::Expire;
+
+    async fn set(context: &CommandExecutorContext, expiry: Option<Expiry>) {
+        context
+            .repository()
+            .set(Entry {
+                key: "foo".into(),
+                value: Data::String("bar".to_string()),
+                expiry,
+            })
+            .await;
+    }
+
+    fn command(time: i64, unit: TimeUnit, condition: ExpireCondition) -> Expire {
+        Expire {
+            key: "foo".to_string(),
+            time,
+            unit,
+            absolute: false,
+            condition,
+        }
+    }
+
+    #[tokio::test]
+    async fn sut_sets_expiry_relative_to_now() {
+        // Arrange
+        let (context, clock) = command_executor_context_with_fake_clock();
+        set(&context, None).await;
+        let command = command(100, TimeUnit::Second, ExpireCondition::default());
+
+        // Act
+        let actual = command.execute(&context).await;
+
+        // Assert
+        assert_eq!(actual, Value::Integer(1));
+        let expiry = context.repository().expiry("foo").await.unwrap().unwrap();
+        assert_eq!(expiry.to_millis(), clock.now_in_millis() + 100_000);
+    }
+
+    #[rstest::rstest]
+    #[case(-1, TimeUnit::Second)]
+    #[case(0, TimeUnit::Millisecond)]
+    #[tokio::test]
+    async fn sut_deletes_key_if_expiry_is_not_in_the_future(
+        #[case] time: i64,
+        #[case] unit: TimeUnit,
+    ) {
+        // Arrange
+        let (context, _) = command_executor_context_with_fake_clock();
+        set(&context, None).await;
+        let command = command(time, unit, ExpireCondition::default());
+
+        // Act
+        let actual = command.execute(&context).await;
+
+        // Assert
+        assert_eq!(actual, Value::Integer(1));
+        assert!(!context.repository().exists("foo").await);
+    }
+
+    #[rstest::rstest]
+    #[case(ExpireCondition { nx: true, ..Default::default() }, None, 1)]
+    #[case(ExpireCondition { nx: true, ..Default::default() }, Some(50), 0)]
+    #[case(ExpireCondition { xx: true, ..Default::default() }, None, 0)]
+    #[case(ExpireCondition { gt: true, ..Default::default() }, None, 0)]
+    #[case(ExpireCondition { gt: true, ..Default::default() }, Some(50), 1)]
+    #[case(ExpireCondition { gt: true, ..Default::default() }, Some(500), 0)]
+    #[case(ExpireCondition { lt: true, ..Default::default() }, None, 1)]
+    #[case(ExpireCondition { lt: true, ..Default::default() }, Some(500), 1)]
+    #[case(ExpireCondition { lt: true, ..Default::default() }, Some(50), 0)]
+    #[tokio::test]
+    async fn sut_sets_expiry_only_if_condition_allows(
+        #[case] condition: ExpireCondition,
+        #[case] current_ttl_in_seconds: Option<u128>,
+        #[case] expected: i64,
+    ) {
+        // Arrange
+        let (context, clock) = command_executor_context_with_fake_clock();
+        let expiry = current_ttl_in_seconds.map(|ttl| Expiry {
+            epoch: clock.now_in_millis() + ttl * 1000,
+            unit: TimeUnit::Millisecond,
+        });
+        set(&context, expiry).await;
+        let command = command(100, TimeUnit::Second, condition);
+
+        // Act
+        let actual = command.execute(&context).await;
+
+        // Assert
+        assert_eq!(actual, Value::Integer(expected));
+    }
+
+    #[tokio::test]
+    async fn sut_responds_zero_if_key_does_not_exist() {
+        // Arrange
+        let (context, _) = command_executor_context_with_fake_clock();
+        let command = command(100, TimeUnit::Second, ExpireCondition::default());
+
+        // Act
+        let actual = command.execute(&context).await;
+
+        // Assert
+        assert_eq!(actual, Value::Integer(0));
+    }
+}
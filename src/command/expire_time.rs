@@ -0,0 +1,134 @@
+use crate::command::executor::Command;
+use crate::command::executor::CommandExecutor;
+use crate::command::executor::CommandExecutorContext;
+use crate::command::parser::extract_array;
+use crate::command::parser::extract_bulk_string;
+use crate::command::parser::validate_array_length;
+use crate::command::parser::validate_main_command;
+use crate::repository::TimeUnit;
+use crate::resp::Value;
+
+#[derive(Debug, PartialEq)]
+pub struct ExpireTime {
+    key: String,
+    unit: TimeUnit,
+}
+
+impl Command for ExpireTime {
+    fn parse_from(value: &Value) -> Result<Self, anyhow::Error> {
+        let array = extract_array(value)?;
+        validate_array_length(array, 2)?;
+        let unit = if validate_main_command(array, "EXPIRETIME").is_ok() {
+            TimeUnit::Second
+        } else {
+            validate_main_command(array, "PEXPIRETIME")?;
+            TimeUnit::Millisecond
+        };
+        let key = extract_bulk_string(array, 1)?;
+        Ok(ExpireTime {
+            key: key.to_string(),
+            unit,
+        })
+    }
+}
+
+#[async_trait::async_trait]
+impl CommandExecutor for ExpireTime {
+    async fn execute(&self, context: &CommandExecutorContext) -> Value {
+        context.track(&self.key);
+        let time = match context.repository().expiry(&self.key).await {
+            Err(_) => -2,
+            Ok(None) => -1,
+            Ok(Some(expiry)) => match self.unit {
+                TimeUnit::Second => (expiry.to_millis() / 1000) as i64,
+                TimeUnit::Millisecond => expiry.to_millis() as i64,
+            },
+        };
+        Value::Integer(time)
+    }
+}
+
+#[cfg(test)]
+mod specs_for_parse_from {
+    use crate::command::executor::Command;
+    use crate::repository::TimeUnit;
+    use crate::resp::Value;
+
+    use super::ExpireTime;
+
+    #[rstest::rstest]
+    #[case("EXPIRETIME", TimeUnit::Second)]
+    #[case("expiretime", TimeUnit::Second)]
+    #[case("PEXPIRETIME", TimeUnit::Millisecond)]
+    #[case("PExpireTime", TimeUnit::Millisecond)]
+    fn sut_parses_expiretime_commands_with_case_insensitive(
+        #[case] expiretime: &str,
+        #[case] unit: TimeUnit,
+    ) {
+        // Arrange
+        let value = Value::Array(vec![
+            Value::BulkString(expiretime.to_string()),
+            Value::BulkString("foo".to_string()),
+        ]);
+
+        // Act
+        let actual = ExpireTime::parse_from(&value).unwrap();
+
+        // Assert
+        let expected = ExpireTime {
+            key: "foo".to_string(),
+            unit,
+        };
+        assert_eq!(actual, expected);
+    }
+}
+
+#[cfg(test)]
+mod specs_for_execute {
+    use crate::command::executor::CommandExecutor;
+    use crate::command::executor::CommandExecutorContext;
+    use crate::command::executor::fixture::command_executor_context;
+    use crate::repository::Data;
+    use crate::repository::Entry;
+    use crate::repository::Expiry;
+    use crate::repository::InMemoryRepository;
+    use crate::repository::TimeUnit;
+    use crate::resp::Value;
+
+    use super::ExpireTime;
+
+    #[rstest::rstest]
+    #[case(TimeUnit::Second, 33177117420)]
+    #[case(TimeUnit::Millisecond, 33177117420123)]
+    #[tokio::test]
+    async fn sut_responds_absolute_expiry_in_unit(
+        #[case] unit: TimeUnit,
+        #[case] expected: i64,
+        #[from(command_executor_context)]
+        #[with(InMemoryRepository::new())]
+        context: CommandExecutorContext,
+    ) {
+        // Arrange
+        context
+            .repository()
+            .set(Entry {
+                key: "foo".into(),
+                value: Data::String("bar".to_string()),
+                expiry: Some(Expiry {
+                    epoch: 33177117420123,
+                    unit: TimeUnit::Millisecond,
+                }),
+            })
+            .await;
+        let command = ExpireTime {
+            key: "foo".to_string(),
+            unit,
+        };
+
+        // Act
+        let actual = command.execute(&context).await;
+
+        // Assert
+        assert_eq!(actual, Value::Integer(expected));
+    }
+}
@@ -0,0 +1,249 @@
+use std::time::Duration;
+
+use crate::command::executor::Command;
+use crate::command::executor::CommandExecutor;
+use crate::command::executor::CommandExecutorContext;
+use crate::command::parser::extract_array;
+use crate::command::parser::extract_bulk_string;
+use crate::command::parser::validate_main_command;
+use crate::command::parser::validate_min_array_length;
+use crate::replication::FailoverState;
+use crate::replication::Handoff;
+use crate::resp::Value;
+
+/// FAILOVER [TO host port [FORCE]] [ABORT] [TIMEOUT milliseconds], whose options are only
+/// checked against each other once executed.
+#[derive(Debug, Default, PartialEq)]
+pub struct Failover {
+    target: Option<(String, u16)>,
+    force: bool,
+    abort: bool,
+    timeout: Option<i64>,
+}
+
+impl Command for Failover {
+    fn parse_from(value: &Value) -> Result<Self, anyhow::Error> {
+        let array = extract_array(value)?;
+        validate_min_array_length(array, 1)?;
+        validate_main_command(array, "FAILOVER")?;
+        let mut failover = Failover::default();
+        let mut index = 1;
+        while index < array.len() {
+            match extract_bulk_string(array, index)?.to_uppercase().as_str() {
+                "TO" if failover.target.is_none() => {
+                    let host = extract_bulk_string(array, index + 1)?.to_string();
+                    let port = extract_bulk_string(array, index + 2)?
+                        .parse()
+                        .map_err(|_| anyhow::anyhow!("value is not an integer or out of range"))?;
+                    failover.target = Some((host, port));
+                    index += 2;
+                }
+                "TIMEOUT" if failover.timeout.is_none() => {
+                    index += 1;
+                    let timeout = extract_bulk_string(array, index)?
+                        .parse()
+                        .map_err(|_| anyhow::anyhow!("value is not an integer or out of range"))?;
+                    failover.timeout = Some(timeout);
+                }
+                "FORCE" if !failover.force => failover.force = true,
+                "ABORT" if !failover.abort => failover.abort = true,
+                _ => return Err(anyhow::anyhow!("syntax error")),
+            }
+            index += 1;
+        }
+        Ok(failover)
+    }
+}
+
+#[async_trait::async_trait]
+impl CommandExecutor for Failover {
+    async fn execute(&self, context: &CommandExecutorContext) -> Value {
+        if context.cluster.is_some() {
+            return Value::Error("ERR FAILOVER not allowed in cluster mode.".to_string());
+        }
+        let replication = &context.replication;
+        if self.timeout.is_some_and(|timeout| timeout <= 0) {
+            return Value::Error("ERR FAILOVER timeout must be greater than 0".to_string());
+        }
+        if self.abort {
+            if replication.failover() == FailoverState::None {
+                return Value::Error("ERR FAILOVER is not in progress.".to_string());
+            }
+            replication.end_failover(false);
+            return Value::SimpleString("OK".to_string());
+        }
+        if self.force && (self.timeout.is_none() || self.target.is_none()) {
+            return Value::Error(
+                "ERR FAILOVER with force option requires both a timeout and target HOST and IP."
+                    .to_string(),
+            );
+        }
+        if replication.is_replica() {
+            return Value::Error("ERR FAILOVER is not valid when server is a replica.".to_string());
+        }
+        let replicas = replication.replicas();
+        if replicas.is_empty() {
+            return Value::Error("ERR FAILOVER requires connected replicas.".to_string());
+        }
+        if replication.failover() != FailoverState::None {
+            return Value::Error("ERR FAILOVER already in progress.".to_string());
+        }
+        if let Some((host, port)) = &self.target {
+            let Some(replica) = replicas
+                .iter()
+                .find(|replica| replica.address == *host && replica.port == Some(*port))
+            else {
+                return Value::Error(
+                    "ERR FAILOVER target HOST and PORT is not a replica.".to_string(),
+                );
+            };
+            if !replica.online {
+                return Value::Error("ERR FAILOVER target replica is not online.".to_string());
+            }
+        }
+
+        replication.begin_failover();
+        let handoff = Handoff {
+            target: self
+                .target
+                .as_ref()
+                .map(|(host, port)| format!("{host}:{port}")),
+            timeout: self
+                .timeout
+                .map(|timeout| Duration::from_millis(timeout as u64)),
+            force: self.force,
+        };
+        tokio::spawn(handoff.run(replication.clone(), context.execution.clone()));
+        Value::SimpleString("OK".to_string())
+    }
+}
+
+#[cfg(test)]
+mod specs_for_parse_from {
+    use crate::command::executor::Command;
+    use crate::resp::Value;
+
+    use super::Failover;
+
+    fn array(arguments: &[&str]) -> Value {
+        Value::Array(
+            arguments
+                .iter()
+                .map(|argument| Value::BulkString(argument.to_string()))
+                .collect(),
+        )
+    }
+
+    #[rstest::rstest]
+    #[case(&["FAILOVER"], Failover::default())]
+    #[case(&["failover", "to", "10.0.0.2", "6380", "force", "timeout", "100"], Failover {
+        target: Some(("10.0.0.2".to_string(), 6380)),
+        force: true,
+        timeout: Some(100),
+        ..Failover::default()
+    })]
+    #[case(&["FAILOVER", "ABORT"], Failover { abort: true, ..Failover::default() })]
+    fn sut_parses_failover_options(#[case] arguments: &[&str], #[case] expected: Failover) {
+        // Act
+        let actual = Failover::parse_from(&array(arguments)).unwrap();
+
+        // Assert
+        assert_eq!(actual, expected);
+    }
+
+    #[rstest::rstest]
+    #[case(&["FAILOVER", "TO", "10.0.0.2"])]
+    #[case(&["FAILOVER", "TIMEOUT", "soon"])]
+    #[case(&["FAILOVER", "ABORT", "ABORT"])]
+    #[case(&["FAILOVER", "NOW"])]
+    fn sut_rejects_malformed_failover(#[case] arguments: &[&str]) {
+        // Act
+        let actual = Failover::parse_from(&array(arguments));
+
+        // Assert
+        assert!(actual.is_err());
+    }
+}
+
+#[cfg(test)]
+mod specs_for_execute {
+    use crate::command::executor::CommandExecutor;
+    use crate::command::executor::CommandExecutorContext;
+    use crate::command::executor::fixture::command_executor_context;
+    use crate::replication::FailoverState;
+    use crate::resp::Value;
+
+    use super::Failover;
+
+    #[rstest::rstest]
+    #[case(Failover { timeout: Some(0), ..Failover::default() }, "ERR FAILOVER timeout must be greater than 0")]
+    #[case(Failover { abort: true, ..Failover::default() }, "ERR FAILOVER is not in progress.")]
+    #[case(Failover { force: true, timeout: Some(100), ..Failover::default() }, "ERR FAILOVER with force option requires both a timeout and target HOST and IP.")]
+    #[case(Failover::default(), "ERR FAILOVER requires connected replicas.")]
+    #[tokio::test]
+    async fn sut_responds_error_if_failover_cannot_start(
+        #[from(command_executor_context)] context: CommandExecutorContext,
+        #[case] sut: Failover,
+        #[case] expected: &str,
+    ) {
+        // Act
+        let actual = sut.execute(&context).await;
+
+        // Assert
+        assert_eq!(actual, Value::Error(expected.to_string()));
+    }
+
+    #[rstest::rstest]
+    #[tokio::test]
+    async fn sut_responds_error_if_target_is_not_a_replica(
+        #[from(command_executor_context)] context: CommandExecutorContext,
+    ) {
+        // Arrange
+        let replica = context.new_session();
+        replica.session.set_address("10.0.0.2");
+        context.replication.register(&replica.session, Some(6380));
+        let sut = Failover {
+            target: Some(("10.0.0.3".to_string(), 6380)),
+            ..Failover::default()
+        };
+
+        // Act
+        let actual = sut.execute(&context).await;
+
+        // Assert
+        let expected =
+            Value::Error("ERR FAILOVER target HOST and PORT is not a replica.".to_string());
+        assert_eq!(actual, expected);
+    }
+
+    #[rstest::rstest]
+    #[tokio::test]
+    async fn sut_pauses_writes_until_aborted(
+        #[from(command_executor_context)] context: CommandExecutorContext,
+    ) {
+        // Arrange
+        let replica = context.new_session();
+        replica.session.set_address("10.0.0.2");
+        context.replication.register(&replica.session, Some(6380));
+        context.replication.attach(&replica.session);
+        let set = Value::Array(vec![Value::BulkString("SET".to_string())]);
+        context.replication.propagate(0, &set);
+
+        // Act
+        let started = Failover::default().execute(&context).await;
+        let state = context.replication.failover();
+        let aborted = Failover {
+            abort: true,
+            ..Failover::default()
+        }
+        .execute(&context)
+        .await;
+
+        // Assert
+        assert_eq!(started, Value::SimpleString("OK".to_string()));
+        assert_eq!(state, FailoverState::WaitingForSync);
+        assert_eq!(aborted, Value::SimpleString("OK".to_string()));
+        assert_eq!(context.replication.failover(), FailoverState::None);
+        assert!(!context.replication.is_replica());
+    }
+}
@@ -0,0 +1,234 @@
+use crate::command::eval::parse_keys_and_args;
+use crate::command::executor::Command;
+use crate::command::executor::CommandExecutor;
+use crate::command::executor::CommandExecutorContext;
+use crate::command::parser::extract_array;
+use crate::command::parser::extract_bulk_string;
+use crate::command::parser::validate_main_command;
+use crate::command::parser::validate_min_array_length;
+use crate::resp::Value;
+use crate::scripting::Program;
+
+/// FCALL and FCALL_RO, the latter only running functions flagged `no-writes`.
+#[derive(Debug, PartialEq)]
+pub struct FCall {
+    function: String,
+    keys: Vec<String>,
+    args: Vec<String>,
+    read_only: bool,
+}
+
+impl Command for FCall {
+    fn parse_from(value: &Value) -> Result<Self, anyhow::Error> {
+        let array = extract_array(value)?;
+        validate_min_array_length(array, 3)?;
+        let read_only = validate_main_command(array, "FCALL").is_err();
+        if read_only {
+            validate_main_command(array, "FCALL_RO")?;
+        }
+        let function = extract_bulk_string(array, 1)?.to_string();
+        let (keys, args) = parse_keys_and_args(array, 2)?;
+        Ok(FCall {
+            function,
+            keys,
+            args,
+            read_only,
+        })
+    }
+}
+
+impl FCall {
+    pub fn is_read_only(&self) -> bool {
+        self.read_only
+    }
+
+    /// Runs the function, the caller holding the execution lock exclusively as EXEC does. Its
+    /// writes are sent to replicas as a transaction of their own if `wrap` is set.
+    pub async fn run(&self, context: &CommandExecutorContext, wrap: bool) -> Value {
+        let Some((code, function)) = context.scripts.libraries.function(&self.function) else {
+            return Value::Error("ERR Function not found".to_string());
+        };
+        if self.read_only && !function.is_read_only() {
+            return Value::Error(
+                "ERR Can not execute a script with write flag using *_ro command.".to_string(),
+            );
+        }
+        let program = Program::Function {
+            code,
+            name: function.name.clone(),
+        };
+        let (keys, args) = (self.keys.clone(), self.args.clone());
+        let read_only = function.is_read_only();
+        context
+            .scripts
+            .run(context, program, keys, args, read_only, wrap)
+            .await
+    }
+}
+
+#[async_trait::async_trait]
+impl CommandExecutor for FCall {
+    async fn execute(&self, context: &CommandExecutorContext) -> Value {
+        let _exclusive = match context.lock_exclusive().await {
+            Ok(exclusive) => exclusive,
+            Err(e) => return e,
+        };
+        self.run(context, true).await
+    }
+}
+
+#[cfg(test)]
+mod specs_for_parse_from {
+    use crate::command::executor::Command;
+    use crate::resp::Value;
+
+    use super::FCall;
+
+    fn array(arguments: &[&str]) -> Value {
+        Value::Array(
+            arguments
+                .iter()
+                .map(|argument| Value::BulkString(argument.to_string()))
+                .collect(),
+        )
+    }
+
+    #[rstest::rstest]
+    #[case("FCALL", false)]
+    #[case("fcall_ro", true)]
+    fn sut_parses_keys_and_args_correctly(#[case] command: &str, #[case] read_only: bool) {
+        // Arrange
+        let value = array(&[command, "hello", "1", "foo", "bar"]);
+
+        // Act
+        let actual = FCall::parse_from(&value).unwrap();
+
+        // Assert
+        let expected = FCall {
+            function: "hello".to_string(),
+            keys: vec!["foo".to_string()],
+            args: vec!["bar".to_string()],
+            read_only,
+        };
+        assert_eq!(actual, expected);
+    }
+
+    #[rstest::rstest]
+    #[case(&["FCALL", "hello"])]
+    #[case(&["FCALL", "hello", "2", "foo"])]
+    #[case(&["FCALL", "hello", "-1"])]
+    #[case(&["FCALL_RW", "hello", "0"])]
+    fn sut_fails_to_parse_invalid_fcall(#[case] arguments: &[&str]) {
+        // Act
+        let actual = FCall::parse_from(&array(arguments));
+
+        // Assert
+        assert!(actual.is_err());
+    }
+}
+
+#[cfg(test)]
+mod specs_for_execute {
+    use crate::command::executor::CommandExecutor;
+    use crate::command::executor::CommandExecutorContext;
+    use crate::command::executor::fixture::command_executor_context;
+    use crate::repository::InMemoryRepository;
+    use crate::resp::Value;
+
+    use super::FCall;
+
+    const LIBRARY: &str = "#!lua name=mylib\n\
+        redis.register_function('set', function(keys, args) return redis.call('SET', keys[1], args[1]) end)\n\
+        redis.register_function{function_name='get', callback=function(keys) return redis.call('GET', keys[1]) end, flags={'no-writes'}}\n\
+        redis.register_function{function_name='sneaky', callback=function(keys) return redis.call('DEL', keys[1]) end, flags={'no-writes'}}";
+
+    fn fcall(function: &str, keys: &[&str], args: &[&str], read_only: bool) -> FCall {
+        FCall {
+            function: function.to_string(),
+            keys: keys.iter().map(|key| key.to_string()).collect(),
+            args: args.iter().map(|arg| arg.to_string()).collect(),
+            read_only,
+        }
+    }
+
+    #[rstest::rstest]
+    #[tokio::test]
+    async fn sut_calls_function_with_keys_and_args(
+        #[from(command_executor_context)]
+        #[with(InMemoryRepository::new())]
+        context: CommandExecutorContext,
+    ) {
+        // Arrange
+        context.scripts.libraries.load(LIBRARY, false).unwrap();
+        fcall("set", &["foo"], &["bar"], false)
+            .execute(&context)
+            .await;
+
+        // Act
+        let actual = fcall("get", &["foo"], &[], true).execute(&context).await;
+
+        // Assert
+        assert_eq!(actual, Value::BulkString("bar".to_string()));
+    }
+
+    #[rstest::rstest]
+    #[tokio::test]
+    async fn sut_sends_writes_of_function_to_replicas_as_one_transaction(
+        #[from(command_executor_context)]
+        #[with(InMemoryRepository::new())]
+        context: CommandExecutorContext,
+    ) {
+        // Arrange
+        context.scripts.libraries.load(LIBRARY, false).unwrap();
+        let replica = context.new_session();
+        context.replication.attach(&replica.session);
+
+        // Act
+        fcall("set", &["foo"], &["bar"], false)
+            .execute(&context)
+            .await;
+
+        // Assert
+        let mut messages = replica.session.subscriber.messages().await;
+        let mut actual = vec![];
+        while let Ok(Value::Raw(bytes)) = messages.try_recv() {
+            actual.extend(bytes);
+        }
+        let actual = String::from_utf8(actual).unwrap();
+        let expected = "*1\r\n$5\r\nMULTI\r\n*3\r\n$3\r\nSET\r\n$3\r\nfoo\r\n$3\r\nbar\r\n*1\r\n$4\r\nEXEC\r\n";
+        assert!(actual.ends_with(expected), "{actual}");
+    }
+
+    #[rstest::rstest]
+    #[case("nosuchfunction", false, "ERR Function not found")]
+    #[case(
+        "set",
+        true,
+        "ERR Can not execute a script with write flag using *_ro command."
+    )]
+    #[case(
+        "sneaky",
+        false,
+        "ERR Write commands are not allowed from read-only scripts."
+    )]
+    #[tokio::test]
+    async fn sut_responds_error_if_function_cannot_run(
+        #[case] function: &str,
+        #[case] read_only: bool,
+        #[case] expected: &str,
+        #[from(command_executor_context)]
+        #[with(InMemoryRepository::new())]
+        context: CommandExecutorContext,
+    ) {
+        // Arrange
+        context.scripts.libraries.load(LIBRARY, false).unwrap();
+
+        // Act
+        let actual = fcall(function, &["foo"], &["bar"], read_only)
+            .execute(&context)
+            .await;
+
+        // Assert
+        assert_eq!(actual, Value::Error(expected.to_string()));
+    }
+}
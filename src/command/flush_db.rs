@@ -0,0 +1,131 @@
+use crate::command::executor::Command;
+use crate::command::executor::CommandExecutor;
+use crate::command::executor::CommandExecutorContext;
+use crate::command::parser::extract_array;
+use crate::command::parser::extract_bulk_string;
+use crate::command::parser::validate_main_command;
+use crate::command::parser::validate_min_array_length;
+use crate::resp::Value;
+
+#[derive(Debug, Default, PartialEq)]
+pub struct FlushDb {
+    all: bool,
+    lazy: bool,
+}
+
+impl Command for FlushDb {
+    fn parse_from(value: &Value) -> Result<Self, anyhow::Error> {
+        let array = extract_array(value)?;
+        validate_min_array_length(array, 1)?;
+        let all = if validate_main_command(array, "FLUSHDB").is_ok() {
+            false
+        } else {
+            validate_main_command(array, "FLUSHALL")?;
+            true
+        };
+        let lazy = match array.len() {
+            1 => false,
+            2 => match extract_bulk_string(array, 1)?.to_uppercase().as_str() {
+                "ASYNC" => true,
+                "SYNC" => false,
+                _ => return Err(anyhow::anyhow!("syntax error")),
+            },
+            _ => return Err(anyhow::anyhow!("syntax error")),
+        };
+        Ok(FlushDb { all, lazy })
+    }
+}
+
+#[async_trait::async_trait]
+impl CommandExecutor for FlushDb {
+    async fn execute(&self, context: &CommandExecutorContext) -> Value {
+        let repositories = if self.all {
+            context.databases.all()
+        } else {
+            vec![context.repository()]
+        };
+        for repository in repositories {
+            repository.flush(self.lazy).await;
+        }
+        context.tracking.invalidate_all();
+        Value::SimpleString("OK".to_string())
+    }
+}
+
+#[cfg(test)]
+mod specs_for_parse_from {
+    use crate::command::executor::Command;
+    use crate::resp::Value;
+
+    use super::FlushDb;
+
+    #[rstest::rstest]
+    #[case(&["FLUSHDB"], false, false)]
+    #[case(&["flushdb", "async"], false, true)]
+    #[case(&["FLUSHALL", "SYNC"], true, false)]
+    #[case(&["FlushAll", "ASYNC"], true, true)]
+    fn sut_parses_flush_commands_correctly(
+        #[case] arguments: &[&str],
+        #[case] all: bool,
+        #[case] lazy: bool,
+    ) {
+        // Arrange
+        let value = Value::Array(
+            arguments
+                .iter()
+                .map(|argument| Value::BulkString(argument.to_string()))
+                .collect(),
+        );
+
+        // Act
+        let actual = FlushDb::parse_from(&value).unwrap();
+
+        // Assert
+        assert_eq!(actual, FlushDb { all, lazy });
+    }
+}
+
+#[cfg(test)]
+mod specs_for_execute {
+    use crate::command::executor::CommandExecutor;
+    use crate::command::executor::fixture::command_executor_context_with_databases;
+    use crate::repository::Data;
+    use crate::repository::Entry;
+    use crate::resp::Value;
+
+    use super::FlushDb;
+
+    #[rstest::rstest]
+    #[case(false, false, 1)]
+    #[case(false, true, 1)]
+    #[case(true, false, 0)]
+    #[case(true, true, 0)]
+    #[tokio::test]
+    async fn sut_removes_keys_of_current_or_all_databases(
+        #[case] all: bool,
+        #[case] lazy: bool,
+        #[case] expected_size_of_other_database: usize,
+    ) {
+        // Arrange
+        let context = command_executor_context_with_databases(2);
+        for repository in context.databases.all() {
+            repository
+                .set(Entry {
+                    key: "foo".into(),
+                    value: Data::String("bar".to_string()),
+                    expiry: None,
+                })
+                .await;
+        }
+        let command = FlushDb { all, lazy };
+
+        // Act
+        let actual = command.execute(&context).await;
+
+        // Assert
+        assert_eq!(actual, Value::SimpleString("OK".to_string()));
+        assert_eq!(context.repository().size().await, 0);
+        let other = context.databases.get(1).unwrap();
+        assert_eq!(other.size().await, expected_size_of_other_database);
+    }
+}
@@ -0,0 +1,407 @@
+use crate::command::executor::Command;
+use crate::command::executor::CommandExecutor;
+use crate::command::executor::CommandExecutorContext;
+use crate::command::parser::extract_array;
+use crate::command::parser::extract_bulk_string;
+use crate::command::parser::validate_main_command;
+use crate::command::parser::validate_min_array_length;
+use crate::glob;
+use crate::resp::Value;
+use crate::scripting::Library;
+use crate::scripting::RestorePolicy;
+
+/// FUNCTION LOAD, LIST, DELETE, DUMP, RESTORE and FLUSH. FUNCTION KILL is served by
+/// [`ScriptKill`](crate::command::script_kill::ScriptKill) as it must never wait for the
+/// running function.
+///
+/// DUMP payloads are binary, so DUMP replies with the bytes as they are, while RESTORE gets back
+/// every byte as the char of the same code point as bulk strings which are not UTF-8 arrive.
+#[derive(Debug, PartialEq)]
+pub enum Function {
+    Load {
+        code: String,
+        replace: bool,
+    },
+    List {
+        pattern: Option<String>,
+        with_code: bool,
+    },
+    Delete(String),
+    Dump,
+    Restore {
+        payload: String,
+        policy: RestorePolicy,
+    },
+    Flush,
+}
+
+impl Command for Function {
+    fn parse_from(value: &Value) -> Result<Self, anyhow::Error> {
+        let array = extract_array(value)?;
+        validate_min_array_length(array, 2)?;
+        validate_main_command(array, "FUNCTION")?;
+        let options = array
+            .iter()
+            .skip(2)
+            .map(|value| match value {
+                Value::BulkString(option) => Ok(option.as_str()),
+                _ => Err(anyhow::anyhow!("expected bulk string")),
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        match extract_bulk_string(array, 1)?.to_uppercase().as_str() {
+            "LOAD" => match options.as_slice() {
+                [code] => Ok(Function::Load {
+                    code: code.to_string(),
+                    replace: false,
+                }),
+                [replace, code] if replace.eq_ignore_ascii_case("REPLACE") => Ok(Function::Load {
+                    code: code.to_string(),
+                    replace: true,
+                }),
+                _ => Err(anyhow::anyhow!("syntax error")),
+            },
+            "LIST" => parse_list(&options),
+            "DELETE" => match options.as_slice() {
+                [name] => Ok(Function::Delete(name.to_string())),
+                _ => Err(anyhow::anyhow!("wrong number of arguments")),
+            },
+            "DUMP" if options.is_empty() => Ok(Function::Dump),
+            "RESTORE" => {
+                let (payload, policy) = match options.as_slice() {
+                    [payload] => (payload, RestorePolicy::Append),
+                    [payload, policy] => (payload, parse_policy(policy)?),
+                    _ => return Err(anyhow::anyhow!("syntax error")),
+                };
+                Ok(Function::Restore {
+                    payload: payload.to_string(),
+                    policy,
+                })
+            }
+            "FLUSH" => match options.as_slice() {
+                [] => Ok(Function::Flush),
+                [mode]
+                    if mode.eq_ignore_ascii_case("ASYNC") || mode.eq_ignore_ascii_case("SYNC") =>
+                {
+                    Ok(Function::Flush)
+                }
+                _ => Err(anyhow::anyhow!("syntax error")),
+            },
+            _ => Err(anyhow::anyhow!(
+                "expected FUNCTION LOAD, LIST, DELETE, DUMP, RESTORE or FLUSH"
+            )),
+        }
+    }
+}
+
+impl Function {
+    /// Whether the subcommand changes the libraries, which replicas must then do as well.
+    pub fn is_write(&self) -> bool {
+        matches!(
+            self,
+            Function::Load { .. }
+                | Function::Delete(_)
+                | Function::Restore { .. }
+                | Function::Flush
+        )
+    }
+}
+
+fn parse_list(options: &[&str]) -> Result<Function, anyhow::Error> {
+    let mut pattern = None;
+    let mut with_code = false;
+    let mut options = options.iter();
+    while let Some(option) = options.next() {
+        match option.to_uppercase().as_str() {
+            "WITHCODE" => with_code = true,
+            "LIBRARYNAME" => {
+                let next = options
+                    .next()
+                    .ok_or_else(|| anyhow::anyhow!("library name argument was not given"))?;
+                pattern = Some(next.to_string());
+            }
+            _ => return Err(anyhow::anyhow!("Unknown argument {option}")),
+        }
+    }
+    Ok(Function::List { pattern, with_code })
+}
+
+fn parse_policy(policy: &str) -> Result<RestorePolicy, anyhow::Error> {
+    match policy.to_uppercase().as_str() {
+        "APPEND" => Ok(RestorePolicy::Append),
+        "REPLACE" => Ok(RestorePolicy::Replace),
+        "FLUSH" => Ok(RestorePolicy::Flush),
+        _ => Err(anyhow::anyhow!("Wrong restore policy given")),
+    }
+}
+
+#[async_trait::async_trait]
+impl CommandExecutor for Function {
+    async fn execute(&self, context: &CommandExecutorContext) -> Value {
+        let libraries = &context.scripts.libraries;
+        match self {
+            Function::Load { code, replace } => match libraries.load(code, *replace) {
+                Ok(name) => Value::BulkString(name),
+                Err(e) => Value::Error(e),
+            },
+            Function::List { pattern, with_code } => Value::Array(
+                libraries
+                    .list()
+                    .into_iter()
+                    .filter(|library| {
+                        pattern.as_ref().is_none_or(|pattern| {
+                            glob::matches_bytes(pattern.as_bytes(), library.name.as_bytes(), true)
+                        })
+                    })
+                    .map(|library| describe(library, *with_code))
+                    .collect(),
+            ),
+            Function::Delete(name) => {
+                if !libraries.delete(name) {
+                    return Value::Error("ERR Library not found".to_string());
+                }
+                Value::SimpleString("OK".to_string())
+            }
+            Function::Dump => {
+                let payload = libraries.dump();
+                let mut reply = format!("${}\r\n", payload.len()).into_bytes();
+                reply.extend(payload);
+                reply.extend(b"\r\n");
+                Value::Raw(reply)
+            }
+            Function::Restore { payload, policy } => {
+                let Some(payload) = payload
+                    .chars()
+                    .map(|c| u8::try_from(c).ok())
+                    .collect::<Option<Vec<u8>>>()
+                else {
+                    return Value::Error("ERR payload version or checksum are wrong".to_string());
+                };
+                match libraries.restore(&payload, *policy) {
+                    Ok(()) => Value::SimpleString("OK".to_string()),
+                    Err(e) => Value::Error(e),
+                }
+            }
+            Function::Flush => {
+                libraries.flush();
+                Value::SimpleString("OK".to_string())
+            }
+        }
+    }
+}
+
+fn describe(library: Library, with_code: bool) -> Value {
+    let functions = library
+        .functions
+        .into_iter()
+        .map(|function| {
+            Value::Array(vec![
+                Value::BulkString("name".to_string()),
+                Value::BulkString(function.name),
+                Value::BulkString("description".to_string()),
+                function
+                    .description
+                    .map(Value::BulkString)
+                    .unwrap_or(Value::Null),
+                Value::BulkString("flags".to_string()),
+                Value::Array(function.flags.into_iter().map(Value::BulkString).collect()),
+            ])
+        })
+        .collect();
+    let mut reply = vec![
+        Value::BulkString("library_name".to_string()),
+        Value::BulkString(library.name),
+        Value::BulkString("engine".to_string()),
+        Value::BulkString("LUA".to_string()),
+        Value::BulkString("functions".to_string()),
+        Value::Array(functions),
+    ];
+    if with_code {
+        reply.push(Value::BulkString("library_code".to_string()));
+        reply.push(Value::BulkString(library.code));
+    }
+    Value::Array(reply)
+}
+
+#[cfg(test)]
+mod specs_for_parse_from {
+    use crate::command::executor::Command;
+    use crate::resp::Value;
+    use crate::scripting::RestorePolicy;
+
+    use super::Function;
+
+    fn array(arguments: &[&str]) -> Value {
+        Value::Array(
+            arguments
+                .iter()
+                .map(|argument| Value::BulkString(argument.to_string()))
+                .collect(),
+        )
+    }
+
+    #[rstest::rstest]
+    #[case(&["FUNCTION", "LOAD", "code"], Function::Load { code: "code".to_string(), replace: false })]
+    #[case(&["function", "load", "replace", "code"], Function::Load { code: "code".to_string(), replace: true })]
+    #[case(&["FUNCTION", "LIST"], Function::List { pattern: None, with_code: false })]
+    #[case(
+        &["FUNCTION", "LIST", "WITHCODE", "LIBRARYNAME", "my*"],
+        Function::List { pattern: Some("my*".to_string()), with_code: true }
+    )]
+    #[case(&["FUNCTION", "DELETE", "mylib"], Function::Delete("mylib".to_string()))]
+    #[case(&["FUNCTION", "DUMP"], Function::Dump)]
+    #[case(
+        &["FUNCTION", "RESTORE", "payload"],
+        Function::Restore { payload: "payload".to_string(), policy: RestorePolicy::Append }
+    )]
+    #[case(
+        &["FUNCTION", "RESTORE", "payload", "flush"],
+        Function::Restore { payload: "payload".to_string(), policy: RestorePolicy::Flush }
+    )]
+    #[case(&["FUNCTION", "FLUSH", "SYNC"], Function::Flush)]
+    fn sut_parses_function_subcommands_correctly(
+        #[case] arguments: &[&str],
+        #[case] expected: Function,
+    ) {
+        // Act
+        let actual = Function::parse_from(&array(arguments)).unwrap();
+
+        // Assert
+        assert_eq!(actual, expected);
+    }
+
+    #[rstest::rstest]
+    #[case(&["FUNCTION", "KILL"])]
+    #[case(&["FUNCTION", "LOAD"])]
+    #[case(&["FUNCTION", "LIST", "LIBRARYNAME"])]
+    #[case(&["FUNCTION", "RESTORE", "payload", "MERGE"])]
+    #[case(&["FUNCTION", "DUMP", "extra"])]
+    fn sut_fails_to_parse_invalid_function_subcommands(#[case] arguments: &[&str]) {
+        // Act
+        let actual = Function::parse_from(&array(arguments));
+
+        // Assert
+        assert!(actual.is_err());
+    }
+}
+
+#[cfg(test)]
+mod specs_for_execute {
+    use crate::command::executor::CommandExecutor;
+    use crate::command::executor::CommandExecutorContext;
+    use crate::command::executor::fixture::command_executor_context;
+    use crate::resp::Value;
+    use crate::scripting::RestorePolicy;
+
+    use super::Function;
+
+    const LIBRARY: &str = "#!lua name=mylib\n\
+        redis.register_function{function_name='hello', callback=function() return 1 end, \
+        flags={'no-writes'}, description='greets'}";
+
+    fn load(code: &str) -> Function {
+        Function::Load {
+            code: code.to_string(),
+            replace: false,
+        }
+    }
+
+    #[rstest::rstest]
+    #[tokio::test]
+    async fn sut_lists_loaded_libraries_with_their_functions(
+        #[from(command_executor_context)] context: CommandExecutorContext,
+    ) {
+        // Arrange
+        load(LIBRARY).execute(&context).await;
+        let command = Function::List {
+            pattern: Some("MY*".to_string()),
+            with_code: true,
+        };
+
+        // Act
+        let actual = command.execute(&context).await;
+
+        // Assert
+        let expected = Value::Array(vec![Value::Array(vec![
+            Value::BulkString("library_name".to_string()),
+            Value::BulkString("mylib".to_string()),
+            Value::BulkString("engine".to_string()),
+            Value::BulkString("LUA".to_string()),
+            Value::BulkString("functions".to_string()),
+            Value::Array(vec![Value::Array(vec![
+                Value::BulkString("name".to_string()),
+                Value::BulkString("hello".to_string()),
+                Value::BulkString("description".to_string()),
+                Value::BulkString("greets".to_string()),
+                Value::BulkString("flags".to_string()),
+                Value::Array(vec![Value::BulkString("no-writes".to_string())]),
+            ])]),
+            Value::BulkString("library_code".to_string()),
+            Value::BulkString(LIBRARY.to_string()),
+        ])]);
+        assert_eq!(actual, expected);
+    }
+
+    #[rstest::rstest]
+    #[tokio::test]
+    async fn sut_responds_error_if_library_already_exists(
+        #[from(command_executor_context)] context: CommandExecutorContext,
+    ) {
+        // Arrange
+        load(LIBRARY).execute(&context).await;
+
+        // Act
+        let actual = load(LIBRARY).execute(&context).await;
+
+        // Assert
+        let expected = Value::Error("ERR Library 'mylib' already exists".to_string());
+        assert_eq!(actual, expected);
+    }
+
+    #[rstest::rstest]
+    #[tokio::test]
+    async fn sut_responds_error_if_deleted_library_does_not_exist(
+        #[from(command_executor_context)] context: CommandExecutorContext,
+    ) {
+        // Act
+        let actual = Function::Delete("mylib".to_string())
+            .execute(&context)
+            .await;
+
+        // Assert
+        assert_eq!(actual, Value::Error("ERR Library not found".to_string()));
+    }
+
+    #[rstest::rstest]
+    #[tokio::test]
+    async fn sut_restores_dumped_libraries(
+        #[from(command_executor_context)] context: CommandExecutorContext,
+    ) {
+        // Arrange
+        load(LIBRARY).execute(&context).await;
+        let Value::Raw(reply) = Function::Dump.execute(&context).await else {
+            panic!("expected the payload as it is");
+        };
+        let Ok(Some((Value::BulkString(payload), _))) = Value::decode(&reply) else {
+            panic!("expected a bulk string payload");
+        };
+        Function::Flush.execute(&context).await;
+        let command = Function::Restore {
+            payload,
+            policy: RestorePolicy::Append,
+        };
+
+        // Act
+        let actual = command.execute(&context).await;
+
+        // Assert
+        assert_eq!(actual, Value::SimpleString("OK".to_string()));
+        let names: Vec<String> = context
+            .scripts
+            .libraries
+            .list()
+            .into_iter()
+            .map(|library| library.name)
+            .collect();
+        assert_eq!(names, vec!["mylib".to_string()]);
+    }
+}
@@ -0,0 +1,228 @@
+use crate::command::executor::Command;
+use crate::command::executor::CommandExecutor;
+use crate::command::executor::CommandExecutorContext;
+use crate::command::parser::extract_array;
+use crate::command::parser::extract_bulk_string;
+use crate::command::parser::validate_main_command;
+use crate::command::parser::validate_min_array_length;
+use crate::geo::Coordinate;
+use crate::notification::Class;
+use crate::repository::AddCondition;
+use crate::repository::AddOutcome;
+use crate::resp::Value;
+
+#[derive(Debug, PartialEq)]
+pub struct GeoAdd {
+    key: String,
+    condition: AddCondition,
+    changed: bool,
+    members: Vec<(f64, f64, String)>,
+}
+
+impl Command for GeoAdd {
+    fn parse_from(value: &Value) -> Result<Self, anyhow::Error> {
+        let array = extract_array(value)?;
+        validate_min_array_length(array, 5)?;
+        validate_main_command(array, "GEOADD")?;
+        let key = extract_bulk_string(array, 1)?;
+
+        let mut condition = AddCondition::Always;
+        let mut changed = false;
+        let mut index = 2;
+        loop {
+            match extract_bulk_string(array, index)?.to_uppercase().as_str() {
+                "NX" if condition != AddCondition::Exists => condition = AddCondition::NotExists,
+                "XX" if condition != AddCondition::NotExists => condition = AddCondition::Exists,
+                "NX" | "XX" => {
+                    return Err(anyhow::anyhow!(
+                        "XX and NX options at the same time are not compatible"
+                    ));
+                }
+                "CH" => changed = true,
+                _ => break,
+            }
+            index += 1;
+        }
+
+        let arguments = &array[index..];
+        if arguments.is_empty() || arguments.len() % 3 != 0 {
+            return Err(anyhow::anyhow!(
+                "wrong number of arguments for 'geoadd' command"
+            ));
+        }
+        let members = (0..arguments.len())
+            .step_by(3)
+            .map(|i| {
+                let longitude = extract_bulk_string(arguments, i)?.parse()?;
+                let latitude = extract_bulk_string(arguments, i + 1)?.parse()?;
+                let member = extract_bulk_string(arguments, i + 2)?;
+                Ok((longitude, latitude, member.to_string()))
+            })
+            .collect::<Result<_, anyhow::Error>>()?;
+
+        Ok(GeoAdd {
+            key: key.to_string(),
+            condition,
+            changed,
+            members,
+        })
+    }
+}
+
+#[async_trait::async_trait]
+impl CommandExecutor for GeoAdd {
+    async fn execute(&self, context: &CommandExecutorContext) -> Value {
+        let mut members = Vec::with_capacity(self.members.len());
+        for (longitude, latitude, member) in self.members.iter() {
+            let Some(coordinate) = Coordinate::new(*longitude, *latitude) else {
+                return Value::Error(format!(
+                    "ERR invalid longitude,latitude pair {longitude:.6},{latitude:.6}"
+                ));
+            };
+            members.push((member.clone(), coordinate.to_score()));
+        }
+
+        match context
+            .repository()
+            .zadd(&self.key, members, self.condition)
+            .await
+        {
+            Ok(outcomes) => {
+                if outcomes.contains(&AddOutcome::Added) || outcomes.contains(&AddOutcome::Updated)
+                {
+                    context.notify(Class::SortedSet, "zadd", &self.key);
+                }
+                let count = outcomes
+                    .into_iter()
+                    .filter(|outcome| match outcome {
+                        AddOutcome::Added => true,
+                        AddOutcome::Updated => self.changed,
+                        AddOutcome::Unchanged => false,
+                    })
+                    .count();
+                Value::Integer(count as i64)
+            }
+            Err(e) => Value::Error(e.to_string()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod specs_for_parse_from {
+    use fake::Fake;
+    use fake::faker::lorem::en::Word;
+
+    use crate::command::executor::Command;
+    use crate::repository::AddCondition;
+    use crate::resp::Value;
+
+    use super::GeoAdd;
+
+    #[test]
+    fn sut_parses_geoadd_command_with_options_correctly() {
+        // Arrange
+        let key: &str = Word().fake();
+        let value = Value::Array(vec![
+            Value::BulkString("GEOADD".to_string()),
+            Value::BulkString(key.to_string()),
+            Value::BulkString("xx".to_string()),
+            Value::BulkString("CH".to_string()),
+            Value::BulkString("13.361389".to_string()),
+            Value::BulkString("38.115556".to_string()),
+            Value::BulkString("Palermo".to_string()),
+        ]);
+
+        // Act
+        let actual = GeoAdd::parse_from(&value).unwrap();
+
+        // Assert
+        let expected = GeoAdd {
+            key: key.to_string(),
+            condition: AddCondition::Exists,
+            changed: true,
+            members: vec![(13.361389, 38.115556, "Palermo".to_string())],
+        };
+        assert_eq!(actual, expected);
+    }
+
+    #[rstest::rstest]
+    #[case(vec!["GEOADD", "Sicily", "NX", "XX", "13.361389", "38.115556", "Palermo"])]
+    #[case(vec!["GEOADD", "Sicily", "13.361389", "38.115556"])]
+    #[case(vec!["GEOADD", "Sicily", "east", "38.115556", "Palermo"])]
+    fn sut_raises_error_if_arguments_are_invalid(#[case] arguments: Vec<&str>) {
+        // Arrange
+        let value = Value::Array(
+            arguments
+                .into_iter()
+                .map(|argument| Value::BulkString(argument.to_string()))
+                .collect(),
+        );
+
+        // Act
+        let actual = GeoAdd::parse_from(&value);
+
+        // Assert
+        assert!(actual.is_err());
+    }
+}
+
+#[cfg(test)]
+mod specs_for_execute {
+    use crate::command::executor::CommandExecutor;
+    use crate::command::executor::CommandExecutorContext;
+    use crate::command::executor::fixture::command_executor_context;
+    use crate::repository::AddCondition;
+    use crate::repository::InMemoryRepository;
+    use crate::resp::Value;
+
+    use super::GeoAdd;
+
+    #[rstest::rstest]
+    #[tokio::test]
+    async fn sut_stores_members_scored_by_geohash(
+        #[from(command_executor_context)]
+        #[with(InMemoryRepository::new())]
+        context: CommandExecutorContext,
+    ) {
+        // Arrange
+        let command = GeoAdd {
+            key: "Sicily".to_string(),
+            condition: AddCondition::Always,
+            changed: false,
+            members: vec![
+                (13.361389, 38.115556, "Palermo".to_string()),
+                (15.087269, 37.502669, "Catania".to_string()),
+            ],
+        };
+
+        // Act
+        let actual = command.execute(&context).await;
+
+        // Assert
+        assert_eq!(actual, Value::Integer(2));
+        let score = context.repository().zscore("Sicily", "Palermo").await;
+        assert_eq!(score, Ok(Some(3479099956230698.0)));
+    }
+
+    #[rstest::rstest]
+    #[tokio::test]
+    async fn sut_responds_error_if_coordinate_is_out_of_range(
+        #[from(command_executor_context)] context: CommandExecutorContext,
+    ) {
+        // Arrange
+        let command = GeoAdd {
+            key: "Sicily".to_string(),
+            condition: AddCondition::Always,
+            changed: false,
+            members: vec![(13.361389, 86.0, "Palermo".to_string())],
+        };
+
+        // Act
+        let actual = command.execute(&context).await;
+
+        // Assert
+        let expected =
+            Value::Error("ERR invalid longitude,latitude pair 13.361389,86.000000".to_string());
+        assert_eq!(actual, expected);
+    }
+}
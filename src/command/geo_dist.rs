@@ -0,0 +1,174 @@
+use crate::command::executor::Command;
+use crate::command::executor::CommandExecutor;
+use crate::command::executor::CommandExecutorContext;
+use crate::command::parser::extract_array;
+use crate::command::parser::extract_bulk_string;
+use crate::command::parser::validate_main_command;
+use crate::command::parser::validate_min_array_length;
+use crate::geo::Coordinate;
+use crate::geo::DistanceUnit;
+use crate::resp::Value;
+
+#[derive(Debug, PartialEq)]
+pub struct GeoDist {
+    key: String,
+    members: (String, String),
+    unit: DistanceUnit,
+}
+
+impl Command for GeoDist {
+    fn parse_from(value: &Value) -> Result<Self, anyhow::Error> {
+        let array = extract_array(value)?;
+        validate_min_array_length(array, 4)?;
+        validate_main_command(array, "GEODIST")?;
+        let key = extract_bulk_string(array, 1)?;
+        let member1 = extract_bulk_string(array, 2)?;
+        let member2 = extract_bulk_string(array, 3)?;
+        let unit = match array.len() {
+            4 => DistanceUnit::Meters,
+            5 => DistanceUnit::parse(extract_bulk_string(array, 4)?).ok_or_else(|| {
+                anyhow::anyhow!("unsupported unit provided. please use M, KM, FT, MI")
+            })?,
+            _ => return Err(anyhow::anyhow!("syntax error")),
+        };
+        Ok(GeoDist {
+            key: key.to_string(),
+            members: (member1.to_string(), member2.to_string()),
+            unit,
+        })
+    }
+}
+
+#[async_trait::async_trait]
+impl CommandExecutor for GeoDist {
+    async fn execute(&self, context: &CommandExecutorContext) -> Value {
+        context.track(&self.key);
+        let repository = &context.repository();
+        let scores = (
+            repository.zscore(&self.key, &self.members.0).await,
+            repository.zscore(&self.key, &self.members.1).await,
+        );
+        match scores {
+            (Ok(Some(score1)), Ok(Some(score2))) => {
+                let distance =
+                    Coordinate::from_score(score1).distance(&Coordinate::from_score(score2));
+                Value::BulkString(format!("{:.4}", self.unit.to_unit(distance)))
+            }
+            (Err(e), _) | (_, Err(e)) => Value::Error(e.to_string()),
+            _ => Value::Null,
+        }
+    }
+}
+
+#[cfg(test)]
+mod specs_for_parse_from {
+    use crate::command::executor::Command;
+    use crate::geo::DistanceUnit;
+    use crate::resp::Value;
+
+    use super::GeoDist;
+
+    #[rstest::rstest]
+    #[case(vec![], DistanceUnit::Meters)]
+    #[case(vec!["km"], DistanceUnit::Kilometers)]
+    #[case(vec!["MI"], DistanceUnit::Miles)]
+    fn sut_parses_geodist_command_with_unit_correctly(
+        #[case] unit: Vec<&str>,
+        #[case] expected_unit: DistanceUnit,
+    ) {
+        // Arrange
+        let mut array = vec![
+            Value::BulkString("GEODIST".to_string()),
+            Value::BulkString("Sicily".to_string()),
+            Value::BulkString("Palermo".to_string()),
+            Value::BulkString("Catania".to_string()),
+        ];
+        array.extend(unit.into_iter().map(|u| Value::BulkString(u.to_string())));
+        let value = Value::Array(array);
+
+        // Act
+        let actual = GeoDist::parse_from(&value).unwrap();
+
+        // Assert
+        let expected = GeoDist {
+            key: "Sicily".to_string(),
+            members: ("Palermo".to_string(), "Catania".to_string()),
+            unit: expected_unit,
+        };
+        assert_eq!(actual, expected);
+    }
+}
+
+#[cfg(test)]
+mod specs_for_execute {
+    use crate::command::executor::CommandExecutor;
+    use crate::command::executor::CommandExecutorContext;
+    use crate::command::executor::fixture::command_executor_context;
+    use crate::geo::Coordinate;
+    use crate::geo::DistanceUnit;
+    use crate::repository::AddCondition;
+    use crate::repository::InMemoryRepository;
+    use crate::resp::Value;
+
+    use super::GeoDist;
+
+    #[rstest::rstest]
+    #[case(DistanceUnit::Meters, "166274.1516")]
+    #[case(DistanceUnit::Kilometers, "166.2742")]
+    #[tokio::test]
+    async fn sut_responds_distance_between_members_in_unit(
+        #[case] unit: DistanceUnit,
+        #[case] expected: &str,
+        #[from(command_executor_context)]
+        #[with(InMemoryRepository::new())]
+        context: CommandExecutorContext,
+    ) {
+        // Arrange
+        let members = vec![
+            ("Palermo".to_string(), Coordinate::new(13.361389, 38.115556)),
+            ("Catania".to_string(), Coordinate::new(15.087269, 37.502669)),
+        ];
+        context
+            .repository()
+            .zadd(
+                "Sicily",
+                members
+                    .into_iter()
+                    .map(|(member, coordinate)| (member, coordinate.unwrap().to_score()))
+                    .collect(),
+                AddCondition::Always,
+            )
+            .await
+            .unwrap();
+        let command = GeoDist {
+            key: "Sicily".to_string(),
+            members: ("Palermo".to_string(), "Catania".to_string()),
+            unit,
+        };
+
+        // Act
+        let actual = command.execute(&context).await;
+
+        // Assert
+        assert_eq!(actual, Value::BulkString(expected.to_string()));
+    }
+
+    #[rstest::rstest]
+    #[tokio::test]
+    async fn sut_responds_null_if_member_does_not_exist(
+        #[from(command_executor_context)] context: CommandExecutorContext,
+    ) {
+        // Arrange
+        let command = GeoDist {
+            key: "Sicily".to_string(),
+            members: ("Palermo".to_string(), "Catania".to_string()),
+            unit: DistanceUnit::Meters,
+        };
+
+        // Act
+        let actual = command.execute(&context).await;
+
+        // Assert
+        assert_eq!(actual, Value::Null);
+    }
+}
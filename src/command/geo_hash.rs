@@ -0,0 +1,137 @@
+use crate::command::executor::Command;
+use crate::command::executor::CommandExecutor;
+use crate::command::executor::CommandExecutorContext;
+use crate::command::parser::extract_array;
+use crate::command::parser::extract_bulk_string;
+use crate::command::parser::extract_bulk_strings;
+use crate::command::parser::validate_main_command;
+use crate::command::parser::validate_min_array_length;
+use crate::geo::Coordinate;
+use crate::resp::Value;
+
+#[derive(Debug, Default, PartialEq)]
+pub struct GeoHash {
+    key: String,
+    members: Vec<String>,
+}
+
+impl Command for GeoHash {
+    fn parse_from(value: &Value) -> Result<Self, anyhow::Error> {
+        let array = extract_array(value)?;
+        validate_min_array_length(array, 2)?;
+        validate_main_command(array, "GEOHASH")?;
+        let key = extract_bulk_string(array, 1)?;
+        let members = extract_bulk_strings(array, 2)?;
+        Ok(GeoHash {
+            key: key.to_string(),
+            members,
+        })
+    }
+}
+
+#[async_trait::async_trait]
+impl CommandExecutor for GeoHash {
+    async fn execute(&self, context: &CommandExecutorContext) -> Value {
+        context.track(&self.key);
+        let mut hashes = Vec::with_capacity(self.members.len());
+        for member in self.members.iter() {
+            match context.repository().zscore(&self.key, member).await {
+                Ok(Some(score)) => hashes.push(Value::BulkString(
+                    Coordinate::from_score(score).to_geohash_string(),
+                )),
+                Ok(None) => hashes.push(Value::Null),
+                Err(e) => return Value::Error(e.to_string()),
+            }
+        }
+        Value::Array(hashes)
+    }
+}
+
+#[cfg(test)]
+mod specs_for_parse_from {
+    use crate::command::executor::Command;
+    use crate::resp::Value;
+
+    use super::GeoHash;
+
+    #[rstest::rstest]
+    #[case("GEOHASH")]
+    #[case("geohash")]
+    #[case("GeoHash")]
+    fn sut_parses_geohash_command_with_case_insensitive(#[case] geohash: &str) {
+        // Arrange
+        let value = Value::Array(vec![
+            Value::BulkString(geohash.to_string()),
+            Value::BulkString("Sicily".to_string()),
+            Value::BulkString("Palermo".to_string()),
+        ]);
+
+        // Act
+        let actual = GeoHash::parse_from(&value).unwrap();
+
+        // Assert
+        let expected = GeoHash {
+            key: "Sicily".to_string(),
+            members: vec!["Palermo".to_string()],
+        };
+        assert_eq!(actual, expected);
+    }
+}
+
+#[cfg(test)]
+mod specs_for_execute {
+    use crate::command::executor::CommandExecutor;
+    use crate::command::executor::CommandExecutorContext;
+    use crate::command::executor::fixture::command_executor_context;
+    use crate::geo::Coordinate;
+    use crate::repository::AddCondition;
+    use crate::repository::InMemoryRepository;
+    use crate::resp::Value;
+
+    use super::GeoHash;
+
+    #[rstest::rstest]
+    #[tokio::test]
+    async fn sut_responds_geohash_strings_and_null_for_missing_members(
+        #[from(command_executor_context)]
+        #[with(InMemoryRepository::new())]
+        context: CommandExecutorContext,
+    ) {
+        // Arrange
+        let members = vec![
+            ("Palermo".to_string(), Coordinate::new(13.361389, 38.115556)),
+            ("Catania".to_string(), Coordinate::new(15.087269, 37.502669)),
+        ];
+        context
+            .repository()
+            .zadd(
+                "Sicily",
+                members
+                    .into_iter()
+                    .map(|(member, coordinate)| (member, coordinate.unwrap().to_score()))
+                    .collect(),
+                AddCondition::Always,
+            )
+            .await
+            .unwrap();
+        let command = GeoHash {
+            key: "Sicily".to_string(),
+            members: vec![
+                "Palermo".to_string(),
+                "Catania".to_string(),
+                "NonExisting".to_string(),
+            ],
+        };
+
+        // Act
+        let actual = command.execute(&context).await;
+
+        // Assert
+        let expected = Value::Array(vec![
+            Value::BulkString("sqc8b49rny0".to_string()),
+            Value::BulkString("sqdtr74hyu0".to_string()),
+            Value::Null,
+        ]);
+        assert_eq!(actual, expected);
+    }
+}
@@ -0,0 +1,134 @@
+use crate::command::executor::Command;
+use crate::command::executor::CommandExecutor;
+use crate::command::executor::CommandExecutorContext;
+use crate::command::parser::extract_array;
+use crate::command::parser::extract_bulk_string;
+use crate::command::parser::extract_bulk_strings;
+use crate::command::parser::validate_main_command;
+use crate::command::parser::validate_min_array_length;
+use crate::geo::Coordinate;
+use crate::resp::Value;
+
+#[derive(Debug, Default, PartialEq)]
+pub struct GeoPos {
+    key: String,
+    members: Vec<String>,
+}
+
+impl Command for GeoPos {
+    fn parse_from(value: &Value) -> Result<Self, anyhow::Error> {
+        let array = extract_array(value)?;
+        validate_min_array_length(array, 2)?;
+        validate_main_command(array, "GEOPOS")?;
+        let key = extract_bulk_string(array, 1)?;
+        let members = extract_bulk_strings(array, 2)?;
+        Ok(GeoPos {
+            key: key.to_string(),
+            members,
+        })
+    }
+}
+
+#[async_trait::async_trait]
+impl CommandExecutor for GeoPos {
+    async fn execute(&self, context: &CommandExecutorContext) -> Value {
+        context.track(&self.key);
+        let mut positions = Vec::with_capacity(self.members.len());
+        for member in self.members.iter() {
+            match context.repository().zscore(&self.key, member).await {
+                Ok(Some(score)) => {
+                    let coordinate = Coordinate::from_score(score);
+                    positions.push(Value::Array(vec![
+                        Value::BulkString(coordinate.longitude.to_string()),
+                        Value::BulkString(coordinate.latitude.to_string()),
+                    ]));
+                }
+                Ok(None) => positions.push(Value::NullArray),
+                Err(e) => return Value::Error(e.to_string()),
+            }
+        }
+        Value::Array(positions)
+    }
+}
+
+#[cfg(test)]
+mod specs_for_parse_from {
+    use crate::command::executor::Command;
+    use crate::resp::Value;
+
+    use super::GeoPos;
+
+    #[rstest::rstest]
+    #[case("GEOPOS")]
+    #[case("geopos")]
+    #[case("GeoPos")]
+    fn sut_parses_geopos_command_with_case_insensitive(#[case] geopos: &str) {
+        // Arrange
+        let value = Value::Array(vec![
+            Value::BulkString(geopos.to_string()),
+            Value::BulkString("Sicily".to_string()),
+            Value::BulkString("Palermo".to_string()),
+            Value::BulkString("Catania".to_string()),
+        ]);
+
+        // Act
+        let actual = GeoPos::parse_from(&value).unwrap();
+
+        // Assert
+        let expected = GeoPos {
+            key: "Sicily".to_string(),
+            members: vec!["Palermo".to_string(), "Catania".to_string()],
+        };
+        assert_eq!(actual, expected);
+    }
+}
+
+#[cfg(test)]
+mod specs_for_execute {
+    use crate::command::executor::CommandExecutor;
+    use crate::command::executor::CommandExecutorContext;
+    use crate::command::executor::fixture::command_executor_context;
+    use crate::geo::Coordinate;
+    use crate::repository::AddCondition;
+    use crate::repository::InMemoryRepository;
+    use crate::resp::Value;
+
+    use super::GeoPos;
+
+    #[rstest::rstest]
+    #[tokio::test]
+    async fn sut_responds_positions_and_null_for_missing_members(
+        #[from(command_executor_context)]
+        #[with(InMemoryRepository::new())]
+        context: CommandExecutorContext,
+    ) {
+        // Arrange
+        let score = Coordinate::new(13.361389, 38.115556).unwrap().to_score();
+        context
+            .repository()
+            .zadd(
+                "Sicily",
+                vec![("Palermo".to_string(), score)],
+                AddCondition::Always,
+            )
+            .await
+            .unwrap();
+        let command = GeoPos {
+            key: "Sicily".to_string(),
+            members: vec!["Palermo".to_string(), "NonExisting".to_string()],
+        };
+
+        // Act
+        let actual = command.execute(&context).await;
+
+        // Assert
+        let expected = Value::Array(vec![
+            Value::Array(vec![
+                Value::BulkString("13.361389338970184".to_string()),
+                Value::BulkString("38.1155563954963".to_string()),
+            ]),
+            Value::NullArray,
+        ]);
+        assert_eq!(actual, expected);
+    }
+}
@@ -0,0 +1,546 @@
+use crate::command::executor::Command;
+use crate::command::executor::CommandExecutor;
+use crate::command::executor::CommandExecutorContext;
+use crate::command::parser::extract_array;
+use crate::command::parser::extract_bulk_string;
+use crate::command::parser::validate_main_command;
+use crate::command::parser::validate_min_array_length;
+use crate::geo::Coordinate;
+use crate::geo::DistanceUnit;
+use crate::geo::Shape;
+use crate::resp::Value;
+
+#[derive(Debug, PartialEq)]
+pub struct GeoSearch {
+    key: String,
+    query: SearchQuery,
+    with_coord: bool,
+    with_dist: bool,
+    with_hash: bool,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Origin {
+    Member(String),
+    LonLat(f64, f64),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Order {
+    Asc,
+    Desc,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SearchQuery {
+    origin: Origin,
+    shape: Shape,
+    pub unit: DistanceUnit,
+    order: Option<Order>,
+    count: Option<usize>,
+    any: bool,
+}
+
+pub struct GeoMatch {
+    pub member: String,
+    pub score: f64,
+    pub distance: f64,
+    pub coordinate: Coordinate,
+}
+
+/// Collects the options shared by GEOSEARCH and GEOSEARCHSTORE in any order.
+#[derive(Default)]
+pub struct SearchQueryParser {
+    origin: Option<Origin>,
+    shape: Option<(Shape, DistanceUnit)>,
+    order: Option<Order>,
+    count: Option<(usize, bool)>,
+}
+
+impl SearchQueryParser {
+    /// Returns how many arguments the search option at `index` takes, or zero if it is not one.
+    pub fn consume(&mut self, array: &[Value], index: usize) -> Result<usize, anyhow::Error> {
+        let argument = |offset: usize| extract_bulk_string(array, index + offset);
+        let unit = |offset: usize| {
+            DistanceUnit::parse(argument(offset)?).ok_or_else(|| {
+                anyhow::anyhow!("unsupported unit provided. please use M, KM, FT, MI")
+            })
+        };
+
+        match argument(0)?.to_uppercase().as_str() {
+            "FROMMEMBER" | "FROMLONLAT" if self.origin.is_some() => Err(anyhow::anyhow!(
+                "exactly one of FROMMEMBER or FROMLONLAT can be specified"
+            )),
+            "BYRADIUS" | "BYBOX" if self.shape.is_some() => Err(anyhow::anyhow!(
+                "exactly one of BYRADIUS and BYBOX can be specified"
+            )),
+            "FROMMEMBER" => {
+                self.origin = Some(Origin::Member(argument(1)?.to_string()));
+                Ok(2)
+            }
+            "FROMLONLAT" => {
+                self.origin = Some(Origin::LonLat(argument(1)?.parse()?, argument(2)?.parse()?));
+                Ok(3)
+            }
+            "BYRADIUS" => {
+                let radius: f64 = argument(1)?.parse()?;
+                if radius < 0.0 {
+                    return Err(anyhow::anyhow!("radius cannot be negative"));
+                }
+                let unit = unit(2)?;
+                self.shape = Some((Shape::Radius(unit.to_meters(radius)), unit));
+                Ok(3)
+            }
+            "BYBOX" => {
+                let width: f64 = argument(1)?.parse()?;
+                let height: f64 = argument(2)?.parse()?;
+                if width < 0.0 || height < 0.0 {
+                    return Err(anyhow::anyhow!("height or width cannot be negative"));
+                }
+                let unit = unit(3)?;
+                let shape = Shape::Box(unit.to_meters(width), unit.to_meters(height));
+                self.shape = Some((shape, unit));
+                Ok(4)
+            }
+            "ASC" => {
+                self.order = Some(Order::Asc);
+                Ok(1)
+            }
+            "DESC" => {
+                self.order = Some(Order::Desc);
+                Ok(1)
+            }
+            "COUNT" => {
+                let count: usize = argument(1)?.parse()?;
+                if count == 0 {
+                    return Err(anyhow::anyhow!("COUNT must be > 0"));
+                }
+                let any = argument(2).is_ok_and(|any| any.eq_ignore_ascii_case("ANY"));
+                self.count = Some((count, any));
+                Ok(if any { 3 } else { 2 })
+            }
+            _ => Ok(0),
+        }
+    }
+
+    pub fn build(self) -> Result<SearchQuery, anyhow::Error> {
+        let origin = self.origin.ok_or_else(|| {
+            anyhow::anyhow!("exactly one of FROMMEMBER or FROMLONLAT can be specified")
+        })?;
+        let (shape, unit) = self
+            .shape
+            .ok_or_else(|| anyhow::anyhow!("exactly one of BYRADIUS and BYBOX can be specified"))?;
+        let (count, any) = match self.count {
+            Some((count, any)) => (Some(count), any),
+            None => (None, false),
+        };
+        // Redis sorts by distance whenever a full COUNT has to pick the nearest members.
+        let order = match self.order {
+            None if count.is_some() && !any => Some(Order::Asc),
+            order => order,
+        };
+        Ok(SearchQuery {
+            origin,
+            shape,
+            unit,
+            order,
+            count,
+            any,
+        })
+    }
+}
+
+impl SearchQuery {
+    pub async fn search(
+        &self,
+        key: &str,
+        context: &CommandExecutorContext,
+    ) -> Result<Vec<GeoMatch>, anyhow::Error> {
+        let repository = &context.repository();
+        if let Origin::LonLat(longitude, latitude) = self.origin {
+            Coordinate::new(longitude, latitude).ok_or_else(|| {
+                anyhow::anyhow!("ERR invalid longitude,latitude pair {longitude:.6},{latitude:.6}")
+            })?;
+        }
+        if repository.zcard(key).await? == 0 {
+            return Ok(vec![]);
+        }
+
+        let center = match &self.origin {
+            Origin::Member(member) => {
+                let score = repository
+                    .zscore(key, member)
+                    .await?
+                    .ok_or_else(|| anyhow::anyhow!("ERR could not decode requested zset member"))?;
+                Coordinate::from_score(score)
+            }
+            Origin::LonLat(longitude, latitude) => Coordinate {
+                longitude: *longitude,
+                latitude: *latitude,
+            },
+        };
+
+        let limit = self.count.filter(|_| self.any);
+        let mut matches = Vec::new();
+        'ranges: for (min, max) in self.shape.score_ranges(&center) {
+            for (member, score) in repository.zrange_by_score(key, min..max).await? {
+                let coordinate = Coordinate::from_score(score);
+                let Some(distance) = self.shape.distance_if_contains(&center, &coordinate) else {
+                    continue;
+                };
+                matches.push(GeoMatch {
+                    member,
+                    score,
+                    distance,
+                    coordinate,
+                });
+                if limit.is_some_and(|limit| matches.len() >= limit) {
+                    break 'ranges;
+                }
+            }
+        }
+
+        match self.order {
+            Some(Order::Asc) => matches.sort_by(|a, b| a.distance.total_cmp(&b.distance)),
+            Some(Order::Desc) => matches.sort_by(|a, b| b.distance.total_cmp(&a.distance)),
+            None => {}
+        }
+        if let Some(count) = self.count {
+            matches.truncate(count);
+        }
+        Ok(matches)
+    }
+}
+
+impl Command for GeoSearch {
+    fn parse_from(value: &Value) -> Result<Self, anyhow::Error> {
+        let array = extract_array(value)?;
+        validate_min_array_length(array, 6)?;
+        validate_main_command(array, "GEOSEARCH")?;
+        let key = extract_bulk_string(array, 1)?;
+
+        let mut parser = SearchQueryParser::default();
+        let (mut with_coord, mut with_dist, mut with_hash) = (false, false, false);
+        let mut index = 2;
+        while index < array.len() {
+            let consumed = parser.consume(array, index)?;
+            if consumed > 0 {
+                index += consumed;
+                continue;
+            }
+            match extract_bulk_string(array, index)?.to_uppercase().as_str() {
+                "WITHCOORD" => with_coord = true,
+                "WITHDIST" => with_dist = true,
+                "WITHHASH" => with_hash = true,
+                _ => return Err(anyhow::anyhow!("syntax error")),
+            }
+            index += 1;
+        }
+
+        Ok(GeoSearch {
+            key: key.to_string(),
+            query: parser.build()?,
+            with_coord,
+            with_dist,
+            with_hash,
+        })
+    }
+}
+
+#[async_trait::async_trait]
+impl CommandExecutor for GeoSearch {
+    async fn execute(&self, context: &CommandExecutorContext) -> Value {
+        context.track(&self.key);
+        let matches = match self.query.search(&self.key, context).await {
+            Ok(matches) => matches,
+            Err(e) => return Value::Error(e.to_string()),
+        };
+
+        let items = matches
+            .into_iter()
+            .map(|geo_match| {
+                let member = Value::BulkString(geo_match.member);
+                if !(self.with_coord || self.with_dist || self.with_hash) {
+                    return member;
+                }
+                let mut item = vec![member];
+                if self.with_dist {
+                    let distance = self.query.unit.to_unit(geo_match.distance);
+                    item.push(Value::BulkString(format!("{distance:.4}")));
+                }
+                if self.with_hash {
+                    item.push(Value::Integer(geo_match.score as i64));
+                }
+                if self.with_coord {
+                    item.push(Value::Array(vec![
+                        Value::BulkString(geo_match.coordinate.longitude.to_string()),
+                        Value::BulkString(geo_match.coordinate.latitude.to_string()),
+                    ]));
+                }
+                Value::Array(item)
+            })
+            .collect();
+        Value::Array(items)
+    }
+}
+
+#[cfg(test)]
+mod specs_for_parse_from {
+    use crate::command::executor::Command;
+    use crate::geo::DistanceUnit;
+    use crate::geo::Shape;
+    use crate::resp::Value;
+
+    use super::GeoSearch;
+    use super::Order;
+    use super::Origin;
+    use super::SearchQuery;
+
+    fn array(arguments: &[&str]) -> Value {
+        Value::Array(
+            arguments
+                .iter()
+                .map(|argument| Value::BulkString(argument.to_string()))
+                .collect(),
+        )
+    }
+
+    #[test]
+    fn sut_parses_geosearch_command_by_radius_correctly() {
+        // Arrange
+        let value = array(&[
+            "GEOSEARCH",
+            "Sicily",
+            "FROMLONLAT",
+            "15",
+            "37",
+            "BYRADIUS",
+            "200",
+            "km",
+            "COUNT",
+            "1",
+            "WITHDIST",
+        ]);
+
+        // Act
+        let actual = GeoSearch::parse_from(&value).unwrap();
+
+        // Assert
+        let expected = GeoSearch {
+            key: "Sicily".to_string(),
+            query: SearchQuery {
+                origin: Origin::LonLat(15.0, 37.0),
+                shape: Shape::Radius(200_000.0),
+                unit: DistanceUnit::Kilometers,
+                order: Some(Order::Asc),
+                count: Some(1),
+                any: false,
+            },
+            with_coord: false,
+            with_dist: true,
+            with_hash: false,
+        };
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn sut_parses_geosearch_command_by_box_from_member_correctly() {
+        // Arrange
+        let value = array(&[
+            "geosearch",
+            "Sicily",
+            "BYBOX",
+            "400",
+            "400",
+            "km",
+            "FROMMEMBER",
+            "Palermo",
+            "DESC",
+            "COUNT",
+            "2",
+            "ANY",
+        ]);
+
+        // Act
+        let actual = GeoSearch::parse_from(&value).unwrap();
+
+        // Assert
+        let expected = GeoSearch {
+            key: "Sicily".to_string(),
+            query: SearchQuery {
+                origin: Origin::Member("Palermo".to_string()),
+                shape: Shape::Box(400_000.0, 400_000.0),
+                unit: DistanceUnit::Kilometers,
+                order: Some(Order::Desc),
+                count: Some(2),
+                any: true,
+            },
+            with_coord: false,
+            with_dist: false,
+            with_hash: false,
+        };
+        assert_eq!(actual, expected);
+    }
+
+    #[rstest::rstest]
+    #[case(&["GEOSEARCH", "Sicily", "FROMLONLAT", "15", "37", "WITHDIST"])]
+    #[case(&["GEOSEARCH", "Sicily", "BYRADIUS", "200", "km", "WITHDIST"])]
+    #[case(&["GEOSEARCH", "Sicily", "FROMMEMBER", "a", "FROMLONLAT", "15", "37", "BYRADIUS", "1", "m"])]
+    #[case(&["GEOSEARCH", "Sicily", "FROMLONLAT", "15", "37", "BYRADIUS", "200", "yards"])]
+    #[case(&["GEOSEARCH", "Sicily", "FROMLONLAT", "15", "37", "BYRADIUS", "200", "km", "COUNT", "0"])]
+    fn sut_raises_error_if_arguments_are_invalid(#[case] arguments: &[&str]) {
+        // Act
+        let actual = GeoSearch::parse_from(&array(arguments));
+
+        // Assert
+        assert!(actual.is_err());
+    }
+}
+
+#[cfg(test)]
+mod specs_for_execute {
+    use crate::command::executor::Command;
+    use crate::command::executor::CommandExecutor;
+    use crate::command::executor::CommandExecutorContext;
+    use crate::command::executor::fixture::command_executor_context;
+    use crate::geo::Coordinate;
+    use crate::repository::AddCondition;
+    use crate::repository::InMemoryRepository;
+    use crate::resp::Value;
+
+    use super::GeoSearch;
+
+    async fn add_sicily(context: &CommandExecutorContext) {
+        let members = vec![
+            ("Palermo", 13.361389, 38.115556),
+            ("Catania", 15.087269, 37.502669),
+            ("edge1", 12.758489, 38.788135),
+            ("edge2", 17.241510, 38.788135),
+        ];
+        context
+            .repository()
+            .zadd(
+                "Sicily",
+                members
+                    .into_iter()
+                    .map(|(member, longitude, latitude)| {
+                        let coordinate = Coordinate::new(longitude, latitude).unwrap();
+                        (member.to_string(), coordinate.to_score())
+                    })
+                    .collect(),
+                AddCondition::Always,
+            )
+            .await
+            .unwrap();
+    }
+
+    fn command(arguments: &[&str]) -> GeoSearch {
+        let mut array = vec![
+            Value::BulkString("GEOSEARCH".to_string()),
+            Value::BulkString("Sicily".to_string()),
+        ];
+        array.extend(
+            arguments
+                .iter()
+                .map(|argument| Value::BulkString(argument.to_string())),
+        );
+        GeoSearch::parse_from(&Value::Array(array)).unwrap()
+    }
+
+    fn members(value: Value) -> Vec<String> {
+        let Value::Array(items) = value else {
+            panic!("expected array but got {value:?}");
+        };
+        items
+            .into_iter()
+            .map(|item| match item {
+                Value::BulkString(member) => member,
+                Value::Array(mut fields) => match fields.remove(0) {
+                    Value::BulkString(member) => member,
+                    other => panic!("expected member but got {other:?}"),
+                },
+                other => panic!("expected member but got {other:?}"),
+            })
+            .collect()
+    }
+
+    #[rstest::rstest]
+    #[case(&["FROMLONLAT", "15", "37", "BYRADIUS", "200", "km", "ASC"], vec!["Catania", "Palermo"])]
+    #[case(&["FROMLONLAT", "15", "37", "BYRADIUS", "200", "km", "DESC"], vec!["Palermo", "Catania"])]
+    #[case(&["FROMLONLAT", "15", "37", "BYBOX", "400", "400", "km", "ASC"], vec!["Catania", "Palermo", "edge2", "edge1"])]
+    #[case(&["FROMMEMBER", "Palermo", "BYRADIUS", "50", "km"], vec!["Palermo"])]
+    #[case(&["FROMLONLAT", "15", "37", "BYBOX", "400", "400", "km", "COUNT", "2"], vec!["Catania", "Palermo"])]
+    #[tokio::test]
+    async fn sut_responds_members_within_shape_in_order(
+        #[case] arguments: &[&str],
+        #[case] expected: Vec<&str>,
+        #[from(command_executor_context)]
+        #[with(InMemoryRepository::new())]
+        context: CommandExecutorContext,
+    ) {
+        // Arrange
+        add_sicily(&context).await;
+
+        // Act
+        let actual = command(arguments).execute(&context).await;
+
+        // Assert
+        assert_eq!(members(actual), expected);
+    }
+
+    #[rstest::rstest]
+    #[tokio::test]
+    async fn sut_responds_distance_hash_and_coordinates_when_requested(
+        #[from(command_executor_context)]
+        #[with(InMemoryRepository::new())]
+        context: CommandExecutorContext,
+    ) {
+        // Arrange
+        add_sicily(&context).await;
+        let command = command(&[
+            "FROMLONLAT",
+            "15",
+            "37",
+            "BYRADIUS",
+            "100",
+            "km",
+            "WITHCOORD",
+            "WITHDIST",
+            "WITHHASH",
+        ]);
+
+        // Act
+        let actual = command.execute(&context).await;
+
+        // Assert
+        let expected = Value::Array(vec![Value::Array(vec![
+            Value::BulkString("Catania".to_string()),
+            Value::BulkString("56.4413".to_string()),
+            Value::Integer(3479447370796909),
+            Value::Array(vec![
+                Value::BulkString("15.087267458438873".to_string()),
+                Value::BulkString("37.50266842333162".to_string()),
+            ]),
+        ])]);
+        assert_eq!(actual, expected);
+    }
+
+    #[rstest::rstest]
+    #[tokio::test]
+    async fn sut_responds_error_if_origin_member_does_not_exist(
+        #[from(command_executor_context)]
+        #[with(InMemoryRepository::new())]
+        context: CommandExecutorContext,
+    ) {
+        // Arrange
+        add_sicily(&context).await;
+        let command = command(&["FROMMEMBER", "Rome", "BYRADIUS", "100", "km"]);
+
+        // Act
+        let actual = command.execute(&context).await;
+
+        // Assert
+        let expected = Value::Error("ERR could not decode requested zset member".to_string());
+        assert_eq!(actual, expected);
+    }
+}
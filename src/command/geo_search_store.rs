@@ -0,0 +1,248 @@
+use crate::command::executor::Command;
+use crate::command::executor::CommandExecutor;
+use crate::command::executor::CommandExecutorContext;
+use crate::command::geo_search::SearchQuery;
+use crate::command::geo_search::SearchQueryParser;
+use crate::command::parser::extract_array;
+use crate::command::parser::extract_bulk_string;
+use crate::command::parser::validate_main_command;
+use crate::command::parser::validate_min_array_length;
+use crate::notification::Class;
+use crate::repository::Data;
+use crate::repository::Entry;
+use crate::repository::SortedSet;
+use crate::resp::Value;
+
+#[derive(Debug, PartialEq)]
+pub struct GeoSearchStore {
+    destination: String,
+    source: String,
+    query: SearchQuery,
+    store_dist: bool,
+}
+
+impl Command for GeoSearchStore {
+    fn parse_from(value: &Value) -> Result<Self, anyhow::Error> {
+        let array = extract_array(value)?;
+        validate_min_array_length(array, 7)?;
+        validate_main_command(array, "GEOSEARCHSTORE")?;
+        let destination = extract_bulk_string(array, 1)?;
+        let source = extract_bulk_string(array, 2)?;
+
+        let mut parser = SearchQueryParser::default();
+        let mut store_dist = false;
+        let mut index = 3;
+        while index < array.len() {
+            let consumed = parser.consume(array, index)?;
+            if consumed > 0 {
+                index += consumed;
+                continue;
+            }
+            match extract_bulk_string(array, index)?.to_uppercase().as_str() {
+                "STOREDIST" => store_dist = true,
+                _ => return Err(anyhow::anyhow!("syntax error")),
+            }
+            index += 1;
+        }
+
+        Ok(GeoSearchStore {
+            destination: destination.to_string(),
+            source: source.to_string(),
+            query: parser.build()?,
+            store_dist,
+        })
+    }
+}
+
+#[async_trait::async_trait]
+impl CommandExecutor for GeoSearchStore {
+    async fn execute(&self, context: &CommandExecutorContext) -> Value {
+        let matches = match self.query.search(&self.source, context).await {
+            Ok(matches) => matches,
+            Err(e) => return Value::Error(e.to_string()),
+        };
+
+        let sorted_set: SortedSet = matches
+            .into_iter()
+            .map(|geo_match| {
+                let score = if self.store_dist {
+                    self.query.unit.to_unit(geo_match.distance)
+                } else {
+                    geo_match.score
+                };
+                (geo_match.member, score)
+            })
+            .collect();
+        let count = sorted_set.len();
+        let repository = context.repository();
+        let existed = count == 0 && repository.exists(&self.destination).await;
+        repository
+            .set(Entry {
+                key: self.destination.as_str().into(),
+                value: Data::SortedSet(sorted_set),
+                expiry: None,
+            })
+            .await;
+        if count > 0 {
+            context.notify(Class::SortedSet, "geosearchstore", &self.destination);
+        } else if existed {
+            context.notify(Class::Generic, "del", &self.destination);
+        }
+        Value::Integer(count as i64)
+    }
+}
+
+#[cfg(test)]
+mod specs_for_parse_from {
+    use crate::command::executor::Command;
+    use crate::resp::Value;
+
+    use super::GeoSearchStore;
+
+    fn array(arguments: &[&str]) -> Value {
+        Value::Array(
+            arguments
+                .iter()
+                .map(|argument| Value::BulkString(argument.to_string()))
+                .collect(),
+        )
+    }
+
+    #[test]
+    fn sut_parses_geosearchstore_command_with_storedist_correctly() {
+        // Arrange
+        let value = array(&[
+            "GEOSEARCHSTORE",
+            "nearby",
+            "Sicily",
+            "FROMLONLAT",
+            "15",
+            "37",
+            "BYRADIUS",
+            "200",
+            "km",
+            "STOREDIST",
+        ]);
+
+        // Act
+        let actual = GeoSearchStore::parse_from(&value).unwrap();
+
+        // Assert
+        assert_eq!(actual.destination, "nearby");
+        assert_eq!(actual.source, "Sicily");
+        assert!(actual.store_dist);
+    }
+
+    #[test]
+    fn sut_raises_error_if_with_options_are_given() {
+        // Arrange
+        let value = array(&[
+            "GEOSEARCHSTORE",
+            "nearby",
+            "Sicily",
+            "FROMLONLAT",
+            "15",
+            "37",
+            "BYRADIUS",
+            "200",
+            "km",
+            "WITHCOORD",
+        ]);
+
+        // Act
+        let actual = GeoSearchStore::parse_from(&value);
+
+        // Assert
+        assert!(actual.is_err());
+    }
+}
+
+#[cfg(test)]
+mod specs_for_execute {
+    use crate::command::executor::Command;
+    use crate::command::executor::CommandExecutor;
+    use crate::command::executor::CommandExecutorContext;
+    use crate::command::executor::fixture::command_executor_context;
+    use crate::geo::Coordinate;
+    use crate::repository::AddCondition;
+    use crate::repository::InMemoryRepository;
+    use crate::resp::Value;
+
+    use super::GeoSearchStore;
+
+    fn command(arguments: &[&str]) -> GeoSearchStore {
+        let array = ["GEOSEARCHSTORE", "nearby", "Sicily"]
+            .iter()
+            .chain(arguments)
+            .map(|argument| Value::BulkString(argument.to_string()))
+            .collect();
+        GeoSearchStore::parse_from(&Value::Array(array)).unwrap()
+    }
+
+    #[rstest::rstest]
+    #[tokio::test]
+    async fn sut_stores_matched_members_with_distance_as_score(
+        #[from(command_executor_context)]
+        #[with(InMemoryRepository::new())]
+        context: CommandExecutorContext,
+    ) {
+        // Arrange
+        let score = Coordinate::new(15.087269, 37.502669).unwrap().to_score();
+        context
+            .repository()
+            .zadd(
+                "Sicily",
+                vec![("Catania".to_string(), score)],
+                AddCondition::Always,
+            )
+            .await
+            .unwrap();
+        let command = command(&[
+            "FROMLONLAT",
+            "15",
+            "37",
+            "BYRADIUS",
+            "100",
+            "km",
+            "STOREDIST",
+        ]);
+
+        // Act
+        let actual = command.execute(&context).await;
+
+        // Assert
+        assert_eq!(actual, Value::Integer(1));
+        let distance = context.repository().zscore("nearby", "Catania").await;
+        assert_eq!(
+            distance.map(|d| d.map(|d| format!("{d:.4}"))),
+            Ok(Some("56.4413".to_string()))
+        );
+    }
+
+    #[rstest::rstest]
+    #[tokio::test]
+    async fn sut_removes_destination_if_nothing_matches(
+        #[from(command_executor_context)]
+        #[with(InMemoryRepository::new())]
+        context: CommandExecutorContext,
+    ) {
+        // Arrange
+        context
+            .repository()
+            .zadd(
+                "nearby",
+                vec![("stale".to_string(), 0.0)],
+                AddCondition::Always,
+            )
+            .await
+            .unwrap();
+        let command = command(&["FROMLONLAT", "15", "37", "BYRADIUS", "100", "km"]);
+
+        // Act
+        let actual = command.execute(&context).await;
+
+        // Assert
+        assert_eq!(actual, Value::Integer(0));
+        assert_eq!(context.repository().zcard("nearby").await, Ok(0));
+    }
+}
@@ -5,6 +5,7 @@ use crate::command::parser::extract_array;
 use crate::command::parser::extract_bulk_string;
 use crate::command::parser::validate_array_length;
 use crate::command::parser::validate_main_command;
+use crate::notification::Class;
 use crate::resp::Value;
 
 #[derive(Debug, Default, PartialEq)]
@@ -27,9 +28,14 @@ impl Command for Get {
 #[async_trait::async_trait]
 impl CommandExecutor for Get {
     async fn execute(&self, context: &CommandExecutorContext) -> Value {
-        match context.repository.get(&self.key).await {
-            Some(value) => Value::BulkString(value),
-            None => Value::Null,
+        context.track(&self.key);
+        match context.repository().get(&self.key).await {
+            Ok(Some(value)) => Value::BulkString(value),
+            Ok(None) => {
+                context.notify(Class::KeyMiss, "keymiss", &self.key);
+                Value::Null
+            }
+            Err(e) => Value::Error(e.to_string()),
         }
     }
 }
@@ -88,15 +94,29 @@ mod specs_for_parse_from {
 
 #[cfg(test)]
 mod specs_for_execute {
+    use std::sync::Arc;
+    use std::time::Duration;
+
     use fake::Fake;
     use fake::faker::lorem::en::Word;
 
+    use crate::clock::Clock;
+    use crate::clock::fixture::FakeClock;
     use crate::command::executor::CommandExecutor;
     use crate::command::executor::CommandExecutorContext;
     use crate::command::executor::fixture::command_executor_context;
+    use crate::config::Config;
+    use crate::config::ReplicationSlave;
+    use crate::notification::Class;
+    use crate::pubsub::Scope;
+    use crate::repository::Data;
+    use crate::repository::Databases;
     use crate::repository::Entry;
+    use crate::repository::Expiry;
     use crate::repository::InMemoryRepository;
+    use crate::repository::TimeUnit;
     use crate::resp::Value;
+    use crate::tracking::TrackingOptions;
 
     use super::Get;
 
@@ -111,11 +131,11 @@ mod specs_for_execute {
         let key = Word().fake::<String>();
         let value = Word().fake::<String>();
         let entry = Entry {
-            key: key.clone(),
-            value: value.clone(),
+            key: key.as_str().into(),
+            value: Data::String(value.clone()),
             expiry: None,
         };
-        context.repository.set(entry).await;
+        context.repository().set(entry).await;
 
         let get_cmd = Get { key: key.clone() };
 
@@ -143,4 +163,116 @@ mod specs_for_execute {
         let expected = Value::Null;
         assert_eq!(actual, expected);
     }
+
+    #[tokio::test]
+    async fn sut_notifies_expired_key_before_key_miss() {
+        // Arrange
+        let clock = Arc::new(FakeClock::default());
+        let mut config = Config::default();
+        config.server.notify_keyspace_events = "Exm".parse().unwrap();
+        let repository = InMemoryRepository::with_clock(clock.clone());
+        let context = command_executor_context(repository, config, clock.clone());
+        let subscriber = context.session.subscriber.clone();
+        context
+            .pubsub
+            .subscribe(Scope::Pattern, "__keyevent@0__:*", &subscriber);
+        let entry = Entry {
+            key: "foo".into(),
+            value: Data::String("bar".to_string()),
+            expiry: Some(Expiry {
+                epoch: clock.now_in_millis() + 10,
+                unit: TimeUnit::Millisecond,
+            }),
+        };
+        context.repository().set(entry).await;
+        clock.advance(Duration::from_millis(20));
+
+        // Act
+        let actual = Get {
+            key: "foo".to_string(),
+        }
+        .execute(&context)
+        .await;
+
+        // Assert
+        assert_eq!(actual, Value::Null);
+        let mut messages = subscriber.messages().await;
+        let mut events = vec![];
+        while let Ok(Value::Array(message)) = messages.try_recv() {
+            events.push(message[2].clone());
+        }
+        let expected = vec![
+            Value::BulkString("__keyevent@0__:expired".to_string()),
+            Value::BulkString("__keyevent@0__:keymiss".to_string()),
+        ];
+        assert_eq!(events, expected);
+    }
+
+    #[rstest::rstest]
+    #[case(None, 0)]
+    #[case(Some("localhost:6380"), 1)]
+    #[tokio::test]
+    async fn sut_leaves_expired_key_to_master_on_replica(
+        #[case] master: Option<&str>,
+        #[case] expected_size: usize,
+    ) {
+        // Arrange
+        let clock = Arc::new(FakeClock::default());
+        let mut config = Config::default();
+        config.replication.slave = master.map(|master| ReplicationSlave {
+            master_address: master.to_string(),
+        });
+        let databases = Arc::new(Databases::in_memory(1, clock.clone()));
+        let context = CommandExecutorContext::new(databases, Arc::new(config), clock.clone());
+        let entry = Entry {
+            key: "foo".into(),
+            value: Data::String("bar".to_string()),
+            expiry: Some(Expiry {
+                epoch: clock.now_in_millis() + 10,
+                unit: TimeUnit::Millisecond,
+            }),
+        };
+        context.repository().set(entry).await;
+        clock.advance(Duration::from_millis(20));
+
+        // Act
+        let actual = Get {
+            key: "foo".to_string(),
+        }
+        .execute(&context)
+        .await;
+
+        // Assert
+        assert_eq!(actual, Value::Null);
+        assert_eq!(context.repository().size().await, expected_size);
+    }
+
+    #[rstest::rstest]
+    #[tokio::test]
+    async fn sut_tracks_key_for_invalidation_once_modified(
+        #[from(command_executor_context)] context: CommandExecutorContext,
+    ) {
+        // Arrange
+        context.session.set_protocol(3);
+        context
+            .tracking
+            .enable(&context.session, TrackingOptions::default());
+        let other = context.new_session();
+
+        // Act
+        Get {
+            key: "foo".to_string(),
+        }
+        .execute(&context)
+        .await;
+        other.notify(Class::String, "set", "foo");
+
+        // Assert
+        let actual = context.session.subscriber.messages().await.try_recv();
+        let expected = Value::Push(vec![
+            Value::BulkString("invalidate".to_string()),
+            Value::Array(vec![Value::BulkString("foo".to_string())]),
+        ]);
+        assert_eq!(actual, Ok(expected));
+    }
 }
@@ -0,0 +1,148 @@
+use crate::command::executor::Command;
+use crate::command::executor::CommandExecutor;
+use crate::command::executor::CommandExecutorContext;
+use crate::command::info::VERSION;
+use crate::command::parser::extract_array;
+use crate::command::parser::extract_bulk_string;
+use crate::command::parser::validate_main_command;
+use crate::resp::Value;
+
+/// HELLO [protover], switching the protocol the client is replied in.
+#[derive(Debug, Default, PartialEq)]
+pub struct Hello {
+    protocol: Option<String>,
+}
+
+impl Command for Hello {
+    fn parse_from(value: &Value) -> Result<Self, anyhow::Error> {
+        let array = extract_array(value)?;
+        validate_main_command(array, "HELLO")?;
+        match array.len() {
+            1 => Ok(Hello { protocol: None }),
+            2 => Ok(Hello {
+                protocol: Some(extract_bulk_string(array, 1)?.to_string()),
+            }),
+            _ => Err(anyhow::anyhow!("wrong number of arguments")),
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl CommandExecutor for Hello {
+    async fn execute(&self, context: &CommandExecutorContext) -> Value {
+        let session = &context.session;
+        if let Some(protocol) = &self.protocol {
+            match protocol.parse::<u8>() {
+                Ok(protocol @ (2 | 3)) => session.set_protocol(protocol),
+                Ok(_) => return Value::Error("NOPROTO unsupported protocol version".to_string()),
+                Err(_) => {
+                    return Value::Error(
+                        "ERR Protocol version is not an integer or out of range".to_string(),
+                    );
+                }
+            }
+        }
+
+        let role = if context.replication.is_replica() {
+            "replica"
+        } else {
+            "master"
+        };
+        let entries = vec![
+            ("server", Value::BulkString("redis".to_string())),
+            ("version", Value::BulkString(VERSION.to_string())),
+            ("proto", Value::Integer(session.protocol() as i64)),
+            ("id", Value::Integer(session.id() as i64)),
+            ("mode", Value::BulkString("standalone".to_string())),
+            ("role", Value::BulkString(role.to_string())),
+            ("modules", Value::Array(vec![])),
+        ]
+        .into_iter()
+        .map(|(field, value)| (Value::BulkString(field.to_string()), value));
+        if session.protocol() == 3 {
+            Value::Map(entries.collect())
+        } else {
+            Value::Array(entries.flat_map(|(field, value)| [field, value]).collect())
+        }
+    }
+}
+
+#[cfg(test)]
+mod specs_for_parse_from {
+    use crate::command::executor::Command;
+    use crate::resp::Value;
+
+    use super::Hello;
+
+    #[rstest::rstest]
+    #[case(&["HELLO"], Hello { protocol: None })]
+    #[case(&["hello", "3"], Hello { protocol: Some("3".to_string()) })]
+    fn sut_parses_hello_command(#[case] arguments: &[&str], #[case] expected: Hello) {
+        // Arrange
+        let value = Value::Array(
+            arguments
+                .iter()
+                .map(|argument| Value::BulkString(argument.to_string()))
+                .collect(),
+        );
+
+        // Act
+        let actual = Hello::parse_from(&value).unwrap();
+
+        // Assert
+        assert_eq!(actual, expected);
+    }
+}
+
+#[cfg(test)]
+mod specs_for_execute {
+    use crate::command::executor::CommandExecutor;
+    use crate::command::executor::CommandExecutorContext;
+    use crate::command::executor::fixture::command_executor_context;
+    use crate::resp::Value;
+
+    use super::Hello;
+
+    #[rstest::rstest]
+    #[tokio::test]
+    async fn sut_switches_to_resp3_and_replies_with_a_map(
+        #[from(command_executor_context)] context: CommandExecutorContext,
+    ) {
+        // Arrange
+        let sut = Hello {
+            protocol: Some("3".to_string()),
+        };
+
+        // Act
+        let actual = sut.execute(&context).await;
+
+        // Assert
+        assert_eq!(context.session.protocol(), 3);
+        let Value::Map(entries) = actual else {
+            panic!("expected a map, got {actual:?}");
+        };
+        assert!(entries.contains(&(Value::BulkString("proto".to_string()), Value::Integer(3))));
+    }
+
+    #[rstest::rstest]
+    #[case("4", "NOPROTO unsupported protocol version")]
+    #[case("three", "ERR Protocol version is not an integer or out of range")]
+    #[tokio::test]
+    async fn sut_rejects_unsupported_protocols(
+        #[case] protocol: &str,
+        #[case] expected: &str,
+        #[from(command_executor_context)] context: CommandExecutorContext,
+    ) {
+        // Arrange
+        let sut = Hello {
+            protocol: Some(protocol.to_string()),
+        };
+
+        // Act
+        let actual = sut.execute(&context).await;
+
+        // Assert
+        assert_eq!(actual, Value::Error(expected.to_string()));
+        assert_eq!(context.session.protocol(), 2);
+    }
+}
@@ -0,0 +1,601 @@
+use std::fmt;
+
+use crate::command::executor::Command;
+use crate::command::executor::CommandExecutor;
+use crate::command::executor::CommandExecutorContext;
+use crate::command::parser::extract_array;
+use crate::command::parser::extract_bulk_string;
+use crate::command::parser::validate_main_command;
+use crate::command::parser::validate_min_array_length;
+use crate::memory;
+use crate::pubsub::Scope;
+use crate::resp::Value;
+use crate::stats;
+
+/// The version of Redis this server behaves like.
+pub const VERSION: &str = "7.2.0";
+
+/// The sections of INFO, in the order they are reported.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Section {
+    Server,
+    Clients,
+    Memory,
+    Persistence,
+    Stats,
+    Replication,
+    Cpu,
+    CommandStats,
+    ErrorStats,
+    Cluster,
+    Keyspace,
+}
+
+impl Section {
+    const ALL: [Section; 11] = [
+        Section::Server,
+        Section::Clients,
+        Section::Memory,
+        Section::Persistence,
+        Section::Stats,
+        Section::Replication,
+        Section::Cpu,
+        Section::CommandStats,
+        Section::ErrorStats,
+        Section::Cluster,
+        Section::Keyspace,
+    ];
+
+    /// The sections reported by INFO without arguments, all but those growing with every
+    /// command.
+    fn defaults() -> impl Iterator<Item = Section> {
+        Section::ALL
+            .into_iter()
+            .filter(|section| *section != Section::CommandStats)
+    }
+
+    fn from_name(name: &str) -> Option<Section> {
+        Section::ALL
+            .into_iter()
+            .find(|section| section.to_string().eq_ignore_ascii_case(name))
+    }
+}
+
+impl fmt::Display for Section {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let title = match self {
+            Section::Server => "Server",
+            Section::Clients => "Clients",
+            Section::Memory => "Memory",
+            Section::Persistence => "Persistence",
+            Section::Stats => "Stats",
+            Section::Replication => "Replication",
+            Section::Cpu => "CPU",
+            Section::CommandStats => "Commandstats",
+            Section::ErrorStats => "Errorstats",
+            Section::Cluster => "Cluster",
+            Section::Keyspace => "Keyspace",
+        };
+        write!(f, "{title}")
+    }
+}
+
+/// INFO [section ...], where unknown sections are left out.
+#[derive(Debug, PartialEq)]
+pub struct Info {
+    sections: Vec<Section>,
+}
+
+impl Command for Info {
+    fn parse_from(value: &Value) -> Result<Self, anyhow::Error> {
+        let array = extract_array(value)?;
+        validate_min_array_length(array, 1)?;
+        validate_main_command(array, "INFO")?;
+        let mut sections = Vec::new();
+        if array.len() == 1 {
+            sections.extend(Section::defaults());
+        }
+        for index in 1..array.len() {
+            let name = extract_bulk_string(array, index)?.to_lowercase();
+            match name.as_str() {
+                "all" | "everything" => sections.extend(Section::ALL),
+                "default" => sections.extend(Section::defaults()),
+                _ => sections.extend(Section::from_name(&name)),
+            }
+        }
+        sections.sort();
+        sections.dedup();
+        Ok(Info { sections })
+    }
+}
+
+#[async_trait::async_trait]
+impl CommandExecutor for Info {
+    async fn execute(&self, context: &CommandExecutorContext) -> Value {
+        let mut reports = Vec::new();
+        for section in &self.sections {
+            let properties = match section {
+                Section::Server => server(context),
+                Section::Clients => clients(context),
+                Section::Memory => memory(),
+                Section::Persistence => persistence(context),
+                Section::Stats => stats(context),
+                Section::Replication => replication(context),
+                Section::Cpu => cpu(),
+                Section::CommandStats => command_stats(context),
+                Section::ErrorStats => error_stats(context),
+                Section::Cluster => vec![format!(
+                    "cluster_enabled:{}",
+                    u8::from(context.cluster.is_some())
+                )],
+                Section::Keyspace => keyspace(context).await,
+            };
+            let mut report = format!("# {section}\r\n");
+            for property in properties {
+                report.push_str(&property);
+                report.push_str("\r\n");
+            }
+            reports.push(report);
+        }
+        Value::BulkString(reports.join("\r\n"))
+    }
+}
+
+fn server(context: &CommandExecutorContext) -> Vec<String> {
+    let uptime = context.stats.uptime().as_secs();
+    let mode = if context.sentinel.is_some() {
+        "sentinel"
+    } else if context.cluster.is_some() {
+        "cluster"
+    } else {
+        "standalone"
+    };
+    vec![
+        format!("redis_version:{VERSION}"),
+        format!("redis_mode:{mode}"),
+        format!("os:{} {}", std::env::consts::OS, std::env::consts::ARCH),
+        format!("arch_bits:{}", usize::BITS),
+        format!("process_id:{}", std::process::id()),
+        format!("run_id:{}", context.stats.run_id()),
+        format!("tcp_port:{}", context.config.server.port),
+        format!("uptime_in_seconds:{uptime}"),
+        format!("uptime_in_days:{}", uptime / 86400),
+    ]
+}
+
+fn clients(context: &CommandExecutorContext) -> Vec<String> {
+    vec![
+        format!("connected_clients:{}", context.stats.connected_clients()),
+        format!("tracking_clients:{}", context.tracking.count()),
+    ]
+}
+
+fn memory() -> Vec<String> {
+    let (used, rss, peak) = (memory::used(), memory::rss(), memory::peak());
+    vec![
+        format!("used_memory:{used}"),
+        format!("used_memory_human:{}", memory::human(used)),
+        format!("used_memory_rss:{rss}"),
+        format!("used_memory_rss_human:{}", memory::human(rss)),
+        format!("used_memory_peak:{peak}"),
+        format!("used_memory_peak_human:{}", memory::human(peak)),
+        "mem_allocator:libc".to_string(),
+    ]
+}
+
+fn persistence(context: &CommandExecutorContext) -> Vec<String> {
+    vec![
+        "loading:0".to_string(),
+        format!("rdb_changes_since_last_save:{}", context.stats.changes()),
+        "rdb_bgsave_in_progress:0".to_string(),
+        format!("rdb_last_save_time:{}", context.stats.last_save()),
+        "aof_enabled:0".to_string(),
+    ]
+}
+
+fn stats(context: &CommandExecutorContext) -> Vec<String> {
+    let stats = &context.stats;
+    let pubsub = &context.pubsub;
+    let total_errors: u64 = stats.errors().values().sum();
+    vec![
+        format!(
+            "total_connections_received:{}",
+            stats.connections_received()
+        ),
+        format!("total_commands_processed:{}", stats.commands_processed()),
+        format!("total_net_input_bytes:{}", stats.net_input_bytes()),
+        format!("total_net_output_bytes:{}", stats.net_output_bytes()),
+        format!(
+            "pubsub_channels:{}",
+            pubsub.names(Scope::Channel, None).len()
+        ),
+        format!(
+            "pubsub_patterns:{}",
+            pubsub.names(Scope::Pattern, None).len()
+        ),
+        format!(
+            "pubsub_shardchannels:{}",
+            pubsub.names(Scope::Shard, None).len()
+        ),
+        format!("total_error_replies:{total_errors}"),
+    ]
+}
+
+fn replication(context: &CommandExecutorContext) -> Vec<String> {
+    let mut properties = Vec::new();
+
+    let replication = &context.replication;
+    match replication.master() {
+        Some(master) => {
+            properties.push("role:slave".to_string());
+            let (host, port) = master.rsplit_once(':').unwrap_or((&master, ""));
+            properties.push(format!("master_host:{host}"));
+            properties.push(format!("master_port:{port}"));
+            let status = if replication.is_linked() {
+                "up"
+            } else {
+                "down"
+            };
+            properties.push(format!("master_link_status:{status}"));
+            properties.push(format!("slave_read_repl_offset:{}", replication.offset()));
+            properties.push(format!("slave_repl_offset:{}", replication.offset()));
+            let read_only = context.config.replication.replica_read_only;
+            properties.push(format!("slave_read_only:{}", read_only as u8));
+        }
+        None => properties.push("role:master".to_string()),
+    }
+
+    let replicas = replication.replicas();
+    // Replicas still waiting for their snapshot are listed but not connected yet.
+    let connected = replicas.iter().filter(|replica| replica.online).count();
+    properties.push(format!("connected_slaves:{connected}"));
+    for (index, replica) in replicas.iter().enumerate() {
+        let state = if replica.online {
+            "online"
+        } else {
+            "wait_bgsave"
+        };
+        properties.push(format!(
+            "slave{index}:ip={},port={},state={state},offset={}",
+            replica.address,
+            replica.port.unwrap_or_default(),
+            replica.offset,
+        ));
+    }
+    properties.push(format!("master_failover_state:{}", replication.failover()));
+    properties.push(format!("master_replid:{}", replication.id()));
+    let (second_id, second_offset) = match replication.second_id() {
+        Some((id, offset)) => (id, offset as i64),
+        None => ("0".repeat(40), -1),
+    };
+    properties.push(format!("master_replid2:{second_id}"));
+    properties.push(format!("master_repl_offset:{}", replication.offset()));
+    properties.push(format!("second_repl_offset:{second_offset}"));
+    let backlog = replication.backlog();
+    properties.push("repl_backlog_active:1".to_string());
+    properties.push(format!("repl_backlog_size:{}", backlog.size));
+    properties.push(format!(
+        "repl_backlog_first_byte_offset:{}",
+        backlog.first_byte_offset
+    ));
+    properties.push(format!("repl_backlog_histlen:{}", backlog.histlen));
+    properties
+}
+
+fn cpu() -> Vec<String> {
+    let (system, user) = stats::cpu();
+    vec![
+        format!("used_cpu_sys:{:.6}", system.as_secs_f64()),
+        format!("used_cpu_user:{:.6}", user.as_secs_f64()),
+    ]
+}
+
+fn command_stats(context: &CommandExecutorContext) -> Vec<String> {
+    context
+        .stats
+        .commands()
+        .into_iter()
+        .filter(|(name, _)| !name.is_empty())
+        .map(|(name, command)| {
+            let per_call = if command.calls == 0 {
+                0.0
+            } else {
+                command.usec as f64 / command.calls as f64
+            };
+            format!(
+                "cmdstat_{name}:calls={},usec={},usec_per_call={per_call:.2},rejected_calls={},failed_calls={}",
+                command.calls, command.usec, command.rejected_calls, command.failed_calls
+            )
+        })
+        .collect()
+}
+
+fn error_stats(context: &CommandExecutorContext) -> Vec<String> {
+    context
+        .stats
+        .errors()
+        .into_iter()
+        .map(|(code, count)| format!("errorstat_{code}:count={count}"))
+        .collect()
+}
+
+async fn keyspace(context: &CommandExecutorContext) -> Vec<String> {
+    let mut properties = Vec::new();
+    for (index, repository) in context.databases.all().iter().enumerate() {
+        let keys = repository.size().await;
+        if keys > 0 {
+            let expires = repository.volatile_size().await;
+            properties.push(format!("db{index}:keys={keys},expires={expires}"));
+        }
+    }
+    properties
+}
+
+#[cfg(test)]
+mod specs_for_parse_from {
+    use crate::command::executor::Command;
+    use crate::resp::Value;
+
+    use super::Info;
+    use super::Section;
+
+    fn array(arguments: &[&str]) -> Value {
+        Value::Array(
+            arguments
+                .iter()
+                .map(|argument| Value::BulkString(argument.to_string()))
+                .collect(),
+        )
+    }
+
+    #[rstest::rstest]
+    #[case(&["INFO", "replication"], vec![Section::Replication])]
+    #[case(&["info", "KEYSPACE", "server", "keyspace"], vec![Section::Server, Section::Keyspace])]
+    #[case(&["INFO", "commandstats", "unknown"], vec![Section::CommandStats])]
+    #[case(&["INFO", "unknown"], vec![])]
+    #[case(&["INFO", "everything"], Section::ALL.to_vec())]
+    fn sut_parses_sections_in_the_order_they_are_reported(
+        #[case] arguments: &[&str],
+        #[case] expected: Vec<Section>,
+    ) {
+        // Act
+        let actual = Info::parse_from(&array(arguments)).unwrap();
+
+        // Assert
+        assert_eq!(actual, Info { sections: expected });
+    }
+
+    #[rstest::rstest]
+    #[case(&["INFO"])]
+    #[case(&["INFO", "default"])]
+    fn sut_parses_default_sections_without_commandstats(#[case] arguments: &[&str]) {
+        // Act
+        let actual = Info::parse_from(&array(arguments)).unwrap();
+
+        // Assert
+        assert_eq!(actual.sections.len(), Section::ALL.len() - 1);
+        assert!(!actual.sections.contains(&Section::CommandStats));
+    }
+
+    #[test]
+    fn sut_raises_error_if_main_command_is_not_info() {
+        // Act
+        let actual = Info::parse_from(&array(&["INFU", "replication"]));
+
+        // Assert
+        assert!(actual.is_err());
+    }
+}
+
+#[cfg(test)]
+mod specs_for_execute {
+    use std::sync::Arc;
+
+    use crate::clock::SystemClock;
+    use crate::command::executor::CommandExecutor;
+    use crate::command::executor::CommandExecutorContext;
+    use crate::command::executor::fixture::command_executor_context;
+    use crate::command::executor::fixture::command_executor_context_with_databases;
+    use crate::config::Config;
+    use crate::config::Replication;
+    use crate::config::ReplicationSlave;
+    use crate::config::SentinelConfig;
+    use crate::repository::Data;
+    use crate::repository::Databases;
+    use crate::repository::Entry;
+    use crate::repository::Expiry;
+    use crate::repository::TimeUnit;
+    use crate::repository::fixture::DummyRepository;
+    use crate::resp::Value;
+
+    use super::Info;
+    use super::Section;
+
+    fn info(sections: &[Section]) -> Info {
+        Info {
+            sections: sections.to_vec(),
+        }
+    }
+
+    #[rstest::rstest]
+    #[case("# Replication\r\nrole:master\r\n")]
+    #[case("connected_slaves:0")]
+    #[case("master_repl_offset:0")]
+    #[case("second_repl_offset:-1")]
+    #[case("repl_backlog_size:1048576")]
+    #[case("repl_backlog_first_byte_offset:1")]
+    #[case("repl_backlog_histlen:0")]
+    #[tokio::test]
+    async fn sut_responds_master_information_of_replication_if_replication_is_not_set(
+        #[case] expected: &str,
+        #[from(command_executor_context)] context: CommandExecutorContext,
+    ) {
+        // Act
+        let actual = extract_bulk_string(info(&[Section::Replication]).execute(&context).await);
+
+        // Assert
+        assert!(actual.contains(expected), "{actual}");
+    }
+
+    #[rstest::rstest]
+    #[case("role:slave")]
+    #[case("master_host:localhost")]
+    #[case("master_port:6380")]
+    #[case("master_link_status:down")]
+    #[case("slave_read_only:1")]
+    #[tokio::test]
+    async fn sut_responds_replication_role_as_slave_if_replication_is_set(#[case] expected: &str) {
+        // Arrange
+        let config = Config {
+            replication: Replication {
+                slave: Some(ReplicationSlave {
+                    master_address: "localhost:6380".to_string(),
+                }),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let context = CommandExecutorContext::new(
+            Arc::new(Databases::new(vec![Arc::new(DummyRepository)])),
+            Arc::new(config),
+            Arc::new(SystemClock),
+        );
+
+        // Act
+        let actual = extract_bulk_string(info(&[Section::Replication]).execute(&context).await);
+
+        // Assert
+        assert!(actual.contains(expected), "{actual}");
+    }
+
+    #[tokio::test]
+    async fn sut_counts_only_online_replicas_as_connected() {
+        // Arrange
+        let context =
+            command_executor_context(DummyRepository, Config::default(), Arc::new(SystemClock));
+        let (online, waiting) = (context.new_session(), context.new_session());
+        context.replication.attach(&online.session);
+        context
+            .replication
+            .announce(&waiting.session, &["eof".to_string()]);
+
+        // Act
+        let actual = extract_bulk_string(info(&[Section::Replication]).execute(&context).await);
+
+        // Assert
+        assert!(actual.contains("connected_slaves:1\r\n"), "{actual}");
+        assert!(actual.contains("state=wait_bgsave"), "{actual}");
+    }
+
+    #[rstest::rstest]
+    #[case(false, false, "redis_mode:standalone")]
+    #[case(true, false, "redis_mode:cluster")]
+    #[case(false, true, "redis_mode:sentinel")]
+    #[tokio::test]
+    async fn sut_reports_the_mode_the_server_runs_in(
+        #[case] cluster_enabled: bool,
+        #[case] sentinel: bool,
+        #[case] expected: &str,
+    ) {
+        // Arrange
+        let mut config = Config::default();
+        config.server.cluster_enabled = cluster_enabled;
+        config.sentinel = sentinel.then(SentinelConfig::default);
+        let context = command_executor_context(DummyRepository, config, Arc::new(SystemClock));
+
+        // Act
+        let actual = extract_bulk_string(info(&[Section::Server]).execute(&context).await);
+
+        // Assert
+        assert!(actual.contains(expected), "{actual}");
+    }
+
+    #[tokio::test]
+    async fn sut_reports_keys_and_expires_of_non_empty_databases() {
+        // Arrange
+        let context = command_executor_context_with_databases(3);
+        let databases = &context.databases;
+        for (index, key, expiry) in [
+            (0, "foo", None),
+            (0, "bar", Some(u128::MAX)),
+            (2, "baz", None),
+        ] {
+            let entry = Entry {
+                key: key.into(),
+                value: Data::String("value".to_string()),
+                expiry: expiry.map(|epoch| Expiry {
+                    epoch,
+                    unit: TimeUnit::Millisecond,
+                }),
+            };
+            databases.get(index).unwrap().set(entry).await;
+        }
+
+        // Act
+        let actual = extract_bulk_string(info(&[Section::Keyspace]).execute(&context).await);
+
+        // Assert
+        assert_eq!(
+            actual,
+            "# Keyspace\r\ndb0:keys=2,expires=1\r\ndb2:keys=1,expires=0\r\n"
+        );
+    }
+
+    #[rstest::rstest]
+    #[tokio::test]
+    async fn sut_reports_calls_and_errors_counted_so_far(
+        #[from(command_executor_context)] context: CommandExecutorContext,
+    ) {
+        // Arrange
+        let request = Value::Array(vec![Value::BulkString("GET".to_string())]);
+        let error = Value::Error("ERR wrong number of arguments".to_string());
+        context
+            .stats
+            .record(&request, std::time::Duration::from_micros(4), &error);
+        context.stats.error("ERR wrong number of arguments");
+        let sections = [Section::Stats, Section::CommandStats, Section::ErrorStats];
+
+        // Act
+        let actual = extract_bulk_string(info(&sections).execute(&context).await);
+
+        // Assert
+        assert!(
+            actual.contains("total_commands_processed:1\r\n"),
+            "{actual}"
+        );
+        assert!(actual.contains("total_error_replies:1\r\n"), "{actual}");
+        assert!(actual.contains(
+            "# Commandstats\r\ncmdstat_get:calls=1,usec=4,usec_per_call=4.00,rejected_calls=0,failed_calls=1\r\n"
+        ), "{actual}");
+        assert!(
+            actual.contains("# Errorstats\r\nerrorstat_ERR:count=1\r\n"),
+            "{actual}"
+        );
+    }
+
+    #[rstest::rstest]
+    #[tokio::test]
+    async fn sut_separates_sections_with_a_blank_line(
+        #[from(command_executor_context)] context: CommandExecutorContext,
+    ) {
+        // Act
+        let actual = extract_bulk_string(
+            info(&[Section::Persistence, Section::Cluster])
+                .execute(&context)
+                .await,
+        );
+
+        // Assert
+        assert!(
+            actual.starts_with("# Persistence\r\nloading:0\r\nrdb_changes_since_last_save:0\r\n")
+        );
+        assert!(actual.ends_with("aof_enabled:0\r\n\r\n# Cluster\r\ncluster_enabled:0\r\n"));
+    }
+
+    fn extract_bulk_string(value: Value) -> String {
+        match value {
+            Value::BulkString(str) => str,
+            value => panic!("not a bulk string: {value:?}"),
+        }
+    }
+}
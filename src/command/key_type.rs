@@ -0,0 +1,118 @@
+use crate::command::executor::Command;
+use crate::command::executor::CommandExecutor;
+use crate::command::executor::CommandExecutorContext;
+use crate::command::parser::extract_array;
+use crate::command::parser::extract_bulk_string;
+use crate::command::parser::validate_array_length;
+use crate::command::parser::validate_main_command;
+use crate::resp::Value;
+
+#[derive(Debug, Default, PartialEq)]
+pub struct KeyType {
+    key: String,
+}
+
+impl Command for KeyType {
+    fn parse_from(value: &Value) -> Result<Self, anyhow::Error> {
+        let array = extract_array(value)?;
+        validate_array_length(array, 2)?;
+        validate_main_command(array, "TYPE")?;
+        let key = extract_bulk_string(array, 1)?;
+        Ok(KeyType {
+            key: key.to_string(),
+        })
+    }
+}
+
+#[async_trait::async_trait]
+impl CommandExecutor for KeyType {
+    async fn execute(&self, context: &CommandExecutorContext) -> Value {
+        context.track(&self.key);
+        let type_name = context.repository().type_of(&self.key).await;
+        Value::SimpleString(type_name.unwrap_or("none").to_string())
+    }
+}
+
+#[cfg(test)]
+mod specs_for_parse_from {
+    use crate::command::executor::Command;
+    use crate::resp::Value;
+
+    use super::KeyType;
+
+    #[rstest::rstest]
+    #[case("TYPE")]
+    #[case("type")]
+    #[case("TyPe")]
+    fn sut_parses_type_command_with_case_insensitive(#[case] type_: &str) {
+        // Arrange
+        let value = Value::Array(vec![
+            Value::BulkString(type_.to_string()),
+            Value::BulkString("foo".to_string()),
+        ]);
+
+        // Act
+        let actual = KeyType::parse_from(&value).unwrap();
+
+        // Assert
+        let expected = KeyType {
+            key: "foo".to_string(),
+        };
+        assert_eq!(actual, expected);
+    }
+}
+
+#[cfg(test)]
+mod specs_for_execute {
+    use crate::command::executor::CommandExecutor;
+    use crate::command::executor::CommandExecutorContext;
+    use crate::command::executor::fixture::command_executor_context;
+    use crate::repository::AddCondition;
+    use crate::repository::Data;
+    use crate::repository::Entry;
+    use crate::repository::InMemoryRepository;
+    use crate::resp::Value;
+
+    use super::KeyType;
+
+    #[rstest::rstest]
+    #[case("string", "string")]
+    #[case("zset", "zset")]
+    #[case("missing", "none")]
+    #[tokio::test]
+    async fn sut_responds_type_name_of_value(
+        #[case] key: &str,
+        #[case] expected: &str,
+        #[from(command_executor_context)]
+        #[with(InMemoryRepository::new())]
+        context: CommandExecutorContext,
+    ) {
+        // Arrange
+        context
+            .repository()
+            .set(Entry {
+                key: "string".into(),
+                value: Data::String("value".to_string()),
+                expiry: None,
+            })
+            .await;
+        context
+            .repository()
+            .zadd(
+                "zset",
+                vec![("member".to_string(), 1.0)],
+                AddCondition::Always,
+            )
+            .await
+            .unwrap();
+        let command = KeyType {
+            key: key.to_string(),
+        };
+
+        // Act
+        let actual = command.execute(&context).await;
+
+        // Assert
+        assert_eq!(actual, Value::SimpleString(expected.to_string()));
+    }
+}
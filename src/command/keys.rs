@@ -1,6 +1,3 @@
-use std::time::SystemTime;
-use std::time::UNIX_EPOCH;
-
 use crate::command::executor::Command;
 use crate::command::executor::CommandExecutor;
 use crate::command::executor::CommandExecutorContext;
@@ -8,6 +5,7 @@ use crate::command::parser::extract_array;
 use crate::command::parser::extract_bulk_string;
 use crate::command::parser::validate_array_length;
 use crate::command::parser::validate_main_command;
+use crate::glob;
 use crate::resp::Value;
 
 #[derive(Debug, Default, PartialEq)]
@@ -22,48 +20,24 @@ impl Command for Keys {
         validate_main_command(array, "KEYS")?;
         let pattern = extract_bulk_string(array, 1)?;
         Ok(Keys {
-            pattern: pattern.trim_matches('"').to_string(),
+            pattern: pattern.to_string(),
         })
     }
 }
 
-impl Keys {
-    fn match_asterisk_pattern(pattern: &str, text: &str) -> bool {
-        if pattern == "*" {
-            return true;
-        }
-
-        if let Some(prefix) = pattern.strip_suffix('*') {
-            text.starts_with(prefix)
-        } else if let Some(suffix) = pattern.strip_prefix('*') {
-            text.ends_with(suffix)
-        } else if let Some(pos) = pattern.find('*') {
-            let prefix = &pattern[..pos];
-            let suffix = &pattern[pos + 1..];
-            text.starts_with(prefix) && text.ends_with(suffix)
-        } else {
-            pattern == text
-        }
-    }
-}
-
 #[async_trait::async_trait]
 impl CommandExecutor for Keys {
     async fn execute(&self, context: &CommandExecutorContext) -> Value {
-        let entries = context.repository.entries().await;
-        let now_in_millis = SystemTime::now()
-            .duration_since(UNIX_EPOCH)
-            .unwrap()
-            .as_millis();
+        let entries = context.repository().entries().await;
+        let now_in_millis = context.clock.now_in_millis();
+        let all_keys = self.pattern == "*";
         let matched_entries = entries
             .into_iter()
             .filter(|entry| {
-                Keys::match_asterisk_pattern(&self.pattern, &entry.key)
-                    && (entry.expiry.is_none()
-                        || (entry.expiry.as_ref().map(|e| e.to_millis()).unwrap_or(0)
-                            >= now_in_millis))
+                (all_keys || glob::matches(&self.pattern, &entry.key))
+                    && !entry.is_expired(now_in_millis)
             })
-            .map(|entry| Value::BulkString(entry.key))
+            .map(|entry| Value::BulkString(entry.key.to_string()))
             .collect();
         Value::Array(matched_entries)
     }
@@ -99,7 +73,7 @@ mod specs_for_parse_from {
     }
 
     #[test]
-    fn sut_parses_keys_command_and_trim_double_quotes_if_exists_in_pattern() {
+    fn sut_parses_keys_command_keeping_double_quotes_in_pattern() {
         // Arrange
         let pattern: String = Word().fake();
         let surrounded_pattern = format!("\"{pattern}\"");
@@ -113,7 +87,7 @@ mod specs_for_parse_from {
 
         // Assert
         let expected = Keys {
-            pattern: pattern.to_string(),
+            pattern: surrounded_pattern,
         };
         assert_eq!(actual, expected);
     }
@@ -148,11 +122,13 @@ mod specs_for_execute {
     use fake::Fake;
     use fake::faker::internet::en::Password;
     use fake::faker::lorem::en::Word;
-    use tokio::time::sleep;
 
+    use crate::clock::Clock;
     use crate::command::executor::CommandExecutor;
     use crate::command::executor::CommandExecutorContext;
     use crate::command::executor::fixture::command_executor_context;
+    use crate::command::executor::fixture::command_executor_context_with_fake_clock;
+    use crate::repository::Data;
     use crate::repository::Entry;
     use crate::repository::Expiry;
     use crate::repository::InMemoryRepository;
@@ -184,10 +160,10 @@ mod specs_for_execute {
         let keys: Vec<String> = (0..n).map(|_| Password(32..33).fake()).collect();
         for key in keys.iter() {
             context
-                .repository
+                .repository()
                 .set(Entry {
-                    key: key.to_string(),
-                    value: Password(32..33).fake::<String>(),
+                    key: key.as_str().into(),
+                    value: Data::String(Password(32..33).fake()),
                     expiry: None,
                 })
                 .await;
@@ -216,10 +192,10 @@ mod specs_for_execute {
         let keys: Vec<String> = (0..n).map(|_| Password(32..33).fake()).collect();
         for key in keys.iter() {
             context
-                .repository
+                .repository()
                 .set(Entry {
-                    key: key.to_string(),
-                    value: Word().fake::<String>(),
+                    key: key.as_str().into(),
+                    value: Data::String(Word().fake()),
                     expiry: None,
                 })
                 .await;
@@ -251,10 +227,10 @@ mod specs_for_execute {
         let keys: Vec<&str> = vec!["healingpaper", "arine"];
         for key in keys.iter() {
             context
-                .repository
+                .repository()
                 .set(Entry {
-                    key: key.to_string(),
-                    value: Password(32..33).fake::<String>(),
+                    key: (*key).into(),
+                    value: Data::String(Password(32..33).fake()),
                     expiry: None,
                 })
                 .await;
@@ -271,29 +247,25 @@ mod specs_for_execute {
         assert_eq!(actual, expected);
     }
 
-    #[rstest::rstest]
     #[tokio::test]
-    async fn sut_responds_with_skipping_expired_keys(
-        #[from(command_executor_context)]
-        #[with(InMemoryRepository::new())]
-        context: CommandExecutorContext,
-    ) {
+    async fn sut_responds_with_skipping_expired_keys() {
         // Arrange
+        let (context, clock) = command_executor_context_with_fake_clock();
         let entry = Entry {
-            key: Word().fake(),
-            value: Word().fake(),
+            key: Word().fake::<String>().into(),
+            value: Data::String(Word().fake()),
             expiry: Some(Expiry {
-                epoch: 0,
+                epoch: clock.now_in_millis() + 10,
                 unit: TimeUnit::Millisecond,
             }),
         };
-        context.repository.set(entry).await;
+        context.repository().set(entry).await;
         let cmd = Keys {
             pattern: "*".to_string(),
         };
 
         // Act
-        sleep(Duration::from_millis(10)).await;
+        clock.advance(Duration::from_millis(11));
         let actual = cmd.execute(&context).await;
 
         // Assert
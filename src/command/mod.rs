@@ -1,9 +1,65 @@
+mod asking;
+mod client_caching;
+mod client_id;
+mod client_tracking;
+mod cluster;
+mod collection_scan;
 mod config_get;
+mod copy;
+mod db_size;
+mod del;
+mod discard;
 mod echo;
+mod eval;
+mod exec;
 pub mod executor;
+mod exists;
+mod expire;
+mod expire_time;
+mod failover;
+mod fcall;
+mod flush_db;
+mod function;
+mod geo_add;
+mod geo_dist;
+mod geo_hash;
+mod geo_pos;
+mod geo_search;
+mod geo_search_store;
 mod get;
-mod info_replication;
+mod hello;
+mod info;
+mod key_type;
 mod keys;
+mod move_key;
+mod multi;
 pub mod parser;
+mod persist;
 mod ping;
+mod psync;
+mod pub_sub;
+mod publish;
+mod quit;
+mod random_key;
+mod rename;
+mod rename_nx;
+mod replconf;
+mod replica_of;
+mod save;
+mod scan;
+mod script;
+mod script_kill;
+mod select;
+mod sentinel;
+pub mod session;
 mod set;
+mod subscribe;
+mod swap_db;
+mod touch;
+mod ttl;
+mod unlink;
+mod unsubscribe;
+mod unwatch;
+mod wait;
+mod wait_aof;
+mod watch;
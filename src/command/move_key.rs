@@ -0,0 +1,172 @@
+use crate::command::executor::Command;
+use crate::command::executor::CommandExecutor;
+use crate::command::executor::CommandExecutorContext;
+use crate::command::parser::extract_array;
+use crate::command::parser::extract_bulk_string;
+use crate::command::parser::validate_array_length;
+use crate::command::parser::validate_main_command;
+use crate::command::select::database_index;
+use crate::notification::Class;
+use crate::resp::Value;
+
+#[derive(Debug, Default, PartialEq)]
+pub struct MoveKey {
+    key: String,
+    database: i64,
+}
+
+impl Command for MoveKey {
+    fn parse_from(value: &Value) -> Result<Self, anyhow::Error> {
+        let array = extract_array(value)?;
+        validate_array_length(array, 3)?;
+        validate_main_command(array, "MOVE")?;
+        let key = extract_bulk_string(array, 1)?;
+        let database = extract_bulk_string(array, 2)?.parse()?;
+        Ok(MoveKey {
+            key: key.to_string(),
+            database,
+        })
+    }
+}
+
+#[async_trait::async_trait]
+impl CommandExecutor for MoveKey {
+    async fn execute(&self, context: &CommandExecutorContext) -> Value {
+        let database = match database_index(context, self.database) {
+            Ok(database) => database,
+            Err(e) => return e,
+        };
+        if database == context.session.database() {
+            return Value::Error("ERR source and destination objects are the same".to_string());
+        }
+        let target = context.databases.get(database).unwrap();
+        if target.exists(&self.key).await {
+            return Value::Integer(0);
+        }
+        match context.repository().delete(&self.key).await {
+            Some(entry) => {
+                target.set(entry).await;
+                context.notify(Class::Generic, "move_from", &self.key);
+                context.notify_in(database, Class::Generic, "move_to", &self.key);
+                Value::Integer(1)
+            }
+            None => Value::Integer(0),
+        }
+    }
+}
+
+#[cfg(test)]
+mod specs_for_parse_from {
+    use crate::command::executor::Command;
+    use crate::resp::Value;
+
+    use super::MoveKey;
+
+    #[rstest::rstest]
+    #[case("MOVE")]
+    #[case("move")]
+    fn sut_parses_move_command_with_case_insensitive(#[case] command: &str) {
+        // Arrange
+        let value = Value::Array(vec![
+            Value::BulkString(command.to_string()),
+            Value::BulkString("foo".to_string()),
+            Value::BulkString("1".to_string()),
+        ]);
+
+        // Act
+        let actual = MoveKey::parse_from(&value).unwrap();
+
+        // Assert
+        let expected = MoveKey {
+            key: "foo".to_string(),
+            database: 1,
+        };
+        assert_eq!(actual, expected);
+    }
+}
+
+#[cfg(test)]
+mod specs_for_execute {
+    use crate::command::executor::CommandExecutor;
+    use crate::command::executor::CommandExecutorContext;
+    use crate::command::executor::fixture::command_executor_context_with_databases;
+    use crate::repository::Data;
+    use crate::repository::Entry;
+    use crate::resp::Value;
+
+    use super::MoveKey;
+
+    async fn set(context: &CommandExecutorContext, database: usize, value: &str) {
+        context
+            .databases
+            .get(database)
+            .unwrap()
+            .set(Entry {
+                key: "foo".into(),
+                value: Data::String(value.to_string()),
+                expiry: None,
+            })
+            .await;
+    }
+
+    #[tokio::test]
+    async fn sut_moves_key_to_other_database() {
+        // Arrange
+        let context = command_executor_context_with_databases(16);
+        set(&context, 0, "bar").await;
+        let command = MoveKey {
+            key: "foo".to_string(),
+            database: 3,
+        };
+
+        // Act
+        let actual = command.execute(&context).await;
+
+        // Assert
+        assert_eq!(actual, Value::Integer(1));
+        assert!(!context.repository().exists("foo").await);
+        let moved = context.databases.get(3).unwrap().get("foo").await;
+        assert_eq!(moved, Ok(Some("bar".to_string())));
+    }
+
+    #[tokio::test]
+    async fn sut_does_not_move_key_if_it_exists_in_target_database() {
+        // Arrange
+        let context = command_executor_context_with_databases(16);
+        set(&context, 0, "bar").await;
+        set(&context, 3, "baz").await;
+        let command = MoveKey {
+            key: "foo".to_string(),
+            database: 3,
+        };
+
+        // Act
+        let actual = command.execute(&context).await;
+
+        // Assert
+        assert_eq!(actual, Value::Integer(0));
+        assert!(context.repository().exists("foo").await);
+    }
+
+    #[rstest::rstest]
+    #[case(0, "ERR source and destination objects are the same")]
+    #[case(16, "ERR DB index is out of range")]
+    #[tokio::test]
+    async fn sut_responds_error_if_target_database_is_invalid(
+        #[case] database: i64,
+        #[case] expected: &str,
+    ) {
+        // Arrange
+        let context = command_executor_context_with_databases(16);
+        let command = MoveKey {
+            key: "foo".to_string(),
+            database,
+        };
+
+        // Act
+        let actual = command.execute(&context).await;
+
+        // Assert
+        assert_eq!(actual, Value::Error(expected.to_string()));
+    }
+}
@@ -0,0 +1,90 @@
+use crate::command::executor::Command;
+use crate::command::executor::CommandExecutor;
+use crate::command::executor::CommandExecutorContext;
+use crate::command::parser::extract_array;
+use crate::command::parser::validate_array_length;
+use crate::command::parser::validate_main_command;
+use crate::resp::Value;
+
+#[derive(Debug, Default, PartialEq)]
+pub struct Multi;
+
+impl Command for Multi {
+    fn parse_from(value: &Value) -> Result<Self, anyhow::Error> {
+        let array = extract_array(value)?;
+        validate_array_length(array, 1)?;
+        validate_main_command(array, "MULTI")?;
+        Ok(Multi)
+    }
+}
+
+#[async_trait::async_trait]
+impl CommandExecutor for Multi {
+    async fn execute(&self, context: &CommandExecutorContext) -> Value {
+        if !context.session.begin() {
+            return Value::Error("ERR MULTI calls can not be nested".to_string());
+        }
+        Value::SimpleString("OK".to_string())
+    }
+}
+
+#[cfg(test)]
+mod specs_for_parse_from {
+    use crate::command::executor::Command;
+    use crate::resp::Value;
+
+    use super::Multi;
+
+    #[rstest::rstest]
+    #[case("MULTI")]
+    #[case("multi")]
+    fn sut_parses_multi_command_with_case_insensitive(#[case] multi: &str) {
+        // Arrange
+        let value = Value::Array(vec![Value::BulkString(multi.to_string())]);
+
+        // Act
+        let actual = Multi::parse_from(&value).unwrap();
+
+        // Assert
+        assert_eq!(actual, Multi);
+    }
+}
+
+#[cfg(test)]
+mod specs_for_execute {
+    use crate::command::executor::CommandExecutor;
+    use crate::command::executor::CommandExecutorContext;
+    use crate::command::executor::fixture::command_executor_context;
+    use crate::resp::Value;
+
+    use super::Multi;
+
+    #[rstest::rstest]
+    #[tokio::test]
+    async fn sut_starts_transaction(
+        #[from(command_executor_context)] context: CommandExecutorContext,
+    ) {
+        // Act
+        let actual = Multi.execute(&context).await;
+
+        // Assert
+        assert_eq!(actual, Value::SimpleString("OK".to_string()));
+        assert!(context.session.in_transaction());
+    }
+
+    #[rstest::rstest]
+    #[tokio::test]
+    async fn sut_responds_error_if_transaction_is_already_started(
+        #[from(command_executor_context)] context: CommandExecutorContext,
+    ) {
+        // Arrange
+        Multi.execute(&context).await;
+
+        // Act
+        let actual = Multi.execute(&context).await;
+
+        // Assert
+        let expected = Value::Error("ERR MULTI calls can not be nested".to_string());
+        assert_eq!(actual, expected);
+    }
+}
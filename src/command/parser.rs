@@ -15,6 +15,12 @@ pub fn extract_bulk_string(array: &[Value], index: usize) -> Result<&str, anyhow
     }
 }
 
+pub fn extract_bulk_strings(array: &[Value], start: usize) -> Result<Vec<String>, anyhow::Error> {
+    (start..array.len())
+        .map(|index| extract_bulk_string(array, index).map(str::to_string))
+        .collect()
+}
+
 pub fn validate_main_command(array: &[Value], expected: &str) -> Result<(), anyhow::Error> {
     let cmd = extract_bulk_string(array, 0)?;
     if cmd.to_uppercase() != expected.to_uppercase() {
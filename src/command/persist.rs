@@ -0,0 +1,111 @@
+use crate::command::executor::Command;
+use crate::command::executor::CommandExecutor;
+use crate::command::executor::CommandExecutorContext;
+use crate::command::parser::extract_array;
+use crate::command::parser::extract_bulk_string;
+use crate::command::parser::validate_array_length;
+use crate::command::parser::validate_main_command;
+use crate::notification::Class;
+use crate::resp::Value;
+
+#[derive(Debug, Default, PartialEq)]
+pub struct Persist {
+    key: String,
+}
+
+impl Command for Persist {
+    fn parse_from(value: &Value) -> Result<Self, anyhow::Error> {
+        let array = extract_array(value)?;
+        validate_array_length(array, 2)?;
+        validate_main_command(array, "PERSIST")?;
+        let key = extract_bulk_string(array, 1)?;
+        Ok(Persist {
+            key: key.to_string(),
+        })
+    }
+}
+
+#[async_trait::async_trait]
+impl CommandExecutor for Persist {
+    async fn execute(&self, context: &CommandExecutorContext) -> Value {
+        let persisted = context.repository().persist(&self.key).await;
+        if persisted {
+            context.notify(Class::Generic, "persist", &self.key);
+        }
+        Value::Integer(persisted as i64)
+    }
+}
+
+#[cfg(test)]
+mod specs_for_parse_from {
+    use crate::command::executor::Command;
+    use crate::resp::Value;
+
+    use super::Persist;
+
+    #[rstest::rstest]
+    #[case("PERSIST")]
+    #[case("persist")]
+    #[case("PeRsIsT")]
+    fn sut_parses_persist_command_with_case_insensitive(#[case] persist: &str) {
+        // Arrange
+        let value = Value::Array(vec![
+            Value::BulkString(persist.to_string()),
+            Value::BulkString("foo".to_string()),
+        ]);
+
+        // Act
+        let actual = Persist::parse_from(&value).unwrap();
+
+        // Assert
+        let expected = Persist {
+            key: "foo".to_string(),
+        };
+        assert_eq!(actual, expected);
+    }
+}
+
+#[cfg(test)]
+mod specs_for_execute {
+    use crate::clock::Clock;
+    use crate::command::executor::CommandExecutor;
+    use crate::command::executor::fixture::command_executor_context_with_fake_clock;
+    use crate::repository::Data;
+    use crate::repository::Entry;
+    use crate::repository::Expiry;
+    use crate::repository::TimeUnit;
+    use crate::resp::Value;
+
+    use super::Persist;
+
+    #[rstest::rstest]
+    #[case(true, 1)]
+    #[case(false, 0)]
+    #[tokio::test]
+    async fn sut_removes_expiry_of_key(#[case] has_expiry: bool, #[case] expected: i64) {
+        // Arrange
+        let (context, clock) = command_executor_context_with_fake_clock();
+        let expiry = has_expiry.then(|| Expiry {
+            epoch: clock.now_in_millis() + 100_000,
+            unit: TimeUnit::Millisecond,
+        });
+        context
+            .repository()
+            .set(Entry {
+                key: "foo".into(),
+                value: Data::String("bar".to_string()),
+                expiry,
+            })
+            .await;
+        let command = Persist {
+            key: "foo".to_string(),
+        };
+
+        // Act
+        let actual = command.execute(&context).await;
+
+        // Assert
+        assert_eq!(actual, Value::Integer(expected));
+        assert_eq!(context.repository().expiry("foo").await, Ok(None));
+    }
+}
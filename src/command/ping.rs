@@ -20,7 +20,13 @@ impl Command for Ping {
 
 #[async_trait::async_trait]
 impl CommandExecutor for Ping {
-    async fn execute(&self, _context: &CommandExecutorContext) -> Value {
+    async fn execute(&self, context: &CommandExecutorContext) -> Value {
+        if context.session.subscriber.is_subscribed() {
+            return Value::Array(vec![
+                Value::BulkString("pong".to_string()),
+                Value::BulkString(String::new()),
+            ]);
+        }
         Value::SimpleString("PONG".to_string())
     }
 }
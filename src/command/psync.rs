@@ -0,0 +1,308 @@
+use std::time::Duration;
+
+use crate::command::executor::Command;
+use crate::command::executor::CommandExecutor;
+use crate::command::executor::CommandExecutorContext;
+use crate::command::parser::extract_array;
+use crate::command::parser::extract_bulk_string;
+use crate::command::parser::validate_main_command;
+use crate::command::parser::validate_min_array_length;
+use crate::replication::Transfer;
+use crate::resp::Value;
+use crate::snapshot::serialize;
+
+/// PSYNC replicationid offset, turning the connection into a replica fed with the writes it
+/// missed when the backlog still holds them, or with a snapshot of the dataset and every
+/// write after it otherwise. The snapshot is streamed without its length to replicas
+/// supporting it when diskless sync is enabled.
+#[derive(Debug, Default, PartialEq)]
+pub struct Psync {
+    id: String,
+    offset: i64,
+    /// Whether the master of this server asks it to take over, as FAILOVER does.
+    failover: bool,
+}
+
+impl Command for Psync {
+    fn parse_from(value: &Value) -> Result<Self, anyhow::Error> {
+        let array = extract_array(value)?;
+        validate_min_array_length(array, 3)?;
+        validate_main_command(array, "PSYNC")?;
+        let failover = match array.len() {
+            3 => false,
+            4 if extract_bulk_string(array, 3)?.eq_ignore_ascii_case("FAILOVER") => true,
+            _ => return Err(anyhow::anyhow!("syntax error")),
+        };
+        Ok(Psync {
+            id: extract_bulk_string(array, 1)?.to_string(),
+            offset: extract_bulk_string(array, 2)?.parse()?,
+            failover,
+        })
+    }
+}
+
+#[async_trait::async_trait]
+impl CommandExecutor for Psync {
+    async fn execute(&self, context: &CommandExecutorContext) -> Value {
+        // The stream may burst well past what subscribers are allowed to fall behind.
+        let limit = context.config.replication.replica_buffer_limit;
+        context.session.subscriber.set_limit(limit);
+        if self.failover {
+            if self.id != context.replication.id() {
+                return Value::Error("ERR PSYNC FAILOVER replid must match my replid.".to_string());
+            }
+            context.replication.promote();
+        }
+        let missed = u64::try_from(self.offset).ok().and_then(|offset| {
+            context
+                .replication
+                .catch_up(&context.session, &self.id, offset)
+        });
+        if let Some((id, missed)) = missed {
+            return Value::Replies(vec![
+                Value::SimpleString(format!("CONTINUE {id}")),
+                Value::Raw(missed),
+            ]);
+        }
+        if context.config.replication.diskless_sync
+            && context.replication.is_capable(&context.session, "eof")
+        {
+            return stream(context).await;
+        }
+        // No write may slip in between the snapshot and the stream which follows it.
+        let _exclusive = match context.lock_exclusive().await {
+            Ok(exclusive) => exclusive,
+            Err(e) => return e,
+        };
+        let snapshot = serialize(
+            &context.databases,
+            &context.scripts.libraries,
+            context.clock.as_ref(),
+        )
+        .await;
+        let (id, offset) = context.replication.attach(&context.session);
+        let mut rdb = format!("${}\r\n", snapshot.len()).into_bytes();
+        rdb.extend(snapshot);
+        Value::Replies(vec![
+            Value::SimpleString(format!("FULLRESYNC {id} {offset}")),
+            Value::Raw(rdb),
+        ])
+    }
+}
+
+/// Joins the diskless transfer about to start, or starts one shared by every replica asking
+/// for a snapshot within the delay.
+async fn stream(context: &CommandExecutorContext) -> Value {
+    let (mut receiver, first) = context.replication.join_transfer(&context.session);
+    if first {
+        let delay = Duration::from_secs(context.config.replication.diskless_sync_delay);
+        tokio::time::sleep(delay).await;
+        match context.lock_exclusive().await {
+            Ok(_exclusive) => {
+                let snapshot = serialize(
+                    &context.databases,
+                    &context.scripts.libraries,
+                    context.clock.as_ref(),
+                )
+                .await;
+                context.replication.start_transfer(snapshot);
+            }
+            Err(e) => {
+                context.replication.cancel_transfer();
+                return e;
+            }
+        }
+    }
+    let transfer = receiver
+        .wait_for(Option::is_some)
+        .await
+        .map(|transfer| transfer.clone());
+    let Ok(Some(Transfer {
+        id,
+        offset,
+        payload,
+    })) = transfer
+    else {
+        return Value::Error("ERR snapshot transfer was cancelled".to_string());
+    };
+    Value::Replies(vec![
+        Value::SimpleString(format!("FULLRESYNC {id} {offset}")),
+        Value::Raw(payload.to_vec()),
+    ])
+}
+
+#[cfg(test)]
+mod specs_for_parse_from {
+    use crate::command::executor::Command;
+    use crate::resp::Value;
+
+    use super::Psync;
+
+    #[test]
+    fn sut_parses_psync_command() {
+        // Arrange
+        let value = Value::Array(vec![
+            Value::BulkString("psync".to_string()),
+            Value::BulkString("?".to_string()),
+            Value::BulkString("-1".to_string()),
+        ]);
+
+        // Act
+        let actual = Psync::parse_from(&value).unwrap();
+
+        // Assert
+        let expected = Psync {
+            id: "?".to_string(),
+            offset: -1,
+            failover: false,
+        };
+        assert_eq!(actual, expected);
+    }
+}
+
+#[cfg(test)]
+mod specs_for_execute {
+    use std::sync::Arc;
+
+    use crate::clock::SystemClock;
+    use crate::command::executor::CommandExecutor;
+    use crate::command::executor::CommandExecutorContext;
+    use crate::command::executor::fixture::command_executor_context;
+    use crate::config::Config;
+    use crate::repository::fixture::DummyRepository;
+    use crate::resp::Value;
+
+    use super::Psync;
+
+    #[rstest::rstest]
+    #[tokio::test]
+    async fn sut_replies_fullresync_with_a_snapshot_then_streams_writes(
+        #[from(command_executor_context)] context: CommandExecutorContext,
+    ) {
+        // Arrange
+        let sut = Psync {
+            id: "?".to_string(),
+            offset: -1,
+            failover: false,
+        };
+
+        // Act
+        let actual = sut.execute(&context).await;
+        let ping = Value::Array(vec![Value::BulkString("PING".to_string())]);
+        context.replication.propagate(0, &ping);
+
+        // Assert
+        let Value::Replies(replies) = actual else {
+            panic!("expected several replies, got {actual:?}");
+        };
+        let expected = format!("FULLRESYNC {} 0", context.replication.id());
+        assert_eq!(replies[0], Value::SimpleString(expected));
+        assert!(matches!(&replies[1], Value::Raw(rdb) if rdb.windows(5).any(|w| w == b"REDIS")));
+        let streamed = context.session.subscriber.messages().await.try_recv();
+        assert!(matches!(streamed, Ok(Value::Raw(bytes)) if bytes.ends_with(&ping.serialize())));
+    }
+
+    #[tokio::test]
+    async fn sut_lets_replicas_fall_further_behind_than_subscribers() {
+        // Arrange
+        let mut config = Config::default();
+        config.server.pubsub_buffer_limit = 2;
+        let context = command_executor_context(DummyRepository, config, Arc::new(SystemClock));
+        let sut = Psync {
+            id: "?".to_string(),
+            offset: -1,
+            failover: false,
+        };
+        sut.execute(&context).await;
+
+        // Act
+        let ping = Value::Array(vec![Value::BulkString("PING".to_string())]);
+        for _ in 0..10 {
+            context.replication.propagate(0, &ping);
+        }
+
+        // Assert
+        let mut messages = context.session.subscriber.messages().await;
+        let mut actual = 0;
+        while messages.try_recv().is_ok() {
+            actual += 1;
+        }
+        assert_eq!(actual, 10);
+    }
+
+    #[rstest::rstest]
+    #[tokio::test]
+    async fn sut_replies_continue_with_the_writes_missed(
+        #[from(command_executor_context)] context: CommandExecutorContext,
+    ) {
+        // Arrange
+        let ping = Value::Array(vec![Value::BulkString("PING".to_string())]);
+        context.replication.propagate(0, &ping);
+        let sut = Psync {
+            id: context.replication.id(),
+            offset: 1,
+            failover: false,
+        };
+
+        // Act
+        let actual = sut.execute(&context).await;
+
+        // Assert
+        let Value::Replies(replies) = actual else {
+            panic!("expected several replies, got {actual:?}");
+        };
+        let expected = format!("CONTINUE {}", context.replication.id());
+        assert_eq!(replies[0], Value::SimpleString(expected));
+        assert!(matches!(&replies[1], Value::Raw(missed) if missed.ends_with(&ping.serialize())));
+    }
+
+    #[rstest::rstest]
+    #[tokio::test]
+    async fn sut_replies_fullresync_to_replicas_of_another_history(
+        #[from(command_executor_context)] context: CommandExecutorContext,
+    ) {
+        // Arrange
+        let sut = Psync {
+            id: "0".repeat(40),
+            offset: 1,
+            failover: false,
+        };
+
+        // Act
+        let actual = sut.execute(&context).await;
+
+        // Assert
+        assert!(
+            matches!(actual, Value::Replies(replies) if matches!(&replies[0], Value::SimpleString(reply) if reply.starts_with("FULLRESYNC")))
+        );
+    }
+
+    #[tokio::test]
+    async fn sut_streams_snapshot_framed_with_eof_mark_to_capable_replica() {
+        // Arrange
+        let mut config = Config::default();
+        config.replication.diskless_sync_delay = 0;
+        let context = command_executor_context(DummyRepository, config, Arc::new(SystemClock));
+        context
+            .replication
+            .announce(&context.session, &["eof".to_string()]);
+        let sut = Psync {
+            id: "?".to_string(),
+            offset: -1,
+            failover: false,
+        };
+
+        // Act
+        let actual = sut.execute(&context).await;
+
+        // Assert
+        let Value::Replies(replies) = actual else {
+            panic!("expected several replies, got {actual:?}");
+        };
+        let expected = format!("FULLRESYNC {} 0", context.replication.id());
+        assert_eq!(replies[0], Value::SimpleString(expected));
+        assert!(
+            matches!(&replies[1], Value::Raw(payload) if payload.starts_with(b"$EOF:") && payload.ends_with(&payload[5..45]))
+        );
+    }
+}
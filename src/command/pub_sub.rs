@@ -0,0 +1,175 @@
+use crate::command::executor::Command;
+use crate::command::executor::CommandExecutor;
+use crate::command::executor::CommandExecutorContext;
+use crate::command::parser::extract_array;
+use crate::command::parser::extract_bulk_string;
+use crate::command::parser::extract_bulk_strings;
+use crate::command::parser::validate_main_command;
+use crate::command::parser::validate_min_array_length;
+use crate::pubsub::Scope;
+use crate::resp::Value;
+
+/// PUBSUB CHANNELS, NUMSUB, NUMPAT, SHARDCHANNELS and SHARDNUMSUB, the scope telling whether
+/// channels or shard channels are asked about.
+#[derive(Debug, PartialEq)]
+pub enum PubSub {
+    Channels(Scope, Option<String>),
+    NumSub(Scope, Vec<String>),
+    NumPat,
+}
+
+impl Command for PubSub {
+    fn parse_from(value: &Value) -> Result<Self, anyhow::Error> {
+        let array = extract_array(value)?;
+        validate_min_array_length(array, 2)?;
+        validate_main_command(array, "PUBSUB")?;
+        let (scope, subcommand) = match extract_bulk_string(array, 1)?.to_uppercase().as_str() {
+            "CHANNELS" => (Scope::Channel, "CHANNELS"),
+            "NUMSUB" => (Scope::Channel, "NUMSUB"),
+            "NUMPAT" => (Scope::Pattern, "NUMPAT"),
+            "SHARDCHANNELS" => (Scope::Shard, "CHANNELS"),
+            "SHARDNUMSUB" => (Scope::Shard, "NUMSUB"),
+            _ => {
+                return Err(anyhow::anyhow!(
+                    "expected PUBSUB CHANNELS, NUMSUB, NUMPAT, SHARDCHANNELS or SHARDNUMSUB"
+                ));
+            }
+        };
+        match subcommand {
+            "CHANNELS" if array.len() == 2 => Ok(PubSub::Channels(scope, None)),
+            "CHANNELS" if array.len() == 3 => Ok(PubSub::Channels(
+                scope,
+                Some(extract_bulk_string(array, 2)?.to_string()),
+            )),
+            "NUMSUB" => Ok(PubSub::NumSub(scope, extract_bulk_strings(array, 2)?)),
+            "NUMPAT" if array.len() == 2 => Ok(PubSub::NumPat),
+            _ => Err(anyhow::anyhow!("wrong number of arguments")),
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl CommandExecutor for PubSub {
+    async fn execute(&self, context: &CommandExecutorContext) -> Value {
+        let pubsub = &context.pubsub;
+        match self {
+            PubSub::Channels(scope, pattern) => Value::Array(
+                pubsub
+                    .names(*scope, pattern.as_deref())
+                    .into_iter()
+                    .map(Value::BulkString)
+                    .collect(),
+            ),
+            PubSub::NumSub(scope, channels) => Value::Array(
+                channels
+                    .iter()
+                    .flat_map(|channel| {
+                        let count = pubsub.subscribers(*scope, channel);
+                        [
+                            Value::BulkString(channel.clone()),
+                            Value::Integer(count as i64),
+                        ]
+                    })
+                    .collect(),
+            ),
+            PubSub::NumPat => Value::Integer(pubsub.names(Scope::Pattern, None).len() as i64),
+        }
+    }
+}
+
+#[cfg(test)]
+mod specs_for_parse_from {
+    use crate::command::executor::Command;
+    use crate::pubsub::Scope;
+    use crate::resp::Value;
+
+    use super::PubSub;
+
+    fn array(arguments: &[&str]) -> Value {
+        Value::Array(
+            arguments
+                .iter()
+                .map(|argument| Value::BulkString(argument.to_string()))
+                .collect(),
+        )
+    }
+
+    #[rstest::rstest]
+    #[case(&["PUBSUB", "CHANNELS"], PubSub::Channels(Scope::Channel, None))]
+    #[case(&["pubsub", "channels", "n*"], PubSub::Channels(Scope::Channel, Some("n*".to_string())))]
+    #[case(&["PUBSUB", "NUMSUB", "a", "b"], PubSub::NumSub(Scope::Channel, vec!["a".to_string(), "b".to_string()]))]
+    #[case(&["PUBSUB", "SHARDCHANNELS"], PubSub::Channels(Scope::Shard, None))]
+    #[case(&["PUBSUB", "shardnumsub", "a"], PubSub::NumSub(Scope::Shard, vec!["a".to_string()]))]
+    #[case(&["PUBSUB", "NUMPAT"], PubSub::NumPat)]
+    fn sut_parses_pubsub_subcommands_correctly(
+        #[case] arguments: &[&str],
+        #[case] expected: PubSub,
+    ) {
+        // Act
+        let actual = PubSub::parse_from(&array(arguments)).unwrap();
+
+        // Assert
+        assert_eq!(actual, expected);
+    }
+}
+
+#[cfg(test)]
+mod specs_for_execute {
+    use std::sync::Arc;
+
+    use crate::command::executor::CommandExecutor;
+    use crate::command::executor::CommandExecutorContext;
+    use crate::command::executor::fixture::command_executor_context;
+    use crate::pubsub::Scope;
+    use crate::pubsub::Subscriber;
+    use crate::resp::Value;
+
+    use super::PubSub;
+
+    #[rstest::rstest]
+    #[case(PubSub::Channels(Scope::Channel, None), Value::Array(vec![
+        Value::BulkString("news".to_string()),
+        Value::BulkString("sports".to_string()),
+    ]))]
+    #[case(PubSub::Channels(Scope::Channel, Some("s*".to_string())), Value::Array(vec![
+        Value::BulkString("sports".to_string()),
+    ]))]
+    #[case(PubSub::NumSub(Scope::Channel, vec!["news".to_string(), "weather".to_string()]), Value::Array(vec![
+        Value::BulkString("news".to_string()),
+        Value::Integer(2),
+        Value::BulkString("weather".to_string()),
+        Value::Integer(0),
+    ]))]
+    #[case(PubSub::NumPat, Value::Integer(1))]
+    #[case(PubSub::Channels(Scope::Shard, None), Value::Array(vec![
+        Value::BulkString("orders".to_string()),
+    ]))]
+    #[case(PubSub::NumSub(Scope::Shard, vec!["news".to_string(), "orders".to_string()]), Value::Array(vec![
+        Value::BulkString("news".to_string()),
+        Value::Integer(0),
+        Value::BulkString("orders".to_string()),
+        Value::Integer(1),
+    ]))]
+    #[tokio::test]
+    async fn sut_introspects_subscriptions(
+        #[case] command: PubSub,
+        #[case] expected: Value,
+        #[from(command_executor_context)] context: CommandExecutorContext,
+    ) {
+        // Arrange
+        let first = Arc::new(Subscriber::new(8));
+        let second = Arc::new(Subscriber::new(8));
+        context.pubsub.subscribe(Scope::Channel, "news", &first);
+        context.pubsub.subscribe(Scope::Channel, "news", &second);
+        context.pubsub.subscribe(Scope::Channel, "sports", &second);
+        context.pubsub.subscribe(Scope::Pattern, "n*", &first);
+        context.pubsub.subscribe(Scope::Pattern, "n*", &second);
+        context.pubsub.subscribe(Scope::Shard, "orders", &first);
+
+        // Act
+        let actual = command.execute(&context).await;
+
+        // Assert
+        assert_eq!(actual, expected);
+    }
+}
@@ -0,0 +1,137 @@
+use crate::command::executor::Command;
+use crate::command::executor::CommandExecutor;
+use crate::command::executor::CommandExecutorContext;
+use crate::command::parser::extract_array;
+use crate::command::parser::extract_bulk_string;
+use crate::command::parser::validate_array_length;
+use crate::command::parser::validate_main_command;
+use crate::resp::Value;
+
+/// PUBLISH, or SPUBLISH to a shard channel.
+#[derive(Debug, PartialEq)]
+pub struct Publish {
+    channel: String,
+    message: String,
+    sharded: bool,
+}
+
+impl Command for Publish {
+    fn parse_from(value: &Value) -> Result<Self, anyhow::Error> {
+        let array = extract_array(value)?;
+        validate_array_length(array, 3)?;
+        let sharded = validate_main_command(array, "PUBLISH").is_err();
+        if sharded {
+            validate_main_command(array, "SPUBLISH")?;
+        }
+        Ok(Publish {
+            channel: extract_bulk_string(array, 1)?.to_string(),
+            message: extract_bulk_string(array, 2)?.to_string(),
+            sharded,
+        })
+    }
+}
+
+#[async_trait::async_trait]
+impl CommandExecutor for Publish {
+    async fn execute(&self, context: &CommandExecutorContext) -> Value {
+        let receivers = match self.sharded {
+            true => context.pubsub.publish_sharded(&self.channel, &self.message),
+            false => context.pubsub.publish(&self.channel, &self.message),
+        };
+        Value::Integer(receivers as i64)
+    }
+}
+
+#[cfg(test)]
+mod specs_for_parse_from {
+    use crate::command::executor::Command;
+    use crate::resp::Value;
+
+    use super::Publish;
+
+    #[rstest::rstest]
+    #[case("PUBLISH", false)]
+    #[case("publish", false)]
+    #[case("SPUBLISH", true)]
+    fn sut_parses_channel_and_message_correctly(#[case] publish: &str, #[case] sharded: bool) {
+        // Arrange
+        let value = Value::Array(vec![
+            Value::BulkString(publish.to_string()),
+            Value::BulkString("news".to_string()),
+            Value::BulkString("hello".to_string()),
+        ]);
+
+        // Act
+        let actual = Publish::parse_from(&value).unwrap();
+
+        // Assert
+        let expected = Publish {
+            channel: "news".to_string(),
+            message: "hello".to_string(),
+            sharded,
+        };
+        assert_eq!(actual, expected);
+    }
+}
+
+#[cfg(test)]
+mod specs_for_execute {
+    use std::sync::Arc;
+
+    use crate::command::executor::CommandExecutor;
+    use crate::command::executor::CommandExecutorContext;
+    use crate::command::executor::fixture::command_executor_context;
+    use crate::pubsub::Scope;
+    use crate::pubsub::Subscriber;
+    use crate::resp::Value;
+
+    use super::Publish;
+
+    #[rstest::rstest]
+    #[tokio::test]
+    async fn sut_replies_number_of_receivers(
+        #[from(command_executor_context)] context: CommandExecutorContext,
+    ) {
+        // Arrange
+        let subscriber = Arc::new(Subscriber::new(8));
+        context
+            .pubsub
+            .subscribe(Scope::Channel, "news", &subscriber);
+        context.pubsub.subscribe(Scope::Pattern, "n*", &subscriber);
+        let command = Publish {
+            channel: "news".to_string(),
+            message: "hello".to_string(),
+            sharded: false,
+        };
+
+        // Act
+        let actual = command.execute(&context).await;
+
+        // Assert
+        assert_eq!(actual, Value::Integer(2));
+    }
+
+    #[rstest::rstest]
+    #[tokio::test]
+    async fn sut_only_reaches_shard_channel_subscribers_if_sharded(
+        #[from(command_executor_context)] context: CommandExecutorContext,
+    ) {
+        // Arrange
+        let subscriber = Arc::new(Subscriber::new(8));
+        context
+            .pubsub
+            .subscribe(Scope::Channel, "news", &subscriber);
+        context.pubsub.subscribe(Scope::Shard, "news", &subscriber);
+        let command = Publish {
+            channel: "news".to_string(),
+            message: "hello".to_string(),
+            sharded: true,
+        };
+
+        // Act
+        let actual = command.execute(&context).await;
+
+        // Assert
+        assert_eq!(actual, Value::Integer(1));
+    }
+}
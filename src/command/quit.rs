@@ -0,0 +1,49 @@
+use crate::command::executor::Command;
+use crate::command::executor::CommandExecutor;
+use crate::command::executor::CommandExecutorContext;
+use crate::command::parser::extract_array;
+use crate::command::parser::validate_array_length;
+use crate::command::parser::validate_main_command;
+use crate::resp::Value;
+
+/// Replies OK, after which the connection is closed.
+#[derive(Debug, Default, PartialEq)]
+pub struct Quit;
+
+impl Command for Quit {
+    fn parse_from(value: &Value) -> Result<Self, anyhow::Error> {
+        let array = extract_array(value)?;
+        validate_array_length(array, 1)?;
+        validate_main_command(array, "QUIT")?;
+        Ok(Quit)
+    }
+}
+
+#[async_trait::async_trait]
+impl CommandExecutor for Quit {
+    async fn execute(&self, _context: &CommandExecutorContext) -> Value {
+        Value::SimpleString("OK".to_string())
+    }
+}
+
+#[cfg(test)]
+mod specs_for_parse_from {
+    use crate::command::executor::Command;
+    use crate::resp::Value;
+
+    use super::Quit;
+
+    #[rstest::rstest]
+    #[case("QUIT")]
+    #[case("quit")]
+    fn sut_parses_quit_command_with_case_insensitive(#[case] quit: &str) {
+        // Arrange
+        let value = Value::Array(vec![Value::BulkString(quit.to_string())]);
+
+        // Act
+        let actual = Quit::parse_from(&value).unwrap();
+
+        // Assert
+        assert_eq!(actual, Quit);
+    }
+}
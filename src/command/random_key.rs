@@ -0,0 +1,84 @@
+use crate::command::executor::Command;
+use crate::command::executor::CommandExecutor;
+use crate::command::executor::CommandExecutorContext;
+use crate::command::parser::extract_array;
+use crate::command::parser::validate_array_length;
+use crate::command::parser::validate_main_command;
+use crate::resp::Value;
+
+#[derive(Debug, Default, PartialEq)]
+pub struct RandomKey;
+
+impl Command for RandomKey {
+    fn parse_from(value: &Value) -> Result<Self, anyhow::Error> {
+        let array = extract_array(value)?;
+        validate_array_length(array, 1)?;
+        validate_main_command(array, "RANDOMKEY")?;
+        Ok(RandomKey)
+    }
+}
+
+#[async_trait::async_trait]
+impl CommandExecutor for RandomKey {
+    async fn execute(&self, context: &CommandExecutorContext) -> Value {
+        match context.repository().random_key().await {
+            Some(key) => Value::BulkString(key),
+            None => Value::Null,
+        }
+    }
+}
+
+#[cfg(test)]
+mod specs_for_execute {
+    use crate::command::executor::CommandExecutor;
+    use crate::command::executor::CommandExecutorContext;
+    use crate::command::executor::fixture::command_executor_context;
+    use crate::repository::Data;
+    use crate::repository::Entry;
+    use crate::repository::InMemoryRepository;
+    use crate::resp::Value;
+
+    use super::RandomKey;
+
+    #[rstest::rstest]
+    #[tokio::test]
+    async fn sut_responds_one_of_existing_keys(
+        #[from(command_executor_context)]
+        #[with(InMemoryRepository::new())]
+        context: CommandExecutorContext,
+    ) {
+        // Arrange
+        let keys = ["foo", "bar", "baz"];
+        for key in keys {
+            context
+                .repository()
+                .set(Entry {
+                    key: key.into(),
+                    value: Data::String(key.to_string()),
+                    expiry: None,
+                })
+                .await;
+        }
+
+        // Act
+        let actual = RandomKey.execute(&context).await;
+
+        // Assert
+        let Value::BulkString(key) = actual else {
+            panic!("expected bulk string but got {actual:?}");
+        };
+        assert!(keys.contains(&key.as_str()));
+    }
+
+    #[rstest::rstest]
+    #[tokio::test]
+    async fn sut_responds_null_if_keyspace_is_empty(
+        #[from(command_executor_context)] context: CommandExecutorContext,
+    ) {
+        // Act
+        let actual = RandomKey.execute(&context).await;
+
+        // Assert
+        assert_eq!(actual, Value::Null);
+    }
+}
@@ -0,0 +1,148 @@
+use crate::command::executor::Command;
+use crate::command::executor::CommandExecutor;
+use crate::command::executor::CommandExecutorContext;
+use crate::command::parser::extract_array;
+use crate::command::parser::extract_bulk_string;
+use crate::command::parser::validate_array_length;
+use crate::command::parser::validate_main_command;
+use crate::notification::Class;
+use crate::resp::Value;
+
+#[derive(Debug, Default, PartialEq)]
+pub struct Rename {
+    key: String,
+    new_key: String,
+}
+
+impl Command for Rename {
+    fn parse_from(value: &Value) -> Result<Self, anyhow::Error> {
+        let array = extract_array(value)?;
+        validate_array_length(array, 3)?;
+        validate_main_command(array, "RENAME")?;
+        let key = extract_bulk_string(array, 1)?;
+        let new_key = extract_bulk_string(array, 2)?;
+        Ok(Rename {
+            key: key.to_string(),
+            new_key: new_key.to_string(),
+        })
+    }
+}
+
+#[async_trait::async_trait]
+impl CommandExecutor for Rename {
+    async fn execute(&self, context: &CommandExecutorContext) -> Value {
+        match context
+            .repository()
+            .rename(&self.key, &self.new_key, true)
+            .await
+        {
+            Ok(renamed) => {
+                if renamed && self.key != self.new_key {
+                    context.notify(Class::Generic, "rename_from", &self.key);
+                    context.notify(Class::Generic, "rename_to", &self.new_key);
+                }
+                Value::SimpleString("OK".to_string())
+            }
+            Err(e) => Value::Error(e.to_string()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod specs_for_parse_from {
+    use crate::command::executor::Command;
+    use crate::resp::Value;
+
+    use super::Rename;
+
+    #[rstest::rstest]
+    #[case("RENAME")]
+    #[case("rename")]
+    #[case("ReNaMe")]
+    fn sut_parses_rename_command_with_case_insensitive(#[case] rename: &str) {
+        // Arrange
+        let value = Value::Array(vec![
+            Value::BulkString(rename.to_string()),
+            Value::BulkString("foo".to_string()),
+            Value::BulkString("bar".to_string()),
+        ]);
+
+        // Act
+        let actual = Rename::parse_from(&value).unwrap();
+
+        // Assert
+        let expected = Rename {
+            key: "foo".to_string(),
+            new_key: "bar".to_string(),
+        };
+        assert_eq!(actual, expected);
+    }
+}
+
+#[cfg(test)]
+mod specs_for_execute {
+    use crate::command::executor::CommandExecutor;
+    use crate::command::executor::CommandExecutorContext;
+    use crate::command::executor::fixture::command_executor_context;
+    use crate::repository::Data;
+    use crate::repository::Entry;
+    use crate::repository::InMemoryRepository;
+    use crate::resp::Value;
+
+    use super::Rename;
+
+    #[rstest::rstest]
+    #[tokio::test]
+    async fn sut_moves_value_to_new_key_overwriting_existing_one(
+        #[from(command_executor_context)]
+        #[with(InMemoryRepository::new())]
+        context: CommandExecutorContext,
+    ) {
+        // Arrange
+        for (key, value) in [("foo", "1"), ("bar", "2")] {
+            context
+                .repository()
+                .set(Entry {
+                    key: key.into(),
+                    value: Data::String(value.to_string()),
+                    expiry: None,
+                })
+                .await;
+        }
+        let command = Rename {
+            key: "foo".to_string(),
+            new_key: "bar".to_string(),
+        };
+
+        // Act
+        let actual = command.execute(&context).await;
+
+        // Assert
+        assert_eq!(actual, Value::SimpleString("OK".to_string()));
+        assert_eq!(context.repository().get("foo").await, Ok(None));
+        assert_eq!(
+            context.repository().get("bar").await,
+            Ok(Some("1".to_string()))
+        );
+    }
+
+    #[rstest::rstest]
+    #[tokio::test]
+    async fn sut_responds_error_if_key_does_not_exist(
+        #[from(command_executor_context)]
+        #[with(InMemoryRepository::new())]
+        context: CommandExecutorContext,
+    ) {
+        // Arrange
+        let command = Rename {
+            key: "foo".to_string(),
+            new_key: "bar".to_string(),
+        };
+
+        // Act
+        let actual = command.execute(&context).await;
+
+        // Assert
+        assert_eq!(actual, Value::Error("ERR no such key".to_string()));
+    }
+}
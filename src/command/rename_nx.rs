@@ -0,0 +1,128 @@
+use crate::command::executor::Command;
+use crate::command::executor::CommandExecutor;
+use crate::command::executor::CommandExecutorContext;
+use crate::command::parser::extract_array;
+use crate::command::parser::extract_bulk_string;
+use crate::command::parser::validate_array_length;
+use crate::command::parser::validate_main_command;
+use crate::notification::Class;
+use crate::resp::Value;
+
+#[derive(Debug, Default, PartialEq)]
+pub struct RenameNx {
+    key: String,
+    new_key: String,
+}
+
+impl Command for RenameNx {
+    fn parse_from(value: &Value) -> Result<Self, anyhow::Error> {
+        let array = extract_array(value)?;
+        validate_array_length(array, 3)?;
+        validate_main_command(array, "RENAMENX")?;
+        let key = extract_bulk_string(array, 1)?;
+        let new_key = extract_bulk_string(array, 2)?;
+        Ok(RenameNx {
+            key: key.to_string(),
+            new_key: new_key.to_string(),
+        })
+    }
+}
+
+#[async_trait::async_trait]
+impl CommandExecutor for RenameNx {
+    async fn execute(&self, context: &CommandExecutorContext) -> Value {
+        match context
+            .repository()
+            .rename(&self.key, &self.new_key, false)
+            .await
+        {
+            Ok(renamed) => {
+                if renamed && self.key != self.new_key {
+                    context.notify(Class::Generic, "rename_from", &self.key);
+                    context.notify(Class::Generic, "rename_to", &self.new_key);
+                }
+                Value::Integer(renamed as i64)
+            }
+            Err(e) => Value::Error(e.to_string()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod specs_for_parse_from {
+    use crate::command::executor::Command;
+    use crate::resp::Value;
+
+    use super::RenameNx;
+
+    #[rstest::rstest]
+    #[case("RENAMENX")]
+    #[case("renamenx")]
+    #[case("ReNaMeNx")]
+    fn sut_parses_renamenx_command_with_case_insensitive(#[case] renamenx: &str) {
+        // Arrange
+        let value = Value::Array(vec![
+            Value::BulkString(renamenx.to_string()),
+            Value::BulkString("foo".to_string()),
+            Value::BulkString("bar".to_string()),
+        ]);
+
+        // Act
+        let actual = RenameNx::parse_from(&value).unwrap();
+
+        // Assert
+        let expected = RenameNx {
+            key: "foo".to_string(),
+            new_key: "bar".to_string(),
+        };
+        assert_eq!(actual, expected);
+    }
+}
+
+#[cfg(test)]
+mod specs_for_execute {
+    use crate::command::executor::CommandExecutor;
+    use crate::command::executor::CommandExecutorContext;
+    use crate::command::executor::fixture::command_executor_context;
+    use crate::repository::Data;
+    use crate::repository::Entry;
+    use crate::repository::InMemoryRepository;
+    use crate::resp::Value;
+
+    use super::RenameNx;
+
+    #[rstest::rstest]
+    #[case("baz", 1)]
+    #[case("bar", 0)]
+    #[tokio::test]
+    async fn sut_renames_only_if_new_key_does_not_exist(
+        #[case] new_key: &str,
+        #[case] expected: i64,
+        #[from(command_executor_context)]
+        #[with(InMemoryRepository::new())]
+        context: CommandExecutorContext,
+    ) {
+        // Arrange
+        for key in ["foo", "bar"] {
+            context
+                .repository()
+                .set(Entry {
+                    key: key.into(),
+                    value: Data::String(key.to_string()),
+                    expiry: None,
+                })
+                .await;
+        }
+        let command = RenameNx {
+            key: "foo".to_string(),
+            new_key: new_key.to_string(),
+        };
+
+        // Act
+        let actual = command.execute(&context).await;
+
+        // Assert
+        assert_eq!(actual, Value::Integer(expected));
+        assert_eq!(context.repository().exists("foo").await, expected == 0);
+    }
+}
@@ -0,0 +1,194 @@
+use crate::command::executor::Command;
+use crate::command::executor::CommandExecutor;
+use crate::command::executor::CommandExecutorContext;
+use crate::command::parser::extract_array;
+use crate::command::parser::extract_bulk_string;
+use crate::command::parser::validate_main_command;
+use crate::command::parser::validate_min_array_length;
+use crate::resp::Value;
+
+/// REPLCONF, sent by replicas to introduce themselves and acknowledge the stream they applied.
+#[derive(Debug, PartialEq)]
+pub enum Replconf {
+    ListeningPort(u16),
+    Capabilities(Vec<String>),
+    /// The offset of the stream the replica applied, and the one it wrote to its AOF if any.
+    Ack {
+        offset: u64,
+        fsynced: Option<u64>,
+    },
+    GetAck,
+}
+
+impl Command for Replconf {
+    fn parse_from(value: &Value) -> Result<Self, anyhow::Error> {
+        let array = extract_array(value)?;
+        validate_min_array_length(array, 3)?;
+        validate_main_command(array, "REPLCONF")?;
+        let option = extract_bulk_string(array, 1)?.to_lowercase();
+        let argument = extract_bulk_string(array, 2)?;
+        match option.as_str() {
+            "listening-port" if array.len() == 3 => Ok(Replconf::ListeningPort(argument.parse()?)),
+            "capa" if array.len() % 2 == 1 => {
+                let capabilities = (2..array.len())
+                    .step_by(2)
+                    .map(|index| {
+                        validate_option(array, index - 1, "capa")?;
+                        Ok(extract_bulk_string(array, index)?.to_lowercase())
+                    })
+                    .collect::<Result<_, anyhow::Error>>()?;
+                Ok(Replconf::Capabilities(capabilities))
+            }
+            "ack" if array.len() == 3 || array.len() == 5 => {
+                let fsynced = match array.len() {
+                    5 => {
+                        validate_option(array, 3, "fack")?;
+                        Some(extract_bulk_string(array, 4)?.parse()?)
+                    }
+                    _ => None,
+                };
+                Ok(Replconf::Ack {
+                    offset: argument.parse()?,
+                    fsynced,
+                })
+            }
+            "getack" if array.len() == 3 => Ok(Replconf::GetAck),
+            _ => Err(anyhow::anyhow!("Unrecognized REPLCONF option: {option}")),
+        }
+    }
+}
+
+fn validate_option(array: &[Value], index: usize, expected: &str) -> Result<(), anyhow::Error> {
+    if !extract_bulk_string(array, index)?.eq_ignore_ascii_case(expected) {
+        return Err(anyhow::anyhow!("expected {expected}"));
+    }
+    Ok(())
+}
+
+#[async_trait::async_trait]
+impl CommandExecutor for Replconf {
+    async fn execute(&self, context: &CommandExecutorContext) -> Value {
+        match self {
+            Replconf::ListeningPort(port) => {
+                context.replication.register(&context.session, Some(*port));
+                Value::SimpleString("OK".to_string())
+            }
+            Replconf::Capabilities(capabilities) => {
+                context.replication.announce(&context.session, capabilities);
+                Value::SimpleString("OK".to_string())
+            }
+            // Neither is ever replied to.
+            Replconf::Ack { offset, fsynced } => {
+                context
+                    .replication
+                    .acknowledge(&context.session, *offset, *fsynced);
+                Value::Replies(vec![])
+            }
+            Replconf::GetAck => Value::Replies(vec![]),
+        }
+    }
+}
+
+#[cfg(test)]
+mod specs_for_parse_from {
+    use crate::command::executor::Command;
+    use crate::resp::Value;
+
+    use super::Replconf;
+
+    fn array(arguments: &[&str]) -> Value {
+        Value::Array(
+            arguments
+                .iter()
+                .map(|argument| Value::BulkString(argument.to_string()))
+                .collect(),
+        )
+    }
+
+    #[rstest::rstest]
+    #[case(&["REPLCONF", "listening-port", "6380"], Replconf::ListeningPort(6380))]
+    #[case(&["replconf", "capa", "eof", "capa", "psync2"], Replconf::Capabilities(vec!["eof".to_string(), "psync2".to_string()]))]
+    #[case(&["REPLCONF", "ACK", "31"], Replconf::Ack { offset: 31, fsynced: None })]
+    #[case(&["REPLCONF", "ACK", "31", "FACK", "14"], Replconf::Ack { offset: 31, fsynced: Some(14) })]
+    #[case(&["REPLCONF", "GETACK", "*"], Replconf::GetAck)]
+    fn sut_parses_replconf_options(#[case] arguments: &[&str], #[case] expected: Replconf) {
+        // Act
+        let actual = Replconf::parse_from(&array(arguments)).unwrap();
+
+        // Assert
+        assert_eq!(actual, expected);
+    }
+
+    #[rstest::rstest]
+    #[case(&["REPLCONF", "listening-port", "port"])]
+    #[case(&["REPLCONF", "capa", "eof", "psync2"])]
+    #[case(&["REPLCONF", "ACK", "31", "FACK"])]
+    #[case(&["REPLCONF", "unknown", "value"])]
+    fn sut_rejects_malformed_replconf(#[case] arguments: &[&str]) {
+        // Act
+        let actual = Replconf::parse_from(&array(arguments));
+
+        // Assert
+        assert!(actual.is_err());
+    }
+}
+
+#[cfg(test)]
+mod specs_for_execute {
+    use crate::command::executor::CommandExecutor;
+    use crate::command::executor::CommandExecutorContext;
+    use crate::command::executor::fixture::command_executor_context;
+    use crate::replication::ReplicaInfo;
+    use crate::resp::Value;
+
+    use super::Replconf;
+
+    #[rstest::rstest]
+    #[tokio::test]
+    async fn sut_registers_replica_with_its_listening_port(
+        #[from(command_executor_context)] context: CommandExecutorContext,
+    ) {
+        // Arrange
+        context.session.set_address("10.0.0.2");
+
+        // Act
+        let port = Replconf::ListeningPort(6380).execute(&context).await;
+        let capa = Replconf::Capabilities(vec!["psync2".to_string()])
+            .execute(&context)
+            .await;
+
+        // Assert
+        assert_eq!(port, Value::SimpleString("OK".to_string()));
+        assert_eq!(capa, Value::SimpleString("OK".to_string()));
+        let expected = vec![ReplicaInfo {
+            address: "10.0.0.2".to_string(),
+            port: Some(6380),
+            online: false,
+            offset: 0,
+        }];
+        assert_eq!(context.replication.replicas(), expected);
+    }
+
+    #[rstest::rstest]
+    #[tokio::test]
+    async fn sut_records_offset_acknowledged_by_replica(
+        #[from(command_executor_context)] context: CommandExecutorContext,
+    ) {
+        // Arrange
+        context.replication.attach(&context.session);
+
+        // Act
+        let actual = Replconf::Ack {
+            offset: 31,
+            fsynced: None,
+        }
+        .execute(&context)
+        .await;
+
+        // Assert
+        assert_eq!(actual, Value::Replies(vec![]));
+        assert_eq!(context.replication.replicas()[0].offset, 31);
+        assert_eq!(context.replication.acknowledged(31, false), 1);
+        assert_eq!(context.replication.acknowledged(31, true), 0);
+    }
+}
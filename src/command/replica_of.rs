@@ -0,0 +1,137 @@
+use crate::command::executor::Command;
+use crate::command::executor::CommandExecutor;
+use crate::command::executor::CommandExecutorContext;
+use crate::command::parser::extract_array;
+use crate::command::parser::extract_bulk_string;
+use crate::command::parser::validate_array_length;
+use crate::command::parser::validate_main_command;
+use crate::replication::FailoverState;
+use crate::resp::Value;
+
+/// REPLICAOF host port, or REPLICAOF NO ONE to go on as master. SLAVEOF is the same.
+#[derive(Debug, Default, PartialEq)]
+pub struct ReplicaOf {
+    master: Option<(String, u16)>,
+}
+
+impl Command for ReplicaOf {
+    fn parse_from(value: &Value) -> Result<Self, anyhow::Error> {
+        let array = extract_array(value)?;
+        validate_array_length(array, 3)?;
+        validate_main_command(array, "REPLICAOF").or(validate_main_command(array, "SLAVEOF"))?;
+        let host = extract_bulk_string(array, 1)?;
+        let port = extract_bulk_string(array, 2)?;
+        if host.eq_ignore_ascii_case("NO") && port.eq_ignore_ascii_case("ONE") {
+            return Ok(ReplicaOf { master: None });
+        }
+        let port = port
+            .parse()
+            .map_err(|_| anyhow::anyhow!("value is not an integer or out of range"))?;
+        Ok(ReplicaOf {
+            master: Some((host.to_string(), port)),
+        })
+    }
+}
+
+#[async_trait::async_trait]
+impl CommandExecutor for ReplicaOf {
+    async fn execute(&self, context: &CommandExecutorContext) -> Value {
+        if context.cluster.is_some() {
+            return Value::Error("ERR REPLICAOF not allowed in cluster mode.".to_string());
+        }
+        let replication = &context.replication;
+        if replication.failover() != FailoverState::None {
+            return Value::Error("ERR REPLICAOF not allowed while failing over.".to_string());
+        }
+        match &self.master {
+            Some((host, port)) => {
+                if !replication.replicate_from(format!("{host}:{port}")) {
+                    return Value::SimpleString(
+                        "OK Already connected to specified master".to_string(),
+                    );
+                }
+            }
+            None => replication.promote(),
+        }
+        Value::SimpleString("OK".to_string())
+    }
+}
+
+#[cfg(test)]
+mod specs_for_parse_from {
+    use crate::command::executor::Command;
+    use crate::resp::Value;
+
+    use super::ReplicaOf;
+
+    fn array(arguments: &[&str]) -> Value {
+        Value::Array(
+            arguments
+                .iter()
+                .map(|argument| Value::BulkString(argument.to_string()))
+                .collect(),
+        )
+    }
+
+    #[rstest::rstest]
+    #[case(&["REPLICAOF", "localhost", "6380"], Some(("localhost".to_string(), 6380)))]
+    #[case(&["slaveof", "localhost", "6380"], Some(("localhost".to_string(), 6380)))]
+    #[case(&["replicaof", "no", "one"], None)]
+    fn sut_parses_replicaof_command(
+        #[case] arguments: &[&str],
+        #[case] master: Option<(String, u16)>,
+    ) {
+        // Act
+        let actual = ReplicaOf::parse_from(&array(arguments)).unwrap();
+
+        // Assert
+        assert_eq!(actual, ReplicaOf { master });
+    }
+
+    #[rstest::rstest]
+    #[case(&["REPLICAOF", "localhost", "port"])]
+    #[case(&["REPLICAOF", "NO"])]
+    fn sut_rejects_malformed_replicaof(#[case] arguments: &[&str]) {
+        // Act
+        let actual = ReplicaOf::parse_from(&array(arguments));
+
+        // Assert
+        assert!(actual.is_err());
+    }
+}
+
+#[cfg(test)]
+mod specs_for_execute {
+    use crate::command::executor::CommandExecutor;
+    use crate::command::executor::CommandExecutorContext;
+    use crate::command::executor::fixture::command_executor_context;
+    use crate::resp::Value;
+
+    use super::ReplicaOf;
+
+    #[rstest::rstest]
+    #[tokio::test]
+    async fn sut_follows_master_then_promotes_under_a_new_replication_id(
+        #[from(command_executor_context)] context: CommandExecutorContext,
+    ) {
+        // Arrange
+        let id = context.replication.id();
+        let follow = ReplicaOf {
+            master: Some(("localhost".to_string(), 6380)),
+        };
+
+        // Act
+        let followed = follow.execute(&context).await;
+        let again = follow.execute(&context).await;
+        let promoted = ReplicaOf { master: None }.execute(&context).await;
+
+        // Assert
+        assert_eq!(followed, Value::SimpleString("OK".to_string()));
+        let expected = Value::SimpleString("OK Already connected to specified master".to_string());
+        assert_eq!(again, expected);
+        assert_eq!(promoted, Value::SimpleString("OK".to_string()));
+        assert!(!context.replication.is_replica());
+        assert_ne!(context.replication.id(), id);
+        assert_eq!(context.replication.second_id(), Some((id, 1)));
+    }
+}
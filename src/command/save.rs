@@ -0,0 +1,108 @@
+use std::path::Path;
+
+use crate::command::executor::Command;
+use crate::command::executor::CommandExecutor;
+use crate::command::executor::CommandExecutorContext;
+use crate::command::parser::extract_array;
+use crate::command::parser::validate_array_length;
+use crate::command::parser::validate_main_command;
+use crate::resp::Value;
+use crate::snapshot;
+
+const DEFAULT_PATH: &str = "dump.rdb";
+
+#[derive(Debug, Default, PartialEq)]
+pub struct Save;
+
+impl Command for Save {
+    fn parse_from(value: &Value) -> Result<Self, anyhow::Error> {
+        let array = extract_array(value)?;
+        validate_array_length(array, 1)?;
+        validate_main_command(array, "SAVE")?;
+        Ok(Save)
+    }
+}
+
+#[async_trait::async_trait]
+impl CommandExecutor for Save {
+    async fn execute(&self, context: &CommandExecutorContext) -> Value {
+        let path = match &context.config.rdb {
+            Some(rdb) => rdb.path(),
+            None => DEFAULT_PATH.to_string(),
+        };
+        let saved = snapshot::save(
+            Path::new(&path),
+            &context.databases,
+            &context.scripts.libraries,
+            context.clock.as_ref(),
+        )
+        .await;
+        match saved {
+            Ok(()) => {
+                context.stats.saved();
+                Value::SimpleString("OK".to_string())
+            }
+            Err(e) => Value::Error(format!("ERR {e}")),
+        }
+    }
+}
+
+#[cfg(test)]
+mod specs_for_parse_from {
+    use crate::command::executor::Command;
+    use crate::resp::Value;
+
+    use super::Save;
+
+    #[rstest::rstest]
+    #[case("SAVE")]
+    #[case("save")]
+    fn sut_parses_save_command_with_case_insensitive(#[case] save: &str) {
+        // Arrange
+        let value = Value::Array(vec![Value::BulkString(save.to_string())]);
+
+        // Act
+        let actual = Save::parse_from(&value).unwrap();
+
+        // Assert
+        assert_eq!(actual, Save);
+    }
+}
+
+#[cfg(test)]
+mod specs_for_execute {
+    use std::sync::Arc;
+
+    use crate::clock::SystemClock;
+    use crate::command::executor::CommandExecutor;
+    use crate::command::executor::fixture::command_executor_context;
+    use crate::config::Config;
+    use crate::config::RdbConfig;
+    use crate::repository::InMemoryRepository;
+    use crate::resp::Value;
+
+    use super::Save;
+
+    #[tokio::test]
+    async fn sut_writes_rdb_file_to_configured_path() {
+        // Arrange
+        let directory = tempfile::tempdir().unwrap();
+        let config = Config {
+            rdb: Some(RdbConfig {
+                directory: directory.path().to_string_lossy().to_string(),
+                filename: "saved.rdb".to_string(),
+            }),
+            ..Default::default()
+        };
+        let context =
+            command_executor_context(InMemoryRepository::new(), config, Arc::new(SystemClock));
+
+        // Act
+        let actual = Save.execute(&context).await;
+
+        // Assert
+        assert_eq!(actual, Value::SimpleString("OK".to_string()));
+        let bytes = std::fs::read(directory.path().join("saved.rdb")).unwrap();
+        assert!(bytes.starts_with(b"REDIS0011"));
+    }
+}
@@ -0,0 +1,251 @@
+use crate::command::executor::Command;
+use crate::command::executor::CommandExecutor;
+use crate::command::executor::CommandExecutorContext;
+use crate::command::parser::extract_array;
+use crate::command::parser::extract_bulk_string;
+use crate::command::parser::validate_main_command;
+use crate::command::parser::validate_min_array_length;
+use crate::glob;
+use crate::resp::Value;
+
+const DEFAULT_COUNT: usize = 10;
+
+#[derive(Debug, PartialEq)]
+pub struct Scan {
+    cursor: u64,
+    options: ScanOptions,
+}
+
+/// The MATCH, COUNT and TYPE options shared by SCAN and its per-collection variants.
+#[derive(Debug, PartialEq)]
+pub struct ScanOptions {
+    pub pattern: Option<String>,
+    pub count: usize,
+    pub type_name: Option<String>,
+}
+
+impl ScanOptions {
+    /// Parses the options from `start` onwards, where TYPE is only accepted by SCAN itself.
+    pub fn parse(array: &[Value], start: usize, allow_type: bool) -> Result<Self, anyhow::Error> {
+        let mut options = ScanOptions {
+            pattern: None,
+            count: DEFAULT_COUNT,
+            type_name: None,
+        };
+        let mut index = start;
+        while index < array.len() {
+            let option = extract_bulk_string(array, index)?.to_uppercase();
+            let argument = extract_bulk_string(array, index + 1)
+                .map_err(|_| anyhow::anyhow!("syntax error"))?;
+            match option.as_str() {
+                "MATCH" => options.pattern = Some(argument.to_string()),
+                "COUNT" => {
+                    options.count = argument
+                        .parse()
+                        .ok()
+                        .filter(|count| *count > 0)
+                        .ok_or_else(|| anyhow::anyhow!("syntax error"))?;
+                }
+                "TYPE" if allow_type => options.type_name = Some(argument.to_lowercase()),
+                _ => return Err(anyhow::anyhow!("syntax error")),
+            }
+            index += 2;
+        }
+        Ok(options)
+    }
+
+    pub fn matches(&self, text: &str) -> bool {
+        self.pattern
+            .as_deref()
+            .is_none_or(|pattern| pattern == "*" || glob::matches(pattern, text))
+    }
+}
+
+pub fn parse_cursor(array: &[Value], index: usize) -> Result<u64, anyhow::Error> {
+    extract_bulk_string(array, index)?
+        .parse()
+        .map_err(|_| anyhow::anyhow!("invalid cursor"))
+}
+
+/// Builds the two element reply of the SCAN family: the next cursor and the page of elements.
+pub fn scan_reply(cursor: u64, elements: Vec<String>) -> Value {
+    Value::Array(vec![
+        Value::BulkString(cursor.to_string()),
+        Value::Array(elements.into_iter().map(Value::BulkString).collect()),
+    ])
+}
+
+impl Command for Scan {
+    fn parse_from(value: &Value) -> Result<Self, anyhow::Error> {
+        let array = extract_array(value)?;
+        validate_min_array_length(array, 2)?;
+        validate_main_command(array, "SCAN")?;
+        Ok(Scan {
+            cursor: parse_cursor(array, 1)?,
+            options: ScanOptions::parse(array, 2, true)?,
+        })
+    }
+}
+
+#[async_trait::async_trait]
+impl CommandExecutor for Scan {
+    async fn execute(&self, context: &CommandExecutorContext) -> Value {
+        let (cursor, keys) = context
+            .repository()
+            .scan(
+                self.cursor,
+                self.options.count,
+                self.options.type_name.as_deref(),
+            )
+            .await;
+        let keys = keys
+            .into_iter()
+            .filter(|key| self.options.matches(key))
+            .collect();
+        scan_reply(cursor, keys)
+    }
+}
+
+#[cfg(test)]
+mod specs_for_parse_from {
+    use crate::command::executor::Command;
+    use crate::resp::Value;
+
+    use super::Scan;
+    use super::ScanOptions;
+
+    fn array(arguments: &[&str]) -> Value {
+        Value::Array(
+            arguments
+                .iter()
+                .map(|argument| Value::BulkString(argument.to_string()))
+                .collect(),
+        )
+    }
+
+    #[test]
+    fn sut_parses_scan_command_with_options_correctly() {
+        // Arrange
+        let value = array(&[
+            "scan", "42", "MATCH", "user:*", "count", "100", "TYPE", "ZSet",
+        ]);
+
+        // Act
+        let actual = Scan::parse_from(&value).unwrap();
+
+        // Assert
+        let expected = Scan {
+            cursor: 42,
+            options: ScanOptions {
+                pattern: Some("user:*".to_string()),
+                count: 100,
+                type_name: Some("zset".to_string()),
+            },
+        };
+        assert_eq!(actual, expected);
+    }
+
+    #[rstest::rstest]
+    #[case(&["SCAN", "-1"])]
+    #[case(&["SCAN", "0", "COUNT", "0"])]
+    #[case(&["SCAN", "0", "MATCH"])]
+    #[case(&["SCAN", "0", "LIMIT", "10"])]
+    fn sut_raises_error_if_arguments_are_invalid(#[case] arguments: &[&str]) {
+        // Act
+        let actual = Scan::parse_from(&array(arguments));
+
+        // Assert
+        assert!(actual.is_err());
+    }
+}
+
+#[cfg(test)]
+mod specs_for_execute {
+    use std::collections::HashSet;
+
+    use crate::command::executor::CommandExecutor;
+    use crate::command::executor::CommandExecutorContext;
+    use crate::command::executor::fixture::command_executor_context;
+    use crate::repository::AddCondition;
+    use crate::repository::Data;
+    use crate::repository::Entry;
+    use crate::repository::InMemoryRepository;
+    use crate::resp::Value;
+
+    use super::Scan;
+    use super::ScanOptions;
+
+    fn command(cursor: u64, pattern: Option<&str>, type_name: Option<&str>) -> Scan {
+        Scan {
+            cursor,
+            options: ScanOptions {
+                pattern: pattern.map(str::to_string),
+                count: 3,
+                type_name: type_name.map(str::to_string),
+            },
+        }
+    }
+
+    async fn scan_all(context: &CommandExecutorContext, command: &mut Scan) -> HashSet<String> {
+        let mut keys = HashSet::new();
+        loop {
+            let Value::Array(reply) = command.execute(context).await else {
+                panic!("expected array reply");
+            };
+            let [Value::BulkString(cursor), Value::Array(page)] = reply.as_slice() else {
+                panic!("expected cursor and page");
+            };
+            keys.extend(page.iter().map(|key| match key {
+                Value::BulkString(key) => key.clone(),
+                _ => panic!("expected bulk string"),
+            }));
+            command.cursor = cursor.parse().unwrap();
+            if command.cursor == 0 {
+                return keys;
+            }
+        }
+    }
+
+    #[rstest::rstest]
+    #[case(None, None, &["user:1", "user:2", "session:1", "geo"])]
+    #[case(Some("user:*"), None, &["user:1", "user:2"])]
+    #[case(None, Some("zset"), &["geo"])]
+    #[tokio::test]
+    async fn sut_iterates_over_matching_keys(
+        #[case] pattern: Option<&str>,
+        #[case] type_name: Option<&str>,
+        #[case] expected: &[&str],
+        #[from(command_executor_context)]
+        #[with(InMemoryRepository::new())]
+        context: CommandExecutorContext,
+    ) {
+        // Arrange
+        for key in ["user:1", "user:2", "session:1"] {
+            context
+                .repository()
+                .set(Entry {
+                    key: key.into(),
+                    value: Data::String("value".to_string()),
+                    expiry: None,
+                })
+                .await;
+        }
+        context
+            .repository()
+            .zadd(
+                "geo",
+                vec![("Palermo".to_string(), 1.0)],
+                AddCondition::Always,
+            )
+            .await
+            .unwrap();
+        let mut command = command(0, pattern, type_name);
+
+        // Act
+        let actual = scan_all(&context, &mut command).await;
+
+        // Assert
+        let expected = expected.iter().map(|key| key.to_string()).collect();
+        assert_eq!(actual, expected);
+    }
+}
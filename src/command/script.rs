@@ -0,0 +1,151 @@
+use crate::command::executor::Command;
+use crate::command::executor::CommandExecutor;
+use crate::command::executor::CommandExecutorContext;
+use crate::command::parser::extract_array;
+use crate::command::parser::extract_bulk_string;
+use crate::command::parser::extract_bulk_strings;
+use crate::command::parser::validate_main_command;
+use crate::command::parser::validate_min_array_length;
+use crate::resp::Value;
+
+/// SCRIPT LOAD, EXISTS and FLUSH. SCRIPT KILL is a command on its own as it must never wait
+/// for the running script.
+#[derive(Debug, PartialEq)]
+pub enum Script {
+    Load(String),
+    Exists(Vec<String>),
+    Flush,
+}
+
+impl Command for Script {
+    fn parse_from(value: &Value) -> Result<Self, anyhow::Error> {
+        let array = extract_array(value)?;
+        validate_min_array_length(array, 2)?;
+        validate_main_command(array, "SCRIPT")?;
+        match extract_bulk_string(array, 1)?.to_uppercase().as_str() {
+            "LOAD" if array.len() == 3 => {
+                Ok(Script::Load(extract_bulk_string(array, 2)?.to_string()))
+            }
+            "EXISTS" if array.len() >= 3 => Ok(Script::Exists(extract_bulk_strings(array, 2)?)),
+            "FLUSH" if array.len() == 2 => Ok(Script::Flush),
+            "FLUSH" if array.len() == 3 => {
+                match extract_bulk_string(array, 2)?.to_uppercase().as_str() {
+                    "ASYNC" | "SYNC" => Ok(Script::Flush),
+                    _ => Err(anyhow::anyhow!("syntax error")),
+                }
+            }
+            _ => Err(anyhow::anyhow!("expected SCRIPT LOAD, EXISTS or FLUSH")),
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl CommandExecutor for Script {
+    async fn execute(&self, context: &CommandExecutorContext) -> Value {
+        match self {
+            Script::Load(body) => Value::BulkString(context.scripts.load(body)),
+            Script::Exists(shas) => Value::Array(
+                shas.iter()
+                    .map(|sha| Value::Integer(context.scripts.get(sha).is_some() as i64))
+                    .collect(),
+            ),
+            Script::Flush => {
+                context.scripts.flush();
+                Value::SimpleString("OK".to_string())
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod specs_for_parse_from {
+    use crate::command::executor::Command;
+    use crate::resp::Value;
+
+    use super::Script;
+
+    fn array(arguments: &[&str]) -> Value {
+        Value::Array(
+            arguments
+                .iter()
+                .map(|argument| Value::BulkString(argument.to_string()))
+                .collect(),
+        )
+    }
+
+    #[rstest::rstest]
+    #[case(&["SCRIPT", "LOAD", "return 1"], Script::Load("return 1".to_string()))]
+    #[case(&["script", "exists", "a", "b"], Script::Exists(vec!["a".to_string(), "b".to_string()]))]
+    #[case(&["Script", "Flush"], Script::Flush)]
+    #[case(&["SCRIPT", "FLUSH", "ASYNC"], Script::Flush)]
+    fn sut_parses_script_subcommands_correctly(
+        #[case] arguments: &[&str],
+        #[case] expected: Script,
+    ) {
+        // Act
+        let actual = Script::parse_from(&array(arguments)).unwrap();
+
+        // Assert
+        assert_eq!(actual, expected);
+    }
+
+    #[rstest::rstest]
+    #[case(&["SCRIPT", "LOAD"])]
+    #[case(&["SCRIPT", "EXISTS"])]
+    #[case(&["SCRIPT", "FLUSH", "LATER"])]
+    #[case(&["SCRIPT", "KILL"])]
+    fn sut_raises_error_if_subcommand_is_not_supported(#[case] arguments: &[&str]) {
+        // Act
+        let actual = Script::parse_from(&array(arguments));
+
+        // Assert
+        assert!(actual.is_err());
+    }
+}
+
+#[cfg(test)]
+mod specs_for_execute {
+    use crate::command::executor::CommandExecutor;
+    use crate::command::executor::CommandExecutorContext;
+    use crate::command::executor::fixture::command_executor_context;
+    use crate::resp::Value;
+
+    use super::Script;
+
+    #[rstest::rstest]
+    #[tokio::test]
+    async fn sut_loads_flushes_and_reports_cached_scripts(
+        #[from(command_executor_context)] context: CommandExecutorContext,
+    ) {
+        // Arrange
+        let sha = Script::Load("return 1".to_string()).execute(&context).await;
+        let Value::BulkString(sha) = sha else {
+            panic!("expected a digest but got {sha:?}");
+        };
+        let exists = Script::Exists(vec![sha, "missing".to_string()]);
+
+        // Act
+        let before_flush = exists.execute(&context).await;
+        Script::Flush.execute(&context).await;
+        let after_flush = exists.execute(&context).await;
+
+        // Assert
+        let expected = Value::Array(vec![Value::Integer(1), Value::Integer(0)]);
+        assert_eq!(before_flush, expected);
+        let expected = Value::Array(vec![Value::Integer(0), Value::Integer(0)]);
+        assert_eq!(after_flush, expected);
+    }
+
+    #[rstest::rstest]
+    #[tokio::test]
+    async fn sut_responds_sha1_digest_of_loaded_script(
+        #[from(command_executor_context)] context: CommandExecutorContext,
+    ) {
+        // Act
+        let actual = Script::Load("return 1".to_string()).execute(&context).await;
+
+        // Assert
+        let expected = "e0e1f9fabfc9d4800c877a703b823ac0578ff8db".to_string();
+        assert_eq!(actual, Value::BulkString(expected));
+    }
+}
@@ -0,0 +1,201 @@
+use crate::command::executor::Command;
+use crate::command::executor::CommandExecutor;
+use crate::command::executor::CommandExecutorContext;
+use crate::command::parser::extract_array;
+use crate::command::parser::validate_array_length;
+use crate::command::parser::validate_main_command;
+use crate::command::parser::validate_sub_command;
+use crate::resp::Value;
+
+/// SCRIPT KILL and FUNCTION KILL, each only stopping a script or a function respectively.
+#[derive(Debug, Default, PartialEq)]
+pub struct ScriptKill {
+    function: bool,
+}
+
+impl Command for ScriptKill {
+    fn parse_from(value: &Value) -> Result<Self, anyhow::Error> {
+        let array = extract_array(value)?;
+        validate_array_length(array, 2)?;
+        let function = validate_main_command(array, "SCRIPT").is_err();
+        if function {
+            validate_main_command(array, "FUNCTION")?;
+        }
+        validate_sub_command(array, "KILL")?;
+        Ok(ScriptKill { function })
+    }
+}
+
+#[async_trait::async_trait]
+impl CommandExecutor for ScriptKill {
+    async fn execute(&self, context: &CommandExecutorContext) -> Value {
+        match context.scripts.kill(self.function) {
+            Ok(()) => Value::SimpleString("OK".to_string()),
+            Err(e) => Value::Error(e.to_string()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod specs_for_parse_from {
+    use crate::command::executor::Command;
+    use crate::resp::Value;
+
+    use super::ScriptKill;
+
+    #[rstest::rstest]
+    #[case("SCRIPT", "KILL", false)]
+    #[case("script", "kill", false)]
+    #[case("FUNCTION", "KILL", true)]
+    fn sut_parses_script_kill_command_with_case_insensitive(
+        #[case] script: &str,
+        #[case] kill: &str,
+        #[case] function: bool,
+    ) {
+        // Arrange
+        let value = Value::Array(vec![
+            Value::BulkString(script.to_string()),
+            Value::BulkString(kill.to_string()),
+        ]);
+
+        // Act
+        let actual = ScriptKill::parse_from(&value).unwrap();
+
+        // Assert
+        assert_eq!(actual, ScriptKill { function });
+    }
+}
+
+#[cfg(test)]
+mod specs_for_execute {
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    use tokio::task::JoinHandle;
+
+    use crate::clock::SystemClock;
+    use crate::command::executor::CommandExecutor;
+    use crate::command::executor::CommandExecutorContext;
+    use crate::command::executor::execute;
+    use crate::command::executor::fixture::command_executor_context;
+    use crate::command::executor::parse;
+    use crate::config::Config;
+    use crate::repository::Data;
+    use crate::repository::Entry;
+    use crate::repository::InMemoryRepository;
+    use crate::resp::Value;
+
+    use super::ScriptKill;
+
+    const NOTBUSY: &str = "NOTBUSY No scripts in execution right now.";
+
+    fn array(arguments: &[&str]) -> Value {
+        Value::Array(
+            arguments
+                .iter()
+                .map(|argument| Value::BulkString(argument.to_string()))
+                .collect(),
+        )
+    }
+
+    fn context() -> CommandExecutorContext {
+        let mut config = Config::default();
+        config.server.busy_reply_threshold = 10;
+        command_executor_context(InMemoryRepository::new(), config, Arc::new(SystemClock))
+    }
+
+    /// Runs the command as another client, giving it time to start.
+    async fn start(context: &CommandExecutorContext, arguments: &[&str]) -> JoinHandle<Value> {
+        let request = array(arguments);
+        let running = tokio::spawn({
+            let context = context.new_session();
+            async move { execute(parse(&request).unwrap(), &request, &context).await }
+        });
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        running
+    }
+
+    #[rstest::rstest]
+    #[tokio::test]
+    async fn sut_responds_error_if_no_script_is_running(
+        #[from(command_executor_context)] context: CommandExecutorContext,
+    ) {
+        // Act
+        let actual = ScriptKill::default().execute(&context).await;
+
+        // Assert
+        assert_eq!(actual, Value::Error(NOTBUSY.to_string()));
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn sut_stops_script_running_past_busy_reply_threshold() {
+        // Arrange
+        let context = context();
+        let running = start(&context, &["EVAL", "while true do end", "0"]).await;
+        let get = array(&["GET", "foo"]);
+        let busy = execute(parse(&get).unwrap(), &get, &context).await;
+
+        // Act
+        let function_kill = ScriptKill { function: true }.execute(&context).await;
+        let actual = ScriptKill::default().execute(&context).await;
+
+        // Assert
+        assert_eq!(function_kill, Value::Error(NOTBUSY.to_string()));
+        assert_eq!(actual, Value::SimpleString("OK".to_string()));
+        assert!(matches!(busy, Value::Error(e) if e.starts_with("BUSY")));
+        let killed = Value::Error("ERR Script killed by user with SCRIPT KILL...".to_string());
+        assert_eq!(running.await.unwrap(), killed);
+        assert_eq!(
+            execute(parse(&get).unwrap(), &get, &context).await,
+            Value::Null
+        );
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn sut_refuses_to_stop_script_which_already_wrote() {
+        // Arrange
+        let context = context();
+        let script = "redis.call('SET', KEYS[1], 'bar') \
+            while not redis.call('GET', KEYS[2]) do end \
+            return 'done'";
+        let running = start(&context, &["EVAL", script, "2", "foo", "stop"]).await;
+
+        // Act
+        let actual = ScriptKill::default().execute(&context).await;
+
+        // Assert
+        assert!(matches!(&actual, Value::Error(e) if e.starts_with("UNKILLABLE")));
+        context
+            .repository()
+            .set(Entry {
+                key: "stop".into(),
+                value: Data::String("1".to_string()),
+                expiry: None,
+            })
+            .await;
+        assert_eq!(
+            running.await.unwrap(),
+            Value::BulkString("done".to_string())
+        );
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn sut_only_stops_programs_of_its_own_kind() {
+        // Arrange
+        let context = context();
+        let library = "#!lua name=spin
+            redis.register_function('spin', function() while true do end end)";
+        let load = array(&["FUNCTION", "LOAD", library]);
+        execute(parse(&load).unwrap(), &load, &context).await;
+        let running = start(&context, &["FCALL", "spin", "0"]).await;
+
+        // Act
+        let script_kill = ScriptKill::default().execute(&context).await;
+        let function_kill = ScriptKill { function: true }.execute(&context).await;
+
+        // Assert
+        assert_eq!(script_kill, Value::Error(NOTBUSY.to_string()));
+        assert_eq!(function_kill, Value::SimpleString("OK".to_string()));
+        assert!(matches!(running.await.unwrap(), Value::Error(e) if e.contains("killed")));
+    }
+}
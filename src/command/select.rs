@@ -0,0 +1,132 @@
+use crate::command::executor::Command;
+use crate::command::executor::CommandExecutor;
+use crate::command::executor::CommandExecutorContext;
+use crate::command::parser::extract_array;
+use crate::command::parser::extract_bulk_string;
+use crate::command::parser::validate_array_length;
+use crate::command::parser::validate_main_command;
+use crate::resp::Value;
+
+#[derive(Debug, Default, PartialEq)]
+pub struct Select {
+    database: i64,
+}
+
+/// Resolves a database index given by a client, replying the error Redis uses if it is out of
+/// range.
+pub fn database_index(context: &CommandExecutorContext, index: i64) -> Result<usize, Value> {
+    // A cluster node only has the first database.
+    let len = match context.cluster {
+        Some(_) => 1,
+        None => context.databases.len(),
+    };
+    usize::try_from(index)
+        .ok()
+        .filter(|index| *index < len)
+        .ok_or_else(|| Value::Error("ERR DB index is out of range".to_string()))
+}
+
+impl Command for Select {
+    fn parse_from(value: &Value) -> Result<Self, anyhow::Error> {
+        let array = extract_array(value)?;
+        validate_array_length(array, 2)?;
+        validate_main_command(array, "SELECT")?;
+        let database = extract_bulk_string(array, 1)?.parse()?;
+        Ok(Select { database })
+    }
+}
+
+#[async_trait::async_trait]
+impl CommandExecutor for Select {
+    async fn execute(&self, context: &CommandExecutorContext) -> Value {
+        if context.cluster.is_some() && self.database != 0 {
+            return Value::Error("ERR SELECT is not allowed in cluster mode".to_string());
+        }
+        match database_index(context, self.database) {
+            Ok(database) => {
+                context.session.select(database);
+                Value::SimpleString("OK".to_string())
+            }
+            Err(e) => e,
+        }
+    }
+}
+
+#[cfg(test)]
+mod specs_for_parse_from {
+    use crate::command::executor::Command;
+    use crate::resp::Value;
+
+    use super::Select;
+
+    #[rstest::rstest]
+    #[case("SELECT")]
+    #[case("select")]
+    #[case("SeLeCt")]
+    fn sut_parses_select_command_with_case_insensitive(#[case] select: &str) {
+        // Arrange
+        let value = Value::Array(vec![
+            Value::BulkString(select.to_string()),
+            Value::BulkString("3".to_string()),
+        ]);
+
+        // Act
+        let actual = Select::parse_from(&value).unwrap();
+
+        // Assert
+        assert_eq!(actual, Select { database: 3 });
+    }
+}
+
+#[cfg(test)]
+mod specs_for_execute {
+    use crate::command::executor::CommandExecutor;
+    use crate::command::executor::fixture::command_executor_context_with_databases;
+    use crate::repository::Data;
+    use crate::repository::Entry;
+    use crate::resp::Value;
+
+    use super::Select;
+
+    #[tokio::test]
+    async fn sut_switches_repository_of_session_only() {
+        // Arrange
+        let context = command_executor_context_with_databases(16);
+        let other = context.new_session();
+        let command = Select { database: 15 };
+
+        // Act
+        let actual = command.execute(&context).await;
+
+        // Assert
+        assert_eq!(actual, Value::SimpleString("OK".to_string()));
+        context
+            .repository()
+            .set(Entry {
+                key: "foo".into(),
+                value: Data::String("bar".to_string()),
+                expiry: None,
+            })
+            .await;
+        assert!(context.databases.get(15).unwrap().exists("foo").await);
+        assert!(!other.repository().exists("foo").await);
+    }
+
+    #[rstest::rstest]
+    #[case(16)]
+    #[case(-1)]
+    #[tokio::test]
+    async fn sut_responds_error_if_database_is_out_of_range(#[case] database: i64) {
+        // Arrange
+        let context = command_executor_context_with_databases(16);
+        let command = Select { database };
+
+        // Act
+        let actual = command.execute(&context).await;
+
+        // Assert
+        let expected = Value::Error("ERR DB index is out of range".to_string());
+        assert_eq!(actual, expected);
+        assert_eq!(context.session.database(), 0);
+    }
+}
@@ -0,0 +1,366 @@
+use crate::command::executor::Command;
+use crate::command::executor::CommandExecutor;
+use crate::command::executor::CommandExecutorContext;
+use crate::command::parser::extract_array;
+use crate::command::parser::extract_bulk_string;
+use crate::command::parser::validate_main_command;
+use crate::command::parser::validate_min_array_length;
+use crate::resp::Value;
+use crate::sentinel::MasterInfo;
+use crate::sentinel::PeerInfo;
+use crate::sentinel::ReplicaInfo;
+use crate::sentinel::split_address;
+
+/// SENTINEL, asked by clients for the master to connect to and by other sentinels whether a
+/// master is down.
+#[derive(Debug, PartialEq)]
+pub enum SentinelCommand {
+    GetMasterAddrByName(String),
+    /// Asks whether the master at `address` is down, and to vote for `candidate` to fail it
+    /// over in `epoch` if given.
+    IsMasterDownByAddr {
+        address: String,
+        epoch: u64,
+        candidate: Option<String>,
+    },
+    Masters,
+    Master(String),
+    Replicas(String),
+    Sentinels(String),
+    MyId,
+}
+
+impl Command for SentinelCommand {
+    fn parse_from(value: &Value) -> Result<Self, anyhow::Error> {
+        let array = extract_array(value)?;
+        validate_min_array_length(array, 2)?;
+        validate_main_command(array, "SENTINEL")?;
+        let subcommand = extract_bulk_string(array, 1)?.to_uppercase();
+        let name = || Ok::<_, anyhow::Error>(extract_bulk_string(array, 2)?.to_string());
+        match (subcommand.as_str(), array.len()) {
+            ("GET-MASTER-ADDR-BY-NAME", 3) => Ok(SentinelCommand::GetMasterAddrByName(name()?)),
+            ("IS-MASTER-DOWN-BY-ADDR", 6) => {
+                let ip = extract_bulk_string(array, 2)?;
+                let port = extract_bulk_string(array, 3)?;
+                let candidate = extract_bulk_string(array, 5)?;
+                Ok(SentinelCommand::IsMasterDownByAddr {
+                    address: format!("{ip}:{port}"),
+                    epoch: extract_bulk_string(array, 4)?.parse()?,
+                    candidate: (candidate != "*").then(|| candidate.to_string()),
+                })
+            }
+            ("MASTERS", 2) => Ok(SentinelCommand::Masters),
+            ("MASTER", 3) => Ok(SentinelCommand::Master(name()?)),
+            ("REPLICAS" | "SLAVES", 3) => Ok(SentinelCommand::Replicas(name()?)),
+            ("SENTINELS", 3) => Ok(SentinelCommand::Sentinels(name()?)),
+            ("MYID", 2) => Ok(SentinelCommand::MyId),
+            _ => Err(anyhow::anyhow!(
+                "Unknown sentinel subcommand '{subcommand}'"
+            )),
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl CommandExecutor for SentinelCommand {
+    async fn execute(&self, context: &CommandExecutorContext) -> Value {
+        let Some(sentinel) = &context.sentinel else {
+            return Value::Error("ERR unknown command 'SENTINEL'".to_string());
+        };
+        let unknown = || Value::Error("ERR No such master with that name".to_string());
+        match self {
+            SentinelCommand::GetMasterAddrByName(name) => match sentinel.master_address(name) {
+                Some(address) => {
+                    let (ip, port) = split_address(&address);
+                    Value::Array(vec![
+                        Value::BulkString(ip.to_string()),
+                        Value::BulkString(port.to_string()),
+                    ])
+                }
+                None => Value::NullArray,
+            },
+            SentinelCommand::IsMasterDownByAddr {
+                address,
+                epoch,
+                candidate,
+            } => {
+                let (down, vote) =
+                    sentinel.is_master_down_by_addr(address, *epoch, candidate.as_deref());
+                let (leader, voted) = vote.unwrap_or(("*".to_string(), 0));
+                Value::Array(vec![
+                    Value::Integer(down as i64),
+                    Value::BulkString(leader),
+                    Value::Integer(voted as i64),
+                ])
+            }
+            SentinelCommand::Masters => {
+                Value::Array(sentinel.masters().iter().map(master_fields).collect())
+            }
+            SentinelCommand::Master(name) => sentinel
+                .master(name)
+                .map_or_else(unknown, |master| master_fields(&master)),
+            SentinelCommand::Replicas(name) => sentinel.replicas(name).map_or_else(unknown, |r| {
+                Value::Array(r.iter().map(replica_fields).collect())
+            }),
+            SentinelCommand::Sentinels(name) => sentinel.peers(name).map_or_else(unknown, |p| {
+                Value::Array(p.iter().map(peer_fields).collect())
+            }),
+            SentinelCommand::MyId => Value::BulkString(sentinel.id().to_string()),
+        }
+    }
+}
+
+fn fields(fields: Vec<(&str, String)>) -> Value {
+    Value::Array(
+        fields
+            .into_iter()
+            .flat_map(|(field, value)| {
+                [
+                    Value::BulkString(field.to_string()),
+                    Value::BulkString(value),
+                ]
+            })
+            .collect(),
+    )
+}
+
+/// The flags set, in the order Redis lists them.
+fn flags(flags: &[(&str, bool)]) -> String {
+    flags
+        .iter()
+        .filter(|(_, set)| *set)
+        .map(|(flag, _)| *flag)
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+fn master_fields(master: &MasterInfo) -> Value {
+    let (ip, port) = split_address(&master.address);
+    let flags = flags(&[
+        ("s_down", master.subjectively_down),
+        ("o_down", master.objectively_down),
+        ("master", true),
+        ("failover_in_progress", master.failover_in_progress),
+    ]);
+    fields(vec![
+        ("name", master.name.clone()),
+        ("ip", ip.to_string()),
+        ("port", port.to_string()),
+        ("flags", flags),
+        ("config-epoch", master.config_epoch.to_string()),
+        ("num-slaves", master.replicas.to_string()),
+        ("num-other-sentinels", master.peers.to_string()),
+        ("quorum", master.quorum.to_string()),
+    ])
+}
+
+fn replica_fields(replica: &ReplicaInfo) -> Value {
+    let (ip, port) = split_address(&replica.address);
+    fields(vec![
+        ("name", replica.address.clone()),
+        ("ip", ip.to_string()),
+        ("port", port.to_string()),
+        ("flags", flags(&[("s_down", replica.down), ("slave", true)])),
+        ("slave-repl-offset", replica.offset.to_string()),
+    ])
+}
+
+fn peer_fields(peer: &PeerInfo) -> Value {
+    let (ip, port) = split_address(&peer.address);
+    fields(vec![
+        ("name", peer.id.clone()),
+        ("ip", ip.to_string()),
+        ("port", port.to_string()),
+        ("runid", peer.id.clone()),
+        ("flags", "sentinel".to_string()),
+    ])
+}
+
+#[cfg(test)]
+mod specs_for_parse_from {
+    use crate::command::executor::Command;
+    use crate::resp::Value;
+
+    use super::SentinelCommand;
+
+    fn array(arguments: &[&str]) -> Value {
+        Value::Array(
+            arguments
+                .iter()
+                .map(|argument| Value::BulkString(argument.to_string()))
+                .collect(),
+        )
+    }
+
+    #[rstest::rstest]
+    #[case(&["SENTINEL", "get-master-addr-by-name", "mymaster"],
+        SentinelCommand::GetMasterAddrByName("mymaster".to_string()))]
+    #[case(&["sentinel", "IS-MASTER-DOWN-BY-ADDR", "127.0.0.1", "6379", "3", "*"],
+        SentinelCommand::IsMasterDownByAddr { address: "127.0.0.1:6379".to_string(), epoch: 3, candidate: None })]
+    #[case(&["SENTINEL", "IS-MASTER-DOWN-BY-ADDR", "127.0.0.1", "6379", "4", "abc"],
+        SentinelCommand::IsMasterDownByAddr {
+            address: "127.0.0.1:6379".to_string(),
+            epoch: 4,
+            candidate: Some("abc".to_string()),
+        })]
+    #[case(&["SENTINEL", "MASTERS"], SentinelCommand::Masters)]
+    #[case(&["SENTINEL", "master", "mymaster"], SentinelCommand::Master("mymaster".to_string()))]
+    #[case(&["SENTINEL", "SLAVES", "mymaster"], SentinelCommand::Replicas("mymaster".to_string()))]
+    #[case(&["SENTINEL", "SENTINELS", "mymaster"], SentinelCommand::Sentinels("mymaster".to_string()))]
+    #[case(&["SENTINEL", "MYID"], SentinelCommand::MyId)]
+    fn sut_parses_sentinel_subcommands(
+        #[case] arguments: &[&str],
+        #[case] expected: SentinelCommand,
+    ) {
+        // Act
+        let actual = SentinelCommand::parse_from(&array(arguments)).unwrap();
+
+        // Assert
+        assert_eq!(actual, expected);
+    }
+
+    #[rstest::rstest]
+    #[case(&["SENTINEL"])]
+    #[case(&["SENTINEL", "GET-MASTER-ADDR-BY-NAME"])]
+    #[case(&["SENTINEL", "IS-MASTER-DOWN-BY-ADDR", "127.0.0.1", "6379", "epoch", "*"])]
+    #[case(&["SENTINEL", "FLUSHCONFIG", "now"])]
+    fn sut_rejects_malformed_sentinel_commands(#[case] arguments: &[&str]) {
+        // Act
+        let actual = SentinelCommand::parse_from(&array(arguments));
+
+        // Assert
+        assert!(actual.is_err());
+    }
+}
+
+#[cfg(test)]
+mod specs_for_execute {
+    use std::sync::Arc;
+
+    use crate::clock::SystemClock;
+    use crate::command::executor::CommandExecutor;
+    use crate::command::executor::CommandExecutorContext;
+    use crate::command::executor::fixture::command_executor_context;
+    use crate::config::Config;
+    use crate::config::Monitor;
+    use crate::config::SentinelConfig;
+    use crate::repository::fixture::DummyRepository;
+    use crate::resp::Value;
+
+    use super::SentinelCommand;
+
+    fn sentinel() -> CommandExecutorContext {
+        let config = Config {
+            sentinel: Some(SentinelConfig {
+                masters: vec![Monitor {
+                    name: "mymaster".to_string(),
+                    address: "127.0.0.1:6379".to_string(),
+                    quorum: 2,
+                }],
+                ..SentinelConfig::default()
+            }),
+            ..Config::default()
+        };
+        command_executor_context(DummyRepository, config, Arc::new(SystemClock))
+    }
+
+    fn bulk(value: &str) -> Value {
+        Value::BulkString(value.to_string())
+    }
+
+    #[rstest::rstest]
+    #[case("mymaster", Value::Array(vec![bulk("127.0.0.1"), bulk("6379")]))]
+    #[case("other", Value::NullArray)]
+    #[tokio::test]
+    async fn sut_responds_address_of_master_by_name(#[case] name: &str, #[case] expected: Value) {
+        // Arrange
+        let context = sentinel();
+
+        // Act
+        let actual = SentinelCommand::GetMasterAddrByName(name.to_string())
+            .execute(&context)
+            .await;
+
+        // Assert
+        assert_eq!(actual, expected);
+    }
+
+    #[tokio::test]
+    async fn sut_votes_for_candidate_when_asked_whether_master_is_down() {
+        // Arrange
+        let context = sentinel();
+        let ask = |candidate: Option<&str>| SentinelCommand::IsMasterDownByAddr {
+            address: "127.0.0.1:6379".to_string(),
+            epoch: 1,
+            candidate: candidate.map(str::to_string),
+        };
+
+        // Act
+        let asked = ask(None).execute(&context).await;
+        let voted = ask(Some("abc")).execute(&context).await;
+
+        // Assert
+        assert_eq!(
+            asked,
+            Value::Array(vec![Value::Integer(0), bulk("*"), Value::Integer(0)])
+        );
+        assert_eq!(
+            voted,
+            Value::Array(vec![Value::Integer(0), bulk("abc"), Value::Integer(1)])
+        );
+    }
+
+    #[tokio::test]
+    async fn sut_describes_monitored_master() {
+        // Arrange
+        let context = sentinel();
+
+        // Act
+        let actual = SentinelCommand::Master("mymaster".to_string())
+            .execute(&context)
+            .await;
+        let unknown = SentinelCommand::Replicas("other".to_string())
+            .execute(&context)
+            .await;
+
+        // Assert
+        let expected = [
+            "name",
+            "mymaster",
+            "ip",
+            "127.0.0.1",
+            "port",
+            "6379",
+            "flags",
+            "master",
+            "config-epoch",
+            "0",
+            "num-slaves",
+            "0",
+            "num-other-sentinels",
+            "0",
+            "quorum",
+            "2",
+        ];
+        assert_eq!(actual, Value::Array(expected.map(bulk).to_vec()));
+        assert_eq!(
+            unknown,
+            Value::Error("ERR No such master with that name".to_string())
+        );
+    }
+
+    #[rstest::rstest]
+    #[tokio::test]
+    async fn sut_is_unknown_unless_running_as_a_sentinel(
+        #[from(command_executor_context)] context: CommandExecutorContext,
+    ) {
+        // Act
+        let actual = SentinelCommand::MyId.execute(&context).await;
+
+        // Assert
+        assert_eq!(
+            actual,
+            Value::Error("ERR unknown command 'SENTINEL'".to_string())
+        );
+    }
+}
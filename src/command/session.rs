@@ -0,0 +1,169 @@
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::AtomicU8;
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
+
+use crate::command::executor::CommandSet;
+use crate::pubsub::Subscriber;
+use crate::resp::Value;
+
+/// The state of a single client connection that outlives individual commands.
+pub struct Session {
+    database: AtomicUsize,
+    transaction: Mutex<Option<Transaction>>,
+    /// Raised by the repositories once any key watched since the last UNWATCH is modified.
+    dirty: Mutex<Arc<AtomicBool>>,
+    pub subscriber: Arc<Subscriber>,
+    /// The RESP version chosen with HELLO.
+    protocol: AtomicU8,
+    /// Set by CLIENT CACHING for the command that follows only.
+    caching: Mutex<Option<bool>>,
+    /// The IP address of the client, empty until known.
+    address: Mutex<String>,
+    /// Whether the session applies the replication stream of the master of this server.
+    master: AtomicBool,
+    /// Set by ASKING for the command that follows only.
+    asking: AtomicBool,
+}
+
+/// Commands queued after MULTI, executed all at once by EXEC.
+#[derive(Default)]
+pub struct Transaction {
+    /// The commands along with their requests, which are propagated to replicas as they are.
+    pub commands: Vec<(CommandSet, Value)>,
+    /// Whether a command failed to queue, in which case EXEC discards the transaction.
+    pub aborted: bool,
+}
+
+impl Session {
+    /// Buffers up to `pubsub_buffer_limit` messages published to the client.
+    pub fn new(pubsub_buffer_limit: usize) -> Self {
+        Self {
+            database: AtomicUsize::default(),
+            transaction: Mutex::default(),
+            dirty: Mutex::default(),
+            subscriber: Arc::new(Subscriber::new(pubsub_buffer_limit)),
+            protocol: AtomicU8::new(2),
+            caching: Mutex::default(),
+            address: Mutex::default(),
+            master: AtomicBool::default(),
+            asking: AtomicBool::default(),
+        }
+    }
+
+    /// The ID of the client, as returned by CLIENT ID.
+    pub fn id(&self) -> u64 {
+        self.subscriber.id()
+    }
+
+    pub fn protocol(&self) -> u8 {
+        self.protocol.load(Ordering::SeqCst)
+    }
+
+    pub fn set_protocol(&self, protocol: u8) {
+        self.protocol.store(protocol, Ordering::SeqCst);
+    }
+
+    pub fn caching(&self) -> Option<bool> {
+        *self.caching.lock().unwrap()
+    }
+
+    pub fn set_caching(&self, caching: bool) {
+        *self.caching.lock().unwrap() = Some(caching);
+    }
+
+    pub fn reset_caching(&self) {
+        *self.caching.lock().unwrap() = None;
+    }
+
+    pub fn address(&self) -> String {
+        self.address.lock().unwrap().clone()
+    }
+
+    pub fn set_address(&self, address: &str) {
+        *self.address.lock().unwrap() = address.to_string();
+    }
+
+    pub fn set_asking(&self) {
+        self.asking.store(true, Ordering::SeqCst);
+    }
+
+    /// Whether ASKING was sent before the command, which it applies to only.
+    pub fn take_asking(&self) -> bool {
+        self.asking.swap(false, Ordering::SeqCst)
+    }
+
+    pub fn is_master(&self) -> bool {
+        self.master.load(Ordering::SeqCst)
+    }
+
+    /// Marks the session as the link to the master, whose writes are not propagated again.
+    pub fn mark_master(&self) {
+        self.master.store(true, Ordering::SeqCst);
+    }
+
+    pub fn database(&self) -> usize {
+        self.database.load(Ordering::SeqCst)
+    }
+
+    pub fn select(&self, database: usize) {
+        self.database.store(database, Ordering::SeqCst);
+    }
+
+    pub fn in_transaction(&self) -> bool {
+        self.transaction.lock().unwrap().is_some()
+    }
+
+    /// Starts queueing commands, unless a transaction is already started.
+    pub fn begin(&self) -> bool {
+        let mut transaction = self.transaction.lock().unwrap();
+        if transaction.is_some() {
+            return false;
+        }
+        *transaction = Some(Transaction::default());
+        true
+    }
+
+    /// Queues the command if a transaction is started, otherwise hands it back to be executed
+    /// right away. Commands controlling the transaction itself are never queued.
+    pub fn queue(&self, command: CommandSet, request: &Value) -> Option<CommandSet> {
+        let mut transaction = self.transaction.lock().unwrap();
+        match (transaction.as_mut(), &command) {
+            (None, _)
+            | (
+                _,
+                CommandSet::Multi(_)
+                | CommandSet::Exec(_)
+                | CommandSet::Discard(_)
+                | CommandSet::Watch(_),
+            ) => Some(command),
+            (Some(transaction), _) => {
+                transaction.commands.push((command, request.clone()));
+                None
+            }
+        }
+    }
+
+    /// Marks the started transaction, if any, to be discarded by EXEC.
+    pub fn abort(&self) {
+        if let Some(transaction) = self.transaction.lock().unwrap().as_mut() {
+            transaction.aborted = true;
+        }
+    }
+
+    pub fn finish(&self) -> Option<Transaction> {
+        self.transaction.lock().unwrap().take()
+    }
+
+    /// The flag to hand to the repositories for keys being watched.
+    pub fn watching(&self) -> Arc<AtomicBool> {
+        self.dirty.lock().unwrap().clone()
+    }
+
+    /// Forgets every watched key, returning the flag telling whether any of them was modified.
+    pub fn unwatch(&self) -> Arc<AtomicBool> {
+        std::mem::take(&mut *self.dirty.lock().unwrap())
+    }
+}
@@ -5,16 +5,21 @@ use crate::command::parser::extract_array;
 use crate::command::parser::extract_bulk_string;
 use crate::command::parser::validate_main_command;
 use crate::command::parser::validate_min_array_length;
+use crate::notification::Class;
+use crate::repository::Data;
 use crate::repository::Entry;
 use crate::repository::Expiry;
 use crate::repository::TimeUnit;
 use crate::resp::Value;
 
+/// SET, its EX and PX options giving `expires_after` and EXAT and PXAT `expires_at`, both in
+/// milliseconds.
 #[derive(Debug, Default, PartialEq)]
 pub struct Set {
     key: String,
     value: String,
     expires_after: Option<u128>,
+    expires_at: Option<u128>,
 }
 
 impl Command for Set {
@@ -25,47 +30,68 @@ impl Command for Set {
         let key = extract_bulk_string(array, 1)?;
         let value = extract_bulk_string(array, 2)?;
 
-        let expires_after = if array.len() >= 5 {
-            let option_key = extract_bulk_string(array, 3)?;
-            if option_key.to_uppercase() == "PX" {
-                let option_value = extract_bulk_string(array, 4)?;
-                Some(option_value.parse()?)
-            } else {
-                None
-            }
-        } else {
-            None
-        };
-
-        Ok(Set {
+        let mut set = Set {
             key: key.to_string(),
             value: value.to_string(),
-            expires_after,
-        })
+            ..Set::default()
+        };
+        let mut index = 3;
+        while index < array.len() {
+            let option = extract_bulk_string(array, index)?.to_uppercase();
+            let factor = match option.as_str() {
+                "EX" | "EXAT" => 1000,
+                "PX" | "PXAT" => 1,
+                _ => {
+                    index += 1;
+                    continue;
+                }
+            };
+            let time = extract_bulk_string(array, index + 1)?.parse::<u128>()? * factor;
+            match option.as_str() {
+                "EX" | "PX" => set.expires_after = Some(time),
+                _ => set.expires_at = Some(time),
+            }
+            index += 2;
+        }
+        Ok(set)
+    }
+}
+
+impl Set {
+    fn expiry_in_millis(&self, now_in_millis: u128) -> Option<u128> {
+        self.expires_after
+            .map(|after| now_in_millis + after)
+            .or(self.expires_at)
+    }
+
+    /// The request setting the same expiry as of `now_in_millis` at any later time, for replicas.
+    pub fn absolute(&self, now_in_millis: u128) -> Value {
+        let mut arguments = vec!["SET".to_string(), self.key.clone(), self.value.clone()];
+        if let Some(expiry) = self.expiry_in_millis(now_in_millis) {
+            arguments.extend(["PXAT".to_string(), expiry.to_string()]);
+        }
+        Value::Array(arguments.into_iter().map(Value::BulkString).collect())
     }
 }
 
 #[async_trait::async_trait]
 impl CommandExecutor for Set {
     async fn execute(&self, context: &CommandExecutorContext) -> Value {
-        let expiry = self.expires_after.map(|after| {
-            let current_time = std::time::SystemTime::now()
-                .duration_since(std::time::UNIX_EPOCH)
-                .unwrap_or_default()
-                .as_millis();
-            Expiry {
-                epoch: current_time + after,
+        let expiry = self
+            .expiry_in_millis(context.clock.now_in_millis())
+            .map(|epoch| Expiry {
+                epoch,
                 unit: TimeUnit::Millisecond,
-            }
-        });
+            });
 
         let entry = Entry {
-            key: self.key.clone(),
-            value: self.value.clone(),
+            key: self.key.as_str().into(),
+            value: Data::String(self.value.clone()),
             expiry,
         };
 
-        context.repository.set(entry).await;
+        context.repository().set(entry).await;
+        context.notify(Class::String, "set", &self.key);
         Value::SimpleString("OK".to_string())
     }
 }
@@ -100,6 +126,7 @@ mod specs_for_parse_from {
             key: set_key.to_string(),
             value: set_value.to_string(),
             expires_after: None,
+            expires_at: None,
         };
         assert_eq!(actual, expected);
     }
@@ -126,6 +153,39 @@ mod specs_for_parse_from {
             key: set_key.to_string(),
             value: set_value.to_string(),
             expires_after: Some(set_expires_after),
+            expires_at: None,
+        };
+        assert_eq!(actual, expected);
+    }
+
+    #[rstest::rstest]
+    #[case("EX", "10", Some(10_000), None)]
+    #[case("px", "10", Some(10), None)]
+    #[case("EXAT", "10", None, Some(10_000))]
+    #[case("PXAT", "10", None, Some(10))]
+    fn sut_parses_set_command_with_expiry_options_in_milliseconds(
+        #[case] option: &str,
+        #[case] time: &str,
+        #[case] expires_after: Option<u128>,
+        #[case] expires_at: Option<u128>,
+    ) {
+        // Arrange
+        let value = Value::Array(
+            ["SET", "foo", "bar", "NX", option, time]
+                .iter()
+                .map(|argument| Value::BulkString(argument.to_string()))
+                .collect(),
+        );
+
+        // Act
+        let actual = Set::parse_from(&value).unwrap();
+
+        // Assert
+        let expected = Set {
+            key: "foo".to_string(),
+            value: "bar".to_string(),
+            expires_after,
+            expires_at,
         };
         assert_eq!(actual, expected);
     }
@@ -152,6 +212,7 @@ mod specs_for_parse_from {
             key: set_key.to_string(),
             value: set_value.to_string(),
             expires_after: None,
+            expires_at: None,
         };
         assert_eq!(actual, expected);
     }
@@ -159,19 +220,17 @@ mod specs_for_parse_from {
 
 #[cfg(test)]
 mod specs_for_execute {
-    use std::sync::Arc;
     use std::time::Duration;
 
     use fake::Fake;
     use fake::faker::lorem::en::Word;
-    use tokio::time::sleep;
 
     use crate::command::executor::CommandExecutor;
     use crate::command::executor::CommandExecutorContext;
     use crate::command::executor::fixture::command_executor_context;
+    use crate::command::executor::fixture::command_executor_context_with_fake_clock;
     use crate::config::Config;
     use crate::repository::InMemoryRepository;
-    use crate::repository::Repository;
     use crate::resp::Value;
 
     use super::Set;
@@ -188,6 +247,7 @@ mod specs_for_execute {
             key: key.clone(),
             value: value.clone(),
             expires_after: None,
+            expires_at: None,
         };
 
         // Act
@@ -212,21 +272,21 @@ mod specs_for_execute {
             key: key.clone(),
             value: value.clone(),
             expires_after: None,
+            expires_at: None,
         };
         set_cmd.execute(&context).await;
 
         // Act
-        let actual = context.repository.get(&key).await;
+        let actual = context.repository().get(&key).await;
 
         // Assert
-        assert_eq!(actual, Some(value));
+        assert_eq!(actual, Ok(Some(value)));
     }
 
     #[tokio::test]
     async fn sut_responds_null_when_gets_get_command_but_value_is_expired() {
         // Arrange
-        let repository = Arc::new(InMemoryRepository::new());
-        let context = CommandExecutorContext::new(repository.clone(), Arc::new(Config::default()));
+        let (context, clock) = command_executor_context_with_fake_clock();
         let key = Word().fake::<String>();
         let value = Word().fake::<String>();
         let expires_after: u128 = 50;
@@ -234,14 +294,15 @@ mod specs_for_execute {
             key: key.clone(),
             value: value.clone(),
             expires_after: Some(expires_after),
+            expires_at: None,
         };
         set_cmd.execute(&context).await;
 
         // Act
-        sleep(Duration::from_millis(60)).await;
-        let actual = repository.get(&key).await;
+        clock.advance(Duration::from_millis(51));
+        let actual = context.repository().get(&key).await;
 
         // Assert
-        assert_eq!(actual, None);
+        assert_eq!(actual, Ok(None));
     }
 }
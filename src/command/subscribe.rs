@@ -0,0 +1,143 @@
+use crate::command::executor::Command;
+use crate::command::executor::CommandExecutor;
+use crate::command::executor::CommandExecutorContext;
+use crate::command::parser::extract_array;
+use crate::command::parser::extract_bulk_string;
+use crate::command::parser::extract_bulk_strings;
+use crate::command::parser::validate_min_array_length;
+use crate::pubsub::Scope;
+use crate::resp::Value;
+
+/// SUBSCRIBE, PSUBSCRIBE and SSUBSCRIBE, replying once for every channel or pattern.
+#[derive(Debug, PartialEq)]
+pub struct Subscribe {
+    scope: Scope,
+    names: Vec<String>,
+}
+
+impl Command for Subscribe {
+    fn parse_from(value: &Value) -> Result<Self, anyhow::Error> {
+        let array = extract_array(value)?;
+        validate_min_array_length(array, 2)?;
+        let scope = match extract_bulk_string(array, 0)?.to_uppercase().as_str() {
+            "SUBSCRIBE" => Scope::Channel,
+            "PSUBSCRIBE" => Scope::Pattern,
+            "SSUBSCRIBE" => Scope::Shard,
+            _ => {
+                return Err(anyhow::anyhow!(
+                    "expected SUBSCRIBE, PSUBSCRIBE or SSUBSCRIBE"
+                ));
+            }
+        };
+        Ok(Subscribe {
+            scope,
+            names: extract_bulk_strings(array, 1)?,
+        })
+    }
+}
+
+#[async_trait::async_trait]
+impl CommandExecutor for Subscribe {
+    async fn execute(&self, context: &CommandExecutorContext) -> Value {
+        let subscriber = &context.session.subscriber;
+        let replies = self
+            .names
+            .iter()
+            .map(|name| {
+                let count = context.pubsub.subscribe(self.scope, name, subscriber);
+                Value::Array(vec![
+                    Value::BulkString(self.scope.subscribe_reply().to_string()),
+                    Value::BulkString(name.clone()),
+                    Value::Integer(count as i64),
+                ])
+            })
+            .collect();
+        Value::Replies(replies)
+    }
+}
+
+#[cfg(test)]
+mod specs_for_parse_from {
+    use crate::command::executor::Command;
+    use crate::pubsub::Scope;
+    use crate::resp::Value;
+
+    use super::Subscribe;
+
+    fn array(arguments: &[&str]) -> Value {
+        Value::Array(
+            arguments
+                .iter()
+                .map(|argument| Value::BulkString(argument.to_string()))
+                .collect(),
+        )
+    }
+
+    #[rstest::rstest]
+    #[case("SUBSCRIBE", Scope::Channel)]
+    #[case("subscribe", Scope::Channel)]
+    #[case("PSUBSCRIBE", Scope::Pattern)]
+    #[case("ssubscribe", Scope::Shard)]
+    fn sut_parses_channels_correctly(#[case] command: &str, #[case] scope: Scope) {
+        // Arrange
+        let value = array(&[command, "foo", "bar"]);
+
+        // Act
+        let actual = Subscribe::parse_from(&value).unwrap();
+
+        // Assert
+        let expected = Subscribe {
+            scope,
+            names: vec!["foo".to_string(), "bar".to_string()],
+        };
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn sut_fails_to_parse_subscribe_without_channels() {
+        // Act
+        let actual = Subscribe::parse_from(&array(&["SUBSCRIBE"]));
+
+        // Assert
+        assert!(actual.is_err());
+    }
+}
+
+#[cfg(test)]
+mod specs_for_execute {
+    use crate::command::executor::CommandExecutor;
+    use crate::command::executor::CommandExecutorContext;
+    use crate::command::executor::fixture::command_executor_context;
+    use crate::pubsub::Scope;
+    use crate::resp::Value;
+
+    use super::Subscribe;
+
+    #[rstest::rstest]
+    #[tokio::test]
+    async fn sut_replies_with_subscription_count_for_each_channel(
+        #[from(command_executor_context)] context: CommandExecutorContext,
+    ) {
+        // Arrange
+        let command = Subscribe {
+            scope: Scope::Pattern,
+            names: vec!["foo*".to_string(), "bar*".to_string()],
+        };
+
+        // Act
+        let actual = command.execute(&context).await;
+
+        // Assert
+        let reply = |pattern: &str, count| {
+            Value::Array(vec![
+                Value::BulkString("psubscribe".to_string()),
+                Value::BulkString(pattern.to_string()),
+                Value::Integer(count),
+            ])
+        };
+        let expected = Value::Replies(vec![reply("foo*", 1), reply("bar*", 2)]);
+        assert_eq!(actual, expected);
+        assert!(context.session.subscriber.is_subscribed());
+        assert_eq!(context.pubsub.publish("foobar", "hello"), 1);
+    }
+}
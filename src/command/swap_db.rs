@@ -0,0 +1,142 @@
+use crate::command::executor::Command;
+use crate::command::executor::CommandExecutor;
+use crate::command::executor::CommandExecutorContext;
+use crate::command::parser::extract_array;
+use crate::command::parser::extract_bulk_string;
+use crate::command::parser::validate_array_length;
+use crate::command::parser::validate_main_command;
+use crate::command::select::database_index;
+use crate::resp::Value;
+
+#[derive(Debug, Default, PartialEq)]
+pub struct SwapDb {
+    first: i64,
+    second: i64,
+}
+
+impl Command for SwapDb {
+    fn parse_from(value: &Value) -> Result<Self, anyhow::Error> {
+        let array = extract_array(value)?;
+        validate_array_length(array, 3)?;
+        validate_main_command(array, "SWAPDB")?;
+        let first = extract_bulk_string(array, 1)?
+            .parse()
+            .map_err(|_| anyhow::anyhow!("invalid first DB index"))?;
+        let second = extract_bulk_string(array, 2)?
+            .parse()
+            .map_err(|_| anyhow::anyhow!("invalid second DB index"))?;
+        Ok(SwapDb { first, second })
+    }
+}
+
+#[async_trait::async_trait]
+impl CommandExecutor for SwapDb {
+    async fn execute(&self, context: &CommandExecutorContext) -> Value {
+        let indexes = (
+            database_index(context, self.first),
+            database_index(context, self.second),
+        );
+        match indexes {
+            (Ok(first), Ok(second)) => {
+                for database in [first, second] {
+                    context
+                        .databases
+                        .get(database)
+                        .unwrap()
+                        .touch_watched()
+                        .await;
+                }
+                context.databases.swap(first, second);
+                Value::SimpleString("OK".to_string())
+            }
+            (Err(e), _) | (_, Err(e)) => e,
+        }
+    }
+}
+
+#[cfg(test)]
+mod specs_for_parse_from {
+    use crate::command::executor::Command;
+    use crate::resp::Value;
+
+    use super::SwapDb;
+
+    #[rstest::rstest]
+    #[case("SWAPDB")]
+    #[case("swapdb")]
+    fn sut_parses_swapdb_command_with_case_insensitive(#[case] swapdb: &str) {
+        // Arrange
+        let value = Value::Array(vec![
+            Value::BulkString(swapdb.to_string()),
+            Value::BulkString("0".to_string()),
+            Value::BulkString("1".to_string()),
+        ]);
+
+        // Act
+        let actual = SwapDb::parse_from(&value).unwrap();
+
+        // Assert
+        assert_eq!(
+            actual,
+            SwapDb {
+                first: 0,
+                second: 1
+            }
+        );
+    }
+}
+
+#[cfg(test)]
+mod specs_for_execute {
+    use crate::command::executor::CommandExecutor;
+    use crate::command::executor::fixture::command_executor_context_with_databases;
+    use crate::repository::Data;
+    use crate::repository::Entry;
+    use crate::resp::Value;
+
+    use super::SwapDb;
+
+    #[tokio::test]
+    async fn sut_swaps_databases_for_every_session() {
+        // Arrange
+        let context = command_executor_context_with_databases(16);
+        let other = context.new_session();
+        context
+            .repository()
+            .set(Entry {
+                key: "foo".into(),
+                value: Data::String("bar".to_string()),
+                expiry: None,
+            })
+            .await;
+        let command = SwapDb {
+            first: 0,
+            second: 1,
+        };
+
+        // Act
+        let actual = command.execute(&context).await;
+
+        // Assert
+        assert_eq!(actual, Value::SimpleString("OK".to_string()));
+        assert!(!other.repository().exists("foo").await);
+        assert!(context.databases.get(1).unwrap().exists("foo").await);
+    }
+
+    #[tokio::test]
+    async fn sut_responds_error_if_database_is_out_of_range() {
+        // Arrange
+        let context = command_executor_context_with_databases(16);
+        let command = SwapDb {
+            first: 0,
+            second: 16,
+        };
+
+        // Act
+        let actual = command.execute(&context).await;
+
+        // Assert
+        let expected = Value::Error("ERR DB index is out of range".to_string());
+        assert_eq!(actual, expected);
+    }
+}
@@ -0,0 +1,67 @@
+use crate::command::executor::Command;
+use crate::command::executor::CommandExecutor;
+use crate::command::executor::CommandExecutorContext;
+use crate::command::parser::extract_array;
+use crate::command::parser::extract_bulk_strings;
+use crate::command::parser::validate_main_command;
+use crate::command::parser::validate_min_array_length;
+use crate::resp::Value;
+
+#[derive(Debug, Default, PartialEq)]
+pub struct Touch {
+    keys: Vec<String>,
+}
+
+impl Command for Touch {
+    fn parse_from(value: &Value) -> Result<Self, anyhow::Error> {
+        let array = extract_array(value)?;
+        validate_min_array_length(array, 2)?;
+        validate_main_command(array, "TOUCH")?;
+        let keys = extract_bulk_strings(array, 1)?;
+        Ok(Touch { keys })
+    }
+}
+
+#[async_trait::async_trait]
+impl CommandExecutor for Touch {
+    async fn execute(&self, context: &CommandExecutorContext) -> Value {
+        let mut count = 0;
+        for key in self.keys.iter() {
+            context.track(key);
+            if context.repository().exists(key).await {
+                count += 1;
+            }
+        }
+        Value::Integer(count)
+    }
+}
+
+#[cfg(test)]
+mod specs_for_parse_from {
+    use crate::command::executor::Command;
+    use crate::resp::Value;
+
+    use super::Touch;
+
+    #[rstest::rstest]
+    #[case("TOUCH")]
+    #[case("touch")]
+    #[case("ToUcH")]
+    fn sut_parses_touch_command_with_case_insensitive(#[case] touch: &str) {
+        // Arrange
+        let value = Value::Array(vec![
+            Value::BulkString(touch.to_string()),
+            Value::BulkString("foo".to_string()),
+            Value::BulkString("bar".to_string()),
+        ]);
+
+        // Act
+        let actual = Touch::parse_from(&value).unwrap();
+
+        // Assert
+        let expected = Touch {
+            keys: vec!["foo".to_string(), "bar".to_string()],
+        };
+        assert_eq!(actual, expected);
+    }
+}
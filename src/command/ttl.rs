@@ -0,0 +1,180 @@
+use crate::command::executor::Command;
+use crate::command::executor::CommandExecutor;
+use crate::command::executor::CommandExecutorContext;
+use crate::command::parser::extract_array;
+use crate::command::parser::extract_bulk_string;
+use crate::command::parser::validate_array_length;
+use crate::command::parser::validate_main_command;
+use crate::repository::TimeUnit;
+use crate::resp::Value;
+
+#[derive(Debug, PartialEq)]
+pub struct Ttl {
+    key: String,
+    unit: TimeUnit,
+}
+
+impl Command for Ttl {
+    fn parse_from(value: &Value) -> Result<Self, anyhow::Error> {
+        let array = extract_array(value)?;
+        validate_array_length(array, 2)?;
+        let unit = if validate_main_command(array, "TTL").is_ok() {
+            TimeUnit::Second
+        } else {
+            validate_main_command(array, "PTTL")?;
+            TimeUnit::Millisecond
+        };
+        let key = extract_bulk_string(array, 1)?;
+        Ok(Ttl {
+            key: key.to_string(),
+            unit,
+        })
+    }
+}
+
+#[async_trait::async_trait]
+impl CommandExecutor for Ttl {
+    async fn execute(&self, context: &CommandExecutorContext) -> Value {
+        context.track(&self.key);
+        let ttl = match context.repository().expiry(&self.key).await {
+            Err(_) => -2,
+            Ok(None) => -1,
+            Ok(Some(expiry)) => {
+                let remaining = expiry
+                    .to_millis()
+                    .saturating_sub(context.clock.now_in_millis())
+                    as i64;
+                match self.unit {
+                    TimeUnit::Second => (remaining + 500) / 1000,
+                    TimeUnit::Millisecond => remaining,
+                }
+            }
+        };
+        Value::Integer(ttl)
+    }
+}
+
+#[cfg(test)]
+mod specs_for_parse_from {
+    use crate::command::executor::Command;
+    use crate::repository::TimeUnit;
+    use crate::resp::Value;
+
+    use super::Ttl;
+
+    #[rstest::rstest]
+    #[case("TTL", TimeUnit::Second)]
+    #[case("ttl", TimeUnit::Second)]
+    #[case("PTTL", TimeUnit::Millisecond)]
+    #[case("pTtL", TimeUnit::Millisecond)]
+    fn sut_parses_ttl_commands_with_case_insensitive(#[case] ttl: &str, #[case] unit: TimeUnit) {
+        // Arrange
+        let value = Value::Array(vec![
+            Value::BulkString(ttl.to_string()),
+            Value::BulkString("foo".to_string()),
+        ]);
+
+        // Act
+        let actual = Ttl::parse_from(&value).unwrap();
+
+        // Assert
+        let expected = Ttl {
+            key: "foo".to_string(),
+            unit,
+        };
+        assert_eq!(actual, expected);
+    }
+}
+
+#[cfg(test)]
+mod specs_for_execute {
+    use std::time::Duration;
+
+    use crate::clock::Clock;
+    use crate::command::executor::CommandExecutor;
+    use crate::command::executor::fixture::command_executor_context_with_fake_clock;
+    use crate::repository::Data;
+    use crate::repository::Entry;
+    use crate::repository::Expiry;
+    use crate::repository::TimeUnit;
+    use crate::resp::Value;
+
+    use super::Ttl;
+
+    #[rstest::rstest]
+    #[case("volatile", TimeUnit::Second, 100)]
+    #[case("persistent", TimeUnit::Second, -1)]
+    #[case("missing", TimeUnit::Millisecond, -2)]
+    #[tokio::test]
+    async fn sut_responds_remaining_time_to_live(
+        #[case] key: &str,
+        #[case] unit: TimeUnit,
+        #[case] expected: i64,
+    ) {
+        // Arrange
+        let (context, clock) = command_executor_context_with_fake_clock();
+        for (key, expiry) in [
+            (
+                "volatile",
+                Some(Expiry {
+                    epoch: clock.now_in_millis() + 100_000,
+                    unit: TimeUnit::Millisecond,
+                }),
+            ),
+            ("persistent", None),
+        ] {
+            context
+                .repository()
+                .set(Entry {
+                    key: key.into(),
+                    value: Data::String("value".to_string()),
+                    expiry,
+                })
+                .await;
+        }
+        let command = Ttl {
+            key: key.to_string(),
+            unit,
+        };
+
+        // Act
+        let actual = command.execute(&context).await;
+
+        // Assert
+        assert_eq!(actual, Value::Integer(expected));
+    }
+
+    #[rstest::rstest]
+    #[case(TimeUnit::Second, 60)]
+    #[case(TimeUnit::Millisecond, 59_600)]
+    #[tokio::test]
+    async fn sut_responds_time_to_live_decreasing_as_time_passes(
+        #[case] unit: TimeUnit,
+        #[case] expected: i64,
+    ) {
+        // Arrange
+        let (context, clock) = command_executor_context_with_fake_clock();
+        context
+            .repository()
+            .set(Entry {
+                key: "foo".into(),
+                value: Data::String("value".to_string()),
+                expiry: Some(Expiry {
+                    epoch: clock.now_in_millis() + 100_000,
+                    unit: TimeUnit::Millisecond,
+                }),
+            })
+            .await;
+        let command = Ttl {
+            key: "foo".to_string(),
+            unit,
+        };
+
+        // Act
+        clock.advance(Duration::from_millis(40_400));
+        let actual = command.execute(&context).await;
+
+        // Assert
+        assert_eq!(actual, Value::Integer(expected));
+    }
+}
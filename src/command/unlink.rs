@@ -0,0 +1,161 @@
+use tokio::task::JoinHandle;
+
+use crate::command::executor::Command;
+use crate::command::executor::CommandExecutor;
+use crate::command::executor::CommandExecutorContext;
+use crate::command::parser::extract_array;
+use crate::command::parser::extract_bulk_strings;
+use crate::command::parser::validate_main_command;
+use crate::command::parser::validate_min_array_length;
+use crate::notification::Class;
+use crate::resp::Value;
+
+/// Values cheaper than this to free are dropped inline, as handing them off costs more.
+const LAZYFREE_THRESHOLD: usize = 64;
+
+#[derive(Debug, Default, PartialEq)]
+pub struct Unlink {
+    keys: Vec<String>,
+}
+
+impl Command for Unlink {
+    fn parse_from(value: &Value) -> Result<Self, anyhow::Error> {
+        let array = extract_array(value)?;
+        validate_min_array_length(array, 2)?;
+        validate_main_command(array, "UNLINK")?;
+        let keys = extract_bulk_strings(array, 1)?;
+        Ok(Unlink { keys })
+    }
+}
+
+#[async_trait::async_trait]
+impl CommandExecutor for Unlink {
+    async fn execute(&self, context: &CommandExecutorContext) -> Value {
+        let mut count = 0;
+        for key in self.keys.iter() {
+            let Some(entry) = context.repository().delete(key).await else {
+                continue;
+            };
+            context.notify(Class::Generic, "del", key);
+            count += 1;
+            let effort = entry.value.free_effort();
+            free(entry, effort);
+        }
+        Value::Integer(count)
+    }
+}
+
+/// Drops `value` on a blocking thread when `effort`, the work freeing it takes, is above the
+/// threshold, returning the task doing so. Cheap values are dropped right away.
+fn free<T: Send + 'static>(value: T, effort: usize) -> Option<JoinHandle<()>> {
+    if effort <= LAZYFREE_THRESHOLD {
+        return None;
+    }
+    Some(tokio::task::spawn_blocking(move || drop(value)))
+}
+
+#[cfg(test)]
+mod specs_for_parse_from {
+    use crate::command::executor::Command;
+    use crate::resp::Value;
+
+    use super::Unlink;
+
+    #[rstest::rstest]
+    #[case("UNLINK")]
+    #[case("unlink")]
+    #[case("UnLiNk")]
+    fn sut_parses_unlink_command_with_case_insensitive(#[case] unlink: &str) {
+        // Arrange
+        let value = Value::Array(vec![
+            Value::BulkString(unlink.to_string()),
+            Value::BulkString("foo".to_string()),
+        ]);
+
+        // Act
+        let actual = Unlink::parse_from(&value).unwrap();
+
+        // Assert
+        let expected = Unlink {
+            keys: vec!["foo".to_string()],
+        };
+        assert_eq!(actual, expected);
+    }
+}
+
+#[cfg(test)]
+mod specs_for_execute {
+    use std::sync::Arc;
+    use std::sync::Mutex;
+    use std::thread::ThreadId;
+
+    use crate::command::executor::CommandExecutor;
+    use crate::command::executor::CommandExecutorContext;
+    use crate::command::executor::fixture::command_executor_context;
+    use crate::repository::AddCondition;
+    use crate::repository::InMemoryRepository;
+    use crate::resp::Value;
+
+    use super::LAZYFREE_THRESHOLD;
+    use super::Unlink;
+    use super::free;
+
+    /// Records the thread it is dropped on.
+    struct Dropped(Arc<Mutex<Option<ThreadId>>>);
+
+    impl Drop for Dropped {
+        fn drop(&mut self) {
+            *self.0.lock().unwrap() = Some(std::thread::current().id());
+        }
+    }
+
+    #[rstest::rstest]
+    #[case(LAZYFREE_THRESHOLD, true)]
+    #[case(LAZYFREE_THRESHOLD + 1, false)]
+    #[tokio::test]
+    async fn sut_frees_only_costly_values_off_the_command_path(
+        #[case] effort: usize,
+        #[case] expected: bool,
+    ) {
+        // Arrange
+        let thread = Arc::new(Mutex::new(None));
+        let value = Dropped(thread.clone());
+
+        // Act
+        if let Some(task) = free(value, effort) {
+            task.await.unwrap();
+        }
+
+        // Assert
+        let actual = *thread.lock().unwrap() == Some(std::thread::current().id());
+        assert_eq!(actual, expected);
+    }
+
+    #[rstest::rstest]
+    #[tokio::test]
+    async fn sut_removes_large_values_immediately_from_keyspace(
+        #[from(command_executor_context)]
+        #[with(InMemoryRepository::new())]
+        context: CommandExecutorContext,
+    ) {
+        // Arrange
+        let members = (0..1000)
+            .map(|i| (format!("member-{i}"), i as f64))
+            .collect();
+        context
+            .repository()
+            .zadd("large", members, AddCondition::Always)
+            .await
+            .unwrap();
+        let command = Unlink {
+            keys: vec!["large".to_string(), "missing".to_string()],
+        };
+
+        // Act
+        let actual = command.execute(&context).await;
+
+        // Assert
+        assert_eq!(actual, Value::Integer(1));
+        assert!(!context.repository().exists("large").await);
+    }
+}
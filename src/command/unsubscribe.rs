@@ -0,0 +1,168 @@
+use crate::command::executor::Command;
+use crate::command::executor::CommandExecutor;
+use crate::command::executor::CommandExecutorContext;
+use crate::command::parser::extract_array;
+use crate::command::parser::extract_bulk_string;
+use crate::command::parser::extract_bulk_strings;
+use crate::command::parser::validate_min_array_length;
+use crate::pubsub::Scope;
+use crate::resp::Value;
+
+/// UNSUBSCRIBE, PUNSUBSCRIBE and SUNSUBSCRIBE, from every channel or pattern if none is given.
+#[derive(Debug, PartialEq)]
+pub struct Unsubscribe {
+    scope: Scope,
+    names: Vec<String>,
+}
+
+impl Command for Unsubscribe {
+    fn parse_from(value: &Value) -> Result<Self, anyhow::Error> {
+        let array = extract_array(value)?;
+        validate_min_array_length(array, 1)?;
+        let scope = match extract_bulk_string(array, 0)?.to_uppercase().as_str() {
+            "UNSUBSCRIBE" => Scope::Channel,
+            "PUNSUBSCRIBE" => Scope::Pattern,
+            "SUNSUBSCRIBE" => Scope::Shard,
+            _ => {
+                return Err(anyhow::anyhow!(
+                    "expected UNSUBSCRIBE, PUNSUBSCRIBE or SUNSUBSCRIBE"
+                ));
+            }
+        };
+        Ok(Unsubscribe {
+            scope,
+            names: extract_bulk_strings(array, 1)?,
+        })
+    }
+}
+
+#[async_trait::async_trait]
+impl CommandExecutor for Unsubscribe {
+    async fn execute(&self, context: &CommandExecutorContext) -> Value {
+        let subscriber = &context.session.subscriber;
+        let names = match self.names.is_empty() {
+            true => subscriber.subscriptions(self.scope),
+            false => self.names.clone(),
+        };
+        let kind = Value::BulkString(self.scope.unsubscribe_reply().to_string());
+        if names.is_empty() {
+            let count = subscriber.count(self.scope);
+            return Value::Array(vec![kind, Value::Null, Value::Integer(count as i64)]);
+        }
+        let replies = names
+            .into_iter()
+            .map(|name| {
+                let count = context.pubsub.unsubscribe(self.scope, &name, subscriber);
+                Value::Array(vec![
+                    kind.clone(),
+                    Value::BulkString(name),
+                    Value::Integer(count as i64),
+                ])
+            })
+            .collect();
+        Value::Replies(replies)
+    }
+}
+
+#[cfg(test)]
+mod specs_for_parse_from {
+    use crate::command::executor::Command;
+    use crate::pubsub::Scope;
+    use crate::resp::Value;
+
+    use super::Unsubscribe;
+
+    fn array(arguments: &[&str]) -> Value {
+        Value::Array(
+            arguments
+                .iter()
+                .map(|argument| Value::BulkString(argument.to_string()))
+                .collect(),
+        )
+    }
+
+    #[rstest::rstest]
+    #[case(&["UNSUBSCRIBE", "foo"], Scope::Channel, &["foo"])]
+    #[case(&["punsubscribe"], Scope::Pattern, &[])]
+    #[case(&["SUNSUBSCRIBE", "foo", "bar"], Scope::Shard, &["foo", "bar"])]
+    fn sut_parses_channels_correctly(
+        #[case] arguments: &[&str],
+        #[case] scope: Scope,
+        #[case] names: &[&str],
+    ) {
+        // Act
+        let actual = Unsubscribe::parse_from(&array(arguments)).unwrap();
+
+        // Assert
+        let expected = Unsubscribe {
+            scope,
+            names: names.iter().map(|name| name.to_string()).collect(),
+        };
+        assert_eq!(actual, expected);
+    }
+}
+
+#[cfg(test)]
+mod specs_for_execute {
+    use crate::command::executor::CommandExecutor;
+    use crate::command::executor::CommandExecutorContext;
+    use crate::command::executor::fixture::command_executor_context;
+    use crate::pubsub::Scope;
+    use crate::resp::Value;
+
+    use super::Unsubscribe;
+
+    #[rstest::rstest]
+    #[tokio::test]
+    async fn sut_unsubscribes_from_every_channel_if_none_is_given(
+        #[from(command_executor_context)] context: CommandExecutorContext,
+    ) {
+        // Arrange
+        let subscriber = &context.session.subscriber;
+        context.pubsub.subscribe(Scope::Channel, "bar", subscriber);
+        context.pubsub.subscribe(Scope::Channel, "foo", subscriber);
+        context.pubsub.subscribe(Scope::Pattern, "f*", subscriber);
+        let command = Unsubscribe {
+            scope: Scope::Channel,
+            names: vec![],
+        };
+
+        // Act
+        let actual = command.execute(&context).await;
+
+        // Assert
+        let reply = |channel: &str, count| {
+            Value::Array(vec![
+                Value::BulkString("unsubscribe".to_string()),
+                Value::BulkString(channel.to_string()),
+                Value::Integer(count),
+            ])
+        };
+        let expected = Value::Replies(vec![reply("bar", 2), reply("foo", 1)]);
+        assert_eq!(actual, expected);
+        assert!(context.pubsub.names(Scope::Channel, None).is_empty());
+    }
+
+    #[rstest::rstest]
+    #[tokio::test]
+    async fn sut_replies_null_channel_if_not_subscribed(
+        #[from(command_executor_context)] context: CommandExecutorContext,
+    ) {
+        // Arrange
+        let command = Unsubscribe {
+            scope: Scope::Pattern,
+            names: vec![],
+        };
+
+        // Act
+        let actual = command.execute(&context).await;
+
+        // Assert
+        let expected = Value::Array(vec![
+            Value::BulkString("punsubscribe".to_string()),
+            Value::Null,
+            Value::Integer(0),
+        ]);
+        assert_eq!(actual, expected);
+    }
+}
@@ -0,0 +1,49 @@
+use crate::command::executor::Command;
+use crate::command::executor::CommandExecutor;
+use crate::command::executor::CommandExecutorContext;
+use crate::command::parser::extract_array;
+use crate::command::parser::validate_array_length;
+use crate::command::parser::validate_main_command;
+use crate::resp::Value;
+
+#[derive(Debug, Default, PartialEq)]
+pub struct Unwatch;
+
+impl Command for Unwatch {
+    fn parse_from(value: &Value) -> Result<Self, anyhow::Error> {
+        let array = extract_array(value)?;
+        validate_array_length(array, 1)?;
+        validate_main_command(array, "UNWATCH")?;
+        Ok(Unwatch)
+    }
+}
+
+#[async_trait::async_trait]
+impl CommandExecutor for Unwatch {
+    async fn execute(&self, context: &CommandExecutorContext) -> Value {
+        context.session.unwatch();
+        Value::SimpleString("OK".to_string())
+    }
+}
+
+#[cfg(test)]
+mod specs_for_parse_from {
+    use crate::command::executor::Command;
+    use crate::resp::Value;
+
+    use super::Unwatch;
+
+    #[rstest::rstest]
+    #[case("UNWATCH")]
+    #[case("unwatch")]
+    fn sut_parses_unwatch_command_with_case_insensitive(#[case] unwatch: &str) {
+        // Arrange
+        let value = Value::Array(vec![Value::BulkString(unwatch.to_string())]);
+
+        // Act
+        let actual = Unwatch::parse_from(&value).unwrap();
+
+        // Assert
+        assert_eq!(actual, Unwatch);
+    }
+}
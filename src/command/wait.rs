@@ -0,0 +1,205 @@
+use std::time::Duration;
+
+use crate::command::executor::Command;
+use crate::command::executor::CommandExecutor;
+use crate::command::executor::CommandExecutorContext;
+use crate::command::parser::extract_array;
+use crate::command::parser::extract_bulk_string;
+use crate::command::parser::validate_array_length;
+use crate::command::parser::validate_main_command;
+use crate::resp::Value;
+
+/// WAIT numreplicas timeout, blocking until as many replicas acknowledged every write so far.
+#[derive(Debug, Default, PartialEq)]
+pub struct Wait {
+    replicas: i64,
+    timeout: i64,
+}
+
+impl Command for Wait {
+    fn parse_from(value: &Value) -> Result<Self, anyhow::Error> {
+        let array = extract_array(value)?;
+        validate_array_length(array, 3)?;
+        validate_main_command(array, "WAIT")?;
+        Ok(Wait {
+            replicas: parse_integer(extract_bulk_string(array, 1)?)?,
+            timeout: parse_integer(extract_bulk_string(array, 2)?)?,
+        })
+    }
+}
+
+pub(crate) fn parse_integer(value: &str) -> Result<i64, anyhow::Error> {
+    value
+        .parse()
+        .map_err(|_| anyhow::anyhow!("value is not an integer or out of range"))
+}
+
+/// How long to wait for, a timeout of zero meaning forever.
+pub(crate) fn timeout(milliseconds: i64) -> Result<Option<Duration>, Value> {
+    match milliseconds {
+        ..0 => Err(Value::Error("ERR timeout is negative".to_string())),
+        0 => Ok(None),
+        milliseconds => Ok(Some(Duration::from_millis(milliseconds as u64))),
+    }
+}
+
+impl Wait {
+    /// Replies how many replicas acknowledged every write so far without waiting, as WAIT
+    /// does within a transaction.
+    pub fn count(&self, context: &CommandExecutorContext) -> Value {
+        if let Err(e) = self.validate(context) {
+            return e;
+        }
+        let offset = context.replication.offset();
+        Value::Integer(context.replication.acknowledged(offset, false) as i64)
+    }
+
+    fn validate(&self, context: &CommandExecutorContext) -> Result<Option<Duration>, Value> {
+        if context.replication.is_replica() {
+            return Err(Value::Error(
+                "ERR WAIT cannot be used with replica instances. Please also note that since Redis 4.0 if a replica is configured to be writable (which is not the default) writes to replicas are just local and are not propagated.".to_string(),
+            ));
+        }
+        timeout(self.timeout)
+    }
+}
+
+#[async_trait::async_trait]
+impl CommandExecutor for Wait {
+    async fn execute(&self, context: &CommandExecutorContext) -> Value {
+        let timeout = match self.validate(context) {
+            Ok(timeout) => timeout,
+            Err(e) => return e,
+        };
+        let offset = context.replication.offset();
+        let needed = self.replicas.max(0) as usize;
+        let count = context
+            .replication
+            .wait(offset, needed, timeout, false)
+            .await;
+        Value::Integer(count as i64)
+    }
+}
+
+#[cfg(test)]
+mod specs_for_parse_from {
+    use crate::command::executor::Command;
+    use crate::resp::Value;
+
+    use super::Wait;
+
+    fn array(arguments: &[&str]) -> Value {
+        Value::Array(
+            arguments
+                .iter()
+                .map(|argument| Value::BulkString(argument.to_string()))
+                .collect(),
+        )
+    }
+
+    #[test]
+    fn sut_parses_wait_command() {
+        // Act
+        let actual = Wait::parse_from(&array(&["wait", "2", "500"])).unwrap();
+
+        // Assert
+        let expected = Wait {
+            replicas: 2,
+            timeout: 500,
+        };
+        assert_eq!(actual, expected);
+    }
+
+    #[rstest::rstest]
+    #[case(&["WAIT", "two", "500"])]
+    #[case(&["WAIT", "2"])]
+    fn sut_rejects_malformed_wait(#[case] arguments: &[&str]) {
+        // Act
+        let actual = Wait::parse_from(&array(arguments));
+
+        // Assert
+        assert!(actual.is_err());
+    }
+}
+
+#[cfg(test)]
+mod specs_for_execute {
+    use crate::command::executor::CommandExecutor;
+    use crate::command::executor::CommandExecutorContext;
+    use crate::command::executor::fixture::command_executor_context;
+    use crate::resp::Value;
+
+    use super::Wait;
+
+    #[rstest::rstest]
+    #[tokio::test]
+    async fn sut_replies_replicas_acknowledging_writes_so_far_once_enough_did(
+        #[from(command_executor_context)] context: CommandExecutorContext,
+    ) {
+        // Arrange
+        let replica = context.new_session();
+        context.replication.attach(&replica.session);
+        let set = Value::Array(vec![Value::BulkString("SET".to_string())]);
+        context.replication.propagate(0, &set);
+        let offset = context.replication.offset();
+        let sut = Wait {
+            replicas: 1,
+            timeout: 0,
+        };
+
+        // Act
+        let (actual, _) = tokio::join!(sut.execute(&context), async {
+            tokio::task::yield_now().await;
+            context
+                .replication
+                .acknowledge(&replica.session, offset, None);
+        });
+
+        // Assert
+        assert_eq!(actual, Value::Integer(1));
+        let mut messages = replica.session.subscriber.messages().await;
+        messages.try_recv().unwrap();
+        let getack = messages.try_recv().unwrap();
+        assert!(matches!(getack, Value::Raw(bytes) if bytes.ends_with(b"GETACK\r\n$1\r\n*\r\n")));
+    }
+
+    #[rstest::rstest]
+    #[tokio::test]
+    async fn sut_replies_replicas_acknowledging_writes_so_far_once_timed_out(
+        #[from(command_executor_context)] context: CommandExecutorContext,
+    ) {
+        // Arrange
+        let replica = context.new_session();
+        context.replication.attach(&replica.session);
+        let set = Value::Array(vec![Value::BulkString("SET".to_string())]);
+        context.replication.propagate(0, &set);
+        let sut = Wait {
+            replicas: 1,
+            timeout: 10,
+        };
+
+        // Act
+        let actual = sut.execute(&context).await;
+
+        // Assert
+        assert_eq!(actual, Value::Integer(0));
+    }
+
+    #[rstest::rstest]
+    #[tokio::test]
+    async fn sut_responds_error_if_timeout_is_negative(
+        #[from(command_executor_context)] context: CommandExecutorContext,
+    ) {
+        // Arrange
+        let sut = Wait {
+            replicas: 1,
+            timeout: -1,
+        };
+
+        // Act
+        let actual = sut.execute(&context).await;
+
+        // Assert
+        assert_eq!(actual, Value::Error("ERR timeout is negative".to_string()));
+    }
+}
@@ -0,0 +1,178 @@
+use std::time::Duration;
+
+use crate::command::executor::Command;
+use crate::command::executor::CommandExecutor;
+use crate::command::executor::CommandExecutorContext;
+use crate::command::parser::extract_array;
+use crate::command::parser::extract_bulk_string;
+use crate::command::parser::validate_array_length;
+use crate::command::parser::validate_main_command;
+use crate::command::wait::parse_integer;
+use crate::command::wait::timeout;
+use crate::resp::Value;
+
+/// WAITAOF numlocal numreplicas timeout, blocking until every write so far was written to the
+/// AOF locally and by as many replicas. As this server keeps no AOF, numlocal must be zero.
+#[derive(Debug, Default, PartialEq)]
+pub struct WaitAof {
+    local: i64,
+    replicas: i64,
+    timeout: i64,
+}
+
+impl Command for WaitAof {
+    fn parse_from(value: &Value) -> Result<Self, anyhow::Error> {
+        let array = extract_array(value)?;
+        validate_array_length(array, 4)?;
+        validate_main_command(array, "WAITAOF")?;
+        Ok(WaitAof {
+            local: parse_integer(extract_bulk_string(array, 1)?)?,
+            replicas: parse_integer(extract_bulk_string(array, 2)?)?,
+            timeout: parse_integer(extract_bulk_string(array, 3)?)?,
+        })
+    }
+}
+
+impl WaitAof {
+    /// Replies how many replicas wrote every write so far to their AOF without waiting, as
+    /// WAITAOF does within a transaction.
+    pub fn count(&self, context: &CommandExecutorContext) -> Value {
+        if let Err(e) = self.validate(context) {
+            return e;
+        }
+        let offset = context.replication.offset();
+        reply(context.replication.acknowledged(offset, true))
+    }
+
+    fn validate(&self, context: &CommandExecutorContext) -> Result<Option<Duration>, Value> {
+        if context.replication.is_replica() {
+            return Err(Value::Error(
+                "ERR WAITAOF cannot be used with replica instances. Please also note that writes to replicas are just local and are not propagated.".to_string(),
+            ));
+        }
+        if self.local > 0 {
+            return Err(Value::Error(
+                "ERR WAITAOF cannot be used when numlocal is set but appendonly is disabled."
+                    .to_string(),
+            ));
+        }
+        timeout(self.timeout)
+    }
+}
+
+fn reply(replicas: usize) -> Value {
+    Value::Array(vec![Value::Integer(0), Value::Integer(replicas as i64)])
+}
+
+#[async_trait::async_trait]
+impl CommandExecutor for WaitAof {
+    async fn execute(&self, context: &CommandExecutorContext) -> Value {
+        let timeout = match self.validate(context) {
+            Ok(timeout) => timeout,
+            Err(e) => return e,
+        };
+        let offset = context.replication.offset();
+        let needed = self.replicas.max(0) as usize;
+        let count = context
+            .replication
+            .wait(offset, needed, timeout, true)
+            .await;
+        reply(count)
+    }
+}
+
+#[cfg(test)]
+mod specs_for_parse_from {
+    use crate::command::executor::Command;
+    use crate::resp::Value;
+
+    use super::WaitAof;
+
+    #[test]
+    fn sut_parses_waitaof_command() {
+        // Arrange
+        let value = Value::Array(vec![
+            Value::BulkString("waitaof".to_string()),
+            Value::BulkString("0".to_string()),
+            Value::BulkString("1".to_string()),
+            Value::BulkString("100".to_string()),
+        ]);
+
+        // Act
+        let actual = WaitAof::parse_from(&value).unwrap();
+
+        // Assert
+        let expected = WaitAof {
+            local: 0,
+            replicas: 1,
+            timeout: 100,
+        };
+        assert_eq!(actual, expected);
+    }
+}
+
+#[cfg(test)]
+mod specs_for_execute {
+    use crate::command::executor::CommandExecutor;
+    use crate::command::executor::CommandExecutorContext;
+    use crate::command::executor::fixture::command_executor_context;
+    use crate::resp::Value;
+
+    use super::WaitAof;
+
+    #[rstest::rstest]
+    #[tokio::test]
+    async fn sut_responds_error_if_numlocal_is_set(
+        #[from(command_executor_context)] context: CommandExecutorContext,
+    ) {
+        // Arrange
+        let sut = WaitAof {
+            local: 1,
+            replicas: 0,
+            timeout: 0,
+        };
+
+        // Act
+        let actual = sut.execute(&context).await;
+
+        // Assert
+        let expected = Value::Error(
+            "ERR WAITAOF cannot be used when numlocal is set but appendonly is disabled."
+                .to_string(),
+        );
+        assert_eq!(actual, expected);
+    }
+
+    #[rstest::rstest]
+    #[tokio::test]
+    async fn sut_counts_replicas_acknowledging_their_aof_only(
+        #[from(command_executor_context)] context: CommandExecutorContext,
+    ) {
+        // Arrange
+        let fsyncing = context.new_session();
+        let applying = context.new_session();
+        context.replication.attach(&fsyncing.session);
+        context.replication.attach(&applying.session);
+        let set = Value::Array(vec![Value::BulkString("SET".to_string())]);
+        context.replication.propagate(0, &set);
+        let offset = context.replication.offset();
+        context
+            .replication
+            .acknowledge(&fsyncing.session, offset, Some(offset));
+        context
+            .replication
+            .acknowledge(&applying.session, offset, None);
+        let sut = WaitAof {
+            local: 0,
+            replicas: 2,
+            timeout: 10,
+        };
+
+        // Act
+        let actual = sut.execute(&context).await;
+
+        // Assert
+        let expected = Value::Array(vec![Value::Integer(0), Value::Integer(1)]);
+        assert_eq!(actual, expected);
+    }
+}
@@ -0,0 +1,130 @@
+use crate::command::executor::Command;
+use crate::command::executor::CommandExecutor;
+use crate::command::executor::CommandExecutorContext;
+use crate::command::parser::extract_array;
+use crate::command::parser::extract_bulk_strings;
+use crate::command::parser::validate_main_command;
+use crate::command::parser::validate_min_array_length;
+use crate::resp::Value;
+
+#[derive(Debug, Default, PartialEq)]
+pub struct Watch {
+    keys: Vec<String>,
+}
+
+impl Command for Watch {
+    fn parse_from(value: &Value) -> Result<Self, anyhow::Error> {
+        let array = extract_array(value)?;
+        validate_min_array_length(array, 2)?;
+        validate_main_command(array, "WATCH")?;
+        let keys = extract_bulk_strings(array, 1)?;
+        Ok(Watch { keys })
+    }
+}
+
+#[async_trait::async_trait]
+impl CommandExecutor for Watch {
+    async fn execute(&self, context: &CommandExecutorContext) -> Value {
+        if context.session.in_transaction() {
+            return Value::Error("ERR WATCH inside MULTI is not allowed".to_string());
+        }
+        let repository = context.repository();
+        let dirty = context.session.watching();
+        for key in &self.keys {
+            repository.watch(key, &dirty).await;
+        }
+        Value::SimpleString("OK".to_string())
+    }
+}
+
+#[cfg(test)]
+mod specs_for_parse_from {
+    use crate::command::executor::Command;
+    use crate::resp::Value;
+
+    use super::Watch;
+
+    #[rstest::rstest]
+    #[case("WATCH")]
+    #[case("watch")]
+    fn sut_parses_watch_command_with_case_insensitive(#[case] watch: &str) {
+        // Arrange
+        let value = Value::Array(vec![
+            Value::BulkString(watch.to_string()),
+            Value::BulkString("foo".to_string()),
+            Value::BulkString("bar".to_string()),
+        ]);
+
+        // Act
+        let actual = Watch::parse_from(&value).unwrap();
+
+        // Assert
+        let expected = Watch {
+            keys: vec!["foo".to_string(), "bar".to_string()],
+        };
+        assert_eq!(actual, expected);
+    }
+}
+
+#[cfg(test)]
+mod specs_for_execute {
+    use std::sync::atomic::Ordering;
+
+    use crate::command::executor::CommandExecutor;
+    use crate::command::executor::CommandExecutorContext;
+    use crate::command::executor::fixture::command_executor_context;
+    use crate::repository::Data;
+    use crate::repository::Entry;
+    use crate::repository::InMemoryRepository;
+    use crate::resp::Value;
+
+    use super::Watch;
+
+    #[rstest::rstest]
+    #[tokio::test]
+    async fn sut_flags_session_once_watched_key_is_modified_by_another_client(
+        #[from(command_executor_context)]
+        #[with(InMemoryRepository::new())]
+        context: CommandExecutorContext,
+    ) {
+        // Arrange
+        let other = context.new_session();
+        let command = Watch {
+            keys: vec!["foo".to_string()],
+        };
+
+        // Act
+        let actual = command.execute(&context).await;
+        other
+            .repository()
+            .set(Entry {
+                key: "foo".into(),
+                value: Data::String("bar".to_string()),
+                expiry: None,
+            })
+            .await;
+
+        // Assert
+        assert_eq!(actual, Value::SimpleString("OK".to_string()));
+        assert!(context.session.watching().load(Ordering::SeqCst));
+    }
+
+    #[rstest::rstest]
+    #[tokio::test]
+    async fn sut_responds_error_inside_transaction(
+        #[from(command_executor_context)] context: CommandExecutorContext,
+    ) {
+        // Arrange
+        context.session.begin();
+        let command = Watch {
+            keys: vec!["foo".to_string()],
+        };
+
+        // Act
+        let actual = command.execute(&context).await;
+
+        // Assert
+        let expected = Value::Error("ERR WATCH inside MULTI is not allowed".to_string());
+        assert_eq!(actual, expected);
+    }
+}
@@ -1,27 +1,114 @@
+use std::fmt;
 use std::path::Path;
+use std::str::FromStr;
+
+use crate::notification::KeyspaceEvents;
 
 #[derive(Clone, Debug, Default)]
 pub struct Config {
     pub server: Server,
     pub replication: Replication,
     pub rdb: Option<RdbConfig>,
+    /// Set when the server runs as a sentinel, monitoring masters rather than serving data.
+    pub sentinel: Option<SentinelConfig>,
 }
 
 #[derive(Clone, Debug)]
 pub struct Server {
     pub port: usize,
+    pub databases: usize,
+    /// How long a script may run, in milliseconds, before other clients are replied BUSY.
+    pub busy_reply_threshold: u64,
+    /// How many published messages may wait to be written to a subscriber before it is
+    /// disconnected for being too slow.
+    pub pubsub_buffer_limit: usize,
+    pub notify_keyspace_events: KeyspaceEvents,
+    /// Whether the server runs as a node of a cluster, serving only the keys of its hash slots.
+    pub cluster_enabled: bool,
 }
 
 impl Default for Server {
     fn default() -> Self {
-        Server { port: 6379 }
+        Server {
+            port: 6379,
+            databases: 16,
+            busy_reply_threshold: 5000,
+            pubsub_buffer_limit: 1024,
+            notify_keyspace_events: KeyspaceEvents::default(),
+            cluster_enabled: false,
+        }
     }
 }
 
-#[derive(Clone, Debug, Default)]
+#[derive(Clone, Debug)]
 pub struct Replication {
-    pub master: ReplicationMaster,
     pub slave: Option<ReplicationSlave>,
+    /// How many bytes of the replication stream are kept for replicas to catch up with.
+    pub backlog_size: usize,
+    /// Whether snapshots are streamed to the replicas supporting it as they are serialized.
+    pub diskless_sync: bool,
+    /// How many seconds a diskless transfer waits for more replicas to share it.
+    pub diskless_sync_delay: u64,
+    pub diskless_load: DisklessLoad,
+    /// Whether a replica refuses writes of clients other than its master.
+    pub replica_read_only: bool,
+    /// Whether a replica goes on serving its dataset while the link to its master is down.
+    pub replica_serve_stale_data: bool,
+    /// How many chunks of the replication stream may wait to be written to a replica before it
+    /// is disconnected for being too slow, apart from the limit of subscribers.
+    pub replica_buffer_limit: usize,
+}
+
+impl Default for Replication {
+    fn default() -> Self {
+        Replication {
+            slave: None,
+            backlog_size: 1024 * 1024,
+            diskless_sync: true,
+            diskless_sync_delay: 5,
+            diskless_load: DisklessLoad::default(),
+            replica_read_only: true,
+            replica_serve_stale_data: true,
+            replica_buffer_limit: 1024 * 1024,
+        }
+    }
+}
+
+/// How a replica loads the snapshot of its master.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum DisklessLoad {
+    /// Saves the snapshot to the RDB file before loading it.
+    #[default]
+    Disabled,
+    /// Loads the snapshot as it was received while the dataset is empty, as disabled otherwise.
+    OnEmptyDb,
+    /// Loads the snapshot as it was received into a fresh keyspace, which replaces the
+    /// current one only once complete.
+    Swapdb,
+}
+
+impl FromStr for DisklessLoad {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "disabled" => Ok(DisklessLoad::Disabled),
+            "on-empty-db" => Ok(DisklessLoad::OnEmptyDb),
+            "swapdb" => Ok(DisklessLoad::Swapdb),
+            _ => Err(format!("invalid repl-diskless-load: {s}")),
+        }
+    }
+}
+
+impl fmt::Display for DisklessLoad {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            DisklessLoad::Disabled => "disabled",
+            DisklessLoad::OnEmptyDb => "on-empty-db",
+            DisklessLoad::Swapdb => "swapdb",
+        };
+        f.write_str(name)
+    }
 }
 
 impl Replication {
@@ -35,23 +122,59 @@ impl Replication {
 }
 
 #[derive(Clone, Debug)]
-pub struct ReplicationMaster {
-    pub id: String,
-    pub offset: usize,
+pub struct ReplicationSlave {
+    pub master_address: String,
 }
 
-impl Default for ReplicationMaster {
+#[derive(Clone, Debug)]
+pub struct SentinelConfig {
+    pub masters: Vec<Monitor>,
+    /// How long, in milliseconds, a server may go without answering before it is considered
+    /// down.
+    pub down_after: u64,
+    /// How long, in milliseconds, a failover may take before it is given up.
+    pub failover_timeout: u64,
+}
+
+impl Default for SentinelConfig {
     fn default() -> Self {
-        Self {
-            id: "8371b4fb1155b71f4a04d3e1bc3e18c4a990aeeb".to_string(),
-            offset: 0,
+        SentinelConfig {
+            masters: Vec::new(),
+            down_after: 30000,
+            failover_timeout: 180000,
         }
     }
 }
 
-#[derive(Clone, Debug)]
-pub struct ReplicationSlave {
-    pub master_address: String,
+/// A master monitored by a sentinel, given as `<name> <host> <port> <quorum>`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Monitor {
+    pub name: String,
+    pub address: String,
+    /// How many sentinels must agree the master is down before it is failed over.
+    pub quorum: usize,
+}
+
+impl FromStr for Monitor {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.split_whitespace().collect::<Vec<_>>()[..] {
+            [name, host, port, quorum] => {
+                let port: u16 = port.parse().map_err(|_| format!("invalid port: {port}"))?;
+                let quorum = match quorum.parse() {
+                    Ok(quorum) if quorum > 0 => quorum,
+                    _ => return Err(format!("invalid quorum: {quorum}")),
+                };
+                Ok(Monitor {
+                    name: name.to_string(),
+                    address: format!("{host}:{port}"),
+                    quorum,
+                })
+            }
+            _ => Err(format!("expected <name> <host> <port> <quorum>, got {s}")),
+        }
+    }
 }
 
 #[derive(Clone, Debug)]
@@ -73,9 +196,32 @@ impl Config {
     pub fn get(&self, arg: &str) -> Option<String> {
         match arg {
             "port" => Some(self.server.port.to_string()),
+            "databases" => Some(self.server.databases.to_string()),
+            "busy-reply-threshold" | "lua-time-limit" => {
+                Some(self.server.busy_reply_threshold.to_string())
+            }
+            "notify-keyspace-events" => Some(self.server.notify_keyspace_events.to_string()),
+            "cluster-enabled" => Some(yes_or_no(self.server.cluster_enabled)),
+            "repl-backlog-size" => Some(self.replication.backlog_size.to_string()),
+            "repl-diskless-sync" => Some(yes_or_no(self.replication.diskless_sync)),
+            "repl-diskless-sync-delay" => Some(self.replication.diskless_sync_delay.to_string()),
+            "repl-diskless-load" => Some(self.replication.diskless_load.to_string()),
+            "replica-read-only" | "slave-read-only" => {
+                Some(yes_or_no(self.replication.replica_read_only))
+            }
+            "replica-serve-stale-data" | "slave-serve-stale-data" => {
+                Some(yes_or_no(self.replication.replica_serve_stale_data))
+            }
             "dir" => self.rdb.as_ref().map(|rdb| rdb.directory.clone()),
             "dbfilename" => self.rdb.as_ref().map(|rdb| rdb.filename.clone()),
             _ => None,
         }
     }
 }
+
+fn yes_or_no(flag: bool) -> String {
+    match flag {
+        true => "yes".to_string(),
+        false => "no".to_string(),
+    }
+}
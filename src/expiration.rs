@@ -0,0 +1,131 @@
+use std::sync::Arc;
+use std::time::Duration;
+use std::time::Instant;
+
+use tokio::time::MissedTickBehavior;
+
+use crate::notification::Notifier;
+use crate::repository::Databases;
+use crate::repository::Repository;
+
+const CYCLE_PERIOD: Duration = Duration::from_millis(100);
+/// Like Redis, a cycle may spend at most a quarter of its period evicting keys.
+const CYCLE_TIME_LIMIT: Duration = Duration::from_millis(25);
+const KEYS_PER_ITERATION: usize = 20;
+/// A cycle keeps sampling while more than this percentage of the last sample had expired.
+const ACCEPTABLE_STALE_PERCENTAGE: usize = 25;
+
+/// Actively evicts expired keys so that keys nobody reads again do not stay in memory forever.
+/// The databases share the time budget of a cycle. A replica leaves this to its master, which
+/// sends the deletions.
+pub async fn run(databases: Arc<Databases>, notifier: Arc<Notifier>) {
+    let mut interval = tokio::time::interval(CYCLE_PERIOD);
+    interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+    loop {
+        interval.tick().await;
+        if databases.is_replica() {
+            continue;
+        }
+        let start = Instant::now();
+        for repository in databases.all() {
+            let Some(time_limit) = CYCLE_TIME_LIMIT.checked_sub(start.elapsed()) else {
+                break;
+            };
+            cycle(repository.as_ref(), time_limit).await;
+        }
+        notifier.flush(&databases);
+    }
+}
+
+/// Runs sampling iterations until few sampled keys are expired or `time_limit` is exceeded,
+/// returning the number of evicted keys.
+pub async fn cycle(repository: &(impl Repository + ?Sized), time_limit: Duration) -> usize {
+    let start = Instant::now();
+    let mut evicted = 0;
+    loop {
+        let sample = repository.evict_expired(KEYS_PER_ITERATION).await;
+        evicted += sample.evicted;
+        if sample.evicted * 100 <= sample.sampled * ACCEPTABLE_STALE_PERCENTAGE
+            || start.elapsed() >= time_limit
+        {
+            return evicted;
+        }
+        tokio::task::yield_now().await;
+    }
+}
+
+#[cfg(test)]
+mod specs_for_cycle {
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    use crate::clock::Clock;
+    use crate::clock::fixture::FakeClock;
+    use crate::repository::Data;
+    use crate::repository::Entry;
+    use crate::repository::Expiry;
+    use crate::repository::InMemoryRepository;
+    use crate::repository::Repository;
+    use crate::repository::TimeUnit;
+
+    use super::cycle;
+
+    async fn populate(repository: &InMemoryRepository, prefix: &str, count: usize, epoch: u128) {
+        for i in 0..count {
+            repository
+                .set(Entry {
+                    key: format!("{prefix}:{i}").into(),
+                    value: Data::String("value".to_string()),
+                    expiry: Some(Expiry {
+                        epoch,
+                        unit: TimeUnit::Millisecond,
+                    }),
+                })
+                .await;
+        }
+    }
+
+    #[tokio::test]
+    async fn sut_evicts_expired_keys_while_most_samples_are_expired() {
+        // Arrange
+        let repository = InMemoryRepository::new();
+        populate(&repository, "expired", 100, 1).await;
+
+        // Act
+        let actual = cycle(&repository, Duration::from_secs(10)).await;
+
+        // Assert
+        assert_eq!(actual, 100);
+        assert_eq!(repository.size().await, 0);
+    }
+
+    #[tokio::test]
+    async fn sut_stops_when_few_samples_are_expired() {
+        // Arrange
+        let clock = Arc::new(FakeClock::default());
+        let repository = InMemoryRepository::with_clock(clock.clone());
+        populate(&repository, "expired", 1, 1).await;
+        populate(&repository, "alive", 1000, clock.now_in_millis() + 100_000).await;
+
+        // Act
+        let actual = cycle(&repository, Duration::from_secs(10)).await;
+
+        // Assert
+        assert!(actual <= 1);
+        assert!(repository.size().await >= 1000);
+    }
+
+    #[tokio::test]
+    async fn sut_stops_when_time_limit_is_exceeded() {
+        // Arrange
+        let repository = InMemoryRepository::new();
+        populate(&repository, "expired", 100, 1).await;
+
+        // Act
+        let actual = cycle(&repository, Duration::ZERO).await;
+
+        // Assert
+        assert!(actual <= 20);
+        assert!(repository.size().await >= 80);
+    }
+}
@@ -0,0 +1,511 @@
+pub const LONGITUDE_MIN: f64 = -180.0;
+pub const LONGITUDE_MAX: f64 = 180.0;
+pub const LATITUDE_MIN: f64 = -85.05112878;
+pub const LATITUDE_MAX: f64 = 85.05112878;
+
+const STEP_MAX: u8 = 26;
+const EARTH_RADIUS_IN_METERS: f64 = 6372797.560856;
+const MERCATOR_MAX: f64 = 20037726.37;
+const BASE32_ALPHABET: &[u8] = b"0123456789bcdefghjkmnpqrstuvwxyz";
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Coordinate {
+    pub longitude: f64,
+    pub latitude: f64,
+}
+
+impl Coordinate {
+    pub fn new(longitude: f64, latitude: f64) -> Option<Self> {
+        let is_valid = (LONGITUDE_MIN..=LONGITUDE_MAX).contains(&longitude)
+            && (LATITUDE_MIN..=LATITUDE_MAX).contains(&latitude);
+        is_valid.then_some(Coordinate {
+            longitude,
+            latitude,
+        })
+    }
+
+    /// Encodes the coordinate into the 52 bit interleaved geohash used as sorted set score.
+    pub fn to_score(self) -> f64 {
+        GeoHash::encode(self, STEP_MAX).bits as f64
+    }
+
+    pub fn from_score(score: f64) -> Self {
+        GeoHash {
+            bits: score as u64,
+            step: STEP_MAX,
+        }
+        .decode()
+        .center()
+    }
+
+    pub fn distance(&self, other: &Coordinate) -> f64 {
+        let v = ((other.longitude.to_radians() - self.longitude.to_radians()) / 2.0).sin();
+        if v == 0.0 {
+            return latitude_distance(self.latitude, other.latitude);
+        }
+        let lat1 = self.latitude.to_radians();
+        let lat2 = other.latitude.to_radians();
+        let u = ((lat2 - lat1) / 2.0).sin();
+        let a = u * u + lat1.cos() * lat2.cos() * v * v;
+        2.0 * EARTH_RADIUS_IN_METERS * a.sqrt().asin()
+    }
+
+    /// Renders the standard 11 character geohash, which uses the full [-90, 90] latitude range.
+    pub fn to_geohash_string(self) -> String {
+        let hash = GeoHash::encode_with_ranges(
+            self,
+            STEP_MAX,
+            (LONGITUDE_MIN, LONGITUDE_MAX),
+            (-90.0, 90.0),
+        );
+        (0..11)
+            .map(|i| {
+                let index = if i == 10 {
+                    0
+                } else {
+                    (hash.bits >> (52 - (i + 1) * 5)) & 0x1f
+                };
+                BASE32_ALPHABET[index as usize] as char
+            })
+            .collect()
+    }
+}
+
+fn latitude_distance(latitude1: f64, latitude2: f64) -> f64 {
+    EARTH_RADIUS_IN_METERS * (latitude2.to_radians() - latitude1.to_radians()).abs()
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DistanceUnit {
+    Meters,
+    Kilometers,
+    Feet,
+    Miles,
+}
+
+impl DistanceUnit {
+    pub fn parse(unit: &str) -> Option<Self> {
+        match unit.to_lowercase().as_str() {
+            "m" => Some(DistanceUnit::Meters),
+            "km" => Some(DistanceUnit::Kilometers),
+            "ft" => Some(DistanceUnit::Feet),
+            "mi" => Some(DistanceUnit::Miles),
+            _ => None,
+        }
+    }
+
+    pub fn to_meters(self, distance: f64) -> f64 {
+        distance * self.meters()
+    }
+
+    pub fn to_unit(self, distance: f64) -> f64 {
+        distance / self.meters()
+    }
+
+    fn meters(self) -> f64 {
+        match self {
+            DistanceUnit::Meters => 1.0,
+            DistanceUnit::Kilometers => 1000.0,
+            DistanceUnit::Feet => 0.3048,
+            DistanceUnit::Miles => 1609.34,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Shape {
+    /// Radius in meters.
+    Radius(f64),
+    /// Width and height in meters.
+    Box(f64, f64),
+}
+
+impl Shape {
+    /// Returns the distance from `center` to `point` in meters if the point lies within the shape.
+    pub fn distance_if_contains(&self, center: &Coordinate, point: &Coordinate) -> Option<f64> {
+        match *self {
+            Shape::Radius(radius) => {
+                let distance = center.distance(point);
+                (distance <= radius).then_some(distance)
+            }
+            Shape::Box(width, height) => {
+                if latitude_distance(point.latitude, center.latitude) > height / 2.0 {
+                    return None;
+                }
+                let horizontal = Coordinate {
+                    longitude: center.longitude,
+                    latitude: point.latitude,
+                };
+                if point.distance(&horizontal) > width / 2.0 {
+                    return None;
+                }
+                Some(center.distance(point))
+            }
+        }
+    }
+
+    /// Computes the score ranges of the geohash boxes covering the shape around `center`.
+    ///
+    /// Each range is half open, `[min, max)`, and overlapping boxes are only returned once.
+    pub fn score_ranges(&self, center: &Coordinate) -> Vec<(f64, f64)> {
+        let (half_width, half_height, radius) = match *self {
+            Shape::Radius(radius) => (radius, radius, radius),
+            Shape::Box(width, height) => (
+                width / 2.0,
+                height / 2.0,
+                ((width / 2.0).powi(2) + (height / 2.0).powi(2)).sqrt(),
+            ),
+        };
+        let bounds = bounding_box(center, half_width, half_height);
+
+        let mut step = estimate_steps_by_radius(radius, center.latitude);
+        let mut hash = GeoHash::encode(*center, step);
+        let mut neighbors = hash.neighbors();
+        let decrease_step = neighbors[Direction::North as usize].decode().latitude.1 < bounds.3
+            || neighbors[Direction::South as usize].decode().latitude.0 > bounds.1
+            || neighbors[Direction::East as usize].decode().longitude.1 < bounds.2
+            || neighbors[Direction::West as usize].decode().longitude.0 > bounds.0;
+        if step > 1 && decrease_step {
+            step -= 1;
+            hash = GeoHash::encode(*center, step);
+            neighbors = hash.neighbors();
+        }
+
+        let mut boxes: Vec<Option<GeoHash>> = neighbors.into_iter().map(Some).collect();
+        if step >= 2 {
+            let area = hash.decode();
+            let mut exclude = |directions: [Direction; 3]| {
+                for direction in directions {
+                    boxes[direction as usize] = None;
+                }
+            };
+            if area.latitude.0 < bounds.1 {
+                exclude([Direction::South, Direction::SouthWest, Direction::SouthEast]);
+            }
+            if area.latitude.1 > bounds.3 {
+                exclude([Direction::North, Direction::NorthEast, Direction::NorthWest]);
+            }
+            if area.longitude.0 < bounds.0 {
+                exclude([Direction::West, Direction::SouthWest, Direction::NorthWest]);
+            }
+            if area.longitude.1 > bounds.2 {
+                exclude([Direction::East, Direction::SouthEast, Direction::NorthEast]);
+            }
+        }
+
+        let mut ranges = vec![hash.score_range()];
+        for neighbor in boxes.into_iter().flatten() {
+            let range = neighbor.score_range();
+            if !ranges.contains(&range) {
+                ranges.push(range);
+            }
+        }
+        ranges
+    }
+}
+
+/// Returns `(min_longitude, min_latitude, max_longitude, max_latitude)` enclosing the area.
+fn bounding_box(center: &Coordinate, half_width: f64, half_height: f64) -> (f64, f64, f64, f64) {
+    let latitude_delta = (half_height / EARTH_RADIUS_IN_METERS).to_degrees();
+    let longitude_delta_top = (half_width
+        / EARTH_RADIUS_IN_METERS
+        / (center.latitude + latitude_delta).to_radians().cos())
+    .to_degrees();
+    let longitude_delta_bottom = (half_width
+        / EARTH_RADIUS_IN_METERS
+        / (center.latitude - latitude_delta).to_radians().cos())
+    .to_degrees();
+    let longitude_delta = if center.latitude < 0.0 {
+        longitude_delta_bottom
+    } else {
+        longitude_delta_top
+    };
+    (
+        center.longitude - longitude_delta,
+        center.latitude - latitude_delta,
+        center.longitude + longitude_delta,
+        center.latitude + latitude_delta,
+    )
+}
+
+fn estimate_steps_by_radius(mut radius: f64, latitude: f64) -> u8 {
+    if radius == 0.0 {
+        return STEP_MAX;
+    }
+    let mut step: i32 = 1;
+    while radius < MERCATOR_MAX {
+        radius *= 2.0;
+        step += 1;
+    }
+    // Make sure the range is included in most of the base cases.
+    step -= 2;
+    // Boxes get narrower towards the poles, so widen the search there.
+    if !(-66.0..=66.0).contains(&latitude) {
+        step -= 1;
+        if !(-80.0..=80.0).contains(&latitude) {
+            step -= 1;
+        }
+    }
+    step.clamp(1, STEP_MAX as i32) as u8
+}
+
+#[derive(Clone, Copy)]
+enum Direction {
+    Center,
+    North,
+    South,
+    East,
+    West,
+    NorthEast,
+    NorthWest,
+    SouthEast,
+    SouthWest,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct GeoHash {
+    bits: u64,
+    step: u8,
+}
+
+struct Area {
+    longitude: (f64, f64),
+    latitude: (f64, f64),
+}
+
+impl Area {
+    fn center(&self) -> Coordinate {
+        Coordinate {
+            longitude: ((self.longitude.0 + self.longitude.1) / 2.0)
+                .clamp(LONGITUDE_MIN, LONGITUDE_MAX),
+            latitude: ((self.latitude.0 + self.latitude.1) / 2.0).clamp(LATITUDE_MIN, LATITUDE_MAX),
+        }
+    }
+}
+
+impl GeoHash {
+    fn encode(coordinate: Coordinate, step: u8) -> Self {
+        Self::encode_with_ranges(
+            coordinate,
+            step,
+            (LONGITUDE_MIN, LONGITUDE_MAX),
+            (LATITUDE_MIN, LATITUDE_MAX),
+        )
+    }
+
+    fn encode_with_ranges(
+        coordinate: Coordinate,
+        step: u8,
+        longitude_range: (f64, f64),
+        latitude_range: (f64, f64),
+    ) -> Self {
+        let scale = (1u64 << step) as f64;
+        let latitude_offset = (coordinate.latitude - latitude_range.0)
+            / (latitude_range.1 - latitude_range.0)
+            * scale;
+        let longitude_offset = (coordinate.longitude - longitude_range.0)
+            / (longitude_range.1 - longitude_range.0)
+            * scale;
+        GeoHash {
+            bits: interleave(latitude_offset as u32, longitude_offset as u32),
+            step,
+        }
+    }
+
+    fn decode(&self) -> Area {
+        let (latitude_bits, longitude_bits) = deinterleave(self.bits);
+        let scale = (1u64 << self.step) as f64;
+        let latitude_scale = LATITUDE_MAX - LATITUDE_MIN;
+        let longitude_scale = LONGITUDE_MAX - LONGITUDE_MIN;
+        Area {
+            latitude: (
+                LATITUDE_MIN + (latitude_bits as f64 / scale) * latitude_scale,
+                LATITUDE_MIN + ((latitude_bits as f64 + 1.0) / scale) * latitude_scale,
+            ),
+            longitude: (
+                LONGITUDE_MIN + (longitude_bits as f64 / scale) * longitude_scale,
+                LONGITUDE_MIN + ((longitude_bits as f64 + 1.0) / scale) * longitude_scale,
+            ),
+        }
+    }
+
+    fn score_range(&self) -> (f64, f64) {
+        let shift = 52 - self.step as u32 * 2;
+        let min = self.bits << shift;
+        let max = (self.bits + 1) << shift;
+        (min as f64, max as f64)
+    }
+
+    /// Returns the box itself followed by its neighbors, indexed by `Direction`.
+    fn neighbors(&self) -> [GeoHash; 9] {
+        let moved = |dx: i8, dy: i8| self.move_x(dx).move_y(dy);
+        let mut neighbors = [*self; 9];
+        neighbors[Direction::Center as usize] = *self;
+        neighbors[Direction::North as usize] = moved(0, 1);
+        neighbors[Direction::South as usize] = moved(0, -1);
+        neighbors[Direction::East as usize] = moved(1, 0);
+        neighbors[Direction::West as usize] = moved(-1, 0);
+        neighbors[Direction::NorthEast as usize] = moved(1, 1);
+        neighbors[Direction::NorthWest as usize] = moved(-1, 1);
+        neighbors[Direction::SouthEast as usize] = moved(1, -1);
+        neighbors[Direction::SouthWest as usize] = moved(-1, -1);
+        neighbors
+    }
+
+    fn move_x(self, d: i8) -> Self {
+        self.shift(d, 0xaaaaaaaaaaaaaaaa)
+    }
+
+    fn move_y(self, d: i8) -> Self {
+        self.shift(d, 0x5555555555555555)
+    }
+
+    /// Moves the box one cell along the axis whose bits are selected by `mask`.
+    fn shift(self, d: i8, mask: u64) -> Self {
+        if d == 0 {
+            return self;
+        }
+        let width = 64 - self.step as u32 * 2;
+        let axis = self.bits & mask;
+        let other = self.bits & !mask;
+        let filler = !mask >> width;
+        let moved = if d > 0 {
+            axis.wrapping_add(filler + 1)
+        } else {
+            (axis | filler).wrapping_sub(filler + 1)
+        };
+        GeoHash {
+            bits: (moved & (mask >> width)) | other,
+            step: self.step,
+        }
+    }
+}
+
+fn interleave(x: u32, y: u32) -> u64 {
+    spread(x) | (spread(y) << 1)
+}
+
+fn deinterleave(bits: u64) -> (u32, u32) {
+    (squash(bits), squash(bits >> 1))
+}
+
+fn spread(value: u32) -> u64 {
+    let mut x = value as u64;
+    x = (x | (x << 16)) & 0x0000FFFF0000FFFF;
+    x = (x | (x << 8)) & 0x00FF00FF00FF00FF;
+    x = (x | (x << 4)) & 0x0F0F0F0F0F0F0F0F;
+    x = (x | (x << 2)) & 0x3333333333333333;
+    (x | (x << 1)) & 0x5555555555555555
+}
+
+fn squash(bits: u64) -> u32 {
+    let mut x = bits & 0x5555555555555555;
+    x = (x | (x >> 1)) & 0x3333333333333333;
+    x = (x | (x >> 2)) & 0x0F0F0F0F0F0F0F0F;
+    x = (x | (x >> 4)) & 0x00FF00FF00FF00FF;
+    x = (x | (x >> 8)) & 0x0000FFFF0000FFFF;
+    x = (x | (x >> 16)) & 0x00000000FFFFFFFF;
+    x as u32
+}
+
+#[cfg(test)]
+mod specs_for_coordinate {
+    use super::Coordinate;
+
+    fn palermo() -> Coordinate {
+        Coordinate::new(13.361389, 38.115556).unwrap()
+    }
+
+    fn catania() -> Coordinate {
+        Coordinate::new(15.087269, 37.502669).unwrap()
+    }
+
+    #[test]
+    fn sut_encodes_coordinate_into_same_score_as_redis() {
+        // Act
+        let actual = palermo().to_score();
+
+        // Assert
+        assert_eq!(actual, 3479099956230698.0);
+    }
+
+    #[test]
+    fn sut_decodes_score_into_coordinate_close_to_original() {
+        // Act
+        let actual = Coordinate::from_score(palermo().to_score());
+
+        // Assert
+        assert!((actual.longitude - 13.361389338970184).abs() < 1e-12);
+        assert!((actual.latitude - 38.1155563954963).abs() < 1e-12);
+    }
+
+    #[test]
+    fn sut_calculates_distance_in_meters() {
+        // Act
+        let actual = Coordinate::from_score(palermo().to_score())
+            .distance(&Coordinate::from_score(catania().to_score()));
+
+        // Assert
+        assert_eq!(format!("{actual:.4}"), "166274.1516");
+    }
+
+    #[test]
+    fn sut_renders_standard_geohash_string() {
+        // Act
+        let actual = Coordinate::from_score(palermo().to_score()).to_geohash_string();
+
+        // Assert
+        assert_eq!(actual, "sqc8b49rny0");
+    }
+
+    #[rstest::rstest]
+    #[case(181.0, 0.0)]
+    #[case(0.0, 86.0)]
+    fn sut_rejects_coordinate_out_of_range(#[case] longitude: f64, #[case] latitude: f64) {
+        // Act
+        let actual = Coordinate::new(longitude, latitude);
+
+        // Assert
+        assert!(actual.is_none());
+    }
+}
+
+#[cfg(test)]
+mod specs_for_shape {
+    use super::Coordinate;
+    use super::Shape;
+
+    #[test]
+    fn sut_covers_points_within_radius_by_score_ranges() {
+        // Arrange
+        let center = Coordinate::new(15.0, 37.0).unwrap();
+        let inside = Coordinate::new(15.087269, 37.502669).unwrap();
+        let shape = Shape::Radius(200_000.0);
+
+        // Act
+        let ranges = shape.score_ranges(&center);
+
+        // Assert
+        let score = inside.to_score();
+        assert!(
+            ranges
+                .iter()
+                .any(|(min, max)| *min <= score && score < *max)
+        );
+        assert!(shape.distance_if_contains(&center, &inside).is_some());
+    }
+
+    #[test]
+    fn sut_excludes_points_outside_of_box() {
+        // Arrange
+        let center = Coordinate::new(15.0, 37.0).unwrap();
+        let point = Coordinate::new(13.361389, 38.115556).unwrap();
+        let shape = Shape::Box(400_000.0, 100_000.0);
+
+        // Act
+        let actual = shape.distance_if_contains(&center, &point);
+
+        // Assert
+        assert!(actual.is_none());
+    }
+}
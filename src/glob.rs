@@ -0,0 +1,300 @@
+/// Redis nests at most this many `*` before giving up on a match, protecting against abusive
+/// patterns.
+const MAX_NESTING: usize = 1000;
+
+/// Matches `string` against a glob style `pattern` exactly like Redis' `stringmatchlen`,
+/// supporting `*`, `?`, `[...]` classes with `^` negation and `a-z` ranges, and `\` escapes.
+/// Like the original, nothing matches the empty string, which is why callers treat a lone `*`
+/// as matching everything before consulting this.
+pub fn matches(pattern: &str, string: &str) -> bool {
+    matches_bytes(pattern.as_bytes(), string.as_bytes(), false)
+}
+
+pub fn matches_bytes(pattern: &[u8], string: &[u8], nocase: bool) -> bool {
+    let mut skip_longer_matches = false;
+    match_from(pattern, string, nocase, &mut skip_longer_matches, 0)
+}
+
+fn match_from(
+    pattern: &[u8],
+    string: &[u8],
+    nocase: bool,
+    skip_longer_matches: &mut bool,
+    nesting: usize,
+) -> bool {
+    if nesting > MAX_NESTING {
+        return false;
+    }
+    // Reads past the end yield 0, mirroring the NUL terminator the original relies on.
+    let at = |index: usize| pattern.get(index).copied().unwrap_or(0);
+    let equals = |a: u8, b: u8| {
+        if nocase {
+            a.eq_ignore_ascii_case(&b)
+        } else {
+            a == b
+        }
+    };
+
+    let (mut p, mut s) = (0, 0);
+    while p < pattern.len() && s < string.len() {
+        match pattern[p] {
+            b'*' => {
+                while at(p + 1) == b'*' {
+                    p += 1;
+                }
+                if p + 1 == pattern.len() {
+                    return true;
+                }
+                while s < string.len() {
+                    let rest = &pattern[p + 1..];
+                    if match_from(rest, &string[s..], nocase, skip_longer_matches, nesting + 1) {
+                        return true;
+                    }
+                    if *skip_longer_matches {
+                        return false;
+                    }
+                    s += 1;
+                }
+                // The rest of the pattern matches nowhere in the rest of the string, so an
+                // earlier `*` consuming more characters cannot help either.
+                *skip_longer_matches = true;
+                return false;
+            }
+            b'?' => s += 1,
+            b'[' => {
+                p += 1;
+                let not = at(p) == b'^';
+                if not {
+                    p += 1;
+                }
+                let mut matched = false;
+                loop {
+                    if at(p) == b'\\' && pattern.len() - p >= 2 {
+                        p += 1;
+                        if pattern[p] == string[s] {
+                            matched = true;
+                        }
+                    } else if at(p) == b']' {
+                        break;
+                    } else if p == pattern.len() {
+                        // An unterminated class ends the pattern.
+                        p -= 1;
+                        break;
+                    } else if pattern.len() - p >= 3 && pattern[p + 1] == b'-' {
+                        let (mut start, mut end) = (pattern[p], pattern[p + 2]);
+                        if start > end {
+                            std::mem::swap(&mut start, &mut end);
+                        }
+                        let mut c = string[s];
+                        if nocase {
+                            start = start.to_ascii_lowercase();
+                            end = end.to_ascii_lowercase();
+                            c = c.to_ascii_lowercase();
+                        }
+                        p += 2;
+                        if (start..=end).contains(&c) {
+                            matched = true;
+                        }
+                    } else if equals(pattern[p], string[s]) {
+                        matched = true;
+                    }
+                    p += 1;
+                }
+                if not {
+                    matched = !matched;
+                }
+                if !matched {
+                    return false;
+                }
+                s += 1;
+            }
+            c => {
+                if c == b'\\' && pattern.len() - p >= 2 {
+                    p += 1;
+                }
+                if !equals(pattern[p], string[s]) {
+                    return false;
+                }
+                s += 1;
+            }
+        }
+        p += 1;
+        if s == string.len() {
+            while at(p) == b'*' {
+                p += 1;
+            }
+            break;
+        }
+    }
+    p == pattern.len() && s == string.len()
+}
+
+#[cfg(test)]
+mod specs_for_matches {
+    use super::matches;
+    use super::matches_bytes;
+
+    #[rstest::rstest]
+    #[case("*", "", false)]
+    #[case("*", "anything", true)]
+    #[case("", "", true)]
+    #[case("", "a", false)]
+    #[case("a", "", false)]
+    #[case("h?llo", "hello", true)]
+    #[case("h?llo", "hallo", true)]
+    #[case("h?llo", "hllo", false)]
+    #[case("h*llo", "hllo", true)]
+    #[case("h*llo", "heeeello", true)]
+    #[case("h[ae]llo", "hello", true)]
+    #[case("h[ae]llo", "hallo", true)]
+    #[case("h[ae]llo", "hillo", false)]
+    #[case("h[^e]llo", "hallo", true)]
+    #[case("h[^e]llo", "hbllo", true)]
+    #[case("h[^e]llo", "hello", false)]
+    #[case("h[a-b]llo", "hallo", true)]
+    #[case("h[a-b]llo", "hbllo", true)]
+    #[case("h[a-b]llo", "hcllo", false)]
+    #[case("h[b-a]llo", "hallo", true)]
+    #[case("user:*:session:*", "user:42:session:abc", true)]
+    #[case("user:*:session:*", "user:42:profile:abc", false)]
+    #[case("*:session:*", "user:session:", true)]
+    #[case("a*b*c", "aXbYc", true)]
+    #[case("a*b*c", "aXbY", false)]
+    #[case("a**", "a", true)]
+    #[case("**a", "a", true)]
+    #[case(r"h\*llo", "h*llo", true)]
+    #[case(r"h\*llo", "hello", false)]
+    #[case(r"h\?llo", "h?llo", true)]
+    #[case(r"\[a]", "[a]", true)]
+    #[case(r"[\]]", "]", true)]
+    #[case(r"[\-]", "-", true)]
+    #[case(r"[a\-z]", "b", false)]
+    #[case(r"abc\", r"abc\", true)]
+    #[case("[abc", "a", true)]
+    #[case("[abc", "b", true)]
+    #[case("[abc", "d", false)]
+    #[case("[", "a", false)]
+    #[case("[^", "a", true)]
+    #[case("[]", "a", false)]
+    #[case("[a-", "-", true)]
+    #[case("foo[]bar", "foobar", false)]
+    #[case("*[", "a", false)]
+    #[case("?", "", false)]
+    #[case("??", "a", false)]
+    #[case("a*", "ab", true)]
+    #[case("*a", "ba", true)]
+    #[case("*a", "ab", false)]
+    fn sut_matches_like_redis(#[case] pattern: &str, #[case] string: &str, #[case] expected: bool) {
+        // Act
+        let actual = matches(pattern, string);
+
+        // Assert
+        assert_eq!(actual, expected, "{pattern:?} against {string:?}");
+    }
+
+    #[rstest::rstest]
+    #[case("HELLO", "hello", true)]
+    #[case("h[A-C]llo", "hbllo", true)]
+    #[case("h[^E]llo", "hello", false)]
+    fn sut_matches_case_insensitively_if_requested(
+        #[case] pattern: &str,
+        #[case] string: &str,
+        #[case] expected: bool,
+    ) {
+        // Act
+        let actual = matches_bytes(pattern.as_bytes(), string.as_bytes(), true);
+
+        // Assert
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn sut_gives_up_on_pathological_patterns_quickly() {
+        // Arrange
+        let pattern = "a*".repeat(50) + "b";
+        let string = "a".repeat(100);
+
+        // Act
+        let actual = matches(&pattern, &string);
+
+        // Assert
+        assert!(!actual);
+    }
+}
+
+#[cfg(test)]
+mod specs_for_matches_properties {
+    use proptest::prelude::*;
+
+    use super::matches;
+    use super::matches_bytes;
+
+    fn escape(string: &str) -> String {
+        string
+            .chars()
+            .flat_map(|c| match c {
+                '*' | '?' | '[' | ']' | '\\' => vec!['\\', c],
+                c => vec![c],
+            })
+            .collect()
+    }
+
+    proptest! {
+        #[test]
+        fn sut_never_panics(pattern in "[a-c*?\\[\\]^\\\\-]{0,12}", string in "[a-c\\-\\]]{0,12}") {
+            let _ = matches(&pattern, &string);
+        }
+
+        #[test]
+        fn sut_matches_escaped_string_only_against_itself(string in ".{0,16}", other in ".{0,16}") {
+            let pattern = escape(&string);
+            prop_assert!(matches(&pattern, &string));
+            prop_assert_eq!(matches(&pattern, &other), string == other);
+        }
+
+        #[test]
+        fn sut_matches_anything_non_empty_with_asterisk(string in ".{1,32}") {
+            prop_assert!(matches("*", &string));
+        }
+
+        #[test]
+        fn sut_matches_wildcarded_string(
+            string in "[a-z:]{1,16}",
+            wildcards in proptest::collection::vec(any::<u8>(), 16),
+        ) {
+            let pattern: String = string
+                .chars()
+                .zip(wildcards)
+                .map(|(c, wildcard)| match wildcard % 4 {
+                    0 => "?".to_string(),
+                    1 => format!("*{c}"),
+                    2 => format!("[{c}]"),
+                    _ => c.to_string(),
+                })
+                .collect();
+            prop_assert!(matches(&pattern, &string), "{} against {}", pattern, string);
+        }
+
+        #[test]
+        fn sut_matches_question_marks_only_against_strings_of_same_length(
+            string in "[a-z]{0,16}",
+            length in 0usize..16,
+        ) {
+            let pattern = "?".repeat(length);
+            prop_assert_eq!(matches(&pattern, &string), string.len() == length);
+        }
+
+        #[test]
+        fn sut_matches_negated_class_as_complement(c in "[a-z]", class in "[a-z]{1,5}") {
+            let pattern = format!("[{class}]");
+            let negated = format!("[^{class}]");
+            prop_assert_ne!(matches(&pattern, &c), matches(&negated, &c));
+        }
+
+        #[test]
+        fn sut_ignores_case_when_requested(string in "[a-zA-Z]{0,16}") {
+            let pattern = escape(&string.to_uppercase());
+            prop_assert!(matches_bytes(pattern.as_bytes(), string.as_bytes(), true));
+        }
+    }
+}
@@ -1,7 +1,20 @@
+pub mod clock;
+mod cluster;
 mod command;
 pub mod config;
+mod expiration;
+mod geo;
+mod glob;
+mod link;
+mod memory;
+pub mod notification;
+mod pubsub;
 pub mod replication;
 pub mod repository;
 mod resp;
 pub mod runner;
+mod scripting;
+mod sentinel;
 pub mod snapshot;
+mod stats;
+mod tracking;
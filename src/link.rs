@@ -0,0 +1,89 @@
+use std::collections::HashMap;
+use std::time::Duration;
+
+use tokio::io::AsyncReadExt;
+use tokio::io::AsyncWriteExt;
+use tokio::net::TcpStream;
+
+use crate::resp::Value;
+
+/// A connection to another server, such as a master monitored by a sentinel or another node
+/// of the cluster.
+pub struct Link {
+    stream: TcpStream,
+    /// Bytes read but not decoded yet.
+    buffer: Vec<u8>,
+}
+
+impl Link {
+    pub async fn connect(address: &str) -> std::io::Result<Self> {
+        let stream = TcpStream::connect(address).await?;
+        Ok(Self {
+            stream,
+            buffer: Vec::new(),
+        })
+    }
+
+    pub async fn request(&mut self, command: &[&str]) -> Result<Value, anyhow::Error> {
+        let request = Value::Array(
+            command
+                .iter()
+                .map(|part| Value::BulkString(part.to_string()))
+                .collect(),
+        );
+        self.stream.write_all(&request.serialize()).await?;
+        self.next().await
+    }
+
+    /// The next value sent by the server, such as a message published to a subscription.
+    pub async fn next(&mut self) -> Result<Value, anyhow::Error> {
+        loop {
+            if let Some((value, size)) = Value::decode(&self.buffer)? {
+                self.buffer.drain(..size);
+                return Ok(value);
+            }
+            let mut chunk = [0; 4096];
+            let read = self.stream.read(&mut chunk).await?;
+            if read == 0 {
+                anyhow::bail!("connection closed");
+            }
+            self.buffer.extend_from_slice(&chunk[..read]);
+        }
+    }
+}
+
+/// Connections to the servers, kept open between requests until one fails.
+pub struct Links {
+    links: HashMap<String, Link>,
+    timeout: Duration,
+}
+
+impl Links {
+    pub fn new(timeout: Duration) -> Self {
+        Self {
+            links: HashMap::new(),
+            timeout,
+        }
+    }
+
+    pub async fn request(
+        &mut self,
+        address: &str,
+        command: &[&str],
+    ) -> Result<Value, anyhow::Error> {
+        let mut link = self.links.remove(address);
+        let reply = tokio::time::timeout(self.timeout, async {
+            let link = match &mut link {
+                Some(link) => link,
+                None => link.insert(Link::connect(address).await?),
+            };
+            link.request(command).await
+        })
+        .await
+        .unwrap_or_else(|_| Err(anyhow::anyhow!("no reply from {address}")));
+        if let (Ok(_), Some(link)) = (&reply, link) {
+            self.links.insert(address.to_string(), link);
+        }
+        reply
+    }
+}
@@ -2,14 +2,22 @@ use std::net::Ipv4Addr;
 use std::sync::Arc;
 
 use clap::Parser;
+use codecrafters_redis::clock::SystemClock;
 use codecrafters_redis::config::ReplicationSlave;
 use tokio::net::TcpListener;
 
 use codecrafters_redis::config::Config;
+use codecrafters_redis::config::DisklessLoad;
+use codecrafters_redis::config::Monitor;
 use codecrafters_redis::config::RdbConfig;
-use codecrafters_redis::repository::InMemoryRepository;
+use codecrafters_redis::config::SentinelConfig;
+use codecrafters_redis::notification::KeyspaceEvents;
+use codecrafters_redis::repository::Databases;
 use codecrafters_redis::runner::run;
 
+/// The port sentinels listen on unless told otherwise.
+const SENTINEL_PORT: usize = 26379;
+
 #[tokio::main]
 async fn main() {
     let args = Args::parse();
@@ -17,8 +25,9 @@ async fn main() {
 
     let url = format!("{}:{}", Ipv4Addr::LOCALHOST, config.server.port);
     let listener = TcpListener::bind(url).await.unwrap();
-    let repository = Arc::new(InMemoryRepository::new());
-    run(listener, repository, config).await
+    let clock = Arc::new(SystemClock);
+    let databases = Arc::new(Databases::in_memory(config.server.databases, clock.clone()));
+    run(listener, databases, config, clock).await
 }
 
 #[derive(Debug, clap::Parser)]
@@ -32,29 +41,117 @@ struct Args {
     #[arg(long = "port")]
     server_port: Option<usize>,
 
+    #[arg(long = "databases")]
+    databases: Option<usize>,
+
+    #[arg(long = "notify-keyspace-events")]
+    notify_keyspace_events: Option<KeyspaceEvents>,
+
     #[arg(long = "replicaof")]
     replication_url: Option<String>,
+
+    #[arg(long = "repl-backlog-size")]
+    replication_backlog_size: Option<usize>,
+
+    #[arg(long = "repl-diskless-sync", value_parser = parse_yes_or_no)]
+    replication_diskless_sync: Option<bool>,
+
+    #[arg(long = "repl-diskless-sync-delay")]
+    replication_diskless_sync_delay: Option<u64>,
+
+    #[arg(long = "repl-diskless-load")]
+    replication_diskless_load: Option<DisklessLoad>,
+
+    #[arg(long = "replica-read-only", value_parser = parse_yes_or_no)]
+    replica_read_only: Option<bool>,
+
+    #[arg(long = "replica-serve-stale-data", value_parser = parse_yes_or_no)]
+    replica_serve_stale_data: Option<bool>,
+
+    #[arg(long = "cluster-enabled", value_parser = parse_yes_or_no)]
+    cluster_enabled: Option<bool>,
+
+    #[arg(long = "sentinel")]
+    sentinel: bool,
+
+    #[arg(long = "sentinel-monitor")]
+    sentinel_monitors: Vec<Monitor>,
+
+    #[arg(long = "sentinel-down-after-milliseconds")]
+    sentinel_down_after: Option<u64>,
+
+    #[arg(long = "sentinel-failover-timeout")]
+    sentinel_failover_timeout: Option<u64>,
 }
 
 impl From<Args> for Config {
     fn from(args: Args) -> Self {
         let mut config = Config::default();
 
+        if args.sentinel {
+            let mut sentinel = SentinelConfig {
+                masters: args.sentinel_monitors,
+                ..SentinelConfig::default()
+            };
+            if let Some(down_after) = args.sentinel_down_after {
+                sentinel.down_after = down_after;
+            }
+            if let Some(failover_timeout) = args.sentinel_failover_timeout {
+                sentinel.failover_timeout = failover_timeout;
+            }
+            config.sentinel = Some(sentinel);
+            config.server.port = SENTINEL_PORT;
+        }
         if let Some(server_port) = args.server_port {
             config.server.port = server_port;
         }
+        if let Some(databases) = args.databases {
+            config.server.databases = databases;
+        }
+        if let Some(notify_keyspace_events) = args.notify_keyspace_events {
+            config.server.notify_keyspace_events = notify_keyspace_events;
+        }
+        if let Some(cluster_enabled) = args.cluster_enabled {
+            config.server.cluster_enabled = cluster_enabled;
+        }
         if let Some(replication_url) = args.replication_url {
             config.replication.slave = Some(ReplicationSlave {
                 master_address: replication_url.replace(' ', ":"),
             })
         }
-        if args.rdb_directory.is_some() && args.rdb_filename.is_some() {
+        if let Some(backlog_size) = args.replication_backlog_size {
+            config.replication.backlog_size = backlog_size;
+        }
+        if let Some(diskless_sync) = args.replication_diskless_sync {
+            config.replication.diskless_sync = diskless_sync;
+        }
+        if let Some(delay) = args.replication_diskless_sync_delay {
+            config.replication.diskless_sync_delay = delay;
+        }
+        if let Some(diskless_load) = args.replication_diskless_load {
+            config.replication.diskless_load = diskless_load;
+        }
+        if let Some(read_only) = args.replica_read_only {
+            config.replication.replica_read_only = read_only;
+        }
+        if let Some(serve_stale_data) = args.replica_serve_stale_data {
+            config.replication.replica_serve_stale_data = serve_stale_data;
+        }
+        if let (Some(directory), Some(filename)) = (args.rdb_directory, args.rdb_filename) {
             config.rdb = Some(RdbConfig {
-                directory: args.rdb_directory.unwrap(),
-                filename: args.rdb_filename.unwrap(),
+                directory,
+                filename,
             });
         };
 
         config
     }
 }
+
+fn parse_yes_or_no(value: &str) -> Result<bool, String> {
+    match value.to_lowercase().as_str() {
+        "yes" => Ok(true),
+        "no" => Ok(false),
+        _ => Err(format!("expected yes or no, got {value}")),
+    }
+}
@@ -0,0 +1,126 @@
+use std::alloc::GlobalAlloc;
+use std::alloc::Layout;
+use std::alloc::System;
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
+
+static ALLOCATED: AtomicUsize = AtomicUsize::new(0);
+static PEAK: AtomicUsize = AtomicUsize::new(0);
+
+/// The system allocator, keeping count of the bytes allocated for INFO memory to report.
+struct CountingAllocator;
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+impl CountingAllocator {
+    fn grow(size: usize) {
+        let allocated = ALLOCATED.fetch_add(size, Ordering::Relaxed) + size;
+        PEAK.fetch_max(allocated, Ordering::Relaxed);
+    }
+
+    fn shrink(size: usize) {
+        ALLOCATED.fetch_sub(size, Ordering::Relaxed);
+    }
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = unsafe { System.alloc(layout) };
+        if !ptr.is_null() {
+            Self::grow(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = unsafe { System.alloc_zeroed(layout) };
+        if !ptr.is_null() {
+            Self::grow(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        unsafe { System.dealloc(ptr, layout) };
+        Self::shrink(layout.size());
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = unsafe { System.realloc(ptr, layout, new_size) };
+        if !new_ptr.is_null() {
+            Self::shrink(layout.size());
+            Self::grow(new_size);
+        }
+        new_ptr
+    }
+}
+
+/// The bytes currently allocated.
+pub fn used() -> usize {
+    ALLOCATED.load(Ordering::Relaxed)
+}
+
+/// The most bytes ever allocated at once.
+pub fn peak() -> usize {
+    PEAK.load(Ordering::Relaxed)
+}
+
+/// The resident set size of the process as the operating system sees it, if it tells.
+pub fn rss() -> usize {
+    std::fs::read_to_string("/proc/self/status")
+        .ok()
+        .and_then(|status| {
+            status
+                .lines()
+                .find_map(|line| line.strip_prefix("VmRSS:"))
+                .and_then(|rss| rss.trim().trim_end_matches("kB").trim().parse().ok())
+        })
+        .map_or(0, |kilobytes: usize| kilobytes * 1024)
+}
+
+/// Formats a size the way INFO does, e.g. `1.50M`.
+pub fn human(bytes: usize) -> String {
+    const UNITS: [&str; 5] = ["B", "K", "M", "G", "T"];
+    let mut size = bytes as f64;
+    let mut unit = 0;
+    while size >= 1024.0 && unit < UNITS.len() - 1 {
+        size /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{bytes}B")
+    } else {
+        format!("{size:.2}{}", UNITS[unit])
+    }
+}
+
+#[cfg(test)]
+mod specs_for_memory {
+    use super::human;
+    use super::peak;
+    use super::used;
+
+    #[rstest::rstest]
+    #[case(512, "512B")]
+    #[case(1024, "1.00K")]
+    #[case(1536 * 1024, "1.50M")]
+    #[case(3 * 1024 * 1024 * 1024, "3.00G")]
+    fn sut_formats_sizes_for_humans(#[case] bytes: usize, #[case] expected: &str) {
+        // Act
+        let actual = human(bytes);
+
+        // Assert
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn sut_counts_allocated_bytes() {
+        // Act
+        let buffer = vec![0u8; 1 << 20];
+
+        // Assert
+        assert!(used() >= buffer.len());
+        assert!(peak() >= used());
+    }
+}
@@ -0,0 +1,303 @@
+use std::fmt;
+use std::str::FromStr;
+use std::sync::Arc;
+
+use crate::pubsub::Broker;
+use crate::replication::Replication;
+use crate::repository::Databases;
+use crate::repository::KeyEvent;
+use crate::resp::Value;
+use crate::tracking::Tracking;
+
+/// The class of an event, each enabled by one flag of `notify-keyspace-events`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Class {
+    Generic,
+    String,
+    List,
+    Set,
+    Hash,
+    SortedSet,
+    Expired,
+    Evicted,
+    Stream,
+    KeyMiss,
+    New,
+}
+
+impl Class {
+    const ALL: [Class; 11] = [
+        Class::Generic,
+        Class::String,
+        Class::List,
+        Class::Set,
+        Class::Hash,
+        Class::SortedSet,
+        Class::Expired,
+        Class::Evicted,
+        Class::Stream,
+        Class::KeyMiss,
+        Class::New,
+    ];
+
+    fn flag(&self) -> char {
+        match self {
+            Class::Generic => 'g',
+            Class::String => '$',
+            Class::List => 'l',
+            Class::Set => 's',
+            Class::Hash => 'h',
+            Class::SortedSet => 'z',
+            Class::Expired => 'x',
+            Class::Evicted => 'e',
+            Class::Stream => 't',
+            Class::KeyMiss => 'm',
+            Class::New => 'n',
+        }
+    }
+
+    fn bit(&self) -> u16 {
+        1 << (*self as u16)
+    }
+
+    /// Whether the class is part of the `A` alias, which leaves out key misses and new keys.
+    fn is_aliased(&self) -> bool {
+        !matches!(self, Class::KeyMiss | Class::New)
+    }
+}
+
+const KEYSPACE: u16 = 1 << 14;
+const KEYEVENT: u16 = 1 << 15;
+
+/// The parsed value of `notify-keyspace-events`, empty by default so nothing is published.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct KeyspaceEvents(u16);
+
+impl KeyspaceEvents {
+    /// Whether events of the class are published to any channel at all.
+    pub fn allows(&self, class: Class) -> bool {
+        self.0 & (KEYSPACE | KEYEVENT) != 0 && self.0 & class.bit() != 0
+    }
+
+    fn keyspace(&self) -> bool {
+        self.0 & KEYSPACE != 0
+    }
+
+    fn keyevent(&self) -> bool {
+        self.0 & KEYEVENT != 0
+    }
+}
+
+impl FromStr for KeyspaceEvents {
+    type Err = String;
+
+    fn from_str(flags: &str) -> Result<Self, Self::Err> {
+        let mut bits = 0;
+        for flag in flags.chars() {
+            bits |= match flag {
+                'K' => KEYSPACE,
+                'E' => KEYEVENT,
+                'A' => Class::ALL
+                    .iter()
+                    .filter(|class| class.is_aliased())
+                    .fold(0, |bits, class| bits | class.bit()),
+                _ => Class::ALL
+                    .iter()
+                    .find(|class| class.flag() == flag)
+                    .map(Class::bit)
+                    .ok_or_else(|| format!("Invalid event class character '{flag}'"))?,
+            };
+        }
+        Ok(KeyspaceEvents(bits))
+    }
+}
+
+impl fmt::Display for KeyspaceEvents {
+    /// Writes the flags the way CONFIG GET shows them, using `A` whenever it applies.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (aliased, others): (Vec<Class>, Vec<Class>) =
+            Class::ALL.into_iter().partition(Class::is_aliased);
+        if aliased.iter().all(|class| self.0 & class.bit() != 0) {
+            write!(f, "A")?;
+        } else {
+            for class in aliased.iter().filter(|class| self.0 & class.bit() != 0) {
+                write!(f, "{}", class.flag())?;
+            }
+        }
+        if self.keyspace() {
+            write!(f, "K")?;
+        }
+        if self.keyevent() {
+            write!(f, "E")?;
+        }
+        for class in others.iter().filter(|class| self.0 & class.bit() != 0) {
+            write!(f, "{}", class.flag())?;
+        }
+        Ok(())
+    }
+}
+
+/// Publishes keyspace and keyevent notifications of the enabled classes, and invalidates the
+/// keys modified for the clients tracking them.
+pub struct Notifier {
+    events: KeyspaceEvents,
+    broker: Arc<Broker>,
+    tracking: Arc<Tracking>,
+    /// Told about keys found expired, which replicas delete as the master does.
+    replication: Arc<Replication>,
+}
+
+impl Notifier {
+    pub fn new(
+        events: KeyspaceEvents,
+        broker: Arc<Broker>,
+        tracking: Arc<Tracking>,
+        replication: Arc<Replication>,
+    ) -> Self {
+        Self {
+            events,
+            broker,
+            tracking,
+            replication,
+        }
+    }
+
+    /// Publishes `event` to `__keyspace@<database>__:<key>` and `key` to
+    /// `__keyevent@<database>__:<event>`, as far as the configuration asks for either. `origin`
+    /// is the client whose command caused the event, if any.
+    pub fn notify(
+        &self,
+        database: usize,
+        class: Class,
+        event: &str,
+        key: &str,
+        origin: Option<u64>,
+    ) {
+        if !matches!(class, Class::KeyMiss | Class::New) {
+            self.tracking.invalidate(key, origin);
+        }
+        if !self.events.allows(class) {
+            return;
+        }
+        if self.events.keyspace() {
+            let channel = format!("__keyspace@{database}__:{key}");
+            self.broker.publish(&channel, event);
+        }
+        if self.events.keyevent() {
+            let channel = format!("__keyevent@{database}__:{event}");
+            self.broker.publish(&channel, key);
+        }
+    }
+
+    /// Publishes what the repositories recorded on their own, such as keys found expired, so
+    /// they come before any event of the command that follows.
+    pub fn flush(&self, databases: &Databases) {
+        for (database, repository) in databases.all().into_iter().enumerate() {
+            for event in repository.take_events() {
+                match event {
+                    KeyEvent::Expired(key) => {
+                        let del = Value::Array(vec![
+                            Value::BulkString("DEL".to_string()),
+                            Value::BulkString(key.clone()),
+                        ]);
+                        self.replication.propagate(database, &del);
+                        self.notify(database, Class::Expired, "expired", &key, None)
+                    }
+                    KeyEvent::New(key) => self.notify(database, Class::New, "new", &key, None),
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod specs_for_keyspace_events {
+    use super::Class;
+    use super::KeyspaceEvents;
+
+    #[rstest::rstest]
+    #[case("", "")]
+    #[case("Ex", "xE")]
+    #[case("KEA", "AKE")]
+    #[case("g$lshzxetKE", "AKE")]
+    #[case("AKEmn", "AKEmn")]
+    #[case("nK$", "$Kn")]
+    fn sut_formats_flags_like_config_get(#[case] flags: &str, #[case] expected: &str) {
+        // Act
+        let actual = flags.parse::<KeyspaceEvents>().unwrap().to_string();
+
+        // Assert
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn sut_rejects_unknown_flags() {
+        // Act
+        let actual = "KEq".parse::<KeyspaceEvents>();
+
+        // Assert
+        assert!(actual.is_err());
+    }
+
+    #[rstest::rstest]
+    #[case("Ex", Class::Expired, true)]
+    #[case("x", Class::Expired, false)]
+    #[case("KA", Class::Generic, true)]
+    #[case("KA", Class::KeyMiss, false)]
+    #[case("Em", Class::KeyMiss, true)]
+    fn sut_allows_classes_only_with_a_channel_kind(
+        #[case] flags: &str,
+        #[case] class: Class,
+        #[case] expected: bool,
+    ) {
+        // Act
+        let actual = flags.parse::<KeyspaceEvents>().unwrap().allows(class);
+
+        // Assert
+        assert_eq!(actual, expected);
+    }
+}
+
+#[cfg(test)]
+mod specs_for_notifier {
+    use std::sync::Arc;
+
+    use crate::pubsub::Broker;
+    use crate::pubsub::Scope;
+    use crate::pubsub::Subscriber;
+    use crate::replication::Replication;
+    use crate::resp::Value;
+
+    use super::Class;
+    use super::Notifier;
+
+    #[tokio::test]
+    async fn sut_publishes_to_keyspace_and_keyevent_channels() {
+        // Arrange
+        let broker = Arc::new(Broker::default());
+        let subscriber = Arc::new(Subscriber::new(8));
+        broker.subscribe(Scope::Pattern, "__key*__:*", &subscriber);
+        let sut = Notifier::new(
+            "KEg".parse().unwrap(),
+            broker,
+            Arc::default(),
+            Arc::new(Replication::new(None, 1024)),
+        );
+
+        // Act
+        sut.notify(3, Class::Generic, "del", "foo", None);
+        sut.notify(3, Class::String, "set", "foo", None);
+
+        // Assert
+        let mut messages = subscriber.messages().await;
+        let mut channels = vec![];
+        while let Ok(Value::Array(message)) = messages.try_recv() {
+            channels.push(message[2].clone());
+        }
+        let expected = vec![
+            Value::BulkString("__keyspace@3__:foo".to_string()),
+            Value::BulkString("__keyevent@3__:del".to_string()),
+        ];
+        assert_eq!(channels, expected);
+    }
+}
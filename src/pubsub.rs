@@ -0,0 +1,398 @@
+use std::collections::BTreeSet;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::sync::atomic::AtomicU64;
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
+
+use tokio::sync::MutexGuard;
+use tokio::sync::Notify;
+use tokio::sync::Semaphore;
+use tokio::sync::mpsc;
+use tokio::sync::mpsc::error::TrySendError;
+
+use crate::glob;
+use crate::resp::Value;
+
+static NEXT_SUBSCRIBER_ID: AtomicU64 = AtomicU64::new(1);
+
+/// What a client subscribes to: a channel by its name, or every channel matching a pattern.
+/// Shard channels live apart from the others, so a message published to one never reaches
+/// subscribers of the other.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Scope {
+    Channel,
+    Pattern,
+    Shard,
+}
+
+impl Scope {
+    pub fn subscribe_reply(&self) -> &'static str {
+        match self {
+            Scope::Channel => "subscribe",
+            Scope::Pattern => "psubscribe",
+            Scope::Shard => "ssubscribe",
+        }
+    }
+
+    pub fn unsubscribe_reply(&self) -> &'static str {
+        match self {
+            Scope::Channel => "unsubscribe",
+            Scope::Pattern => "punsubscribe",
+            Scope::Shard => "sunsubscribe",
+        }
+    }
+}
+
+/// The receiving end of a client connection, holding the messages yet to be written to it.
+pub struct Subscriber {
+    id: u64,
+    sender: mpsc::Sender<Value>,
+    receiver: tokio::sync::Mutex<mpsc::Receiver<Value>>,
+    /// How many messages may be buffered before the client is considered too slow to keep up.
+    limit: AtomicUsize,
+    /// Notified once the client is to be disconnected, as when a message could not be buffered.
+    closed: Notify,
+    subscriptions: Mutex<BTreeSet<(Scope, String)>>,
+}
+
+impl Subscriber {
+    /// Buffers up to `limit` messages before the client is considered too slow to keep up.
+    pub fn new(limit: usize) -> Self {
+        // The channel itself is never full, the limit being changed as the client becomes a
+        // replica.
+        let (sender, receiver) = mpsc::channel(Semaphore::MAX_PERMITS);
+        Self {
+            id: NEXT_SUBSCRIBER_ID.fetch_add(1, Ordering::SeqCst),
+            sender,
+            receiver: tokio::sync::Mutex::new(receiver),
+            limit: AtomicUsize::new(limit.max(1)),
+            closed: Notify::new(),
+            subscriptions: Mutex::default(),
+        }
+    }
+
+    /// The buffered messages, only ever read by the connection of the client.
+    pub async fn messages(&self) -> MutexGuard<'_, mpsc::Receiver<Value>> {
+        self.receiver.lock().await
+    }
+
+    pub async fn closed(&self) {
+        self.closed.notified().await;
+    }
+
+    /// Disconnects the client once its connection gets to it.
+    pub fn close(&self) {
+        self.closed.notify_one();
+    }
+
+    pub fn id(&self) -> u64 {
+        self.id
+    }
+
+    pub fn is_subscribed(&self) -> bool {
+        !self.subscriptions.lock().unwrap().is_empty()
+    }
+
+    pub fn subscriptions(&self, scope: Scope) -> Vec<String> {
+        self.subscriptions
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|(s, _)| *s == scope)
+            .map(|(_, name)| name.clone())
+            .collect()
+    }
+
+    /// How many shard channels the subscriber has for the shard scope, or how many channels
+    /// and patterns otherwise.
+    pub fn count(&self, scope: Scope) -> usize {
+        let sharded = scope == Scope::Shard;
+        self.subscriptions
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|(s, _)| (*s == Scope::Shard) == sharded)
+            .count()
+    }
+
+    pub fn set_limit(&self, limit: usize) {
+        self.limit.store(limit.max(1), Ordering::SeqCst);
+    }
+
+    pub fn push(&self, message: Value) {
+        let buffered = self.sender.max_capacity() - self.sender.capacity();
+        if buffered >= self.limit.load(Ordering::SeqCst) {
+            self.close();
+            return;
+        }
+        if let Err(TrySendError::Full(_)) = self.sender.try_send(message) {
+            self.close();
+        }
+    }
+}
+
+/// The subscribers of every channel or pattern, by name.
+type Subscriptions = HashMap<String, Vec<Arc<Subscriber>>>;
+
+/// Routes published messages to the subscribers of channels and patterns.
+#[derive(Default)]
+pub struct Broker {
+    subscriptions: Mutex<HashMap<Scope, Subscriptions>>,
+}
+
+impl Broker {
+    /// Subscribes to the channel or pattern, returning how many subscriptions of the same
+    /// kind the subscriber has now.
+    pub fn subscribe(&self, scope: Scope, name: &str, subscriber: &Arc<Subscriber>) -> usize {
+        let mut subscriptions = self.subscriptions.lock().unwrap();
+        let added = subscriber
+            .subscriptions
+            .lock()
+            .unwrap()
+            .insert((scope, name.to_string()));
+        if added {
+            subscriptions
+                .entry(scope)
+                .or_default()
+                .entry(name.to_string())
+                .or_default()
+                .push(subscriber.clone());
+        }
+        subscriber.count(scope)
+    }
+
+    /// Unsubscribes from the channel or pattern, returning how many subscriptions of the
+    /// same kind the subscriber has left.
+    pub fn unsubscribe(&self, scope: Scope, name: &str, subscriber: &Subscriber) -> usize {
+        let mut subscriptions = self.subscriptions.lock().unwrap();
+        subscriber
+            .subscriptions
+            .lock()
+            .unwrap()
+            .remove(&(scope, name.to_string()));
+        if let Some(names) = subscriptions.get_mut(&scope)
+            && let Some(subscribers) = names.get_mut(name)
+        {
+            subscribers.retain(|other| other.id != subscriber.id);
+            if subscribers.is_empty() {
+                names.remove(name);
+            }
+        }
+        subscriber.count(scope)
+    }
+
+    /// Drops every subscription of a client which disconnected.
+    pub fn unsubscribe_all(&self, subscriber: &Subscriber) {
+        let subscribed: Vec<(Scope, String)> = subscriber
+            .subscriptions
+            .lock()
+            .unwrap()
+            .iter()
+            .cloned()
+            .collect();
+        for (scope, name) in subscribed {
+            self.unsubscribe(scope, &name, subscriber);
+        }
+    }
+
+    /// Pushes the message to the subscribers of the channel and of the patterns matching it,
+    /// returning how many received it.
+    pub fn publish(&self, channel: &str, message: &str) -> usize {
+        let subscriptions = self.subscriptions.lock().unwrap();
+        let mut receivers = 0;
+        if let Some(subscribers) = subscriptions
+            .get(&Scope::Channel)
+            .and_then(|channels| channels.get(channel))
+        {
+            for subscriber in subscribers {
+                subscriber.push(Value::Array(vec![
+                    Value::BulkString("message".to_string()),
+                    Value::BulkString(channel.to_string()),
+                    Value::BulkString(message.to_string()),
+                ]));
+            }
+            receivers += subscribers.len();
+        }
+        for (pattern, subscribers) in subscriptions.get(&Scope::Pattern).into_iter().flatten() {
+            if !glob::matches(pattern, channel) {
+                continue;
+            }
+            for subscriber in subscribers {
+                subscriber.push(Value::Array(vec![
+                    Value::BulkString("pmessage".to_string()),
+                    Value::BulkString(pattern.clone()),
+                    Value::BulkString(channel.to_string()),
+                    Value::BulkString(message.to_string()),
+                ]));
+            }
+            receivers += subscribers.len();
+        }
+        receivers
+    }
+
+    /// Pushes the message to the subscribers of the shard channel, returning how many received
+    /// it.
+    pub fn publish_sharded(&self, channel: &str, message: &str) -> usize {
+        let subscriptions = self.subscriptions.lock().unwrap();
+        let Some(subscribers) = subscriptions
+            .get(&Scope::Shard)
+            .and_then(|channels| channels.get(channel))
+        else {
+            return 0;
+        };
+        for subscriber in subscribers {
+            subscriber.push(Value::Array(vec![
+                Value::BulkString("smessage".to_string()),
+                Value::BulkString(channel.to_string()),
+                Value::BulkString(message.to_string()),
+            ]));
+        }
+        subscribers.len()
+    }
+
+    /// The channels or patterns with at least one subscriber, optionally only those matching
+    /// the pattern.
+    pub fn names(&self, scope: Scope, pattern: Option<&str>) -> Vec<String> {
+        let subscriptions = self.subscriptions.lock().unwrap();
+        let mut names: Vec<String> = subscriptions
+            .get(&scope)
+            .into_iter()
+            .flat_map(|names| names.keys())
+            .filter(|name| pattern.is_none_or(|pattern| glob::matches(pattern, name)))
+            .cloned()
+            .collect();
+        names.sort();
+        names
+    }
+
+    pub fn subscribers(&self, scope: Scope, name: &str) -> usize {
+        self.subscriptions
+            .lock()
+            .unwrap()
+            .get(&scope)
+            .and_then(|names| names.get(name))
+            .map_or(0, Vec::len)
+    }
+}
+
+#[cfg(test)]
+mod specs_for_broker {
+    use std::sync::Arc;
+
+    use crate::resp::Value;
+
+    use super::Broker;
+    use super::Scope;
+    use super::Subscriber;
+
+    #[tokio::test]
+    async fn sut_delivers_message_to_channel_and_pattern_subscribers() {
+        // Arrange
+        let sut = Broker::default();
+        let channel = Arc::new(Subscriber::new(8));
+        let pattern = Arc::new(Subscriber::new(8));
+        sut.subscribe(Scope::Channel, "news.tech", &channel);
+        sut.subscribe(Scope::Pattern, "news.*", &pattern);
+
+        // Act
+        let actual = sut.publish("news.tech", "hello");
+
+        // Assert
+        assert_eq!(actual, 2);
+        let message = channel.messages().await.recv().await.unwrap();
+        let expected = Value::Array(vec![
+            Value::BulkString("message".to_string()),
+            Value::BulkString("news.tech".to_string()),
+            Value::BulkString("hello".to_string()),
+        ]);
+        assert_eq!(message, expected);
+        let message = pattern.messages().await.recv().await.unwrap();
+        let expected = Value::Array(vec![
+            Value::BulkString("pmessage".to_string()),
+            Value::BulkString("news.*".to_string()),
+            Value::BulkString("news.tech".to_string()),
+            Value::BulkString("hello".to_string()),
+        ]);
+        assert_eq!(message, expected);
+    }
+
+    #[tokio::test]
+    async fn sut_keeps_shard_channels_apart_from_channels() {
+        // Arrange
+        let sut = Broker::default();
+        let channel = Arc::new(Subscriber::new(8));
+        let shard = Arc::new(Subscriber::new(8));
+        sut.subscribe(Scope::Channel, "orders", &channel);
+        sut.subscribe(Scope::Pattern, "*", &channel);
+        let count = sut.subscribe(Scope::Shard, "orders", &shard);
+
+        // Act
+        let actual = sut.publish_sharded("orders", "hello");
+
+        // Assert
+        assert_eq!(count, 1);
+        assert_eq!(actual, 1);
+        let message = shard.messages().await.recv().await.unwrap();
+        let expected = Value::Array(vec![
+            Value::BulkString("smessage".to_string()),
+            Value::BulkString("orders".to_string()),
+            Value::BulkString("hello".to_string()),
+        ]);
+        assert_eq!(message, expected);
+        assert!(channel.messages().await.try_recv().is_err());
+        assert_eq!(sut.publish("orders", "hello"), 2);
+        assert!(shard.messages().await.try_recv().is_err());
+    }
+
+    #[tokio::test]
+    async fn sut_counts_each_subscription_once() {
+        // Arrange
+        let sut = Broker::default();
+        let subscriber = Arc::new(Subscriber::new(8));
+        sut.subscribe(Scope::Channel, "foo", &subscriber);
+
+        // Act
+        let actual = sut.subscribe(Scope::Channel, "foo", &subscriber);
+
+        // Assert
+        assert_eq!(actual, 1);
+        assert_eq!(sut.subscribers(Scope::Channel, "foo"), 1);
+    }
+
+    #[tokio::test]
+    async fn sut_forgets_channels_without_subscribers() {
+        // Arrange
+        let sut = Broker::default();
+        let subscriber = Arc::new(Subscriber::new(8));
+        sut.subscribe(Scope::Channel, "foo", &subscriber);
+        sut.subscribe(Scope::Pattern, "f*", &subscriber);
+
+        // Act
+        sut.unsubscribe_all(&subscriber);
+
+        // Assert
+        assert!(sut.names(Scope::Channel, None).is_empty());
+        assert!(sut.names(Scope::Pattern, None).is_empty());
+        assert!(!subscriber.is_subscribed());
+        assert_eq!(sut.publish("foo", "bar"), 0);
+    }
+
+    #[tokio::test]
+    async fn sut_notifies_subscriber_whose_buffer_overflows() {
+        // Arrange
+        let sut = Broker::default();
+        let subscriber = Arc::new(Subscriber::new(1));
+        sut.subscribe(Scope::Channel, "foo", &subscriber);
+        sut.publish("foo", "first");
+
+        // Act
+        sut.publish("foo", "second");
+
+        // Assert
+        let overflowed =
+            tokio::time::timeout(std::time::Duration::from_secs(1), subscriber.closed()).await;
+        assert!(overflowed.is_ok());
+    }
+}
@@ -0,0 +1,76 @@
+use std::collections::VecDeque;
+
+/// The latest bytes of the replication stream, for replicas which lost their link for a
+/// while to catch up without a full resynchronization.
+pub struct Backlog {
+    bytes: VecDeque<u8>,
+    capacity: usize,
+    /// The offset of the replication stream right after the last byte kept.
+    end: u64,
+}
+
+impl Backlog {
+    pub fn new(capacity: usize, end: u64) -> Self {
+        Self {
+            bytes: VecDeque::new(),
+            capacity,
+            end,
+        }
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// The offset of the replication stream at the first byte kept.
+    pub fn start(&self) -> u64 {
+        self.end - self.bytes.len() as u64
+    }
+
+    pub fn len(&self) -> usize {
+        self.bytes.len()
+    }
+
+    /// Keeps the bytes, dropping the oldest ones once over capacity.
+    pub fn append(&mut self, bytes: &[u8]) {
+        self.bytes.extend(bytes);
+        self.end += bytes.len() as u64;
+        let excess = self.bytes.len().saturating_sub(self.capacity);
+        self.bytes.drain(..excess);
+    }
+
+    /// The bytes from `offset` on, unless some of them were dropped already or `offset` lies
+    /// past the end of the stream.
+    pub fn since(&self, offset: u64) -> Option<Vec<u8>> {
+        if offset < self.start() || offset > self.end {
+            return None;
+        }
+        let skip = (offset - self.start()) as usize;
+        Some(self.bytes.range(skip..).copied().collect())
+    }
+}
+
+#[cfg(test)]
+mod specs_for_backlog {
+    use super::Backlog;
+
+    #[rstest::rstest]
+    #[case(100, Some(b"".to_vec()))]
+    #[case(96, Some(b"ghij".to_vec()))]
+    #[case(94, Some(b"efghij".to_vec()))]
+    #[case(93, None)]
+    #[case(101, None)]
+    fn sut_keeps_only_the_latest_bytes(#[case] offset: u64, #[case] expected: Option<Vec<u8>>) {
+        // Arrange
+        let mut sut = Backlog::new(6, 90);
+
+        // Act
+        sut.append(b"abcd");
+        sut.append(b"efghij");
+
+        // Assert
+        assert_eq!(sut.since(offset), expected);
+        assert_eq!(sut.start(), 94);
+        assert_eq!(sut.len(), 6);
+    }
+}
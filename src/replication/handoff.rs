@@ -0,0 +1,102 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use tokio::sync::RwLock;
+use tokio::time::Instant;
+
+use crate::replication::FailoverState;
+use crate::replication::Replication;
+
+/// A coordinated handoff of the master role to one of the replicas, as FAILOVER asks.
+#[derive(Debug, Default, PartialEq)]
+pub struct Handoff {
+    /// The replica to hand over to, as `host:port`, or the first one to catch up otherwise.
+    pub target: Option<String>,
+    pub timeout: Option<Duration>,
+    /// Whether to hand over to the target even though it did not catch up in time.
+    pub force: bool,
+}
+
+impl Handoff {
+    /// Waits for the target to catch up with the stream while writes are paused since the
+    /// failover began, then follows it, asking it to take over. Writes go on as before once
+    /// the timeout elapses, unless forced.
+    pub async fn run(self, replication: Arc<Replication>, execution: Arc<RwLock<()>>) {
+        let deadline = self.timeout.map(|timeout| Instant::now() + timeout);
+        // Writes let in before the pause may still be running, so the stream ends after them.
+        let offset = tokio::select! {
+            drained = execution.write() => {
+                let offset = replication.offset();
+                drop(drained);
+                offset
+            }
+            // Aborted meanwhile.
+            _ = replication.writable() => return,
+        };
+        let synced = tokio::select! {
+            synced = replication.wait_for_replica(self.target.as_deref(), offset, self.timeout) => synced,
+            // Aborted meanwhile.
+            _ = replication.writable() => return,
+        };
+        let Some(target) = synced.or(self.target.filter(|_| self.force)) else {
+            replication.end_failover(false);
+            return;
+        };
+        if replication.failover() != FailoverState::WaitingForSync {
+            return;
+        }
+        replication.hand_over(target);
+        if let Some(deadline) = deadline {
+            tokio::select! {
+                _ = replication.writable() => {}
+                _ = tokio::time::sleep_until(deadline) => replication.end_failover(false),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod specs_for_run {
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    use tokio::sync::RwLock;
+
+    use crate::command::session::Session;
+    use crate::replication::FailoverState;
+    use crate::replication::Replication;
+    use crate::resp::Value;
+
+    use super::Handoff;
+
+    #[tokio::test]
+    async fn sut_waits_for_writes_let_in_before_the_pause_to_reach_the_target() {
+        // Arrange
+        let replication = Arc::new(Replication::new(None, 1024));
+        let replica = Session::new(16);
+        replica.set_address("10.0.0.2");
+        replication.register(&replica, Some(6380));
+        replication.attach(&replica);
+        let execution = Arc::new(RwLock::new(()));
+        let in_flight = execution.clone().write_owned().await;
+        replication.begin_failover();
+        let sut = Handoff {
+            timeout: Some(Duration::from_millis(100)),
+            ..Handoff::default()
+        };
+
+        // Act
+        let running = tokio::spawn(sut.run(replication.clone(), execution));
+        tokio::time::sleep(Duration::from_millis(10)).await;
+        let set = Value::Array(vec![Value::BulkString("SET".to_string())]);
+        replication.propagate(0, &set);
+        drop(in_flight);
+        tokio::time::sleep(Duration::from_millis(10)).await;
+        let actual = replication.failover();
+        running.await.unwrap();
+
+        // Assert
+        assert_eq!(actual, FailoverState::WaitingForSync);
+        assert!(!replication.is_replica());
+    }
+}
@@ -0,0 +1,836 @@
+mod backlog;
+mod handoff;
+mod replicator;
+
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::Ordering;
+use std::time::Duration;
+
+use rand::Rng;
+use tokio::sync::Notify;
+use tokio::sync::futures::Notified;
+use tokio::sync::watch;
+
+use crate::command::session::Session;
+use crate::pubsub::Subscriber;
+use crate::resp::Value;
+
+use backlog::Backlog;
+
+pub use handoff::Handoff;
+pub use replicator::Replicator;
+pub use replicator::Resync;
+pub use replicator::run;
+
+/// A replica connected to this server, known from its first REPLCONF on.
+struct Replica {
+    id: u64,
+    subscriber: Arc<Subscriber>,
+    address: String,
+    port: Option<u16>,
+    /// Whether the replica asked for PSYNC and is sent the replication stream since.
+    online: bool,
+    /// The offset of the stream the replica last acknowledged applying.
+    acknowledged: u64,
+    /// The offset of the stream the replica last acknowledged writing to its AOF, if any.
+    fsynced: Option<u64>,
+    /// What the replica announced it supports with REPLCONF capa.
+    capabilities: Vec<String>,
+}
+
+/// What INFO replication reports of a replica.
+#[derive(Debug, PartialEq)]
+pub struct ReplicaInfo {
+    pub address: String,
+    pub port: Option<u16>,
+    pub online: bool,
+    pub offset: u64,
+}
+
+/// What INFO replication reports of the backlog.
+#[derive(Debug, PartialEq)]
+pub struct BacklogInfo {
+    pub size: usize,
+    /// The offset of the first byte kept, counting from 1 as Redis does.
+    pub first_byte_offset: u64,
+    pub histlen: usize,
+}
+
+/// A snapshot streamed at once to every replica which asked for it meanwhile, framed with
+/// `$EOF:<mark>` and the same mark after it rather than with its length.
+#[derive(Clone, Debug)]
+pub struct Transfer {
+    pub id: String,
+    pub offset: u64,
+    pub payload: Arc<Vec<u8>>,
+}
+
+/// The replicas waiting for a diskless transfer to start.
+struct Batch {
+    replicas: Vec<u64>,
+    sender: watch::Sender<Option<Transfer>>,
+}
+
+/// Where a coordinated handoff to a replica, started with FAILOVER, stands.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum FailoverState {
+    #[default]
+    None,
+    /// Writes are paused until the target replica caught up with the stream.
+    WaitingForSync,
+    /// This server follows the target, asking it to take over as master.
+    InProgress,
+}
+
+impl std::fmt::Display for FailoverState {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            FailoverState::None => "no-failover",
+            FailoverState::WaitingForSync => "waiting-for-sync",
+            FailoverState::InProgress => "failover-in-progress",
+        };
+        f.write_str(name)
+    }
+}
+
+struct State {
+    id: String,
+    /// How many bytes of the replication stream were produced, or applied by a replica.
+    offset: u64,
+    /// The replication ID of the history this one continues, and the offset up to which
+    /// both are the same, so replicas of the former master may still catch up.
+    second_id: Option<(String, u64)>,
+    backlog: Backlog,
+    /// The address of the master, as long as this server is a replica.
+    master: Option<String>,
+    /// Whether the dataset follows the history under the replication ID, so a master sharing
+    /// it may send the writes missed rather than a snapshot.
+    resumable: bool,
+    /// Whether the link to the master is up, synchronized and following its writes.
+    linked: bool,
+    failover: FailoverState,
+    replicas: Vec<Replica>,
+    /// The database of the last command propagated, so SELECT precedes any of another one.
+    database: Option<usize>,
+    batch: Option<Batch>,
+}
+
+/// The replication ID and offset of this server, its role, and the replicas it feeds.
+pub struct Replication {
+    state: Mutex<State>,
+    /// Notified whenever a replica acknowledges the stream, for WAIT to count again.
+    acknowledgements: Notify,
+    /// Notified whenever this server is to follow another master, or none anymore.
+    relinked: Notify,
+    /// Whether writes of clients wait, as during a failover.
+    paused: watch::Sender<bool>,
+    /// Raised as long as this server is a replica, for the databases to leave expired keys to
+    /// the master.
+    replica: Arc<AtomicBool>,
+}
+
+impl Replication {
+    /// Starts a new history under a random replication ID, following `master` if given, and
+    /// keeping up to `backlog_size` bytes of it for replicas to catch up.
+    pub fn new(master: Option<String>, backlog_size: usize) -> Self {
+        let replica = Arc::new(AtomicBool::new(master.is_some()));
+        Self {
+            state: Mutex::new(State {
+                id: random_id(),
+                offset: 0,
+                second_id: None,
+                backlog: Backlog::new(backlog_size, 0),
+                master,
+                resumable: false,
+                linked: false,
+                failover: FailoverState::None,
+                replicas: Vec::new(),
+                database: None,
+                batch: None,
+            }),
+            acknowledgements: Notify::new(),
+            relinked: Notify::new(),
+            paused: watch::Sender::new(false),
+            replica,
+        }
+    }
+
+    /// Keeps `replica` raised as long as this server is a replica from now on.
+    pub fn with_role(mut self, replica: Arc<AtomicBool>) -> Self {
+        replica.store(self.is_replica(), Ordering::SeqCst);
+        self.replica = replica;
+        self
+    }
+
+    pub fn id(&self) -> String {
+        self.state.lock().unwrap().id.clone()
+    }
+
+    pub fn offset(&self) -> u64 {
+        self.state.lock().unwrap().offset
+    }
+
+    pub fn master(&self) -> Option<String> {
+        self.state.lock().unwrap().master.clone()
+    }
+
+    pub fn is_replica(&self) -> bool {
+        self.master().is_some()
+    }
+
+    pub fn second_id(&self) -> Option<(String, u64)> {
+        self.state.lock().unwrap().second_id.clone()
+    }
+
+    pub fn backlog(&self) -> BacklogInfo {
+        let state = self.state.lock().unwrap();
+        BacklogInfo {
+            size: state.backlog.capacity(),
+            first_byte_offset: state.backlog.start() + 1,
+            histlen: state.backlog.len(),
+        }
+    }
+
+    /// The replication ID and offset to go on from once connected to the master, as long as
+    /// the dataset follows a history the master may share.
+    pub fn resume_point(&self) -> Option<(String, u64)> {
+        let state = self.state.lock().unwrap();
+        state.resumable.then(|| (state.id.clone(), state.offset))
+    }
+
+    pub fn is_linked(&self) -> bool {
+        self.state.lock().unwrap().linked
+    }
+
+    /// Records the link to the master broke, or is about to be replaced.
+    pub fn unlink(&self) {
+        self.state.lock().unwrap().linked = false;
+    }
+
+    /// Notified once this server is to follow another master, or none anymore.
+    pub fn relinked(&self) -> Notified<'_> {
+        self.relinked.notified()
+    }
+
+    /// Follows the master at `address` from now on, returning false if it already does. The
+    /// dataset is kept, so the master may send only the writes it misses.
+    pub fn replicate_from(&self, address: String) -> bool {
+        let mut state = self.state.lock().unwrap();
+        if state.master.as_ref() == Some(&address) {
+            return false;
+        }
+        state.master = Some(address);
+        state.resumable = true;
+        self.replica.store(true, Ordering::SeqCst);
+        drop(state);
+        self.relinked.notify_waiters();
+        true
+    }
+
+    /// Takes over the history of the master after a full resynchronization. The replicas of
+    /// this server are disconnected, as what they hold belongs to the former history.
+    pub fn follow(&self, id: String, offset: u64) {
+        let mut state = self.state.lock().unwrap();
+        state.id = id;
+        state.offset = offset;
+        state.resumable = true;
+        state.linked = true;
+        state.second_id = None;
+        state.backlog = Backlog::new(state.backlog.capacity(), offset);
+        state.database = None;
+        for replica in state.replicas.drain(..) {
+            replica.subscriber.close();
+        }
+    }
+
+    /// Goes on with the history of the master after a partial resynchronization, under the
+    /// replication ID it replied, which changes once the master was itself promoted.
+    pub fn resume(&self, id: Option<String>) {
+        let mut state = self.state.lock().unwrap();
+        state.resumable = true;
+        state.linked = true;
+        if let Some(id) = id.filter(|id| *id != state.id) {
+            Self::shift(&mut state, id);
+        }
+    }
+
+    /// Stops following the master, starting a new history which the replicas of the former
+    /// one may still catch up with.
+    pub fn promote(&self) {
+        let mut state = self.state.lock().unwrap();
+        if state.master.take().is_none() {
+            return;
+        }
+        self.replica.store(false, Ordering::SeqCst);
+        Self::shift(&mut state, random_id());
+        drop(state);
+        self.relinked.notify_waiters();
+    }
+
+    pub fn failover(&self) -> FailoverState {
+        self.state.lock().unwrap().failover
+    }
+
+    /// Pauses writes until the failover ends, returning false if one is going on already.
+    pub fn begin_failover(&self) -> bool {
+        let mut state = self.state.lock().unwrap();
+        if state.failover != FailoverState::None {
+            return false;
+        }
+        state.failover = FailoverState::WaitingForSync;
+        self.paused.send_replace(true);
+        true
+    }
+
+    /// Follows the replica at `address`, which is asked to take over as master.
+    pub fn hand_over(&self, address: String) {
+        self.state.lock().unwrap().failover = FailoverState::InProgress;
+        self.replicate_from(address);
+    }
+
+    /// Resumes writes once the failover completed or was given up, going on as master in the
+    /// latter case.
+    pub fn end_failover(&self, completed: bool) {
+        let mut state = self.state.lock().unwrap();
+        let failover = std::mem::take(&mut state.failover);
+        drop(state);
+        if failover == FailoverState::InProgress && !completed {
+            self.promote();
+        }
+        self.paused.send_replace(false);
+    }
+
+    /// Waits as long as writes are paused.
+    pub async fn writable(&self) {
+        let _ = self.paused.subscribe().wait_for(|paused| !paused).await;
+    }
+
+    fn shift(state: &mut State, id: String) {
+        let previous = std::mem::replace(&mut state.id, id);
+        state.second_id = Some((previous, state.offset + 1));
+    }
+
+    /// Records the replica introducing itself with REPLCONF, along with its listening port.
+    pub fn register(&self, session: &Session, port: Option<u16>) {
+        Self::enroll(&mut self.state.lock().unwrap(), session, port);
+    }
+
+    /// Records what the replica announced it supports with REPLCONF capa.
+    pub fn announce(&self, session: &Session, capabilities: &[String]) {
+        let mut state = self.state.lock().unwrap();
+        let replica = Self::enroll(&mut state, session, None);
+        replica.capabilities.extend_from_slice(capabilities);
+    }
+
+    pub fn is_capable(&self, session: &Session, capability: &str) -> bool {
+        self.state
+            .lock()
+            .unwrap()
+            .replicas
+            .iter()
+            .find(|replica| replica.id == session.id())
+            .is_some_and(|replica| replica.capabilities.iter().any(|c| c == capability))
+    }
+
+    /// Adds the replica to the diskless transfer about to start, returning where the transfer
+    /// is to be received, and whether the replica is the first one, which is to start it.
+    pub fn join_transfer(&self, session: &Session) -> (watch::Receiver<Option<Transfer>>, bool) {
+        let mut state = self.state.lock().unwrap();
+        Self::enroll(&mut state, session, None);
+        match &mut state.batch {
+            Some(batch) => {
+                batch.replicas.push(session.id());
+                (batch.sender.subscribe(), false)
+            }
+            None => {
+                let (sender, receiver) = watch::channel(None);
+                state.batch = Some(Batch {
+                    replicas: vec![session.id()],
+                    sender,
+                });
+                (receiver, true)
+            }
+        }
+    }
+
+    /// Sends the snapshot to every replica which joined the transfer, the replication stream
+    /// following it from then on.
+    pub fn start_transfer(&self, snapshot: Vec<u8>) {
+        let mut state = self.state.lock().unwrap();
+        let Some(batch) = state.batch.take() else {
+            return;
+        };
+        state.database = None;
+        for replica in state.replicas.iter_mut() {
+            if batch.replicas.contains(&replica.id) {
+                replica.online = true;
+            }
+        }
+        let mark = random_id();
+        let mut payload = format!("$EOF:{mark}\r\n").into_bytes();
+        payload.extend(snapshot);
+        payload.extend(mark.as_bytes());
+        let transfer = Transfer {
+            id: state.id.clone(),
+            offset: state.offset,
+            payload: Arc::new(payload),
+        };
+        batch.sender.send_replace(Some(transfer));
+    }
+
+    /// Gives up the transfer about to start, the replicas which joined it being told so.
+    pub fn cancel_transfer(&self) {
+        self.state.lock().unwrap().batch = None;
+    }
+
+    /// Starts sending the replication stream to the replica, returning the replication ID
+    /// and offset it starts from.
+    pub fn attach(&self, session: &Session) -> (String, u64) {
+        let mut state = self.state.lock().unwrap();
+        // The replica has yet to select any database.
+        state.database = None;
+        Self::enroll(&mut state, session, None).online = true;
+        (state.id.clone(), state.offset)
+    }
+
+    /// Starts sending the replication stream to the replica from `offset` on, which counts
+    /// from 1 as in PSYNC, returning the replication ID and the part of the stream the
+    /// replica missed. Nothing is returned when the replica follows another history, or
+    /// when the backlog does not reach that far back anymore.
+    pub fn catch_up(&self, session: &Session, id: &str, offset: u64) -> Option<(String, Vec<u8>)> {
+        let mut state = self.state.lock().unwrap();
+        let known = id == state.id
+            || matches!(&state.second_id, Some((second, until)) if second == id && offset <= *until);
+        if !known || offset == 0 {
+            return None;
+        }
+        let missed = state.backlog.since(offset - 1)?;
+        Self::enroll(&mut state, session, None).online = true;
+        Some((state.id.clone(), missed))
+    }
+
+    fn enroll<'a>(state: &'a mut State, session: &Session, port: Option<u16>) -> &'a mut Replica {
+        let index = match state
+            .replicas
+            .iter()
+            .position(|replica| replica.id == session.id())
+        {
+            Some(index) => index,
+            None => {
+                state.replicas.push(Replica {
+                    id: session.id(),
+                    subscriber: session.subscriber.clone(),
+                    address: session.address(),
+                    port: None,
+                    online: false,
+                    acknowledged: 0,
+                    fsynced: None,
+                    capabilities: Vec::new(),
+                });
+                state.replicas.len() - 1
+            }
+        };
+        let replica = &mut state.replicas[index];
+        replica.port = port.or(replica.port);
+        replica
+    }
+
+    /// Forgets a replica which disconnected.
+    pub fn detach(&self, id: u64) {
+        self.state
+            .lock()
+            .unwrap()
+            .replicas
+            .retain(|replica| replica.id != id);
+    }
+
+    pub fn replicas(&self) -> Vec<ReplicaInfo> {
+        self.state
+            .lock()
+            .unwrap()
+            .replicas
+            .iter()
+            .map(|replica| ReplicaInfo {
+                address: replica.address.clone(),
+                port: replica.port,
+                online: replica.online,
+                offset: replica.acknowledged,
+            })
+            .collect()
+    }
+
+    /// Records the offsets the replica acknowledged with REPLCONF ACK.
+    pub fn acknowledge(&self, session: &Session, offset: u64, fsynced: Option<u64>) {
+        let mut state = self.state.lock().unwrap();
+        if let Some(replica) = state
+            .replicas
+            .iter_mut()
+            .find(|replica| replica.id == session.id())
+        {
+            replica.acknowledged = offset;
+            replica.fsynced = fsynced;
+        }
+        drop(state);
+        self.acknowledgements.notify_waiters();
+    }
+
+    /// How many replicas acknowledged applying the stream up to `offset`, or writing it to
+    /// their AOF when `fsynced`.
+    pub fn acknowledged(&self, offset: u64, fsynced: bool) -> usize {
+        self.state
+            .lock()
+            .unwrap()
+            .replicas
+            .iter()
+            .filter(|replica| replica.online)
+            .filter(|replica| match fsynced {
+                true => replica.fsynced.is_some_and(|fsynced| fsynced >= offset),
+                false => replica.acknowledged >= offset,
+            })
+            .count()
+    }
+
+    /// Waits until `needed` replicas acknowledged the stream up to `offset`, asking them to,
+    /// or until the timeout elapses, returning how many did.
+    pub async fn wait(
+        &self,
+        offset: u64,
+        needed: usize,
+        timeout: Option<Duration>,
+        fsynced: bool,
+    ) -> usize {
+        let enough = || {
+            let count = self.acknowledged(offset, fsynced);
+            (count >= needed).then_some(count)
+        };
+        match self.until(enough, true, timeout).await {
+            Some(count) => count,
+            None => self.acknowledged(offset, fsynced),
+        }
+    }
+
+    /// Waits until a replica, the one at `target` if given, acknowledged the stream up to
+    /// `offset`, or until the timeout elapses, returning the address it listens on. Replicas
+    /// are not asked to acknowledge, as that would add to the stream they are to catch up with.
+    pub async fn wait_for_replica(
+        &self,
+        target: Option<&str>,
+        offset: u64,
+        timeout: Option<Duration>,
+    ) -> Option<String> {
+        let synced = || {
+            self.state
+                .lock()
+                .unwrap()
+                .replicas
+                .iter()
+                .filter(|replica| replica.online && replica.acknowledged >= offset)
+                .filter_map(|replica| {
+                    let port = replica.port?;
+                    Some(format!("{}:{port}", replica.address))
+                })
+                .find(|address| target.is_none_or(|target| target == address))
+        };
+        self.until(synced, false, timeout).await
+    }
+
+    /// Checks again every time a replica acknowledges the stream, asking them to once if
+    /// `ask`, until `check` gives something or the timeout elapses.
+    async fn until<T>(
+        &self,
+        check: impl Fn() -> Option<T>,
+        ask: bool,
+        timeout: Option<Duration>,
+    ) -> Option<T> {
+        let mut asked = !ask;
+        let waiting = async {
+            loop {
+                let notified = self.acknowledgements.notified();
+                tokio::pin!(notified);
+                notified.as_mut().enable();
+                if let Some(found) = check() {
+                    return found;
+                }
+                if !asked {
+                    self.request_acks();
+                    asked = true;
+                }
+                notified.await;
+            }
+        };
+        match timeout {
+            Some(timeout) => tokio::time::timeout(timeout, waiting).await.ok(),
+            None => Some(waiting.await),
+        }
+    }
+
+    /// Sends REPLCONF GETACK to the replicas, as part of the stream.
+    fn request_acks(&self) {
+        let mut state = self.state.lock().unwrap();
+        if !state.replicas.iter().any(|replica| replica.online) {
+            return;
+        }
+        let getack = Value::Array(
+            ["REPLCONF", "GETACK", "*"]
+                .iter()
+                .map(|part| Value::BulkString(part.to_string()))
+                .collect(),
+        );
+        Self::append(&mut state, getack.serialize());
+    }
+
+    /// Sends a write applied to `database` to the replicas, unless this server is a replica
+    /// itself, which only passes on the stream of its master.
+    pub fn propagate(&self, database: usize, request: &Value) {
+        let mut state = self.state.lock().unwrap();
+        if state.master.is_some() {
+            return;
+        }
+        let mut bytes = Vec::new();
+        if state.database != Some(database) {
+            let select = Value::Array(vec![
+                Value::BulkString("SELECT".to_string()),
+                Value::BulkString(database.to_string()),
+            ]);
+            bytes.extend(select.serialize());
+            state.database = Some(database);
+        }
+        bytes.extend(request.serialize());
+        Self::append(&mut state, bytes);
+    }
+
+    /// Passes on bytes of the replication stream received from the master.
+    pub fn feed(&self, bytes: &[u8]) {
+        Self::append(&mut self.state.lock().unwrap(), bytes.to_vec());
+    }
+
+    fn append(state: &mut State, bytes: Vec<u8>) {
+        state.offset += bytes.len() as u64;
+        state.backlog.append(&bytes);
+        for replica in state.replicas.iter().filter(|replica| replica.online) {
+            replica.subscriber.push(Value::Raw(bytes.clone()));
+        }
+    }
+}
+
+pub(crate) fn random_id() -> String {
+    let mut rng = rand::thread_rng();
+    (0..40)
+        .map(|_| format!("{:x}", rng.gen_range(0..16)))
+        .collect()
+}
+
+#[cfg(test)]
+mod specs_for_replication {
+    use std::sync::Arc;
+    use std::sync::atomic::AtomicBool;
+    use std::sync::atomic::Ordering;
+
+    use crate::command::session::Session;
+    use crate::resp::Value;
+
+    use super::Replication;
+
+    fn request(arguments: &[&str]) -> Value {
+        Value::Array(
+            arguments
+                .iter()
+                .map(|argument| Value::BulkString(argument.to_string()))
+                .collect(),
+        )
+    }
+
+    #[test]
+    fn sut_starts_with_a_random_replication_id() {
+        // Act
+        let first = Replication::new(None, 1024).id();
+        let second = Replication::new(None, 1024).id();
+
+        // Assert
+        assert_eq!(first.len(), 40);
+        assert!(first.chars().all(|c| c.is_ascii_hexdigit()));
+        assert_ne!(first, second);
+    }
+
+    #[tokio::test]
+    async fn sut_selects_database_before_propagating_writes_to_online_replicas() {
+        // Arrange
+        let sut = Replication::new(None, 1024);
+        let online = Session::new(8);
+        let handshaking = Session::new(8);
+        sut.register(&handshaking, Some(6380));
+        let (_, offset) = sut.attach(&online);
+
+        // Act
+        sut.propagate(0, &request(&["SET", "foo", "bar"]));
+        sut.propagate(0, &request(&["DEL", "foo"]));
+
+        // Assert
+        let mut messages = online.subscriber.messages().await;
+        let Ok(Value::Raw(first)) = messages.try_recv() else {
+            panic!("expected the first write");
+        };
+        let expected =
+            b"*2\r\n$6\r\nSELECT\r\n$1\r\n0\r\n*3\r\n$3\r\nSET\r\n$3\r\nfoo\r\n$3\r\nbar\r\n";
+        assert_eq!(first, expected);
+        assert!(
+            matches!(messages.try_recv(), Ok(Value::Raw(second)) if second.starts_with(b"*2\r\n$3\r\nDEL"))
+        );
+        assert_eq!(offset, 0);
+        assert_eq!(sut.offset(), expected.len() as u64 + 22);
+        assert!(handshaking.subscriber.messages().await.try_recv().is_err());
+    }
+
+    #[tokio::test]
+    async fn sut_passes_on_the_stream_of_its_master_only() {
+        // Arrange
+        let sut = Replication::new(Some("localhost:6379".to_string()), 1024);
+        let replica = Session::new(8);
+        sut.follow("abc".to_string(), 100);
+        sut.attach(&replica);
+
+        // Act
+        sut.propagate(0, &request(&["SET", "foo", "bar"]));
+        sut.feed(b"*1\r\n$4\r\nPING\r\n");
+
+        // Assert
+        let actual = replica.subscriber.messages().await.try_recv().unwrap();
+        assert_eq!(actual, Value::Raw(b"*1\r\n$4\r\nPING\r\n".to_vec()));
+        assert_eq!(sut.offset(), 114);
+        assert_eq!(sut.id(), "abc");
+    }
+
+    #[rstest::rstest]
+    #[case(1, Some(b"*1\r\n$4\r\nPING\r\n".to_vec()))]
+    #[case(15, Some(vec![]))]
+    #[case(16, None)]
+    #[case(0, None)]
+    #[tokio::test]
+    async fn sut_sends_replicas_the_writes_they_missed_from_the_backlog(
+        #[case] offset: u64,
+        #[case] expected: Option<Vec<u8>>,
+    ) {
+        // Arrange
+        let sut = Replication::new(None, 1024);
+        sut.feed(b"*1\r\n$4\r\nPING\r\n");
+        let replica = Session::new(8);
+
+        // Act
+        let actual = sut.catch_up(&replica, &sut.id(), offset);
+
+        // Assert
+        assert_eq!(actual, expected.map(|missed| (sut.id(), missed)));
+        let online = sut.replicas().first().is_some_and(|replica| replica.online);
+        assert_eq!(online, actual.is_some());
+    }
+
+    #[tokio::test]
+    async fn sut_leaves_replicas_to_resynchronize_once_the_backlog_moved_on() {
+        // Arrange
+        let sut = Replication::new(None, 8);
+        sut.feed(b"*1\r\n$4\r\nPING\r\n");
+
+        // Act
+        let actual = sut.catch_up(&Session::new(8), &sut.id(), 1);
+
+        // Assert
+        assert_eq!(actual, None);
+        assert_eq!(sut.backlog().first_byte_offset, 7);
+        assert_eq!(sut.backlog().histlen, 8);
+    }
+
+    #[test]
+    fn sut_keeps_role_flag_raised_as_long_as_it_is_replica() {
+        // Arrange
+        let role = Arc::new(AtomicBool::new(false));
+        let sut =
+            Replication::new(Some("localhost:6379".to_string()), 1024).with_role(role.clone());
+        let mut actual = vec![role.load(Ordering::SeqCst)];
+
+        // Act
+        sut.promote();
+        actual.push(role.load(Ordering::SeqCst));
+        sut.replicate_from("localhost:6380".to_string());
+        actual.push(role.load(Ordering::SeqCst));
+
+        // Assert
+        assert_eq!(actual, [true, false, true]);
+    }
+
+    #[tokio::test]
+    async fn sut_serves_replicas_of_the_former_master_once_promoted() {
+        // Arrange
+        let sut = Replication::new(Some("localhost:6379".to_string()), 1024);
+        sut.follow("abc".to_string(), 100);
+        sut.feed(b"*1\r\n$4\r\nPING\r\n");
+
+        // Act
+        sut.promote();
+        sut.propagate(0, &request(&["DEL", "foo"]));
+
+        // Assert
+        assert!(!sut.is_replica());
+        assert_ne!(sut.id(), "abc");
+        assert_eq!(sut.second_id(), Some(("abc".to_string(), 115)));
+        let sibling = sut.catch_up(&Session::new(8), "abc", 115);
+        assert!(matches!(sibling, Some((_, missed)) if missed.ends_with(b"$3\r\nfoo\r\n")));
+        assert_eq!(sut.catch_up(&Session::new(8), "abc", 116), None);
+    }
+
+    #[tokio::test]
+    async fn sut_disconnects_its_replicas_on_a_full_resynchronization() {
+        // Arrange
+        let sut = Replication::new(Some("localhost:6379".to_string()), 1024);
+        let replica = Session::new(8);
+        sut.attach(&replica);
+
+        // Act
+        sut.follow("abc".to_string(), 100);
+
+        // Assert
+        let closed = tokio::time::timeout(
+            std::time::Duration::from_secs(1),
+            replica.subscriber.closed(),
+        )
+        .await;
+        assert!(closed.is_ok());
+        assert!(sut.replicas().is_empty());
+        assert_eq!(sut.backlog().first_byte_offset, 101);
+    }
+
+    #[tokio::test]
+    async fn sut_streams_one_snapshot_to_every_replica_which_joined_the_transfer() {
+        // Arrange
+        let sut = Replication::new(None, 1024);
+        let first = Session::new(8);
+        let second = Session::new(8);
+        let (mut first_transfer, first_starts) = sut.join_transfer(&first);
+        let (mut second_transfer, second_starts) = sut.join_transfer(&second);
+
+        // Act
+        sut.start_transfer(b"REDIS0011".to_vec());
+
+        // Assert
+        assert!(first_starts);
+        assert!(!second_starts);
+        let first_transfer = first_transfer
+            .wait_for(Option::is_some)
+            .await
+            .unwrap()
+            .clone();
+        let second_transfer = second_transfer
+            .wait_for(Option::is_some)
+            .await
+            .unwrap()
+            .clone();
+        let payload = first_transfer.unwrap().payload;
+        assert!(payload.starts_with(b"$EOF:"));
+        assert_eq!(&payload[45..56], b"\r\nREDIS0011");
+        assert!(payload.ends_with(&payload[5..45]));
+        assert_eq!(second_transfer.unwrap().payload, payload);
+        assert!(sut.replicas().iter().all(|replica| replica.online));
+        assert!(sut.join_transfer(&Session::new(8)).1);
+    }
+}
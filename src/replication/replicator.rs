@@ -0,0 +1,628 @@
+use std::io;
+use std::path::Path;
+use std::pin::Pin;
+use std::task::Context;
+use std::task::Poll;
+use std::task::ready;
+use std::time::Duration;
+
+use tokio::fs::File;
+use tokio::io::AsyncRead;
+use tokio::io::AsyncReadExt;
+use tokio::io::AsyncWriteExt;
+use tokio::io::ReadBuf;
+use tokio::net::TcpStream;
+use tokio::net::ToSocketAddrs;
+use tokio::time::Instant;
+
+use crate::command::executor::CommandExecutorContext;
+use crate::command::executor::execute;
+use crate::command::executor::parse;
+use crate::config::DisklessLoad;
+use crate::replication::FailoverState;
+use crate::repository::Databases;
+use crate::resp::Value;
+use crate::scripting::Libraries;
+use crate::snapshot::load;
+
+/// How the master agreed to synchronize the replica.
+#[derive(Debug, PartialEq)]
+pub enum Resync {
+    /// A snapshot follows, from which the replication ID and offset given go on.
+    Full { id: String, offset: u64 },
+    /// The writes the replica missed follow, under a new replication ID if given.
+    Partial { id: Option<String> },
+}
+
+/// The link of a replica to its master, over which the dataset is synchronized once and every
+/// write applied afterwards.
+pub struct Replicator {
+    stream: TcpStream,
+    /// Bytes read from the master but not processed yet.
+    buffer: Vec<u8>,
+}
+
+impl Replicator {
+    pub async fn new<A: ToSocketAddrs>(address: A) -> std::io::Result<Self> {
+        let stream = TcpStream::connect(address).await?;
+        Ok(Self {
+            stream,
+            buffer: Vec::new(),
+        })
+    }
+
+    /// Introduces the replica to the master and asks to go on from `resume`, the replication
+    /// ID and offset the replica got to, or for a full resynchronization without it. With
+    /// `failover`, the master is asked to take over from this server first.
+    pub async fn handshake(
+        &mut self,
+        listening_port: usize,
+        resume: Option<(String, u64)>,
+        failover: bool,
+    ) -> Result<Resync, anyhow::Error> {
+        self.expect(&["PING"], "PONG").await?;
+        let port = listening_port.to_string();
+        self.expect(&["REPLCONF", "listening-port", &port], "OK")
+            .await?;
+        self.expect(&["REPLCONF", "capa", "eof", "capa", "psync2"], "OK")
+            .await?;
+        let (id, offset) = match resume {
+            Some((id, offset)) => (id, (offset + 1).to_string()),
+            None => ("?".to_string(), "-1".to_string()),
+        };
+        let mut psync = vec!["PSYNC", &id, &offset];
+        if failover {
+            psync.push("FAILOVER");
+        }
+        match self.request(&psync).await? {
+            Value::SimpleString(reply) => match reply.split(' ').collect::<Vec<_>>()[..] {
+                ["FULLRESYNC", id, offset] => Ok(Resync::Full {
+                    id: id.to_string(),
+                    offset: offset.parse()?,
+                }),
+                ["CONTINUE"] => Ok(Resync::Partial { id: None }),
+                ["CONTINUE", id] => Ok(Resync::Partial {
+                    id: Some(id.to_string()),
+                }),
+                _ => anyhow::bail!("unexpected reply to PSYNC: {reply}"),
+            },
+            reply => anyhow::bail!("unexpected reply to PSYNC: {reply:?}"),
+        }
+    }
+
+    /// Starts reading the RDB file sent after FULLRESYNC, a bulk string without the trailing
+    /// CRLF, or streamed between `$EOF:<mark>` and the same mark when the master is diskless.
+    pub async fn snapshot(&mut self) -> Result<Payload<'_>, anyhow::Error> {
+        let line = self.read_line().await?;
+        let end = if let Some(mark) = line.strip_prefix("$EOF:") {
+            if mark.is_empty() {
+                anyhow::bail!("missing EOF mark");
+            }
+            End::Mark(mark.as_bytes().to_vec())
+        } else if let Some(size) = line.strip_prefix('$') {
+            End::Length(size.parse()?)
+        } else {
+            anyhow::bail!("expected an RDB file, got {line}");
+        };
+        Ok(Payload {
+            replicator: self,
+            end,
+        })
+    }
+
+    /// Applies the commands propagated by the master until the link breaks, passing the
+    /// stream on to the replicas of this server. The offset applied is acknowledged every
+    /// second, and whenever the master asks.
+    pub async fn replicate(
+        &mut self,
+        context: &CommandExecutorContext,
+    ) -> Result<(), anyhow::Error> {
+        let period = Duration::from_secs(1);
+        let mut ticks = tokio::time::interval_at(Instant::now() + period, period);
+        loop {
+            let (value, bytes) = tokio::select! {
+                next = self.next_value() => next?,
+                _ = ticks.tick() => {
+                    self.acknowledge(context).await?;
+                    continue;
+                }
+            };
+            if is_getack(&value) {
+                self.acknowledge(context).await?;
+            } else {
+                match parse(&value) {
+                    Ok(command) => {
+                        if let Some(command) = context.session.queue(command, &value) {
+                            execute(command, &value, context).await;
+                        }
+                    }
+                    Err(e) => eprintln!("skipping command from master which fails to parse: {e}"),
+                }
+            }
+            context.replication.feed(&bytes);
+        }
+    }
+
+    async fn acknowledge(&mut self, context: &CommandExecutorContext) -> Result<(), anyhow::Error> {
+        let offset = context.replication.offset().to_string();
+        let ack = Value::Array(
+            ["REPLCONF", "ACK", &offset]
+                .iter()
+                .map(|part| Value::BulkString(part.to_string()))
+                .collect(),
+        );
+        self.stream.write_all(&ack.serialize()).await?;
+        Ok(())
+    }
+
+    async fn expect(&mut self, command: &[&str], expected: &str) -> Result<(), anyhow::Error> {
+        match self.request(command).await? {
+            Value::SimpleString(reply) if reply.eq_ignore_ascii_case(expected) => Ok(()),
+            reply => anyhow::bail!("unexpected reply to {}: {reply:?}", command.join(" ")),
+        }
+    }
+
+    async fn request(&mut self, command: &[&str]) -> Result<Value, anyhow::Error> {
+        let request = Value::Array(
+            command
+                .iter()
+                .map(|part| Value::BulkString(part.to_string()))
+                .collect(),
+        );
+        self.stream.write_all(&request.serialize()).await?;
+        Ok(self.next_value().await?.0)
+    }
+
+    /// The next value sent by the master, along with the bytes it was sent as.
+    async fn next_value(&mut self) -> Result<(Value, Vec<u8>), anyhow::Error> {
+        loop {
+            if let Some((value, size)) = Value::decode(&self.buffer)? {
+                return Ok((value, self.buffer.drain(..size).collect()));
+            }
+            self.fill().await?;
+        }
+    }
+
+    async fn read_line(&mut self) -> Result<String, anyhow::Error> {
+        loop {
+            if let Some(end) = self.buffer.windows(2).position(|window| window == b"\r\n") {
+                let line = String::from_utf8_lossy(&self.buffer[..end]).to_string();
+                self.buffer.drain(..end + 2);
+                return Ok(line);
+            }
+            self.fill().await?;
+        }
+    }
+
+    async fn fill(&mut self) -> Result<(), anyhow::Error> {
+        let mut chunk = [0; 4096];
+        let read = self.stream.read(&mut chunk).await?;
+        if read == 0 {
+            anyhow::bail!("connection closed by master");
+        }
+        self.buffer.extend_from_slice(&chunk[..read]);
+        Ok(())
+    }
+}
+
+/// How the end of the snapshot is told.
+enum End {
+    /// The snapshot has this many bytes left.
+    Length(usize),
+    /// The snapshot ends with the mark, which is dropped along with it. Emptied once reached.
+    Mark(Vec<u8>),
+}
+
+/// The snapshot sent by the master, read from the link as it arrives rather than held in
+/// memory whole.
+pub struct Payload<'a> {
+    replicator: &'a mut Replicator,
+    end: End,
+}
+
+impl AsyncRead for Payload<'_> {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        let payload = self.get_mut();
+        loop {
+            let buffer = &mut payload.replicator.buffer;
+            // How many of the bytes buffered surely belong to the snapshot.
+            let available = match &mut payload.end {
+                End::Length(left) => buffer.len().min(*left),
+                End::Mark(mark) if mark.is_empty() => return Poll::Ready(Ok(())),
+                End::Mark(mark) => match buffer.windows(mark.len()).position(|w| w == mark) {
+                    Some(0) => {
+                        buffer.drain(..mark.len());
+                        mark.clear();
+                        return Poll::Ready(Ok(()));
+                    }
+                    Some(position) => position,
+                    // The end of the buffer may be the start of the mark.
+                    None => buffer.len().saturating_sub(mark.len() - 1),
+                },
+            };
+            if available > 0 || matches!(payload.end, End::Length(0)) {
+                let count = available.min(buf.remaining());
+                buf.put_slice(&buffer[..count]);
+                buffer.drain(..count);
+                if let End::Length(left) = &mut payload.end {
+                    *left -= count;
+                }
+                return Poll::Ready(Ok(()));
+            }
+            let mut chunk = [0; 4096];
+            let mut filled = ReadBuf::new(&mut chunk);
+            ready!(Pin::new(&mut payload.replicator.stream).poll_read(cx, &mut filled))?;
+            if filled.filled().is_empty() {
+                return Poll::Ready(Err(io::Error::new(
+                    io::ErrorKind::UnexpectedEof,
+                    "connection closed by master",
+                )));
+            }
+            payload.replicator.buffer.extend_from_slice(filled.filled());
+        }
+    }
+}
+
+fn is_getack(value: &Value) -> bool {
+    match value {
+        Value::Array(parts) => matches!(
+            &parts[..],
+            [Value::BulkString(command), Value::BulkString(subcommand), ..]
+                if command.eq_ignore_ascii_case("REPLCONF")
+                    && subcommand.eq_ignore_ascii_case("GETACK")
+        ),
+        _ => false,
+    }
+}
+
+/// Keeps the server in sync with its master as long as it has one, connecting again a second
+/// after the link breaks, or at once to the master it follows next.
+pub async fn run(context: CommandExecutorContext) {
+    context.session.mark_master();
+    loop {
+        let relinked = context.replication.relinked();
+        tokio::pin!(relinked);
+        relinked.as_mut().enable();
+        let Some(address) = context.replication.master() else {
+            relinked.await;
+            continue;
+        };
+        context.replication.unlink();
+        tokio::select! {
+            result = sync(&address, &context) => {
+                if let Err(e) = result {
+                    eprintln!("replication from {address} stopped: {e}");
+                }
+                context.replication.unlink();
+                tokio::select! {
+                    _ = tokio::time::sleep(Duration::from_secs(1)) => {}
+                    _ = relinked => {}
+                }
+            }
+            _ = relinked.as_mut() => {}
+        }
+    }
+}
+
+/// Catches up with the master, replacing the dataset with its snapshot unless it sends the
+/// writes missed since the link broke, then follows its writes.
+async fn sync(address: &str, context: &CommandExecutorContext) -> Result<(), anyhow::Error> {
+    let mut replicator = Replicator::new(address).await?;
+    let replication = &context.replication;
+    let failover = replication.failover() == FailoverState::InProgress;
+    match replicator
+        .handshake(
+            context.config.server.port,
+            replication.resume_point(),
+            failover,
+        )
+        .await?
+    {
+        Resync::Full { id, offset } => {
+            // A transaction the link broke in the middle of belongs to the former history,
+            // while going on from the backlog completes it.
+            context.session.finish();
+            replace_dataset(context, replicator.snapshot().await?).await?;
+            // Only a complete snapshot lets the replica go on from the backlog later.
+            replication.follow(id, offset);
+        }
+        Resync::Partial { id } => replication.resume(id),
+    }
+    if failover {
+        replication.end_failover(true);
+    }
+    replicator.replicate(context).await
+}
+
+/// Replaces the dataset with the snapshot of the master, as repl-diskless-load tells. Clients
+/// never see a dataset partly loaded.
+async fn replace_dataset(
+    context: &CommandExecutorContext,
+    mut snapshot: Payload<'_>,
+) -> Result<(), anyhow::Error> {
+    let mut diskless = context.config.replication.diskless_load;
+    if diskless == DisklessLoad::OnEmptyDb && !is_empty(&context.databases).await {
+        diskless = DisklessLoad::Disabled;
+    }
+    if diskless == DisklessLoad::Swapdb {
+        // Clients keep using the former keys and functions until the snapshot is loaded.
+        let fresh = context.databases.fresh(context.clock.clone());
+        let fresh_libraries = Libraries::default();
+        load(
+            &mut snapshot,
+            &fresh,
+            &fresh_libraries,
+            context.clock.as_ref(),
+        )
+        .await?;
+        tokio::io::copy(&mut snapshot, &mut tokio::io::sink()).await?;
+        let _exclusive = context.lock_exclusive().await.map_err(busy)?;
+        context.databases.replace(fresh.all());
+        context.scripts.libraries.replace(fresh_libraries);
+        return Ok(());
+    }
+    match (&context.config.rdb, diskless) {
+        (Some(rdb), DisklessLoad::Disabled) => {
+            let path = rdb.path();
+            let temporary = Path::new(&path).with_extension("rdb.tmp");
+            let mut file = File::create(&temporary).await?;
+            tokio::io::copy(&mut snapshot, &mut file).await?;
+            file.sync_all().await?;
+            tokio::fs::rename(&temporary, &path).await?;
+            let file = File::open(&path).await?;
+            let _exclusive = context.lock_exclusive().await.map_err(busy)?;
+            load_in_place(context, file).await
+        }
+        _ => {
+            let _exclusive = context.lock_exclusive().await.map_err(busy)?;
+            load_in_place(context, snapshot).await
+        }
+    }
+}
+
+/// Loads the snapshot over an emptied dataset, which is left empty again if the snapshot
+/// breaks off.
+async fn load_in_place<R: AsyncRead + Unpin + Send>(
+    context: &CommandExecutorContext,
+    mut snapshot: R,
+) -> Result<(), anyhow::Error> {
+    flush(context).await;
+    let libraries = &context.scripts.libraries;
+    let mut loaded = load(
+        &mut snapshot,
+        &context.databases,
+        libraries,
+        context.clock.as_ref(),
+    )
+    .await;
+    if loaded.is_ok() {
+        loaded = tokio::io::copy(&mut snapshot, &mut tokio::io::sink())
+            .await
+            .map(drop)
+            .map_err(Into::into);
+    }
+    if loaded.is_err() {
+        flush(context).await;
+    }
+    loaded
+}
+
+async fn flush(context: &CommandExecutorContext) {
+    context.scripts.libraries.flush();
+    for repository in context.databases.all() {
+        repository.flush(false).await;
+    }
+}
+
+fn busy(reply: Value) -> anyhow::Error {
+    anyhow::anyhow!("unable to load the snapshot: {reply:?}")
+}
+
+async fn is_empty(databases: &Databases) -> bool {
+    for repository in databases.all() {
+        if repository.size().await > 0 {
+            return false;
+        }
+    }
+    true
+}
+
+#[cfg(test)]
+mod specs_for_snapshot {
+    use std::time::Duration;
+
+    use tokio::io::AsyncReadExt;
+    use tokio::io::AsyncWriteExt;
+    use tokio::net::TcpListener;
+    use tokio::net::TcpStream;
+
+    use crate::resp::Value;
+
+    use super::Replicator;
+
+    const MARK: &str = "0123456789abcdef0123456789abcdef01234567";
+
+    /// A replicator linked to a master sending `parts` one after the other.
+    async fn replicator(parts: Vec<Vec<u8>>) -> Replicator {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            let (mut master, _) = listener.accept().await.unwrap();
+            for part in parts {
+                master.write_all(&part).await.unwrap();
+                tokio::time::sleep(Duration::from_millis(10)).await;
+            }
+            // Keeps the link open until the replica is done.
+            let _ = master.read(&mut [0; 1]).await;
+        });
+        Replicator {
+            stream: TcpStream::connect(address).await.unwrap(),
+            buffer: Vec::new(),
+        }
+    }
+
+    #[rstest::rstest]
+    #[case(vec![b"$5\r\nhel".to_vec(), b"lo*1\r\n$4\r\nPING\r\n".to_vec()])]
+    #[case(vec![
+        format!("$EOF:{MARK}\r\nhello{}", &MARK[..20]).into_bytes(),
+        format!("{}*1\r\n$4\r\nPING\r\n", &MARK[20..]).into_bytes(),
+    ])]
+    #[tokio::test]
+    async fn sut_reads_snapshot_as_it_arrives_up_to_the_stream_which_follows(
+        #[case] parts: Vec<Vec<u8>>,
+    ) {
+        // Arrange
+        let mut sut = replicator(parts).await;
+
+        // Act
+        let mut actual = Vec::new();
+        sut.snapshot()
+            .await
+            .unwrap()
+            .read_to_end(&mut actual)
+            .await
+            .unwrap();
+
+        // Assert
+        assert_eq!(actual, b"hello");
+        let next = sut.next_value().await.unwrap().0;
+        assert_eq!(
+            next,
+            Value::Array(vec![Value::BulkString("PING".to_string())])
+        );
+    }
+}
+
+#[cfg(test)]
+mod specs_for_replace_dataset {
+    use std::sync::Arc;
+
+    use tokio::io::AsyncWriteExt;
+    use tokio::net::TcpListener;
+    use tokio::net::TcpStream;
+
+    use crate::clock::SystemClock;
+    use crate::command::executor::CommandExecutorContext;
+    use crate::config::Config;
+    use crate::config::DisklessLoad;
+    use crate::repository::Data;
+    use crate::repository::Databases;
+    use crate::repository::Entry;
+    use crate::scripting::Libraries;
+    use crate::snapshot::serialize;
+
+    use super::Replicator;
+    use super::replace_dataset;
+
+    /// A context holding key `key` and a library registering function `key`.
+    async fn context(key: &str, diskless_load: DisklessLoad) -> CommandExecutorContext {
+        let mut config = Config::default();
+        config.replication.diskless_load = diskless_load;
+        let clock = Arc::new(SystemClock);
+        let context = CommandExecutorContext::new(
+            Arc::new(Databases::in_memory(1, clock.clone())),
+            Arc::new(config),
+            clock,
+        );
+        fill(&context.databases, &context.scripts.libraries, key).await;
+        context
+    }
+
+    async fn fill(databases: &Databases, libraries: &Libraries, key: &str) {
+        databases
+            .get(0)
+            .unwrap()
+            .set(Entry {
+                key: key.into(),
+                value: Data::String("value".to_string()),
+                expiry: None,
+            })
+            .await;
+        let code = format!("#!lua name={key}\nredis.register_function('{key}', function() end)");
+        libraries.load(&code, false).unwrap();
+    }
+
+    /// The snapshot of a master holding key `new`, of which the link carries `sent` bytes
+    /// before breaking.
+    async fn snapshot(sent: impl FnOnce(usize) -> usize) -> Replicator {
+        let clock = SystemClock;
+        let databases = Databases::in_memory(1, Arc::new(SystemClock));
+        let libraries = Libraries::default();
+        fill(&databases, &libraries, "new").await;
+        let rdb = serialize(&databases, &libraries, &clock).await;
+        let mut bytes = format!("${}\r\n", rdb.len()).into_bytes();
+        bytes.extend(&rdb[..sent(rdb.len())]);
+
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            let (mut master, _) = listener.accept().await.unwrap();
+            master.write_all(&bytes).await.unwrap();
+        });
+        Replicator {
+            stream: TcpStream::connect(address).await.unwrap(),
+            buffer: Vec::new(),
+        }
+    }
+
+    #[rstest::rstest]
+    #[case(DisklessLoad::Swapdb)]
+    #[case(DisklessLoad::Disabled)]
+    #[tokio::test]
+    async fn sut_replaces_keys_and_functions_with_those_of_snapshot(
+        #[case] diskless_load: DisklessLoad,
+    ) {
+        // Arrange
+        let context = context("old", diskless_load).await;
+        let mut replicator = snapshot(|length| length).await;
+
+        // Act
+        let actual = replace_dataset(&context, replicator.snapshot().await.unwrap()).await;
+
+        // Assert
+        assert!(actual.is_ok());
+        let database = context.databases.get(0).unwrap();
+        assert!(!database.exists("old").await);
+        assert!(database.exists("new").await);
+        let libraries = context.scripts.libraries.list();
+        let names: Vec<_> = libraries.iter().map(|library| &library.name[..]).collect();
+        assert_eq!(names, ["new"]);
+    }
+
+    #[tokio::test]
+    async fn sut_keeps_keys_and_functions_when_link_breaks_amid_snapshot_loaded_aside() {
+        // Arrange
+        let context = context("old", DisklessLoad::Swapdb).await;
+        let mut replicator = snapshot(|length| length / 2).await;
+
+        // Act
+        let actual = replace_dataset(&context, replicator.snapshot().await.unwrap()).await;
+
+        // Assert
+        assert!(actual.is_err());
+        let database = context.databases.get(0).unwrap();
+        assert!(database.exists("old").await);
+        assert!(!database.exists("new").await);
+        assert!(context.scripts.libraries.function("old").is_some());
+        assert!(context.scripts.libraries.function("new").is_none());
+    }
+
+    #[tokio::test]
+    async fn sut_leaves_dataset_empty_when_link_breaks_amid_snapshot_loaded_in_place() {
+        // Arrange
+        let context = context("old", DisklessLoad::Disabled).await;
+        let mut replicator = snapshot(|length| length - 9).await;
+
+        // Act
+        let actual = replace_dataset(&context, replicator.snapshot().await.unwrap()).await;
+
+        // Assert
+        assert!(actual.is_err());
+        assert_eq!(context.databases.get(0).unwrap().size().await, 0);
+        assert!(context.scripts.libraries.list().is_empty());
+    }
+}
@@ -0,0 +1,135 @@
+use std::sync::Arc;
+use std::sync::RwLock;
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::Ordering;
+
+use crate::clock::Clock;
+
+use super::InMemoryRepository;
+use super::Repository;
+
+/// The numbered logical databases, each being an independent repository.
+pub struct Databases {
+    databases: RwLock<Vec<Arc<dyn Repository>>>,
+    /// Raised while the server is a replica, which leaves expired keys to its master.
+    replica: Arc<AtomicBool>,
+}
+
+impl Databases {
+    pub fn new(databases: Vec<Arc<dyn Repository>>) -> Self {
+        Self {
+            databases: RwLock::new(databases),
+            replica: Arc::default(),
+        }
+    }
+
+    /// In-memory databases telling expired keys by `clock`.
+    pub fn in_memory(count: usize, clock: Arc<dyn Clock>) -> Self {
+        Self::in_memory_with_role(count, clock, Arc::default())
+    }
+
+    fn in_memory_with_role(count: usize, clock: Arc<dyn Clock>, replica: Arc<AtomicBool>) -> Self {
+        let databases = (0..count)
+            .map(|_| {
+                Arc::new(InMemoryRepository::with_role(
+                    clock.clone(),
+                    replica.clone(),
+                )) as Arc<dyn Repository>
+            })
+            .collect();
+        Self {
+            databases: RwLock::new(databases),
+            replica,
+        }
+    }
+
+    /// Empty in-memory databases as many as these, sharing their role.
+    pub fn fresh(&self, clock: Arc<dyn Clock>) -> Self {
+        Self::in_memory_with_role(self.len(), clock, self.replica.clone())
+    }
+
+    /// The flag raised while the server is a replica, shared by the in-memory databases.
+    pub fn replica(&self) -> Arc<AtomicBool> {
+        self.replica.clone()
+    }
+
+    pub fn is_replica(&self) -> bool {
+        self.replica.load(Ordering::SeqCst)
+    }
+
+    pub fn len(&self) -> usize {
+        self.databases.read().unwrap().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn get(&self, index: usize) -> Option<Arc<dyn Repository>> {
+        self.databases.read().unwrap().get(index).cloned()
+    }
+
+    pub fn all(&self) -> Vec<Arc<dyn Repository>> {
+        self.databases.read().unwrap().clone()
+    }
+
+    /// Replaces every database for every client at once.
+    pub fn replace(&self, databases: Vec<Arc<dyn Repository>>) {
+        *self.databases.write().unwrap() = databases;
+    }
+
+    /// Swaps two databases for every client at once, returning false if either is out of range.
+    pub fn swap(&self, first: usize, second: usize) -> bool {
+        let mut databases = self.databases.write().unwrap();
+        if first >= databases.len() || second >= databases.len() {
+            return false;
+        }
+        databases.swap(first, second);
+        true
+    }
+}
+
+#[cfg(test)]
+mod specs_for_databases {
+    use std::sync::Arc;
+
+    use crate::clock::SystemClock;
+    use crate::repository::Data;
+    use crate::repository::Entry;
+
+    use super::Databases;
+
+    #[tokio::test]
+    async fn sut_swaps_contents_of_databases() {
+        // Arrange
+        let sut = Databases::in_memory(2, Arc::new(SystemClock));
+        sut.get(0)
+            .unwrap()
+            .set(Entry {
+                key: "foo".into(),
+                value: Data::String("bar".to_string()),
+                expiry: None,
+            })
+            .await;
+
+        // Act
+        let swapped = sut.swap(0, 1);
+
+        // Assert
+        assert!(swapped);
+        assert!(!sut.get(0).unwrap().exists("foo").await);
+        assert!(sut.get(1).unwrap().exists("foo").await);
+    }
+
+    #[test]
+    fn sut_refuses_to_swap_databases_out_of_range() {
+        // Arrange
+        let sut = Databases::in_memory(2, Arc::new(SystemClock));
+
+        // Act
+        let actual = sut.swap(0, 2);
+
+        // Assert
+        assert!(!actual);
+    }
+}
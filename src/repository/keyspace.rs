@@ -0,0 +1,411 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::sync::Weak;
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::Ordering;
+
+use rand::Rng;
+
+use super::Data;
+use super::Entry;
+use super::Expiry;
+use super::scan_index::ScanIndex;
+
+/// How many random keys RANDOMKEY draws before looking for one that is not expired in order.
+const RANDOM_KEY_ATTEMPTS: usize = 100;
+
+/// Keys stored by position so that a random one is picked in constant time.
+#[derive(Debug, Default)]
+struct KeyVector {
+    keys: Vec<Arc<str>>,
+    positions: HashMap<Arc<str>, usize>,
+}
+
+impl KeyVector {
+    fn len(&self) -> usize {
+        self.keys.len()
+    }
+
+    fn insert(&mut self, key: &Arc<str>) {
+        if !self.positions.contains_key(key) {
+            self.positions.insert(key.clone(), self.keys.len());
+            self.keys.push(key.clone());
+        }
+    }
+
+    fn remove(&mut self, key: &str) {
+        let Some(position) = self.positions.remove(key) else {
+            return;
+        };
+        self.keys.swap_remove(position);
+        if let Some(moved) = self.keys.get(position) {
+            self.positions.insert(moved.clone(), position);
+        }
+    }
+
+    fn random(&self, rng: &mut impl Rng) -> Option<&Arc<str>> {
+        if self.keys.is_empty() {
+            return None;
+        }
+        self.keys.get(rng.gen_range(0..self.keys.len()))
+    }
+}
+
+/// The entries of a repository, indexing every key so that one can be picked at random and the
+/// keys with an expiry so they can be sampled. The indexes share the string of each key with its
+/// entry.
+#[derive(Debug, Default)]
+pub struct Keyspace {
+    entries: HashMap<Arc<str>, Entry>,
+    keys: KeyVector,
+    volatile_keys: KeyVector,
+    scan_index: ScanIndex,
+    /// Flags of clients that WATCH a key, raised as soon as the key is modified.
+    watchers: HashMap<String, Vec<Weak<AtomicBool>>>,
+}
+
+impl Keyspace {
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// How many keys have an expiry.
+    pub fn volatile_len(&self) -> usize {
+        self.volatile_keys.len()
+    }
+
+    pub fn get(&self, key: &str) -> Option<&Entry> {
+        self.entries.get(key)
+    }
+
+    pub fn values(&self) -> impl Iterator<Item = &Entry> {
+        self.entries.values()
+    }
+
+    /// Gives mutable access to the value only, as expiry changes must go through `set_expiry`.
+    pub fn value_mut(&mut self, key: &str) -> Option<&mut Data> {
+        if self.entries.contains_key(key) {
+            self.touch(key);
+        }
+        self.entries.get_mut(key).map(|entry| &mut entry.value)
+    }
+
+    pub fn insert(&mut self, mut entry: Entry) -> Option<Entry> {
+        if let Some((key, _)) = self.entries.get_key_value(&entry.key) {
+            entry.key = key.clone();
+        }
+        self.touch(&entry.key);
+        if entry.expiry.is_some() {
+            self.track(&entry.key);
+        } else {
+            self.untrack(&entry.key);
+        }
+        self.keys.insert(&entry.key);
+        self.scan_index.insert(&entry.key);
+        self.entries.insert(entry.key.clone(), entry)
+    }
+
+    pub fn remove(&mut self, key: &str) -> Option<Entry> {
+        let removed = self.entries.remove(key)?;
+        self.touch(key);
+        self.untrack(key);
+        self.keys.remove(key);
+        self.scan_index.remove(&removed.key);
+        Some(removed)
+    }
+
+    /// Empties the keyspace and returns the previous one, raising the flags of watched keys that
+    /// existed while keeping the watchers of the others.
+    pub fn take(&mut self) -> Keyspace {
+        let existing: Vec<String> = self
+            .watchers
+            .keys()
+            .filter(|key| self.entries.contains_key(key.as_str()))
+            .cloned()
+            .collect();
+        for key in existing {
+            self.touch(&key);
+        }
+        let watchers = std::mem::take(&mut self.watchers);
+        std::mem::replace(
+            self,
+            Keyspace {
+                watchers,
+                ..Default::default()
+            },
+        )
+    }
+
+    /// Replaces the expiry of an existing key, returning the previous one.
+    pub fn set_expiry(&mut self, key: &str, expiry: Option<Expiry>) -> Option<Expiry> {
+        self.entries.get(key)?;
+        self.touch(key);
+        let entry = self.entries.get_mut(key)?;
+        let previous = std::mem::replace(&mut entry.expiry, expiry);
+        if entry.expiry.is_some() {
+            let key = entry.key.clone();
+            self.track(&key);
+        } else {
+            self.untrack(key);
+        }
+        previous
+    }
+
+    /// Lazily deletes the key if it is expired, reporting whether it did.
+    pub fn remove_if_expired(&mut self, key: &str, now_in_millis: u128) -> bool {
+        if self
+            .entries
+            .get(key)
+            .is_some_and(|entry| entry.is_expired(now_in_millis))
+        {
+            self.remove(key);
+            return true;
+        }
+        false
+    }
+
+    /// Picks a random key that is not expired. Like Redis, it gives up drawing after a bounded
+    /// number of expired keys, falling back to a full scan only when most keys are expired.
+    pub fn random_key(&self, now_in_millis: u128) -> Option<String> {
+        if self.is_empty() {
+            return None;
+        }
+        let mut rng = rand::thread_rng();
+        for _ in 0..RANDOM_KEY_ATTEMPTS {
+            let key = self.keys.random(&mut rng)?;
+            if !self.entries[key].is_expired(now_in_millis) {
+                return Some(key.to_string());
+            }
+        }
+        self.entries
+            .values()
+            .find(|entry| !entry.is_expired(now_in_millis))
+            .map(|entry| entry.key.to_string())
+    }
+
+    /// Returns the entries of one page of a cursor based iteration, see `ScanIndex::scan`.
+    pub fn scan(&self, cursor: u64, count: usize) -> (u64, Vec<&Entry>) {
+        let (cursor, keys) = self.scan_index.scan(cursor, count);
+        let entries = keys.into_iter().filter_map(|key| self.entries.get(key));
+        (cursor, entries.collect())
+    }
+
+    /// Picks up to `count` random keys with an expiry, possibly repeating some of them.
+    pub fn sample_volatile(&self, count: usize) -> Vec<String> {
+        let mut rng = rand::thread_rng();
+        (0..count.min(self.volatile_keys.len()))
+            .filter_map(|_| self.volatile_keys.random(&mut rng))
+            .map(|key| key.to_string())
+            .collect()
+    }
+
+    /// Raises `dirty` once `key` is modified, until the flag is dropped by its owner.
+    pub fn watch(&mut self, key: &str, dirty: &Arc<AtomicBool>) {
+        self.watchers.retain(|_, flags| {
+            flags.retain(|flag| flag.strong_count() > 0);
+            !flags.is_empty()
+        });
+        self.watchers
+            .entry(key.to_string())
+            .or_default()
+            .push(Arc::downgrade(dirty));
+    }
+
+    /// Raises the flags of every watched key, whether it exists or not.
+    pub fn touch_all(&mut self) {
+        let keys: Vec<String> = self.watchers.keys().cloned().collect();
+        for key in keys {
+            self.touch(&key);
+        }
+    }
+
+    fn touch(&mut self, key: &str) {
+        for flag in self.watchers.remove(key).into_iter().flatten() {
+            if let Some(flag) = flag.upgrade() {
+                flag.store(true, Ordering::SeqCst);
+            }
+        }
+    }
+
+    fn track(&mut self, key: &Arc<str>) {
+        self.volatile_keys.insert(key);
+    }
+
+    fn untrack(&mut self, key: &str) {
+        self.volatile_keys.remove(key);
+    }
+}
+
+#[cfg(test)]
+mod specs_for_keyspace {
+    use std::sync::Arc;
+    use std::sync::atomic::AtomicBool;
+    use std::sync::atomic::Ordering;
+
+    use crate::repository::Data;
+    use crate::repository::Entry;
+    use crate::repository::Expiry;
+    use crate::repository::TimeUnit;
+
+    use super::Keyspace;
+
+    fn entry(key: &str, expiry: Option<u128>) -> Entry {
+        Entry {
+            key: key.into(),
+            value: Data::String("value".to_string()),
+            expiry: expiry.map(|epoch| Expiry {
+                epoch,
+                unit: TimeUnit::Millisecond,
+            }),
+        }
+    }
+
+    #[test]
+    fn sut_tracks_only_keys_with_expiry() {
+        // Arrange
+        let mut sut = Keyspace::default();
+
+        // Act
+        sut.insert(entry("foo", Some(1)));
+        sut.insert(entry("bar", None));
+        sut.insert(entry("baz", Some(2)));
+
+        // Assert
+        assert_eq!(sut.len(), 3);
+        assert_eq!(sut.volatile_len(), 2);
+        let mut sample = sut.sample_volatile(20);
+        sample.sort();
+        sample.dedup();
+        assert!(sample.iter().all(|key| key == "foo" || key == "baz"));
+    }
+
+    #[test]
+    fn sut_keeps_index_consistent_when_keys_are_removed_or_persisted() {
+        // Arrange
+        let mut sut = Keyspace::default();
+        for key in ["foo", "bar", "baz"] {
+            sut.insert(entry(key, Some(1)));
+        }
+
+        // Act
+        sut.remove("foo");
+        sut.set_expiry("bar", None);
+        sut.insert(entry("baz", None));
+        sut.insert(entry("qux", Some(1)));
+
+        // Assert
+        assert_eq!(sut.sample_volatile(3), vec!["qux".to_string()]);
+    }
+
+    #[test]
+    fn sut_shares_one_string_of_key_among_entry_and_indexes() {
+        // Arrange
+        let mut sut = Keyspace::default();
+        sut.insert(entry("foo", Some(1)));
+        let key = sut.get("foo").unwrap().key.clone();
+
+        // Act
+        sut.insert(entry("foo", Some(2)));
+        let replaced = sut.get("foo").unwrap().key.clone();
+        sut.remove("foo");
+
+        // Assert
+        assert!(Arc::ptr_eq(&key, &replaced));
+        assert_eq!(Arc::strong_count(&key), 2);
+    }
+
+    #[test]
+    fn sut_removes_key_only_if_expired() {
+        // Arrange
+        let mut sut = Keyspace::default();
+        sut.insert(entry("foo", Some(1)));
+        sut.insert(entry("bar", None));
+
+        // Act
+        let actual = (
+            sut.remove_if_expired("foo", 2),
+            sut.remove_if_expired("bar", 2),
+        );
+
+        // Assert
+        assert_eq!(actual, (true, false));
+        assert_eq!(sut.len(), 1);
+        assert!(sut.sample_volatile(1).is_empty());
+    }
+
+    #[test]
+    fn sut_picks_random_key_among_those_not_expired() {
+        // Arrange
+        let mut sut = Keyspace::default();
+        for i in 0..1000 {
+            sut.insert(entry(&format!("expired:{i}"), Some(1)));
+        }
+        sut.insert(entry("foo", None));
+        sut.remove("expired:0");
+
+        // Act
+        let actual = sut.random_key(2);
+
+        // Assert
+        assert_eq!(actual, Some("foo".to_string()));
+    }
+
+    #[test]
+    fn sut_picks_no_random_key_when_every_key_is_expired() {
+        // Arrange
+        let mut sut = Keyspace::default();
+        sut.insert(entry("foo", Some(1)));
+        sut.insert(entry("bar", Some(1)));
+
+        // Act
+        let actual = (sut.random_key(0), sut.random_key(2));
+
+        // Assert
+        assert!(actual.0.is_some());
+        assert_eq!(actual.1, None);
+    }
+
+    #[rstest::rstest]
+    #[case::insert(|sut: &mut Keyspace| { sut.insert(entry("foo", None)); }, true)]
+    #[case::remove(|sut: &mut Keyspace| { sut.remove("foo"); }, true)]
+    #[case::expire(|sut: &mut Keyspace| { sut.set_expiry("foo", None); }, true)]
+    #[case::modify(|sut: &mut Keyspace| { sut.value_mut("foo"); }, true)]
+    #[case::take(|sut: &mut Keyspace| { sut.take(); }, true)]
+    #[case::other_key(|sut: &mut Keyspace| { sut.insert(entry("bar", None)); }, false)]
+    #[case::missing_key(|sut: &mut Keyspace| { sut.remove("bar"); }, false)]
+    fn sut_raises_flag_of_watched_key_once_modified(
+        #[case] modify: fn(&mut Keyspace),
+        #[case] expected: bool,
+    ) {
+        // Arrange
+        let mut sut = Keyspace::default();
+        sut.insert(entry("foo", Some(1)));
+        let dirty = Arc::new(AtomicBool::new(false));
+        sut.watch("foo", &dirty);
+
+        // Act
+        modify(&mut sut);
+
+        // Assert
+        assert_eq!(dirty.load(Ordering::SeqCst), expected);
+    }
+
+    #[test]
+    fn sut_keeps_watchers_of_missing_keys_when_taken() {
+        // Arrange
+        let mut sut = Keyspace::default();
+        let dirty = Arc::new(AtomicBool::new(false));
+        sut.watch("foo", &dirty);
+
+        // Act
+        sut.take();
+        sut.insert(entry("foo", None));
+
+        // Assert
+        assert!(dirty.load(Ordering::SeqCst));
+    }
+}
@@ -0,0 +1,669 @@
+mod databases;
+mod keyspace;
+mod scan_index;
+mod sorted_set;
+
+use std::ops::Range;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::Ordering;
+
+use tokio::sync::RwLock;
+use tokio::sync::RwLockReadGuard;
+use tokio::sync::RwLockWriteGuard;
+
+use keyspace::Keyspace;
+
+use crate::clock::Clock;
+use crate::clock::SystemClock;
+
+pub use databases::Databases;
+pub use sorted_set::AddCondition;
+pub use sorted_set::AddOutcome;
+pub use sorted_set::SortedSet;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum TimeUnit {
+    Second,
+    Millisecond,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Expiry {
+    pub epoch: u128,
+    pub unit: TimeUnit,
+}
+
+impl Expiry {
+    pub fn to_millis(&self) -> u128 {
+        match self.unit {
+            TimeUnit::Second => self.epoch * 1000,
+            TimeUnit::Millisecond => self.epoch,
+        }
+    }
+
+    pub fn is_expired(&self, now_in_millis: u128) -> bool {
+        now_in_millis > self.to_millis()
+    }
+}
+
+/// The NX, XX, GT and LT flags of the EXPIRE family, where a missing TTL counts as infinite.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct ExpireCondition {
+    pub nx: bool,
+    pub xx: bool,
+    pub gt: bool,
+    pub lt: bool,
+}
+
+impl ExpireCondition {
+    fn allows(&self, current: Option<u128>, new: u128) -> bool {
+        match current {
+            None => !self.xx && !self.gt,
+            Some(current) => !self.nx && (!self.gt || new > current) && (!self.lt || new < current),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Data {
+    String(String),
+    SortedSet(SortedSet),
+}
+
+impl Data {
+    /// Collections never exist empty, so storing an empty one removes the key instead.
+    pub fn is_empty(&self) -> bool {
+        match self {
+            Data::String(_) => false,
+            Data::SortedSet(sorted_set) => sorted_set.is_empty(),
+        }
+    }
+
+    pub fn type_name(&self) -> &'static str {
+        match self {
+            Data::String(_) => "string",
+            Data::SortedSet(_) => "zset",
+        }
+    }
+
+    /// Approximates the work needed to free the value, counted in allocations.
+    pub fn free_effort(&self) -> usize {
+        match self {
+            Data::String(_) => 1,
+            Data::SortedSet(sorted_set) => sorted_set.len(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Entry {
+    pub key: Arc<str>,
+    pub value: Data,
+    pub expiry: Option<Expiry>,
+}
+
+impl Entry {
+    pub fn is_expired(&self, now_in_millis: u128) -> bool {
+        self.expiry
+            .as_ref()
+            .is_some_and(|expiry| expiry.is_expired(now_in_millis))
+    }
+}
+
+/// What happened to a key without being asked for by a command, to be notified later.
+#[derive(Debug, Clone, PartialEq)]
+pub enum KeyEvent {
+    Expired(String),
+    New(String),
+}
+
+/// The outcome of one active expiration step over a random sample of keys with an expiry.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct EvictionSample {
+    pub sampled: usize,
+    pub evicted: usize,
+}
+
+#[derive(Debug, PartialEq, thiserror::Error)]
+pub enum RepositoryError {
+    #[error("WRONGTYPE Operation against a key holding the wrong kind of value")]
+    WrongType,
+    #[error("ERR no such key")]
+    NoSuchKey,
+}
+
+#[async_trait::async_trait]
+pub trait Repository: Send + Sync + 'static {
+    async fn set(&self, entry: Entry);
+    async fn get(&self, key: &str) -> Result<Option<String>, RepositoryError>;
+    async fn entries(&self) -> Vec<Entry>;
+    async fn entry(&self, key: &str) -> Option<Entry>;
+    /// Removes the key and hands back its entry so callers decide where the value is dropped.
+    async fn delete(&self, key: &str) -> Option<Entry>;
+    async fn exists(&self, key: &str) -> bool;
+    async fn type_of(&self, key: &str) -> Option<&'static str>;
+    /// Moves the entry with its expiry to `new_key`, returning false if `new_key` exists and
+    /// `replace` is not set.
+    async fn rename(
+        &self,
+        key: &str,
+        new_key: &str,
+        replace: bool,
+    ) -> Result<bool, RepositoryError>;
+    async fn copy(&self, source: &str, destination: &str, replace: bool) -> bool;
+    async fn random_key(&self) -> Option<String>;
+    async fn size(&self) -> usize;
+    /// How many keys have an expiry.
+    async fn volatile_size(&self) -> usize;
+    async fn expiry(&self, key: &str) -> Result<Option<Expiry>, RepositoryError>;
+    /// Sets the expiry if `condition` allows it, deleting the key right away when it is in the past.
+    async fn expire(&self, key: &str, expiry: Expiry, condition: ExpireCondition) -> bool;
+    async fn persist(&self, key: &str) -> bool;
+    async fn zadd(
+        &self,
+        key: &str,
+        members: Vec<(String, f64)>,
+        condition: AddCondition,
+    ) -> Result<Vec<AddOutcome>, RepositoryError>;
+    async fn zscore(&self, key: &str, member: &str) -> Result<Option<f64>, RepositoryError>;
+    async fn zcard(&self, key: &str) -> Result<usize, RepositoryError>;
+    async fn zrange_by_score(
+        &self,
+        key: &str,
+        range: Range<f64>,
+    ) -> Result<Vec<(String, f64)>, RepositoryError>;
+    /// Returns live keys of one page of a cursor based iteration, keeping only those holding
+    /// `type_name` if given, along with the cursor to continue from (0 when done).
+    async fn scan(&self, cursor: u64, count: usize, type_name: Option<&str>) -> (u64, Vec<String>);
+    async fn zscan(
+        &self,
+        key: &str,
+        cursor: u64,
+        count: usize,
+    ) -> Result<(u64, Vec<(String, f64)>), RepositoryError>;
+    /// Removes every key, dropping them on a blocking thread when `lazy` is set.
+    async fn flush(&self, lazy: bool);
+    /// Raises `dirty` as soon as `key` is modified, for as long as the flag is alive.
+    async fn watch(&self, key: &str, dirty: &Arc<AtomicBool>);
+    /// Raises the flags of every watched key, as when the whole database is replaced.
+    async fn touch_watched(&self);
+    /// Deletes the expired keys among `sample_size` randomly sampled keys with an expiry.
+    async fn evict_expired(&self, sample_size: usize) -> EvictionSample;
+    /// Drains the keys which expired or were created since the last call.
+    fn take_events(&self) -> Vec<KeyEvent>;
+}
+
+pub struct InMemoryRepository {
+    keyspace: RwLock<Keyspace>,
+    clock: Arc<dyn Clock>,
+    events: Mutex<Vec<KeyEvent>>,
+    /// Raised while the server is a replica, which leaves expired keys for its master to delete
+    /// and only hides them from reads.
+    replica: Arc<AtomicBool>,
+}
+
+impl Default for InMemoryRepository {
+    fn default() -> Self {
+        Self::with_clock(Arc::new(SystemClock))
+    }
+}
+
+impl InMemoryRepository {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_clock(clock: Arc<dyn Clock>) -> Self {
+        Self::with_role(clock, Arc::default())
+    }
+
+    /// A repository which keeps expired keys while `replica` is raised.
+    pub fn with_role(clock: Arc<dyn Clock>, replica: Arc<AtomicBool>) -> Self {
+        Self {
+            keyspace: RwLock::default(),
+            clock,
+            events: Mutex::default(),
+            replica,
+        }
+    }
+
+    fn record(&self, event: KeyEvent) {
+        self.events.lock().unwrap().push(event);
+    }
+
+    /// Inserts the entry, recording the key as new unless it replaces one.
+    fn insert(&self, keyspace: &mut Keyspace, entry: Entry) {
+        let key = entry.key.to_string();
+        if keyspace.insert(entry).is_none() {
+            self.record(KeyEvent::New(key));
+        }
+    }
+
+    fn remove_if_expired(&self, keyspace: &mut Keyspace, key: &str, now_in_millis: u128) -> bool {
+        if self.replica.load(Ordering::SeqCst) {
+            return false;
+        }
+        let removed = keyspace.remove_if_expired(key, now_in_millis);
+        if removed {
+            self.record(KeyEvent::Expired(key.to_string()));
+        }
+        removed
+    }
+
+    fn now_in_millis(&self) -> u128 {
+        self.clock.now_in_millis()
+    }
+
+    /// Takes a read lock on the keyspace, lazily deleting `key` first if it has expired.
+    async fn read(&self, key: &str) -> RwLockReadGuard<'_, Keyspace> {
+        let now = self.now_in_millis();
+        let keyspace = self.keyspace.read().await;
+        if self.replica.load(Ordering::SeqCst)
+            || !keyspace.get(key).is_some_and(|entry| entry.is_expired(now))
+        {
+            return keyspace;
+        }
+        drop(keyspace);
+        let mut keyspace = self.keyspace.write().await;
+        self.remove_if_expired(&mut keyspace, key, now);
+        keyspace.downgrade()
+    }
+
+    /// Takes a write lock on the keyspace, lazily deleting the given keys first if they have
+    /// expired.
+    async fn write(&self, keys: &[&str]) -> RwLockWriteGuard<'_, Keyspace> {
+        let now = self.now_in_millis();
+        let mut keyspace = self.keyspace.write().await;
+        for key in keys {
+            self.remove_if_expired(&mut keyspace, key, now);
+        }
+        keyspace
+    }
+
+    /// The entry of `key` unless it has expired, as a replica still holds such keys.
+    fn live<'a>(&self, keyspace: &'a Keyspace, key: &str) -> Option<&'a Entry> {
+        let now = self.now_in_millis();
+        keyspace.get(key).filter(|entry| !entry.is_expired(now))
+    }
+
+    fn sorted_set(entry: Option<&Entry>) -> Result<Option<&SortedSet>, RepositoryError> {
+        match entry.map(|entry| &entry.value) {
+            Some(Data::SortedSet(sorted_set)) => Ok(Some(sorted_set)),
+            Some(_) => Err(RepositoryError::WrongType),
+            None => Ok(None),
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl Repository for InMemoryRepository {
+    async fn set(&self, entry: Entry) {
+        let mut keyspace = self.keyspace.write().await;
+        if entry.value.is_empty() {
+            keyspace.remove(&entry.key);
+        } else {
+            self.insert(&mut keyspace, entry);
+        }
+    }
+
+    async fn get(&self, key: &str) -> Result<Option<String>, RepositoryError> {
+        let keyspace = self.read(key).await;
+        match self.live(&keyspace, key).map(|entry| &entry.value) {
+            Some(Data::String(value)) => Ok(Some(value.clone())),
+            Some(_) => Err(RepositoryError::WrongType),
+            None => Ok(None),
+        }
+    }
+
+    async fn entries(&self) -> Vec<Entry> {
+        let keyspace = self.keyspace.read().await;
+        keyspace.values().cloned().collect()
+    }
+
+    async fn entry(&self, key: &str) -> Option<Entry> {
+        let keyspace = self.read(key).await;
+        self.live(&keyspace, key).cloned()
+    }
+
+    async fn delete(&self, key: &str) -> Option<Entry> {
+        let mut keyspace = self.write(&[key]).await;
+        keyspace.remove(key)
+    }
+
+    async fn exists(&self, key: &str) -> bool {
+        let keyspace = self.read(key).await;
+        self.live(&keyspace, key).is_some()
+    }
+
+    async fn type_of(&self, key: &str) -> Option<&'static str> {
+        let keyspace = self.read(key).await;
+        self.live(&keyspace, key)
+            .map(|entry| entry.value.type_name())
+    }
+
+    async fn rename(
+        &self,
+        key: &str,
+        new_key: &str,
+        replace: bool,
+    ) -> Result<bool, RepositoryError> {
+        let mut keyspace = self.write(&[key, new_key]).await;
+        if keyspace.get(key).is_none() {
+            return Err(RepositoryError::NoSuchKey);
+        }
+        if key == new_key {
+            return Ok(replace);
+        }
+        if !replace && keyspace.get(new_key).is_some() {
+            return Ok(false);
+        }
+        let mut entry = keyspace.remove(key).unwrap();
+        entry.key = new_key.into();
+        self.insert(&mut keyspace, entry);
+        Ok(true)
+    }
+
+    async fn copy(&self, source: &str, destination: &str, replace: bool) -> bool {
+        let mut keyspace = self.write(&[source, destination]).await;
+        let Some(entry) = keyspace.get(source) else {
+            return false;
+        };
+        if !replace && keyspace.get(destination).is_some() {
+            return false;
+        }
+        let entry = Entry {
+            key: destination.into(),
+            ..entry.clone()
+        };
+        self.insert(&mut keyspace, entry);
+        true
+    }
+
+    async fn random_key(&self) -> Option<String> {
+        self.keyspace.read().await.random_key(self.now_in_millis())
+    }
+
+    async fn size(&self) -> usize {
+        self.keyspace.read().await.len()
+    }
+
+    async fn volatile_size(&self) -> usize {
+        self.keyspace.read().await.volatile_len()
+    }
+
+    async fn expiry(&self, key: &str) -> Result<Option<Expiry>, RepositoryError> {
+        let keyspace = self.read(key).await;
+        let entry = self
+            .live(&keyspace, key)
+            .ok_or(RepositoryError::NoSuchKey)?;
+        Ok(entry.expiry.clone())
+    }
+
+    async fn expire(&self, key: &str, expiry: Expiry, condition: ExpireCondition) -> bool {
+        let mut keyspace = self.write(&[key]).await;
+        let Some(entry) = keyspace.get(key) else {
+            return false;
+        };
+        let current = entry.expiry.as_ref().map(Expiry::to_millis);
+        if !condition.allows(current, expiry.to_millis()) {
+            return false;
+        }
+        if expiry.to_millis() <= self.now_in_millis() {
+            keyspace.remove(key);
+        } else {
+            keyspace.set_expiry(key, Some(expiry));
+        }
+        true
+    }
+
+    async fn persist(&self, key: &str) -> bool {
+        let mut keyspace = self.write(&[key]).await;
+        keyspace.set_expiry(key, None).is_some()
+    }
+
+    async fn zadd(
+        &self,
+        key: &str,
+        members: Vec<(String, f64)>,
+        condition: AddCondition,
+    ) -> Result<Vec<AddOutcome>, RepositoryError> {
+        let mut keyspace = self.write(&[key]).await;
+        if keyspace.get(key).is_none() {
+            if condition == AddCondition::Exists {
+                return Ok(vec![AddOutcome::Unchanged; members.len()]);
+            }
+            self.insert(
+                &mut keyspace,
+                Entry {
+                    key: key.into(),
+                    value: Data::SortedSet(SortedSet::new()),
+                    expiry: None,
+                },
+            );
+        }
+        let Some(Data::SortedSet(sorted_set)) = keyspace.value_mut(key) else {
+            return Err(RepositoryError::WrongType);
+        };
+
+        let outcomes = members
+            .into_iter()
+            .map(|(member, score)| sorted_set.add(member, score, condition))
+            .collect();
+        if sorted_set.is_empty() {
+            keyspace.remove(key);
+        }
+        Ok(outcomes)
+    }
+
+    async fn zscore(&self, key: &str, member: &str) -> Result<Option<f64>, RepositoryError> {
+        let keyspace = self.read(key).await;
+        Ok(Self::sorted_set(self.live(&keyspace, key))?
+            .and_then(|sorted_set| sorted_set.score(member)))
+    }
+
+    async fn zcard(&self, key: &str) -> Result<usize, RepositoryError> {
+        let keyspace = self.read(key).await;
+        Ok(Self::sorted_set(self.live(&keyspace, key))?.map_or(0, |sorted_set| sorted_set.len()))
+    }
+
+    async fn zrange_by_score(
+        &self,
+        key: &str,
+        range: Range<f64>,
+    ) -> Result<Vec<(String, f64)>, RepositoryError> {
+        let keyspace = self.read(key).await;
+        Ok(Self::sorted_set(self.live(&keyspace, key))?
+            .map(|sorted_set| {
+                sorted_set
+                    .range_by_score(range)
+                    .map(|(member, score)| (member.to_string(), score))
+                    .collect()
+            })
+            .unwrap_or_default())
+    }
+
+    async fn scan(&self, cursor: u64, count: usize, type_name: Option<&str>) -> (u64, Vec<String>) {
+        let now = self.now_in_millis();
+        let keyspace = self.keyspace.read().await;
+        let (cursor, entries) = keyspace.scan(cursor, count);
+        let keys = entries
+            .into_iter()
+            .filter(|entry| !entry.is_expired(now))
+            .filter(|entry| type_name.is_none_or(|type_name| entry.value.type_name() == type_name))
+            .map(|entry| entry.key.to_string())
+            .collect();
+        (cursor, keys)
+    }
+
+    async fn zscan(
+        &self,
+        key: &str,
+        cursor: u64,
+        count: usize,
+    ) -> Result<(u64, Vec<(String, f64)>), RepositoryError> {
+        let keyspace = self.read(key).await;
+        let Some(sorted_set) = Self::sorted_set(self.live(&keyspace, key))? else {
+            return Ok((0, vec![]));
+        };
+        let (cursor, members) = sorted_set.scan(cursor, count);
+        let members = members
+            .into_iter()
+            .map(|(member, score)| (member.to_string(), score))
+            .collect();
+        Ok((cursor, members))
+    }
+
+    async fn flush(&self, lazy: bool) {
+        let keyspace = self.keyspace.write().await.take();
+        if lazy {
+            tokio::task::spawn_blocking(move || drop(keyspace));
+        }
+    }
+
+    async fn watch(&self, key: &str, dirty: &Arc<AtomicBool>) {
+        self.write(&[key]).await.watch(key, dirty);
+    }
+
+    async fn touch_watched(&self) {
+        self.keyspace.write().await.touch_all();
+    }
+
+    async fn evict_expired(&self, sample_size: usize) -> EvictionSample {
+        let now = self.now_in_millis();
+        let mut keyspace = self.keyspace.write().await;
+        let sampled = keyspace.sample_volatile(sample_size);
+        let evicted = sampled
+            .iter()
+            .filter(|key| self.remove_if_expired(&mut keyspace, key, now))
+            .count();
+        EvictionSample {
+            sampled: sampled.len(),
+            evicted,
+        }
+    }
+
+    fn take_events(&self) -> Vec<KeyEvent> {
+        std::mem::take(&mut *self.events.lock().unwrap())
+    }
+}
+
+#[cfg(test)]
+pub mod fixture {
+    use std::ops::Range;
+    use std::sync::Arc;
+    use std::sync::atomic::AtomicBool;
+
+    use super::AddCondition;
+    use super::AddOutcome;
+    use super::Entry;
+    use super::EvictionSample;
+    use super::ExpireCondition;
+    use super::Expiry;
+    use super::KeyEvent;
+    use super::Repository;
+    use super::RepositoryError;
+
+    #[derive(Default)]
+    pub struct DummyRepository;
+
+    #[async_trait::async_trait]
+    impl Repository for DummyRepository {
+        async fn set(&self, _entry: Entry) {}
+        async fn get(&self, _key: &str) -> Result<Option<String>, RepositoryError> {
+            Ok(None)
+        }
+        async fn entries(&self) -> Vec<Entry> {
+            vec![]
+        }
+        async fn entry(&self, _key: &str) -> Option<Entry> {
+            None
+        }
+        async fn delete(&self, _key: &str) -> Option<Entry> {
+            None
+        }
+        async fn exists(&self, _key: &str) -> bool {
+            false
+        }
+        async fn type_of(&self, _key: &str) -> Option<&'static str> {
+            None
+        }
+        async fn rename(
+            &self,
+            _key: &str,
+            _new_key: &str,
+            _replace: bool,
+        ) -> Result<bool, RepositoryError> {
+            Err(RepositoryError::NoSuchKey)
+        }
+        async fn copy(&self, _source: &str, _destination: &str, _replace: bool) -> bool {
+            false
+        }
+        async fn random_key(&self) -> Option<String> {
+            None
+        }
+        async fn size(&self) -> usize {
+            0
+        }
+        async fn volatile_size(&self) -> usize {
+            0
+        }
+        async fn expiry(&self, _key: &str) -> Result<Option<Expiry>, RepositoryError> {
+            Err(RepositoryError::NoSuchKey)
+        }
+        async fn expire(&self, _key: &str, _expiry: Expiry, _condition: ExpireCondition) -> bool {
+            false
+        }
+        async fn persist(&self, _key: &str) -> bool {
+            false
+        }
+        async fn zadd(
+            &self,
+            _key: &str,
+            members: Vec<(String, f64)>,
+            _condition: AddCondition,
+        ) -> Result<Vec<AddOutcome>, RepositoryError> {
+            Ok(vec![AddOutcome::Unchanged; members.len()])
+        }
+        async fn zscore(&self, _key: &str, _member: &str) -> Result<Option<f64>, RepositoryError> {
+            Ok(None)
+        }
+        async fn zcard(&self, _key: &str) -> Result<usize, RepositoryError> {
+            Ok(0)
+        }
+        async fn zrange_by_score(
+            &self,
+            _key: &str,
+            _range: Range<f64>,
+        ) -> Result<Vec<(String, f64)>, RepositoryError> {
+            Ok(vec![])
+        }
+        async fn scan(
+            &self,
+            _cursor: u64,
+            _count: usize,
+            _type_name: Option<&str>,
+        ) -> (u64, Vec<String>) {
+            (0, vec![])
+        }
+        async fn zscan(
+            &self,
+            _key: &str,
+            _cursor: u64,
+            _count: usize,
+        ) -> Result<(u64, Vec<(String, f64)>), RepositoryError> {
+            Ok((0, vec![]))
+        }
+        async fn flush(&self, _lazy: bool) {}
+        async fn watch(&self, _key: &str, _dirty: &Arc<AtomicBool>) {}
+        async fn touch_watched(&self) {}
+        async fn evict_expired(&self, _sample_size: usize) -> EvictionSample {
+            EvictionSample::default()
+        }
+
+        fn take_events(&self) -> Vec<KeyEvent> {
+            vec![]
+        }
+    }
+}
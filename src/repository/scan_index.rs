@@ -0,0 +1,124 @@
+use std::collections::BTreeSet;
+use std::collections::hash_map::DefaultHasher;
+use std::hash::Hash;
+use std::hash::Hasher;
+use std::sync::Arc;
+
+/// Orders keys the way Redis visits hash table buckets with its reverse binary cursor, which is
+/// by the bit-reversed hash. As the order of existing keys never changes with inserts, removals
+/// or table growth, a full iteration returns every key present from start to end.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ScanIndex {
+    slots: BTreeSet<(u64, Arc<str>)>,
+}
+
+impl ScanIndex {
+    pub fn insert(&mut self, key: &Arc<str>) {
+        self.slots.insert((Self::slot(key), key.clone()));
+    }
+
+    pub fn remove(&mut self, key: &Arc<str>) {
+        self.slots.remove(&(Self::slot(key), key.clone()));
+    }
+
+    /// Returns at least `count` keys starting at `cursor` (fewer at the end) along with the
+    /// cursor to continue from, which is 0 once the iteration is complete. Keys sharing a slot
+    /// are never split across calls, just like a bucket is always returned as a whole.
+    pub fn scan(&self, cursor: u64, count: usize) -> (u64, Vec<&str>) {
+        let mut keys = Vec::with_capacity(count);
+        let mut last_slot = None;
+        for (slot, key) in self.slots.range((cursor.reverse_bits(), Arc::from(""))..) {
+            if keys.len() >= count && last_slot != Some(*slot) {
+                return (slot.reverse_bits(), keys);
+            }
+            keys.push(key.as_ref());
+            last_slot = Some(*slot);
+        }
+        (0, keys)
+    }
+
+    fn slot(key: &str) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        key.hash(&mut hasher);
+        hasher.finish().reverse_bits()
+    }
+}
+
+#[cfg(test)]
+mod specs_for_scan {
+    use std::collections::HashSet;
+
+    use super::ScanIndex;
+
+    fn scan_all(sut: &ScanIndex, count: usize) -> Vec<String> {
+        let mut keys = vec![];
+        let mut cursor = 0;
+        loop {
+            let (next, page) = sut.scan(cursor, count);
+            keys.extend(page.into_iter().map(str::to_string));
+            if next == 0 {
+                return keys;
+            }
+            cursor = next;
+        }
+    }
+
+    #[test]
+    fn sut_returns_every_key_exactly_once_over_a_full_iteration() {
+        // Arrange
+        let mut sut = ScanIndex::default();
+        for i in 0..1000 {
+            sut.insert(&format!("key:{i}").into());
+        }
+
+        // Act
+        let actual = scan_all(&sut, 7);
+
+        // Assert
+        assert_eq!(actual.len(), 1000);
+        assert_eq!(actual.iter().collect::<HashSet<_>>().len(), 1000);
+    }
+
+    #[test]
+    fn sut_returns_keys_present_throughout_despite_concurrent_changes() {
+        // Arrange
+        let mut sut = ScanIndex::default();
+        for i in 0..100 {
+            sut.insert(&format!("stable:{i}").into());
+        }
+        let mut actual = HashSet::new();
+        let mut cursor = 0;
+
+        // Act
+        for round in 0.. {
+            let (next, page) = sut.scan(cursor, 10);
+            actual.extend(page.into_iter().map(str::to_string));
+            for i in 0..50 {
+                sut.insert(&format!("added:{round}:{i}").into());
+            }
+            sut.remove(&format!("added:{round}:0").into());
+            if next == 0 {
+                break;
+            }
+            cursor = next;
+        }
+
+        // Assert
+        assert!((0..100).all(|i| actual.contains(&format!("stable:{i}"))));
+    }
+
+    #[test]
+    fn sut_returns_zero_cursor_when_everything_fits_in_one_call() {
+        // Arrange
+        let mut sut = ScanIndex::default();
+        sut.insert(&"foo".into());
+        sut.insert(&"bar".into());
+
+        // Act
+        let (cursor, mut keys) = sut.scan(0, 10);
+
+        // Assert
+        keys.sort();
+        assert_eq!((cursor, keys), (0, vec!["bar", "foo"]));
+    }
+}
@@ -0,0 +1,201 @@
+use std::cmp::Ordering;
+use std::collections::BTreeSet;
+use std::collections::HashMap;
+use std::ops::Range;
+
+use super::scan_index::ScanIndex;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum AddCondition {
+    Always,
+    NotExists,
+    Exists,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum AddOutcome {
+    Added,
+    Updated,
+    Unchanged,
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SortedSet {
+    scores: HashMap<String, f64>,
+    ordered: BTreeSet<ScoredMember>,
+    scan_index: ScanIndex,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct ScoredMember {
+    score: f64,
+    member: String,
+}
+
+impl Eq for ScoredMember {}
+
+impl PartialOrd for ScoredMember {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for ScoredMember {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.score
+            .total_cmp(&other.score)
+            .then_with(|| self.member.cmp(&other.member))
+    }
+}
+
+impl SortedSet {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn len(&self) -> usize {
+        self.scores.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.scores.is_empty()
+    }
+
+    pub fn score(&self, member: &str) -> Option<f64> {
+        self.scores.get(member).copied()
+    }
+
+    pub fn add(&mut self, member: String, score: f64, condition: AddCondition) -> AddOutcome {
+        match (self.scores.get(&member).copied(), condition) {
+            (Some(_), AddCondition::NotExists) | (None, AddCondition::Exists) => {
+                AddOutcome::Unchanged
+            }
+            (Some(current), _) if current == score => AddOutcome::Unchanged,
+            (Some(current), _) => {
+                self.ordered.remove(&ScoredMember {
+                    score: current,
+                    member: member.clone(),
+                });
+                self.insert(member, score);
+                AddOutcome::Updated
+            }
+            (None, _) => {
+                self.insert(member, score);
+                AddOutcome::Added
+            }
+        }
+    }
+
+    /// Returns members whose score falls within `range`, ordered by score and then by member.
+    pub fn range_by_score(&self, range: Range<f64>) -> impl Iterator<Item = (&str, f64)> {
+        let start = ScoredMember {
+            score: range.start,
+            member: String::new(),
+        };
+        let end = ScoredMember {
+            score: range.end,
+            member: String::new(),
+        };
+        self.ordered
+            .range(start..end)
+            .map(|scored| (scored.member.as_str(), scored.score))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&str, f64)> {
+        self.ordered
+            .iter()
+            .map(|scored| (scored.member.as_str(), scored.score))
+    }
+
+    /// Returns one page of a cursor based iteration over the members, see `ScanIndex::scan`.
+    pub fn scan(&self, cursor: u64, count: usize) -> (u64, Vec<(&str, f64)>) {
+        let (cursor, members) = self.scan_index.scan(cursor, count);
+        let members = members
+            .into_iter()
+            .map(|member| (member, self.scores[member]))
+            .collect();
+        (cursor, members)
+    }
+
+    fn insert(&mut self, member: String, score: f64) {
+        self.scan_index.insert(&member.as_str().into());
+        self.scores.insert(member.clone(), score);
+        self.ordered.insert(ScoredMember { score, member });
+    }
+}
+
+impl FromIterator<(String, f64)> for SortedSet {
+    fn from_iter<I: IntoIterator<Item = (String, f64)>>(iter: I) -> Self {
+        let mut sorted_set = SortedSet::new();
+        for (member, score) in iter {
+            sorted_set.add(member, score, AddCondition::Always);
+        }
+        sorted_set
+    }
+}
+
+#[cfg(test)]
+mod specs_for_add {
+    use super::AddCondition;
+    use super::AddOutcome;
+    use super::SortedSet;
+
+    #[test]
+    fn sut_adds_new_member_and_updates_existing_member() {
+        // Arrange
+        let mut sut = SortedSet::new();
+
+        // Act
+        let added = sut.add("a".to_string(), 1.0, AddCondition::Always);
+        let updated = sut.add("a".to_string(), 2.0, AddCondition::Always);
+        let unchanged = sut.add("a".to_string(), 2.0, AddCondition::Always);
+
+        // Assert
+        assert_eq!(added, AddOutcome::Added);
+        assert_eq!(updated, AddOutcome::Updated);
+        assert_eq!(unchanged, AddOutcome::Unchanged);
+        assert_eq!(sut.score("a"), Some(2.0));
+        assert_eq!(sut.len(), 1);
+    }
+
+    #[rstest::rstest]
+    #[case(AddCondition::NotExists, 1.0)]
+    #[case(AddCondition::Exists, 5.0)]
+    fn sut_respects_add_condition_for_existing_member(
+        #[case] condition: AddCondition,
+        #[case] expected: f64,
+    ) {
+        // Arrange
+        let mut sut = SortedSet::new();
+        sut.add("a".to_string(), 1.0, AddCondition::Always);
+
+        // Act
+        sut.add("a".to_string(), 5.0, condition);
+        sut.add("b".to_string(), 5.0, AddCondition::Exists);
+
+        // Assert
+        assert_eq!(sut.score("a"), Some(expected));
+        assert_eq!(sut.score("b"), None);
+    }
+}
+
+#[cfg(test)]
+mod specs_for_range_by_score {
+    use super::SortedSet;
+
+    #[test]
+    fn sut_returns_members_within_half_open_range_in_score_order() {
+        // Arrange
+        let sut: SortedSet = [("c", 3.0), ("a", 1.0), ("b", 2.0), ("bb", 2.0)]
+            .into_iter()
+            .map(|(member, score)| (member.to_string(), score))
+            .collect();
+
+        // Act
+        let actual: Vec<(&str, f64)> = sut.range_by_score(2.0..3.0).collect();
+
+        // Assert
+        let expected = vec![("b", 2.0), ("bb", 2.0)];
+        assert_eq!(actual, expected);
+    }
+}
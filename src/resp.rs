@@ -1,15 +1,36 @@
+/// The longest bulk string accepted, as proto-max-bulk-len defaults to in Redis.
+const PROTO_MAX_BULK_LEN: usize = 512 * 1024 * 1024;
+/// The most elements an array may claim to have.
+const PROTO_MAX_MULTIBULK_LEN: usize = i32::MAX as usize;
+/// How long a line may grow while its CRLF has yet to arrive.
+const PROTO_LINE_MAX_SIZE: usize = 64 * 1024;
+/// How deep arrays may be nested, so that decoding them never runs out of stack.
+const PROTO_MAX_NESTING: usize = 128;
+
 #[derive(Clone, Debug, PartialEq, PartialOrd, Eq, Ord)]
 pub enum Value {
     SimpleString(String),
+    Error(String),
+    Integer(i64),
     BulkString(String),
     Array(Vec<Value>),
     Null,
+    NullArray,
+    /// Several replies to a single command, such as SUBSCRIBE with more than one channel.
+    Replies(Vec<Value>),
+    /// RESP3 only, sent to clients which asked for it with HELLO.
+    Map(Vec<(Value, Value)>),
+    Push(Vec<Value>),
+    /// Bytes written as they are, such as an RDB file or a part of the replication stream.
+    Raw(Vec<u8>),
 }
 
 impl Value {
     pub fn serialize(&self) -> Vec<u8> {
         match self {
             Self::SimpleString(s) => format!("+{s}\r\n").into_bytes(),
+            Self::Error(s) => format!("-{s}\r\n").into_bytes(),
+            Self::Integer(n) => format!(":{n}\r\n").into_bytes(),
             Self::BulkString(s) => format!("${}\r\n{}\r\n", s.len(), s).into_bytes(),
             Self::Array(arr) => {
                 let mut result = format!("*{}\r\n", arr.len()).into_bytes();
@@ -19,6 +40,24 @@ impl Value {
                 result
             }
             Self::Null => b"$-1\r\n".to_vec(),
+            Self::NullArray => b"*-1\r\n".to_vec(),
+            Self::Replies(replies) => replies.iter().flat_map(Value::serialize).collect(),
+            Self::Map(entries) => {
+                let mut result = format!("%{}\r\n", entries.len()).into_bytes();
+                for (key, value) in entries {
+                    result.extend(key.serialize());
+                    result.extend(value.serialize());
+                }
+                result
+            }
+            Self::Raw(bytes) => bytes.clone(),
+            Self::Push(values) => {
+                let mut result = format!(">{}\r\n", values.len()).into_bytes();
+                for value in values {
+                    result.extend(value.serialize());
+                }
+                result
+            }
         }
     }
 
@@ -82,6 +121,80 @@ impl Value {
         }
         panic!("No CRLF found in buffer");
     }
+
+    /// Decodes the value at the start of `buf`, returning it along with how many bytes it
+    /// took, or `None` while `buf` holds only part of it.
+    pub fn decode(buf: &[u8]) -> Result<Option<(Self, usize)>, anyhow::Error> {
+        Self::decode_nested(buf, 0)
+    }
+
+    fn decode_nested(buf: &[u8], depth: usize) -> Result<Option<(Self, usize)>, anyhow::Error> {
+        let Some(line_end) = buf.windows(2).position(|window| window == b"\r\n") else {
+            if buf.len() > PROTO_LINE_MAX_SIZE {
+                anyhow::bail!("too big line");
+            }
+            return Ok(None);
+        };
+        let Some((&kind, line)) = buf[..line_end].split_first() else {
+            anyhow::bail!("empty line");
+        };
+        let line = String::from_utf8_lossy(line).to_string();
+        let rest = line_end + 2;
+        let value = match kind {
+            b'+' => Self::SimpleString(line),
+            b'-' => Self::Error(line),
+            b':' => Self::Integer(line.parse()?),
+            b'$' if line == "-1" => Self::Null,
+            b'$' => {
+                let size = line
+                    .parse::<usize>()
+                    .ok()
+                    .filter(|size| *size <= PROTO_MAX_BULK_LEN)
+                    .ok_or_else(|| anyhow::anyhow!("invalid bulk length"))?;
+                let end = rest
+                    .checked_add(size)
+                    .ok_or_else(|| anyhow::anyhow!("invalid bulk length"))?;
+                let Some(string) = buf.get(rest..end + 2) else {
+                    return Ok(None);
+                };
+                if &string[size..] != b"\r\n" {
+                    anyhow::bail!("expected CRLF after bulk string");
+                }
+                let string = match String::from_utf8(string[..size].to_vec()) {
+                    Ok(string) => string,
+                    // Binary strings, such as DUMP payloads, keep every byte as the char of the
+                    // same code point.
+                    Err(e) => e.into_bytes().into_iter().map(char::from).collect(),
+                };
+                return Ok(Some((Self::BulkString(string), end + 2)));
+            }
+            b'*' if line == "-1" => Self::NullArray,
+            b'*' => {
+                let size = line
+                    .parse::<usize>()
+                    .ok()
+                    .filter(|size| *size <= PROTO_MAX_MULTIBULK_LEN)
+                    .ok_or_else(|| anyhow::anyhow!("invalid multibulk length"))?;
+                if depth >= PROTO_MAX_NESTING {
+                    anyhow::bail!("too deeply nested arrays");
+                }
+                // The length is only claimed so far, the elements are what takes memory.
+                let mut values = Vec::with_capacity(size.min(1024));
+                let mut consumed = rest;
+                for _ in 0..size {
+                    let Some((value, size)) = Self::decode_nested(&buf[consumed..], depth + 1)?
+                    else {
+                        return Ok(None);
+                    };
+                    values.push(value);
+                    consumed += size;
+                }
+                return Ok(Some((Self::Array(values), consumed)));
+            }
+            _ => anyhow::bail!("invalid RESP type '{}'", kind as char),
+        };
+        Ok(Some((value, rest)))
+    }
 }
 
 impl From<&[u8]> for Value {
@@ -144,3 +257,89 @@ mod specs_for_from_bytes_to_value {
         assert_eq!(actual, expected);
     }
 }
+
+#[cfg(test)]
+mod specs_for_decode {
+    use super::Value;
+
+    #[test]
+    fn sut_decodes_values_one_after_another() {
+        // Arrange
+        let buf: &[u8] = b"*3\r\n$3\r\nSET\r\n$3\r\nfoo\r\n$3\r\nbar\r\n:42\r\n";
+
+        // Act
+        let (first, consumed) = Value::decode(buf).unwrap().unwrap();
+        let (second, rest) = Value::decode(&buf[consumed..]).unwrap().unwrap();
+
+        // Assert
+        let expected = Value::Array(vec![
+            Value::BulkString("SET".to_string()),
+            Value::BulkString("foo".to_string()),
+            Value::BulkString("bar".to_string()),
+        ]);
+        assert_eq!(first, expected);
+        assert_eq!(consumed, 31);
+        assert_eq!(second, Value::Integer(42));
+        assert_eq!(consumed + rest, buf.len());
+    }
+
+    #[rstest::rstest]
+    #[case(b"")]
+    #[case(b"+OK")]
+    #[case(b"$3\r\nfo")]
+    #[case(b"*2\r\n$3\r\nfoo\r\n")]
+    fn sut_waits_for_the_rest_of_partial_values(#[case] buf: &[u8]) {
+        // Act
+        let actual = Value::decode(buf).unwrap();
+
+        // Assert
+        assert_eq!(actual, None);
+    }
+
+    #[rstest::rstest]
+    #[case::huge_bulk_length(b"$18446744073709551615\r\n".to_vec())]
+    #[case::bulk_longer_than_allowed(b"$536870913\r\n".to_vec())]
+    #[case::negative_bulk_length(b"$-2\r\n".to_vec())]
+    #[case::huge_array_length(b"*18446744073709551615\r\n".to_vec())]
+    #[case::missing_crlf_after_bulk(b"$3\r\nfooXY".to_vec())]
+    #[case::line_without_end(vec![b'+'; 64 * 1024 + 1])]
+    #[case::deeply_nested_arrays(b"*1\r\n".repeat(1000))]
+    fn sut_rejects_malformed_or_oversized_values(#[case] buf: Vec<u8>) {
+        // Act
+        let actual = Value::decode(&buf);
+
+        // Assert
+        assert!(actual.is_err(), "{actual:?}");
+    }
+
+    #[test]
+    fn sut_waits_for_elements_of_array_claiming_to_be_huge_without_reserving_them() {
+        // Act
+        let actual = Value::decode(b"*1000000000\r\n$3\r\nfoo\r\n");
+
+        // Assert
+        assert_eq!(actual.unwrap(), None);
+    }
+
+    #[test]
+    fn sut_keeps_every_byte_of_bulk_string_which_is_not_utf8() {
+        // Arrange
+        let buf = b"$4\r\n\xF5\x80a\xFF\r\n";
+
+        // Act
+        let actual = Value::decode(buf).unwrap();
+
+        // Assert
+        let expected = Value::BulkString("\u{F5}\u{80}a\u{FF}".to_string());
+        assert_eq!(actual, Some((expected, buf.len())));
+    }
+
+    #[test]
+    fn sut_rejects_unknown_types() {
+        // Act
+        let actual = Value::decode(b"?\r\n");
+
+        // Assert
+        assert!(actual.is_err());
+    }
+}
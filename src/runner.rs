@@ -1,46 +1,72 @@
+use std::io;
 use std::sync::Arc;
+use std::time::Instant;
+
 use tokio::fs::File;
 use tokio::io::AsyncReadExt;
 use tokio::io::AsyncWriteExt;
 use tokio::net::TcpListener;
 
+use crate::clock::Clock;
+use crate::cluster;
 use crate::command::executor::CommandExecutorContext;
+use crate::command::executor::CommandSet;
 use crate::command::executor::execute;
 use crate::command::executor::parse;
 use crate::config::Config;
-use crate::replication::Replicator;
-use crate::repository::Repository;
+use crate::expiration;
+use crate::replication;
+use crate::repository::Databases;
 use crate::resp::Value;
+use crate::sentinel;
 use crate::snapshot::load;
+use crate::stats::Stats;
 
-pub async fn run(listener: TcpListener, repository: Arc<impl Repository>, config: Arc<Config>) {
-    let context = CommandExecutorContext::new(repository.clone(), config.clone());
+/// Serves clients on `listener`, telling the time by `clock`, which the repositories of
+/// `databases` must share so that commands and expiry agree on it.
+pub async fn run(
+    listener: TcpListener,
+    databases: Arc<Databases>,
+    config: Arc<Config>,
+    clock: Arc<dyn Clock>,
+) {
+    let context = CommandExecutorContext::new(databases.clone(), config.clone(), clock);
 
     if let Some(rdb_config) = &config.rdb {
         let path = rdb_config.path();
-        if let Ok(file) = File::open(path).await {
-            load(file, repository).await;
+        if let Ok(file) = File::open(&path).await
+            && let Err(e) = load(
+                file,
+                &databases,
+                &context.scripts.libraries,
+                context.clock.as_ref(),
+            )
+            .await
+        {
+            eprintln!("loading {path} stopped short: {e}");
         }
     }
 
-    if config.replication.is_slave() {
-        let master_address = &config
-            .replication
-            .slave
-            .as_ref()
-            .unwrap()
-            .master_address
-            .clone();
-        let mut replicator = Replicator::new(master_address).await;
-        replicator.initiate().await;
+    tokio::spawn(expiration::run(databases.clone(), context.notifier.clone()));
+
+    if let Some(cluster) = &context.cluster {
+        tokio::spawn(cluster::run(cluster.clone()));
     }
 
+    match &context.sentinel {
+        Some(sentinel) => tokio::spawn(sentinel::run(sentinel.clone())),
+        None => tokio::spawn(replication::run(context.new_session())),
+    };
+
     loop {
         match listener.accept().await {
-            Ok((mut stream, _)) => {
-                let context = context.clone();
+            Ok((mut stream, address)) => {
+                let context = context.new_session();
+                context.session.set_address(&address.ip().to_string());
+                context.stats.connect();
                 tokio::spawn(async move {
                     handle(&context, &mut stream).await;
+                    context.stats.disconnect();
                 });
             }
             Err(e) => {
@@ -50,35 +76,242 @@ pub async fn run(listener: TcpListener, repository: Arc<impl Repository>, config
     }
 }
 
+/// Serves the commands of a client, writing the messages published to its subscriptions in
+/// between replies.
 async fn handle(
     context: &CommandExecutorContext,
     stream: &mut (impl AsyncReadExt + AsyncWriteExt + Unpin),
 ) {
-    let mut buf = [0; 1024];
+    let subscriber = context.session.subscriber.clone();
+    let mut messages = subscriber.messages().await;
+    // Bytes read but not parsed yet, as a request may arrive in parts or along with others.
+    let mut pending = Vec::new();
 
     loop {
-        let value = read(stream, &mut buf).await;
-        if value.is_none() {
+        let value = match Value::decode(&pending) {
+            Ok(Some((value, size))) => {
+                pending.drain(..size);
+                value
+            }
+            Ok(None) => {
+                tokio::select! {
+                    open = read(stream, &mut pending, &context.stats) => if !open {
+                        break;
+                    },
+                    Some(message) = messages.recv() => {
+                        let message = match message {
+                            Value::Array(values) if context.session.protocol() == 3 => {
+                                Value::Push(values)
+                            }
+                            message => message,
+                        };
+                        if write(stream, &message, &context.stats).await.is_err() {
+                            break;
+                        }
+                    }
+                    _ = subscriber.closed() => break,
+                }
+                continue;
+            }
+            Err(e) => {
+                let error = format!("ERR Protocol error: {e}");
+                context.stats.error(&error);
+                let _ = write(stream, &Value::Error(error), &context.stats).await;
+                break;
+            }
+        };
+
+        let mut quit = false;
+        let command = parse(&value);
+        let redirection = match &command {
+            Ok(command) => context.redirect(command, &value).await,
+            Err(_) => None,
+        };
+        let refusal = match &command {
+            Ok(command) => context.refuse(command),
+            Err(_) => None,
+        };
+        let reply = match (command, refusal, redirection) {
+            (Ok(command), _, _)
+                if subscriber.is_subscribed() && !command.is_allowed_when_subscribed() =>
+            {
+                context.stats.reject(&value);
+                Value::Error(format!(
+                    "ERR Can't execute '{}': only (P|S)SUBSCRIBE / (P|S)UNSUBSCRIBE / PING / QUIT / RESET are allowed in this context",
+                    command_name(&value)
+                ))
+            }
+            (Ok(command), _, _)
+                if context.sentinel.is_some() && !command.is_allowed_in_sentinel() =>
+            {
+                context.stats.reject(&value);
+                unknown_command(&value)
+            }
+            (Ok(_), Some(refusal), _) => {
+                context.stats.reject(&value);
+                context.session.abort();
+                refusal
+            }
+            (Ok(_), None, Some(redirection)) => {
+                context.stats.reject(&value);
+                context.session.abort();
+                redirection
+            }
+            (Ok(command), None, None) => match context.session.queue(command, &value) {
+                Some(command) => {
+                    quit = matches!(command, CommandSet::Quit(_));
+                    let caching = matches!(command, CommandSet::ClientCaching(_));
+                    let started = Instant::now();
+                    let reply = execute(command, &value, context).await;
+                    context.stats.record(&value, started.elapsed(), &reply);
+                    if !caching {
+                        context.session.reset_caching();
+                    }
+                    reply
+                }
+                None => Value::SimpleString("QUEUED".to_string()),
+            },
+            (Err(e), _, _) => {
+                context.session.abort();
+                Value::Error(format!("ERR {e}"))
+            }
+        };
+
+        if let Value::Error(e) = &reply {
+            context.stats.error(e);
+        }
+        if write(stream, &reply, &context.stats).await.is_err() || quit {
             break;
         }
-        let value = value.unwrap();
+    }
 
-        let command = parse(&value).unwrap();
-        let value = execute(command, context).await;
+    context.pubsub.unsubscribe_all(&subscriber);
+    context.tracking.forget(context.session.id());
+    context.replication.detach(context.session.id());
+}
 
-        write(stream, &value).await;
+fn command_name(value: &Value) -> String {
+    match value {
+        Value::Array(array) => match array.first() {
+            Some(Value::BulkString(name)) => name.to_lowercase(),
+            _ => String::new(),
+        },
+        _ => String::new(),
     }
 }
 
-async fn read(stream: &mut (impl AsyncReadExt + Unpin), buf: &mut [u8]) -> Option<Value> {
-    let bytes_read = stream.read(buf).await.unwrap();
-    if bytes_read == 0 {
-        return None;
-    }
-    Some(Value::from(&buf[..bytes_read]))
+fn unknown_command(value: &Value) -> Value {
+    let arguments: Vec<&str> = match value {
+        Value::Array(array) => array
+            .iter()
+            .filter_map(|argument| match argument {
+                Value::BulkString(argument) => Some(argument.as_str()),
+                _ => None,
+            })
+            .collect(),
+        _ => Vec::new(),
+    };
+    let (name, arguments) = arguments.split_first().unwrap_or((&"", &[]));
+    let arguments: String = arguments
+        .iter()
+        .map(|argument| format!("'{argument}' "))
+        .collect();
+    Value::Error(format!(
+        "ERR unknown command '{name}', with args beginning with: {arguments}"
+    ))
 }
 
-async fn write(stream: &mut (impl AsyncWriteExt + Unpin), value: &Value) {
+/// Appends what the client sent next to `pending`, returning false once it disconnected.
+async fn read(
+    stream: &mut (impl AsyncReadExt + Unpin),
+    pending: &mut Vec<u8>,
+    stats: &Stats,
+) -> bool {
+    let mut buf = [0; 1024];
+    let bytes_read = stream.read(&mut buf).await.unwrap_or(0);
+    stats.received(bytes_read);
+    pending.extend_from_slice(&buf[..bytes_read]);
+    bytes_read > 0
+}
+
+/// Writes `value` to the client, failing once it disconnected.
+async fn write(
+    stream: &mut (impl AsyncWriteExt + Unpin),
+    value: &Value,
+    stats: &Stats,
+) -> io::Result<()> {
     let bytes = value.serialize();
-    stream.write_all(&bytes).await.unwrap();
+    stats.sent(bytes.len());
+    stream.write_all(&bytes).await
+}
+
+#[cfg(test)]
+mod specs_for_handle {
+    use std::io;
+    use std::pin::Pin;
+    use std::task::Context;
+    use std::task::Poll;
+    use std::time::Duration;
+
+    use tokio::io::AsyncRead;
+    use tokio::io::AsyncWrite;
+    use tokio::io::ReadBuf;
+
+    use crate::command::executor::CommandExecutorContext;
+    use crate::command::executor::fixture::command_executor_context;
+    use crate::pubsub::Scope;
+
+    use super::handle;
+
+    /// A connection whose client went away without it being noticed by reading.
+    struct Broken;
+
+    impl AsyncRead for Broken {
+        fn poll_read(
+            self: Pin<&mut Self>,
+            _: &mut Context<'_>,
+            _: &mut ReadBuf<'_>,
+        ) -> Poll<io::Result<()>> {
+            Poll::Pending
+        }
+    }
+
+    impl AsyncWrite for Broken {
+        fn poll_write(
+            self: Pin<&mut Self>,
+            _: &mut Context<'_>,
+            _: &[u8],
+        ) -> Poll<io::Result<usize>> {
+            Poll::Ready(Err(io::ErrorKind::BrokenPipe.into()))
+        }
+
+        fn poll_flush(self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<io::Result<()>> {
+            Poll::Ready(Ok(()))
+        }
+
+        fn poll_shutdown(self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<io::Result<()>> {
+            Poll::Ready(Ok(()))
+        }
+    }
+
+    #[rstest::rstest]
+    #[tokio::test]
+    async fn sut_forgets_subscriptions_of_client_it_can_no_longer_write_to(
+        #[from(command_executor_context)] context: CommandExecutorContext,
+    ) {
+        // Arrange
+        let subscriber = context.session.subscriber.clone();
+        context
+            .pubsub
+            .subscribe(Scope::Channel, "news", &subscriber);
+        context.pubsub.publish("news", "hello");
+
+        // Act
+        let mut stream = Broken;
+        let handled = tokio::time::timeout(Duration::from_secs(1), handle(&context, &mut stream));
+
+        // Assert
+        assert!(handled.await.is_ok());
+        assert_eq!(context.pubsub.subscribers(Scope::Channel, "news"), 0);
+    }
 }
@@ -0,0 +1,493 @@
+use std::collections::BTreeMap;
+use std::sync::Mutex;
+use std::time::Duration;
+use std::time::Instant;
+
+use mlua::HookTriggers;
+use mlua::Lua;
+use mlua::LuaOptions;
+use mlua::MultiValue;
+use mlua::StdLib;
+use mlua::Table;
+
+use crate::snapshot;
+
+/// Loading a library only registers its functions, so it should never take long.
+const LOAD_TIME_LIMIT: Duration = Duration::from_millis(500);
+const FLAGS: [&str; 5] = [
+    "no-writes",
+    "allow-oom",
+    "allow-stale",
+    "no-cluster",
+    "allow-cross-slot-keys",
+];
+const REGISTERED: &str = "registered_functions";
+/// The RDB version written after the records of a FUNCTION DUMP payload.
+const DUMP_VERSION: u16 = 11;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Library {
+    pub name: String,
+    pub code: String,
+    pub functions: Vec<FunctionInfo>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct FunctionInfo {
+    pub name: String,
+    pub description: Option<String>,
+    pub flags: Vec<String>,
+}
+
+impl FunctionInfo {
+    pub fn is_read_only(&self) -> bool {
+        self.flags.iter().any(|flag| flag == "no-writes")
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum RestorePolicy {
+    #[default]
+    Append,
+    Replace,
+    Flush,
+}
+
+/// The libraries loaded with FUNCTION LOAD, by name.
+#[derive(Default)]
+pub struct Libraries {
+    libraries: Mutex<BTreeMap<String, Library>>,
+}
+
+impl Libraries {
+    /// Compiles and adds the library, returning its name.
+    pub fn load(&self, code: &str, replace: bool) -> Result<String, String> {
+        let library = compile(code)?;
+        let name = library.name.clone();
+        add(&mut self.libraries.lock().unwrap(), library, replace)?;
+        Ok(name)
+    }
+
+    /// Finds the function along with the code of its library.
+    pub fn function(&self, name: &str) -> Option<(String, FunctionInfo)> {
+        self.libraries.lock().unwrap().values().find_map(|library| {
+            library
+                .functions
+                .iter()
+                .find(|function| function.name == name)
+                .map(|function| (library.code.clone(), function.clone()))
+        })
+    }
+
+    pub fn delete(&self, name: &str) -> bool {
+        self.libraries.lock().unwrap().remove(name).is_some()
+    }
+
+    pub fn list(&self) -> Vec<Library> {
+        self.libraries.lock().unwrap().values().cloned().collect()
+    }
+
+    pub fn flush(&self) {
+        self.libraries.lock().unwrap().clear();
+    }
+
+    /// Replaces every library at once with those of `libraries`.
+    pub fn replace(&self, libraries: Libraries) {
+        *self.libraries.lock().unwrap() = libraries.libraries.into_inner().unwrap();
+    }
+
+    /// Serializes every library as RDB function records followed by the RDB version and the
+    /// checksum of everything before it.
+    pub fn dump(&self) -> Vec<u8> {
+        let mut payload = vec![];
+        for library in self.libraries.lock().unwrap().values() {
+            payload.push(snapshot::FUNCTION);
+            snapshot::encode_string(&mut payload, &library.code);
+        }
+        payload.extend(DUMP_VERSION.to_le_bytes());
+        payload.extend(snapshot::crc64(&payload).to_le_bytes());
+        payload
+    }
+
+    /// Adds the libraries of a FUNCTION DUMP payload, leaving everything as is on any error.
+    pub fn restore(&self, payload: &[u8], policy: RestorePolicy) -> Result<(), String> {
+        let invalid = || "ERR payload version or checksum are wrong".to_string();
+        let (checked, checksum) = payload
+            .len()
+            .checked_sub(10)
+            .map(|length| payload.split_at(length + 2))
+            .ok_or_else(invalid)?;
+        let (records, version) = checked.split_at(checked.len() - 2);
+        if u16::from_le_bytes([version[0], version[1]]) > DUMP_VERSION
+            || checksum != snapshot::crc64(checked).to_le_bytes()
+        {
+            return Err(invalid());
+        }
+
+        let mut libraries = Vec::new();
+        let mut rest = records;
+        while let Some((&opcode, remaining)) = rest.split_first() {
+            if opcode != snapshot::FUNCTION {
+                return Err(invalid());
+            }
+            rest = remaining;
+            let code = snapshot::decode_string(&mut rest).ok_or_else(invalid)?;
+            libraries.push(compile(&code)?);
+        }
+
+        let mut current = self.libraries.lock().unwrap();
+        let mut restored = match policy {
+            RestorePolicy::Flush => BTreeMap::new(),
+            RestorePolicy::Append | RestorePolicy::Replace => current.clone(),
+        };
+        for library in libraries {
+            add(&mut restored, library, policy == RestorePolicy::Replace)?;
+        }
+        *current = restored;
+        Ok(())
+    }
+}
+
+fn add(
+    libraries: &mut BTreeMap<String, Library>,
+    library: Library,
+    replace: bool,
+) -> Result<(), String> {
+    if !replace && libraries.contains_key(&library.name) {
+        return Err(format!("ERR Library '{}' already exists", library.name));
+    }
+    let clash = libraries
+        .values()
+        .filter(|other| other.name != library.name)
+        .flat_map(|other| &other.functions)
+        .find(|function| library.functions.iter().any(|f| f.name == function.name));
+    if let Some(function) = clash {
+        return Err(format!("ERR Function {} already exists", function.name));
+    }
+    libraries.insert(library.name.clone(), library);
+    Ok(())
+}
+
+/// Parses the `#!lua name=<library>` shebang and runs the code to learn which functions it
+/// registers, without keeping anything.
+pub fn compile(code: &str) -> Result<Library, String> {
+    let (name, _) = metadata(code)?;
+    let lua = Lua::new_with(
+        StdLib::TABLE | StdLib::STRING | StdLib::MATH,
+        LuaOptions::new(),
+    )
+    .map_err(|e| format!("ERR {e}"))?;
+    let started = Instant::now();
+    lua.set_hook(
+        HookTriggers::new().every_nth_instruction(1000),
+        move |_, _| {
+            if started.elapsed() > LOAD_TIME_LIMIT {
+                return Err(mlua::Error::RuntimeError(
+                    "FUNCTION LOAD timeout".to_string(),
+                ));
+            }
+            Ok(())
+        },
+    );
+    let setup = lua
+        .create_table()
+        .and_then(|redis| lua.globals().set("redis", redis));
+    setup.map_err(|e| format!("ERR {e}"))?;
+
+    let functions = register(&lua, code)?
+        .into_iter()
+        .map(|(function, _)| function)
+        .collect();
+    Ok(Library {
+        name,
+        code: code.to_string(),
+        functions,
+    })
+}
+
+/// Runs the library code with `redis.register_function` available, returning what it
+/// registered along with the callbacks.
+pub fn register<'lua>(
+    lua: &'lua Lua,
+    code: &str,
+) -> Result<Vec<(FunctionInfo, mlua::Function<'lua>)>, String> {
+    let (_, body) = metadata(code)?;
+    let collect = || -> mlua::Result<Table<'lua>> {
+        lua.set_named_registry_value(REGISTERED, lua.create_table()?)?;
+        let redis: Table = lua.globals().get("redis")?;
+        redis.set(
+            "register_function",
+            lua.create_function(|lua, args: MultiValue| {
+                let registered: Table = lua.named_registry_value(REGISTERED)?;
+                let args = lua.create_sequence_from(args)?;
+                registered.raw_push(args)
+            })?,
+        )?;
+        let compiled = lua.load(body).set_name("@user_function").into_function()?;
+        let result = compiled.call::<_, ()>(());
+        redis.set("register_function", mlua::Value::Nil)?;
+        result?;
+        lua.named_registry_value(REGISTERED)
+    };
+    let registered = collect().map_err(|e| {
+        let message = match e {
+            mlua::Error::SyntaxError { message, .. } => message,
+            mlua::Error::CallbackError { cause, .. } => cause.to_string(),
+            e => e.to_string(),
+        };
+        format!("ERR Error registering functions: {message}")
+    })?;
+
+    let mut functions: Vec<(FunctionInfo, mlua::Function)> = vec![];
+    for args in registered.sequence_values::<Table>() {
+        let args = args.map_err(|e| format!("ERR {e}"))?;
+        let (function, callback) = registration(args)?;
+        if functions.iter().any(|(f, _)| f.name == function.name) {
+            return Err("ERR Function already exists in the library".to_string());
+        }
+        functions.push((function, callback));
+    }
+    if functions.is_empty() {
+        return Err("ERR No functions registered".to_string());
+    }
+    Ok(functions)
+}
+
+/// Reads the arguments of either `register_function(name, callback)` or
+/// `register_function{function_name=..., callback=..., flags={...}, description=...}`.
+fn registration(args: Table) -> Result<(FunctionInfo, mlua::Function), String> {
+    let invalid = |what: &str| format!("ERR {what} argument given to redis.register_function");
+    let (name, callback, description, flags) = match args.raw_len() {
+        2 => (
+            args.raw_get::<_, String>(1).map_err(|_| invalid("wrong"))?,
+            args.raw_get::<_, mlua::Function>(2)
+                .map_err(|_| invalid("wrong"))?,
+            None,
+            vec![],
+        ),
+        1 => {
+            let named: Table = args.raw_get(1).map_err(|_| invalid("wrong"))?;
+            let flags: Option<Table> = named.get("flags").map_err(|_| invalid("wrong flags"))?;
+            let flags = match flags {
+                Some(flags) => flags
+                    .sequence_values::<String>()
+                    .collect::<Result<Vec<_>, _>>()
+                    .map_err(|_| invalid("wrong flags"))?,
+                None => vec![],
+            };
+            (
+                named
+                    .get::<_, String>("function_name")
+                    .map_err(|_| invalid("missing function_name"))?,
+                named
+                    .get::<_, mlua::Function>("callback")
+                    .map_err(|_| invalid("missing callback"))?,
+                named
+                    .get::<_, Option<String>>("description")
+                    .map_err(|_| invalid("wrong description"))?,
+                flags,
+            )
+        }
+        _ => return Err(invalid("wrong number of arguments")),
+    };
+    if !is_valid_name(&name) {
+        return Err("ERR Function names can only contain letters, numbers, or underscores(_) and must be at least one character long".to_string());
+    }
+    if let Some(flag) = flags.iter().find(|flag| !FLAGS.contains(&flag.as_str())) {
+        return Err(format!("ERR Unknown flag given: {flag}"));
+    }
+    let function = FunctionInfo {
+        name,
+        description,
+        flags,
+    };
+    Ok((function, callback))
+}
+
+/// Splits the shebang off, returning the library name and the code to run. The shebang line
+/// is kept empty so line numbers in errors still match.
+fn metadata(code: &str) -> Result<(String, &str), String> {
+    let Some(shebang) = code.strip_prefix("#!") else {
+        return Err("ERR Missing library metadata".to_string());
+    };
+    let (shebang, body) = match shebang.find('\n') {
+        Some(end) => (&shebang[..end], &shebang[end..]),
+        None => (shebang, ""),
+    };
+    let mut parts = shebang.split_whitespace();
+    let engine = parts.next().unwrap_or_default();
+    if !engine.eq_ignore_ascii_case("lua") {
+        return Err(format!("ERR Engine '{engine}' not found"));
+    }
+    let mut name = None;
+    for part in parts {
+        match part.strip_prefix("name=") {
+            Some(value) => name = Some(value.to_string()),
+            None => return Err(format!("ERR Invalid metadata value given: {part}")),
+        }
+    }
+    let name = name.ok_or_else(|| "ERR Library name was not given".to_string())?;
+    if !is_valid_name(&name) {
+        return Err("ERR Library names can only contain letters, numbers, or underscores(_) and must be at least one character long".to_string());
+    }
+    Ok((name, body))
+}
+
+fn is_valid_name(name: &str) -> bool {
+    !name.is_empty() && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+#[cfg(test)]
+mod specs_for_libraries {
+    use super::FunctionInfo;
+    use super::Libraries;
+    use super::RestorePolicy;
+
+    const LIBRARY: &str = "#!lua name=mylib
+redis.register_function('echo', function(keys, args) return args[1] end)
+redis.register_function{
+    function_name = 'peek',
+    callback = function(keys, args) return keys[1] end,
+    flags = {'no-writes'},
+    description = 'reads only',
+}
+";
+
+    #[test]
+    fn sut_learns_registered_functions_when_loading() {
+        // Arrange
+        let sut = Libraries::default();
+
+        // Act
+        let actual = sut.load(LIBRARY, false);
+
+        // Assert
+        assert_eq!(actual, Ok("mylib".to_string()));
+        let (code, function) = sut.function("peek").unwrap();
+        assert_eq!(code, LIBRARY);
+        let expected = FunctionInfo {
+            name: "peek".to_string(),
+            description: Some("reads only".to_string()),
+            flags: vec!["no-writes".to_string()],
+        };
+        assert_eq!(function, expected);
+        assert!(!sut.function("echo").unwrap().1.is_read_only());
+    }
+
+    #[rstest::rstest]
+    #[case("return 1", "ERR Missing library metadata")]
+    #[case("#!js name=mylib\n", "ERR Engine 'js' not found")]
+    #[case("#!lua\n", "ERR Library name was not given")]
+    #[case("#!lua name=my-lib\n", "ERR Library names can only")]
+    #[case(
+        "#!lua name=mylib foo=bar\n",
+        "ERR Invalid metadata value given: foo=bar"
+    )]
+    #[case("#!lua name=mylib\nreturn 1", "ERR No functions registered")]
+    #[case("#!lua name=mylib\nreturn +", "ERR Error registering functions")]
+    #[case(
+        "#!lua name=mylib\nwhile true do end",
+        "ERR Error registering functions"
+    )]
+    #[case(
+        "#!lua name=mylib\nredis.register_function('f', function() end)\nredis.register_function('f', function() end)",
+        "ERR Function already exists in the library"
+    )]
+    #[case(
+        "#!lua name=mylib\nredis.register_function{function_name='f', callback=function() end, flags={'bogus'}}",
+        "ERR Unknown flag given: bogus"
+    )]
+    fn sut_rejects_invalid_libraries(#[case] code: &str, #[case] expected: &str) {
+        // Arrange
+        let sut = Libraries::default();
+
+        // Act
+        let actual = sut.load(code, false).unwrap_err();
+
+        // Assert
+        assert!(actual.starts_with(expected), "{actual}");
+    }
+
+    #[rstest::rstest]
+    #[case(false, Err("ERR Library 'mylib' already exists".to_string()))]
+    #[case(true, Ok("mylib".to_string()))]
+    fn sut_replaces_library_only_if_requested(
+        #[case] replace: bool,
+        #[case] expected: Result<String, String>,
+    ) {
+        // Arrange
+        let sut = Libraries::default();
+        sut.load(LIBRARY, false).unwrap();
+
+        // Act
+        let actual = sut.load(LIBRARY, replace);
+
+        // Assert
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn sut_rejects_functions_registered_by_another_library() {
+        // Arrange
+        let sut = Libraries::default();
+        sut.load(LIBRARY, false).unwrap();
+
+        // Act
+        let actual = sut.load(&LIBRARY.replace("mylib", "otherlib"), false);
+
+        // Assert
+        assert_eq!(actual, Err("ERR Function echo already exists".to_string()));
+    }
+
+    #[rstest::rstest]
+    #[case(RestorePolicy::Append, Err("ERR Library 'mylib' already exists".to_string()), 2)]
+    #[case(RestorePolicy::Replace, Ok(()), 2)]
+    #[case(RestorePolicy::Flush, Ok(()), 1)]
+    fn sut_restores_dumped_libraries_according_to_policy(
+        #[case] policy: RestorePolicy,
+        #[case] expected: Result<(), String>,
+        #[case] expected_count: usize,
+    ) {
+        // Arrange
+        let sut = Libraries::default();
+        sut.load(LIBRARY, false).unwrap();
+        let payload = sut.dump();
+        sut.load(
+            "#!lua name=other\nredis.register_function('o', function() end)",
+            false,
+        )
+        .unwrap();
+
+        // Act
+        let actual = sut.restore(&payload, policy);
+
+        // Assert
+        assert_eq!(actual, expected);
+        assert_eq!(sut.list().len(), expected_count);
+    }
+
+    #[rstest::rstest]
+    #[case::corrupt_record(|payload: &mut Vec<u8>| payload[3] ^= 1)]
+    #[case::corrupt_checksum(|payload: &mut Vec<u8>| *payload.last_mut().unwrap() ^= 1)]
+    #[case::newer_version(|payload: &mut Vec<u8>| {
+        let length = payload.len() - 8;
+        payload[length - 2] += 1;
+        let checksum = crate::snapshot::crc64(&payload[..length]);
+        payload[length..].copy_from_slice(&checksum.to_le_bytes());
+    })]
+    #[case::truncated(|payload: &mut Vec<u8>| payload.truncate(9))]
+    fn sut_rejects_corrupt_payload(#[case] corrupt: fn(&mut Vec<u8>)) {
+        // Arrange
+        let sut = Libraries::default();
+        sut.load(LIBRARY, false).unwrap();
+        let mut payload = sut.dump();
+        corrupt(&mut payload);
+
+        // Act
+        let actual = sut.restore(&payload, RestorePolicy::Flush);
+
+        // Assert
+        assert!(actual.is_err());
+        assert_eq!(sut.list().len(), 1);
+    }
+}
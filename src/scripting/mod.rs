@@ -0,0 +1,480 @@
+mod library;
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::AtomicU8;
+use std::sync::atomic::Ordering;
+use std::time::Duration;
+
+use mlua::Function;
+use mlua::HookTriggers;
+use mlua::Lua;
+use mlua::LuaOptions;
+use mlua::MultiValue;
+use mlua::StdLib;
+use mlua::Table;
+use tokio::runtime::Handle;
+use tokio::sync::watch;
+use tokio::time::Instant;
+
+use crate::command::executor::CommandExecutorContext;
+use crate::command::executor::CommandSet;
+use crate::command::executor::dispatch;
+use crate::command::executor::parse;
+use crate::resp::Value;
+
+pub use library::Libraries;
+pub use library::Library;
+pub use library::RestorePolicy;
+
+pub const BUSY: &str =
+    "BUSY Redis is busy running a script. You can only call SCRIPT KILL or FUNCTION KILL.";
+const KILLED: &str = "ERR Script killed by user with SCRIPT KILL...";
+const NOTBUSY: &str = "NOTBUSY No scripts in execution right now.";
+const UNKILLABLE: &str = "UNKILLABLE Sorry the script already executed write commands against the dataset. You can either wait the script termination or kill the server in a hard way using the SHUTDOWN NOSAVE command.";
+
+/// How many VM instructions a script runs between checks for SCRIPT KILL.
+const KILL_CHECK_INTERVAL: u32 = 1000;
+
+/// `redis.call` is `redis.pcall` raising error replies instead of returning them, which can only
+/// be done from Lua as errors raised by Rust functions are never plain tables.
+const CALL: &str = r#"
+redis.call = function(...)
+    local reply = redis.pcall(...)
+    if type(reply) == "table" and reply.err then
+        error(reply)
+    end
+    return reply
+end
+"#;
+
+/// Scripts known by their SHA1 digest, function libraries, and the script being run if any.
+pub struct Scripts {
+    cache: Mutex<HashMap<String, String>>,
+    pub libraries: Libraries,
+    running: watch::Sender<Option<Running>>,
+}
+
+/// What EVAL or FCALL runs: a script, or a function of a library which is loaded first.
+pub enum Program {
+    Script(String),
+    Function { code: String, name: String },
+}
+
+#[derive(Clone)]
+struct Running {
+    started: Instant,
+    /// Whether a function runs, as SCRIPT KILL only stops scripts and FUNCTION KILL functions.
+    function: bool,
+    progress: Arc<Progress>,
+}
+
+/// Whether the running program wrote to the dataset or was asked to stop, either excluding the
+/// other so a program is never stopped halfway through its writes.
+#[derive(Default)]
+struct Progress(AtomicU8);
+
+impl Progress {
+    const RUNNING: u8 = 0;
+    const WRITTEN: u8 = 1;
+    const KILLED: u8 = 2;
+
+    /// Records a write is about to be made, unless the program was killed meanwhile.
+    fn write(&self) -> bool {
+        match self.0.compare_exchange(
+            Self::RUNNING,
+            Self::WRITTEN,
+            Ordering::SeqCst,
+            Ordering::SeqCst,
+        ) {
+            Ok(_) => true,
+            Err(progress) => progress == Self::WRITTEN,
+        }
+    }
+
+    fn kill(&self) -> Result<(), &'static str> {
+        match self.0.compare_exchange(
+            Self::RUNNING,
+            Self::KILLED,
+            Ordering::SeqCst,
+            Ordering::SeqCst,
+        ) {
+            Err(Self::WRITTEN) => Err(UNKILLABLE),
+            _ => Ok(()),
+        }
+    }
+
+    fn is_killed(&self) -> bool {
+        self.0.load(Ordering::SeqCst) == Self::KILLED
+    }
+}
+
+impl Default for Scripts {
+    fn default() -> Self {
+        Self {
+            cache: Mutex::default(),
+            libraries: Libraries::default(),
+            running: watch::channel(None).0,
+        }
+    }
+}
+
+impl Scripts {
+    /// Caches the script, returning its digest.
+    pub fn load(&self, body: &str) -> String {
+        let sha = sha1hex(body);
+        self.cache
+            .lock()
+            .unwrap()
+            .insert(sha.clone(), body.to_string());
+        sha
+    }
+
+    pub fn get(&self, sha: &str) -> Option<String> {
+        self.cache.lock().unwrap().get(&sha.to_lowercase()).cloned()
+    }
+
+    pub fn flush(&self) {
+        self.cache.lock().unwrap().clear();
+    }
+
+    /// Asks the running script, or function if `function` is set, to stop unless it wrote
+    /// anything already, returning the error to reply otherwise.
+    pub fn kill(&self, function: bool) -> Result<(), &'static str> {
+        match self.running.borrow().as_ref() {
+            Some(running) if running.function == function => running.progress.kill(),
+            _ => Err(NOTBUSY),
+        }
+    }
+
+    /// Completes once a script has been running for longer than `threshold`.
+    pub async fn busy(&self, threshold: Duration) {
+        let mut running = self.running.subscribe();
+        loop {
+            let started = running.borrow_and_update().as_ref().map(|r| r.started);
+            match started {
+                Some(started) => tokio::select! {
+                    _ = tokio::time::sleep_until(started + threshold) => {
+                        if running.borrow().as_ref().is_some_and(|r| r.started == started) {
+                            return;
+                        }
+                    }
+                    _ = running.changed() => {}
+                },
+                None => {
+                    let _ = running.changed().await;
+                }
+            }
+        }
+    }
+
+    /// Runs the program on a blocking thread, as Lua cannot yield while calling back into the
+    /// commands. The caller is expected to hold the execution lock exclusively. Write commands
+    /// are rejected if `read_only` is set. Databases selected by the program are only selected
+    /// while it runs. Replicas are sent the writes between MULTI and EXEC if `wrap` is set, so
+    /// they apply them at once unless the caller, such as EXEC, does so already.
+    pub async fn run(
+        &self,
+        context: &CommandExecutorContext,
+        program: Program,
+        keys: Vec<String>,
+        args: Vec<String>,
+        read_only: bool,
+        wrap: bool,
+    ) -> Value {
+        let progress = Arc::new(Progress::default());
+        self.running.send_replace(Some(Running {
+            started: Instant::now(),
+            function: matches!(program, Program::Function { .. }),
+            progress: progress.clone(),
+        }));
+        let database = context.session.database();
+        let invoker = context.clone();
+        let handle = Handle::current();
+        let transaction = wrap.then(|| Arc::new(AtomicBool::new(false)));
+        let begun = transaction.clone();
+        let reply = tokio::task::spawn_blocking(move || {
+            let invocation = Invocation {
+                context: &invoker,
+                handle: &handle,
+                progress: &progress,
+                read_only,
+                transaction: transaction.as_ref(),
+            };
+            evaluate(program, keys, args, invocation)
+                .unwrap_or_else(|e| Value::Error(format!("ERR {e}")))
+        })
+        .await
+        .unwrap_or_else(|e| Value::Error(format!("ERR {e}")));
+        if begun.is_some_and(|begun| begun.load(Ordering::SeqCst)) {
+            context.propagate(&Value::Array(vec![Value::BulkString("EXEC".to_string())]));
+        }
+        context.session.select(database);
+        self.running.send_replace(None);
+        reply
+    }
+}
+
+/// What `redis.call` needs to execute commands on behalf of a script.
+#[derive(Clone)]
+struct Invocation<'a> {
+    context: &'a CommandExecutorContext,
+    handle: &'a Handle,
+    progress: &'a Arc<Progress>,
+    read_only: bool,
+    /// Whether MULTI was propagated ahead of the writes, if the program propagates its own.
+    transaction: Option<&'a Arc<AtomicBool>>,
+}
+
+pub fn sha1hex(body: &str) -> String {
+    sha1_smol::Sha1::from(body).digest().to_string()
+}
+
+fn evaluate(
+    program: Program,
+    keys: Vec<String>,
+    args: Vec<String>,
+    invocation: Invocation,
+) -> mlua::Result<Value> {
+    let lua = Lua::new_with(
+        StdLib::TABLE | StdLib::STRING | StdLib::MATH,
+        LuaOptions::new(),
+    )?;
+    let progress = invocation.progress.clone();
+    let hook_progress = progress.clone();
+    lua.set_hook(
+        HookTriggers::new().every_nth_instruction(KILL_CHECK_INTERVAL),
+        move |_, _| {
+            if hook_progress.is_killed() {
+                return Err(mlua::Error::RuntimeError(KILLED.to_string()));
+            }
+            Ok(())
+        },
+    );
+
+    let globals = lua.globals();
+    globals.set("redis", redis_library(&lua, invocation)?)?;
+    lua.load(CALL).exec()?;
+    let keys = lua.create_sequence_from(keys)?;
+    let args = lua.create_sequence_from(args)?;
+
+    let pcall: Function = globals.get("pcall")?;
+    let results = match program {
+        Program::Script(body) => {
+            globals.set("KEYS", keys)?;
+            globals.set("ARGV", args)?;
+            let function = match lua.load(&body).set_name("@user_script").into_function() {
+                Ok(function) => function,
+                Err(e) => {
+                    return Ok(Value::Error(format!(
+                        "ERR Error compiling script (new function): {e}"
+                    )));
+                }
+            };
+            pcall.call::<_, MultiValue>(function)?
+        }
+        Program::Function { code, name } => {
+            let registered = match library::register(&lua, &code) {
+                Ok(registered) => registered,
+                Err(e) => return Ok(Value::Error(e)),
+            };
+            let Some((_, callback)) = registered.into_iter().find(|(f, _)| f.name == name) else {
+                return Ok(Value::Error("ERR Function not found".to_string()));
+            };
+            pcall.call::<_, MultiValue>((callback, keys, args))?
+        }
+    };
+    let mut results = results.into_iter();
+    let succeeded = matches!(results.next(), Some(mlua::Value::Boolean(true)));
+    let result = results.next().unwrap_or(mlua::Value::Nil);
+
+    if progress.is_killed() {
+        return Ok(Value::Error(KILLED.to_string()));
+    }
+    if succeeded {
+        return Ok(to_resp(result));
+    }
+    Ok(match result {
+        mlua::Value::Table(table) => match table.get::<_, Option<String>>("err")? {
+            Some(error) => Value::Error(error),
+            None => Value::Error("ERR script raised a non string error".to_string()),
+        },
+        mlua::Value::Error(e) => Value::Error(format!("ERR {e}")),
+        error => Value::Error(format!("ERR {}", error.to_string()?)),
+    })
+}
+
+fn redis_library<'lua>(lua: &'lua Lua, invocation: Invocation) -> mlua::Result<Table<'lua>> {
+    let redis = lua.create_table()?;
+    let context = invocation.context.clone();
+    let handle = invocation.handle.clone();
+    let progress = invocation.progress.clone();
+    let read_only = invocation.read_only;
+    let transaction = invocation.transaction.cloned();
+    redis.set(
+        "pcall",
+        lua.create_function(move |lua, args: MultiValue| {
+            let invocation = Invocation {
+                context: &context,
+                handle: &handle,
+                progress: &progress,
+                read_only,
+                transaction: transaction.as_ref(),
+            };
+            to_lua(lua, call(invocation, args))
+        })?,
+    )?;
+    redis.set(
+        "error_reply",
+        lua.create_function(|lua, message: String| reply_table(lua, "err", message))?,
+    )?;
+    redis.set(
+        "status_reply",
+        lua.create_function(|lua, message: String| reply_table(lua, "ok", message))?,
+    )?;
+    redis.set(
+        "sha1hex",
+        lua.create_function(|_, body: String| Ok(sha1hex(&body)))?,
+    )?;
+    Ok(redis)
+}
+
+fn reply_table<'lua>(lua: &'lua Lua, field: &str, message: String) -> mlua::Result<Table<'lua>> {
+    let table = lua.create_table()?;
+    table.set(field, message)?;
+    Ok(table)
+}
+
+fn call(invocation: Invocation, args: MultiValue) -> Value {
+    let mut arguments = Vec::with_capacity(args.len());
+    for arg in args {
+        let argument = match arg {
+            mlua::Value::String(s) => s.to_string_lossy().to_string(),
+            mlua::Value::Integer(n) => n.to_string(),
+            mlua::Value::Number(n) if n.fract() == 0.0 => (n as i64).to_string(),
+            mlua::Value::Number(n) => n.to_string(),
+            _ => {
+                return Value::Error(
+                    "ERR Lua redis lib command arguments must be strings or integers".to_string(),
+                );
+            }
+        };
+        arguments.push(Value::BulkString(argument));
+    }
+    if arguments.is_empty() {
+        return Value::Error(
+            "ERR Please specify at least one argument for this redis lib call".to_string(),
+        );
+    }
+
+    let request = Value::Array(arguments);
+    let Ok(command) = parse(&request) else {
+        return Value::Error("ERR Unknown Redis command called from script".to_string());
+    };
+    if matches!(
+        command,
+        CommandSet::Eval(_)
+            | CommandSet::Script(_)
+            | CommandSet::ScriptKill(_)
+            | CommandSet::Function(_)
+            | CommandSet::FCall(_)
+            | CommandSet::Subscribe(_)
+            | CommandSet::Unsubscribe(_)
+            | CommandSet::Quit(_)
+            | CommandSet::Hello(_)
+            | CommandSet::ClientId(_)
+            | CommandSet::ClientTracking(_)
+            | CommandSet::ClientCaching(_)
+            | CommandSet::Wait(_)
+            | CommandSet::WaitAof(_)
+            | CommandSet::ReplicaOf(_)
+            | CommandSet::Failover(_)
+            | CommandSet::Multi(_)
+            | CommandSet::Exec(_)
+            | CommandSet::Discard(_)
+            | CommandSet::Watch(_)
+            | CommandSet::Unwatch(_)
+    ) {
+        return Value::Error("ERR This Redis command is not allowed from script".to_string());
+    }
+    if invocation.read_only && command.is_write() {
+        return Value::Error(
+            "ERR Write commands are not allowed from read-only scripts.".to_string(),
+        );
+    }
+    if let Some(refusal) = invocation.context.refuse(&command) {
+        return refusal;
+    }
+    let write = command.is_write();
+    if write && !invocation.progress.write() {
+        return Value::Error(KILLED.to_string());
+    }
+    let reply = invocation
+        .handle
+        .block_on(dispatch(command, invocation.context));
+    if write && !matches!(reply, Value::Error(_)) {
+        // Replicas apply the writes of the program at once, as those of a transaction.
+        if invocation
+            .transaction
+            .is_some_and(|begun| !begun.swap(true, Ordering::SeqCst))
+        {
+            let multi = Value::Array(vec![Value::BulkString("MULTI".to_string())]);
+            invocation.context.propagate(&multi);
+        }
+        invocation.context.propagate(&request);
+    }
+    reply
+}
+
+/// Converts a reply the way Redis hands it to scripts: status and error replies become tables
+/// with an `ok` or `err` field, and nulls become false.
+fn to_lua<'lua>(lua: &'lua Lua, value: Value) -> mlua::Result<mlua::Value<'lua>> {
+    Ok(match value {
+        Value::SimpleString(s) => mlua::Value::Table(reply_table(lua, "ok", s)?),
+        Value::Error(s) => mlua::Value::Table(reply_table(lua, "err", s)?),
+        Value::Integer(n) => mlua::Value::Number(n as f64),
+        Value::BulkString(s) => mlua::Value::String(lua.create_string(&s)?),
+        Value::Array(values) | Value::Replies(values) | Value::Push(values) => {
+            let table = lua.create_table()?;
+            for (index, value) in values.into_iter().enumerate() {
+                table.raw_set(index + 1, to_lua(lua, value)?)?;
+            }
+            mlua::Value::Table(table)
+        }
+        Value::Map(entries) => to_lua(
+            lua,
+            Value::Array(entries.into_iter().flat_map(|(k, v)| [k, v]).collect()),
+        )?,
+        Value::Raw(bytes) => mlua::Value::String(lua.create_string(&bytes)?),
+        Value::Null | Value::NullArray => mlua::Value::Boolean(false),
+    })
+}
+
+/// Converts a script result to a reply: numbers are truncated to integers, true becomes 1,
+/// false and nil become null, and tables are read as arrays up to the first nil.
+fn to_resp(value: mlua::Value) -> Value {
+    match value {
+        mlua::Value::Boolean(true) => Value::Integer(1),
+        mlua::Value::Integer(n) => Value::Integer(n),
+        mlua::Value::Number(n) => Value::Integer(n as i64),
+        mlua::Value::String(s) => Value::BulkString(s.to_string_lossy().to_string()),
+        mlua::Value::Table(table) => {
+            if let Ok(Some(error)) = table.get::<_, Option<String>>("err") {
+                return Value::Error(error);
+            }
+            if let Ok(Some(status)) = table.get::<_, Option<String>>("ok") {
+                return Value::SimpleString(status);
+            }
+            let values = table
+                .sequence_values::<mlua::Value>()
+                .map_while(Result::ok)
+                .map(to_resp)
+                .collect();
+            Value::Array(values)
+        }
+        mlua::Value::Error(e) => Value::Error(format!("ERR {e}")),
+        _ => Value::Null,
+    }
+}
@@ -0,0 +1,719 @@
+mod monitor;
+
+use std::collections::BTreeMap;
+use std::net::Ipv4Addr;
+use std::sync::Mutex;
+use std::time::Duration;
+use std::time::Instant;
+
+use crate::config::SentinelConfig;
+use crate::replication::random_id;
+
+pub use monitor::run;
+
+/// The channel sentinels announce themselves and their view of a master on, through the master.
+pub const HELLO_CHANNEL: &str = "__sentinel__:hello";
+
+/// A replica of a monitored master, as last seen.
+#[derive(Debug, Default)]
+struct Replica {
+    /// When it last answered PING, if ever.
+    last_ok: Option<Instant>,
+    /// The offset of the replication stream it applied, to promote the most up to date one.
+    offset: u64,
+}
+
+/// Another sentinel monitoring the same master, known from its hello messages.
+#[derive(Debug)]
+struct Peer {
+    address: String,
+    /// Whether it agreed the master is down when last asked.
+    agrees: bool,
+    /// The sentinel it voted for to lead a failover, and in which epoch.
+    vote: Option<(String, u64)>,
+}
+
+#[derive(Debug)]
+struct Master {
+    address: String,
+    quorum: usize,
+    /// The epoch of the failover which made `address` the master, 0 if none did.
+    config_epoch: u64,
+    last_ok: Instant,
+    replicas: BTreeMap<String, Replica>,
+    /// Other sentinels by their ID.
+    peers: BTreeMap<String, Peer>,
+    /// The sentinel this one voted for to lead a failover, and in which epoch.
+    vote: Option<(String, u64)>,
+    /// The epoch of the failover this sentinel attempts, and when it started.
+    failover: Option<(u64, Instant)>,
+    /// No failover is attempted before then, as one was attempted lately.
+    next_failover: Instant,
+}
+
+impl Master {
+    /// Follows `address` as the master from `config_epoch` on, the previous one becoming a
+    /// replica of it.
+    fn switch(&mut self, address: &str, config_epoch: u64) {
+        let previous = std::mem::replace(&mut self.address, address.to_string());
+        self.config_epoch = config_epoch;
+        self.last_ok = Instant::now();
+        self.failover = None;
+        self.replicas.remove(address);
+        self.replicas.entry(previous).or_default();
+        for peer in self.peers.values_mut() {
+            peer.agrees = false;
+        }
+    }
+}
+
+struct State {
+    /// The latest epoch of failovers known, which votes are cast in.
+    epoch: u64,
+    masters: BTreeMap<String, Master>,
+}
+
+/// A hello message, announcing a sentinel and the master it monitors to the others.
+#[derive(Debug, PartialEq)]
+struct Hello {
+    address: String,
+    id: String,
+    epoch: u64,
+    name: String,
+    master: String,
+    config_epoch: u64,
+}
+
+impl Hello {
+    fn parse(message: &str) -> Option<Hello> {
+        let fields: Vec<&str> = message.split(',').collect();
+        let [
+            ip,
+            port,
+            id,
+            epoch,
+            name,
+            master_ip,
+            master_port,
+            config_epoch,
+        ] = fields[..]
+        else {
+            return None;
+        };
+        Some(Hello {
+            address: format!("{ip}:{port}"),
+            id: id.to_string(),
+            epoch: epoch.parse().ok()?,
+            name: name.to_string(),
+            master: format!("{master_ip}:{master_port}"),
+            config_epoch: config_epoch.parse().ok()?,
+        })
+    }
+
+    fn message(&self) -> String {
+        let (ip, port) = split_address(&self.address);
+        let (master_ip, master_port) = split_address(&self.master);
+        format!(
+            "{ip},{port},{},{},{},{master_ip},{master_port},{}",
+            self.id, self.epoch, self.name, self.config_epoch
+        )
+    }
+}
+
+/// Splits `host:port` in two, the port being empty if missing.
+pub fn split_address(address: &str) -> (&str, &str) {
+    address.rsplit_once(':').unwrap_or((address, ""))
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct MasterInfo {
+    pub name: String,
+    pub address: String,
+    pub quorum: usize,
+    pub config_epoch: u64,
+    pub subjectively_down: bool,
+    pub objectively_down: bool,
+    pub failover_in_progress: bool,
+    pub replicas: usize,
+    pub peers: usize,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReplicaInfo {
+    pub address: String,
+    pub offset: u64,
+    pub down: bool,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct PeerInfo {
+    pub id: String,
+    pub address: String,
+}
+
+/// What a sentinel knows about the masters it monitors, their replicas and the other sentinels
+/// monitoring them, along with the votes cast to elect the one failing a master over.
+pub struct Sentinel {
+    id: String,
+    address: String,
+    down_after: Duration,
+    failover_timeout: Duration,
+    state: Mutex<State>,
+}
+
+impl Sentinel {
+    pub fn new(config: &SentinelConfig, port: usize) -> Self {
+        let now = Instant::now();
+        let masters = config
+            .masters
+            .iter()
+            .map(|monitor| {
+                let master = Master {
+                    address: monitor.address.clone(),
+                    quorum: monitor.quorum,
+                    config_epoch: 0,
+                    last_ok: now,
+                    replicas: BTreeMap::new(),
+                    peers: BTreeMap::new(),
+                    vote: None,
+                    failover: None,
+                    next_failover: now,
+                };
+                (monitor.name.clone(), master)
+            })
+            .collect();
+        Self {
+            id: random_id(),
+            // The server only listens on the loopback interface.
+            address: format!("{}:{port}", Ipv4Addr::LOCALHOST),
+            down_after: Duration::from_millis(config.down_after),
+            failover_timeout: Duration::from_millis(config.failover_timeout),
+            state: Mutex::new(State { epoch: 0, masters }),
+        }
+    }
+
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    /// How often servers are checked on, often enough to tell they are down in time.
+    pub fn period(&self) -> Duration {
+        self.down_after.min(Duration::from_secs(1))
+    }
+
+    /// The latest epoch of failovers known.
+    pub fn epoch(&self) -> u64 {
+        self.state.lock().unwrap().epoch
+    }
+
+    pub fn failover_timeout(&self) -> Duration {
+        self.failover_timeout
+    }
+
+    pub fn names(&self) -> Vec<String> {
+        self.state.lock().unwrap().masters.keys().cloned().collect()
+    }
+
+    pub fn master_address(&self, name: &str) -> Option<String> {
+        let state = self.state.lock().unwrap();
+        state.masters.get(name).map(|master| master.address.clone())
+    }
+
+    pub fn masters(&self) -> Vec<MasterInfo> {
+        self.names()
+            .iter()
+            .filter_map(|name| self.master(name))
+            .collect()
+    }
+
+    pub fn master(&self, name: &str) -> Option<MasterInfo> {
+        let state = self.state.lock().unwrap();
+        let master = state.masters.get(name)?;
+        let subjectively_down = self.is_down(master);
+        Some(MasterInfo {
+            name: name.to_string(),
+            address: master.address.clone(),
+            quorum: master.quorum,
+            config_epoch: master.config_epoch,
+            subjectively_down,
+            objectively_down: subjectively_down && Self::agreeing(master) >= master.quorum,
+            failover_in_progress: master.failover.is_some(),
+            replicas: master.replicas.len(),
+            peers: master.peers.len(),
+        })
+    }
+
+    pub fn replicas(&self, name: &str) -> Option<Vec<ReplicaInfo>> {
+        let state = self.state.lock().unwrap();
+        let master = state.masters.get(name)?;
+        let replicas = master
+            .replicas
+            .iter()
+            .map(|(address, replica)| ReplicaInfo {
+                address: address.clone(),
+                offset: replica.offset,
+                down: !self.is_recent(replica.last_ok),
+            })
+            .collect();
+        Some(replicas)
+    }
+
+    pub fn peers(&self, name: &str) -> Option<Vec<PeerInfo>> {
+        let state = self.state.lock().unwrap();
+        let master = state.masters.get(name)?;
+        let peers = master
+            .peers
+            .iter()
+            .map(|(id, peer)| PeerInfo {
+                id: id.clone(),
+                address: peer.address.clone(),
+            })
+            .collect();
+        Some(peers)
+    }
+
+    /// Records `address`, the master or one of its replicas, answered PING as expected.
+    pub fn answered(&self, name: &str, address: &str) {
+        let mut state = self.state.lock().unwrap();
+        let Some(master) = state.masters.get_mut(name) else {
+            return;
+        };
+        if master.address == address {
+            master.last_ok = Instant::now();
+        } else if let Some(replica) = master.replicas.get_mut(address) {
+            replica.last_ok = Some(Instant::now());
+        }
+    }
+
+    /// Records the replicas the master reported, which are checked on from now on.
+    pub fn discovered(&self, name: &str, replicas: &[String]) {
+        let mut state = self.state.lock().unwrap();
+        let Some(master) = state.masters.get_mut(name) else {
+            return;
+        };
+        for address in replicas {
+            if *address != master.address {
+                master.replicas.entry(address.clone()).or_default();
+            }
+        }
+    }
+
+    /// Records the offset of the replication stream a replica reported to have applied.
+    pub fn replicated(&self, name: &str, address: &str, offset: u64) {
+        let mut state = self.state.lock().unwrap();
+        if let Some(replica) = state
+            .masters
+            .get_mut(name)
+            .and_then(|master| master.replicas.get_mut(address))
+        {
+            replica.offset = offset;
+        }
+    }
+
+    pub fn is_subjectively_down(&self, name: &str) -> bool {
+        self.master(name)
+            .is_some_and(|master| master.subjectively_down)
+    }
+
+    /// Whether enough sentinels, this one included, agree the master is down.
+    pub fn is_objectively_down(&self, name: &str) -> bool {
+        self.master(name)
+            .is_some_and(|master| master.objectively_down)
+    }
+
+    /// Records whether another sentinel agreed the master is down, and who it voted for.
+    pub fn agreed(&self, name: &str, peer: &str, down: bool, vote: Option<(String, u64)>) {
+        let mut state = self.state.lock().unwrap();
+        let Some(master) = state.masters.get_mut(name) else {
+            return;
+        };
+        if let Some(peer) = master.peers.get_mut(peer) {
+            peer.agrees = down;
+            if vote.is_some() {
+                peer.vote = vote;
+            }
+        }
+    }
+
+    /// The hello message announcing this sentinel and the master it follows.
+    pub fn hello(&self, name: &str) -> Option<String> {
+        let state = self.state.lock().unwrap();
+        let master = state.masters.get(name)?;
+        let hello = Hello {
+            address: self.address.clone(),
+            id: self.id.clone(),
+            epoch: state.epoch,
+            name: name.to_string(),
+            master: master.address.clone(),
+            config_epoch: master.config_epoch,
+        };
+        Some(hello.message())
+    }
+
+    /// Learns about the sentinel which sent the hello message, and about the master it follows
+    /// if it was failed over since.
+    pub fn heard(&self, message: &str) {
+        let Some(hello) = Hello::parse(message) else {
+            return;
+        };
+        if hello.id == self.id {
+            return;
+        }
+        let mut state = self.state.lock().unwrap();
+        state.epoch = state.epoch.max(hello.epoch);
+        let Some(master) = state.masters.get_mut(&hello.name) else {
+            return;
+        };
+        master
+            .peers
+            .entry(hello.id)
+            .and_modify(|peer| peer.address = hello.address.clone())
+            .or_insert(Peer {
+                address: hello.address,
+                agrees: false,
+                vote: None,
+            });
+        if hello.config_epoch > master.config_epoch {
+            if hello.master == master.address {
+                master.config_epoch = hello.config_epoch;
+            } else {
+                master.switch(&hello.master, hello.config_epoch);
+            }
+        }
+    }
+
+    /// Answers another sentinel asking whether the master at `address` is down, voting for
+    /// `candidate` to fail it over in `epoch` unless this sentinel voted in that epoch already.
+    /// Hands back whether the master is down, and the vote cast in the latest epoch.
+    pub fn is_master_down_by_addr(
+        &self,
+        address: &str,
+        epoch: u64,
+        candidate: Option<&str>,
+    ) -> (bool, Option<(String, u64)>) {
+        let Some(name) = self.name_of(address) else {
+            return (false, None);
+        };
+        let down = self.is_subjectively_down(&name);
+        let vote = match candidate {
+            Some(candidate) => self.vote(&name, candidate, epoch),
+            None => None,
+        };
+        (down, vote)
+    }
+
+    fn name_of(&self, address: &str) -> Option<String> {
+        let state = self.state.lock().unwrap();
+        state
+            .masters
+            .iter()
+            .find(|(_, master)| master.address == address)
+            .map(|(name, _)| name.clone())
+    }
+
+    fn vote(&self, name: &str, candidate: &str, epoch: u64) -> Option<(String, u64)> {
+        let mut state = self.state.lock().unwrap();
+        state.epoch = state.epoch.max(epoch);
+        let current = state.epoch;
+        let master = state.masters.get_mut(name)?;
+        let voted = master.vote.as_ref().map_or(0, |(_, epoch)| *epoch);
+        if voted < epoch && current <= epoch {
+            master.vote = Some((candidate.to_string(), epoch));
+            if candidate != self.id {
+                // Leave the failover to the candidate for a while.
+                master.next_failover = Instant::now() + self.failover_timeout * 2;
+            }
+        }
+        master.vote.clone()
+    }
+
+    /// Starts a failover of the master in a new epoch if it is down for enough sentinels and
+    /// none was attempted lately, voting for this sentinel to lead it.
+    pub fn start_failover(&self, name: &str) -> Option<u64> {
+        if !self.is_objectively_down(name) {
+            return None;
+        }
+        let mut state = self.state.lock().unwrap();
+        let now = Instant::now();
+        let epoch = state.epoch + 1;
+        let master = state.masters.get_mut(name)?;
+        if master.failover.is_some() || now < master.next_failover {
+            return None;
+        }
+        master.failover = Some((epoch, now));
+        master.next_failover = now + self.failover_timeout * 2;
+        master.vote = Some((self.id.clone(), epoch));
+        state.epoch = epoch;
+        Some(epoch)
+    }
+
+    /// The epoch of the failover this sentinel attempts, given up once it took too long.
+    pub fn failover(&self, name: &str) -> Option<u64> {
+        let mut state = self.state.lock().unwrap();
+        let master = state.masters.get_mut(name)?;
+        let (epoch, started) = master.failover?;
+        if started.elapsed() > self.failover_timeout {
+            master.failover = None;
+            return None;
+        }
+        Some(epoch)
+    }
+
+    pub fn abort_failover(&self, name: &str) {
+        let mut state = self.state.lock().unwrap();
+        if let Some(master) = state.masters.get_mut(name) {
+            master.failover = None;
+        }
+    }
+
+    /// Whether a majority of the sentinels, and at least a quorum, voted for this one in
+    /// `epoch`.
+    pub fn is_leader(&self, name: &str, epoch: u64) -> bool {
+        let state = self.state.lock().unwrap();
+        let Some(master) = state.masters.get(name) else {
+            return false;
+        };
+        let elected = |vote: &Option<(String, u64)>| {
+            vote.as_ref()
+                .is_some_and(|(id, voted)| *id == self.id && *voted == epoch)
+        };
+        let votes = usize::from(elected(&master.vote))
+            + master
+                .peers
+                .values()
+                .filter(|peer| elected(&peer.vote))
+                .count();
+        let majority = master.peers.len().div_ceil(2) + 1;
+        votes >= majority.max(master.quorum)
+    }
+
+    /// The replica to promote: among those answering, the one which applied the most of the
+    /// replication stream.
+    pub fn best_replica(&self, name: &str) -> Option<String> {
+        let state = self.state.lock().unwrap();
+        let master = state.masters.get(name)?;
+        master
+            .replicas
+            .iter()
+            .filter(|(_, replica)| self.is_recent(replica.last_ok))
+            .max_by(|(first, a), (second, b)| a.offset.cmp(&b.offset).then(second.cmp(first)))
+            .map(|(address, _)| address.clone())
+    }
+
+    /// Follows the replica promoted in `epoch` as the master, handing back the replicas to
+    /// reconfigure to replicate from it.
+    pub fn promoted(&self, name: &str, address: &str, epoch: u64) -> Vec<String> {
+        let mut state = self.state.lock().unwrap();
+        let Some(master) = state.masters.get_mut(name) else {
+            return Vec::new();
+        };
+        master.switch(address, epoch);
+        master.replicas.keys().cloned().collect()
+    }
+
+    fn is_down(&self, master: &Master) -> bool {
+        master.last_ok.elapsed() > self.down_after
+    }
+
+    fn is_recent(&self, last_ok: Option<Instant>) -> bool {
+        last_ok.is_some_and(|last_ok| last_ok.elapsed() <= self.down_after)
+    }
+
+    /// How many sentinels agree the master is down, this one included.
+    fn agreeing(master: &Master) -> usize {
+        1 + master.peers.values().filter(|peer| peer.agrees).count()
+    }
+}
+
+#[cfg(test)]
+mod specs_for_sentinel {
+    use std::time::Duration;
+
+    use crate::config::Monitor;
+    use crate::config::SentinelConfig;
+
+    use super::Hello;
+    use super::PeerInfo;
+    use super::Sentinel;
+
+    const MASTER: &str = "127.0.0.1:6379";
+
+    fn sentinel(quorum: usize, down_after: u64) -> Sentinel {
+        let config = SentinelConfig {
+            masters: vec![Monitor {
+                name: "mymaster".to_string(),
+                address: MASTER.to_string(),
+                quorum,
+            }],
+            down_after,
+            failover_timeout: 60000,
+        };
+        Sentinel::new(&config, 26379)
+    }
+
+    fn hello(id: &str, port: u16, epoch: u64, master: &str, config_epoch: u64) -> String {
+        Hello {
+            address: format!("127.0.0.1:{port}"),
+            id: id.to_string(),
+            epoch,
+            name: "mymaster".to_string(),
+            master: master.to_string(),
+            config_epoch,
+        }
+        .message()
+    }
+
+    fn wait_until_down(sut: &Sentinel) {
+        std::thread::sleep(Duration::from_millis(20));
+        assert!(sut.is_subjectively_down("mymaster"));
+    }
+
+    #[test]
+    fn sut_formats_and_parses_hello_messages() {
+        // Arrange
+        let message = "127.0.0.1,26380,abc,3,mymaster,127.0.0.1,6379,2";
+
+        // Act
+        let actual = Hello::parse(message).unwrap();
+
+        // Assert
+        assert_eq!(actual.address, "127.0.0.1:26380");
+        assert_eq!(actual.master, MASTER);
+        assert_eq!((actual.epoch, actual.config_epoch), (3, 2));
+        assert_eq!(actual.message(), message);
+    }
+
+    #[test]
+    fn sut_learns_about_other_sentinels_from_their_hello_messages() {
+        // Arrange
+        let sut = sentinel(2, 30000);
+        let own = sut.hello("mymaster").unwrap();
+
+        // Act
+        sut.heard(&own);
+        sut.heard(&hello("other", 26380, 0, MASTER, 0));
+        sut.heard(&hello("other", 26381, 0, MASTER, 0));
+
+        // Assert
+        let expected = vec![PeerInfo {
+            id: "other".to_string(),
+            address: "127.0.0.1:26381".to_string(),
+        }];
+        assert_eq!(sut.peers("mymaster"), Some(expected));
+    }
+
+    #[test]
+    fn sut_follows_master_announced_with_a_newer_configuration() {
+        // Arrange
+        let sut = sentinel(2, 30000);
+        sut.discovered("mymaster", &["127.0.0.1:6380".to_string()]);
+
+        // Act
+        sut.heard(&hello("other", 26380, 1, "127.0.0.1:6380", 1));
+        sut.heard(&hello("other", 26380, 1, MASTER, 0));
+
+        // Assert
+        let master = sut.master("mymaster").unwrap();
+        assert_eq!(master.address, "127.0.0.1:6380");
+        assert_eq!(master.config_epoch, 1);
+        let replicas = sut.replicas("mymaster").unwrap();
+        assert_eq!(replicas.len(), 1);
+        assert_eq!(replicas[0].address, MASTER);
+    }
+
+    #[test]
+    fn sut_tells_master_is_objectively_down_once_a_quorum_agrees() {
+        // Arrange
+        let sut = sentinel(2, 10);
+        sut.heard(&hello("other", 26380, 0, MASTER, 0));
+        wait_until_down(&sut);
+        let alone = sut.is_objectively_down("mymaster");
+
+        // Act
+        sut.agreed("mymaster", "other", true, None);
+
+        // Assert
+        assert!(!alone);
+        assert!(sut.is_objectively_down("mymaster"));
+    }
+
+    #[test]
+    fn sut_votes_once_per_epoch_for_the_first_candidate() {
+        // Arrange
+        let sut = sentinel(2, 30000);
+
+        // Act
+        let first = sut.is_master_down_by_addr(MASTER, 1, Some("a"));
+        let second = sut.is_master_down_by_addr(MASTER, 1, Some("b"));
+        let next = sut.is_master_down_by_addr(MASTER, 2, Some("b"));
+        let asked = sut.is_master_down_by_addr(MASTER, 2, None);
+        let unknown = sut.is_master_down_by_addr("127.0.0.1:1", 3, Some("c"));
+
+        // Assert
+        assert_eq!(first, (false, Some(("a".to_string(), 1))));
+        assert_eq!(second, (false, Some(("a".to_string(), 1))));
+        assert_eq!(next, (false, Some(("b".to_string(), 2))));
+        assert_eq!(asked, (false, None));
+        assert_eq!(unknown, (false, None));
+    }
+
+    #[test]
+    fn sut_leads_failover_once_a_majority_voted_for_it() {
+        // Arrange
+        let sut = sentinel(2, 10);
+        sut.heard(&hello("b", 26380, 0, MASTER, 0));
+        sut.heard(&hello("c", 26381, 0, MASTER, 0));
+        wait_until_down(&sut);
+        sut.agreed("mymaster", "b", true, None);
+
+        // Act
+        let epoch = sut.start_failover("mymaster").unwrap();
+        let alone = sut.is_leader("mymaster", epoch);
+        let again = sut.start_failover("mymaster");
+        sut.agreed("mymaster", "b", true, Some((sut.id().to_string(), epoch)));
+
+        // Assert
+        assert_eq!(epoch, 1);
+        assert!(!alone);
+        assert_eq!(again, None);
+        assert!(sut.is_leader("mymaster", epoch));
+        assert_eq!(sut.failover("mymaster"), Some(epoch));
+    }
+
+    #[test]
+    fn sut_leaves_failover_to_the_sentinel_it_voted_for() {
+        // Arrange
+        let sut = sentinel(1, 10);
+        wait_until_down(&sut);
+        sut.is_master_down_by_addr(MASTER, 1, Some("other"));
+
+        // Act
+        let actual = sut.start_failover("mymaster");
+
+        // Assert
+        assert_eq!(actual, None);
+    }
+
+    #[test]
+    fn sut_promotes_the_answering_replica_which_replicated_the_most() {
+        // Arrange
+        let sut = sentinel(1, 30000);
+        let replicas = ["127.0.0.1:6380", "127.0.0.1:6381", "127.0.0.1:6382"].map(String::from);
+        sut.discovered("mymaster", &replicas);
+        for (address, offset) in [(&replicas[0], 10), (&replicas[1], 20), (&replicas[2], 30)] {
+            sut.replicated("mymaster", address, offset);
+        }
+        sut.answered("mymaster", &replicas[0]);
+        sut.answered("mymaster", &replicas[1]);
+
+        // Act
+        let best = sut.best_replica("mymaster").unwrap();
+        let reconfigured = sut.promoted("mymaster", &best, 1);
+
+        // Assert
+        assert_eq!(best, replicas[1]);
+        assert_eq!(sut.master_address("mymaster"), Some(best));
+        let expected = vec![MASTER.to_string(), replicas[0].clone(), replicas[2].clone()];
+        assert_eq!(reconfigured, expected);
+    }
+}
@@ -0,0 +1,278 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+use rand::Rng;
+use tokio::task::JoinHandle;
+use tokio::time::Instant;
+
+use crate::link::Link;
+use crate::link::Links;
+use crate::resp::Value;
+use crate::sentinel::HELLO_CHANNEL;
+use crate::sentinel::Sentinel;
+use crate::sentinel::split_address;
+
+/// Monitors every master given to the sentinel, failing it over once down.
+pub async fn run(sentinel: Arc<Sentinel>) {
+    for name in sentinel.names() {
+        tokio::spawn(listen(sentinel.clone(), name.clone()));
+        tokio::spawn(monitor(sentinel.clone(), name));
+    }
+}
+
+async fn monitor(sentinel: Arc<Sentinel>, name: String) {
+    let mut links = Links::new(sentinel.period());
+    let mut interval = tokio::time::interval(sentinel.period());
+    loop {
+        interval.tick().await;
+        check(&sentinel, &name, &mut links).await;
+        if sentinel.is_subjectively_down(&name) {
+            ask(&sentinel, &name, &mut links, None).await;
+        }
+        if sentinel.failover(&name).is_none() && sentinel.is_objectively_down(&name) {
+            // Sentinels starting a failover at once would split their votes.
+            let delay = rand::thread_rng().gen_range(Duration::ZERO..sentinel.period());
+            tokio::time::sleep(delay).await;
+            sentinel.start_failover(&name);
+        }
+        if let Some(epoch) = sentinel.failover(&name) {
+            ask(&sentinel, &name, &mut links, Some(epoch)).await;
+            if sentinel.is_leader(&name, epoch) {
+                fail_over(&sentinel, &name, &mut links, epoch).await;
+            }
+        }
+    }
+}
+
+/// Checks on the master and its replicas, reconfiguring those following another master once
+/// the master is fine, and announces this sentinel to the others.
+async fn check(sentinel: &Sentinel, name: &str, links: &mut Links) {
+    let Some(master) = sentinel.master_address(name) else {
+        return;
+    };
+    if ping(links, &master).await {
+        sentinel.answered(name, &master);
+    }
+    if let Ok(Value::BulkString(info)) = links.request(&master, &["INFO", "replication"]).await {
+        sentinel.discovered(name, &replicas_of(&info));
+    }
+
+    let settled = !sentinel.is_subjectively_down(name) && sentinel.failover(name).is_none();
+    let (host, port) = split_address(&master);
+    for replica in sentinel.replicas(name).unwrap_or_default() {
+        let address = replica.address;
+        if ping(links, &address).await {
+            sentinel.answered(name, &address);
+        }
+        let Ok(Value::BulkString(info)) = links.request(&address, &["INFO", "replication"]).await
+        else {
+            continue;
+        };
+        if let Some(offset) = field(&info, "slave_repl_offset").and_then(|o| o.parse().ok()) {
+            sentinel.replicated(name, &address, offset);
+        }
+        // Such as a master which was failed over while down.
+        let following =
+            field(&info, "master_host") == Some(host) && field(&info, "master_port") == Some(port);
+        if settled && !following {
+            let _ = links.request(&address, &["REPLICAOF", host, port]).await;
+        }
+    }
+
+    publish_hello(sentinel, name, links).await;
+}
+
+/// Announces this sentinel to the others through the master and its replicas, so that those
+/// following another master still hear of a failover.
+async fn publish_hello(sentinel: &Sentinel, name: &str, links: &mut Links) {
+    let Some(message) = sentinel.hello(name) else {
+        return;
+    };
+    for address in instances(sentinel, name) {
+        let _ = links
+            .request(&address, &["PUBLISH", HELLO_CHANNEL, &message])
+            .await;
+    }
+}
+
+/// The replicas of the master, and the master itself first.
+fn instances(sentinel: &Sentinel, name: &str) -> Vec<String> {
+    let replicas = sentinel.replicas(name).unwrap_or_default();
+    sentinel
+        .master_address(name)
+        .into_iter()
+        .chain(replicas.into_iter().map(|replica| replica.address))
+        .collect()
+}
+
+/// Whether the server answered PING as expected, even if it cannot serve its data yet.
+async fn ping(links: &mut Links, address: &str) -> bool {
+    match links.request(address, &["PING"]).await {
+        Ok(Value::SimpleString(reply)) => reply == "PONG",
+        Ok(Value::Error(e)) => e.starts_with("LOADING") || e.starts_with("MASTERDOWN"),
+        _ => false,
+    }
+}
+
+/// Asks the other sentinels whether they agree the master is down, and to vote for this one to
+/// fail it over in `epoch` if given.
+async fn ask(sentinel: &Sentinel, name: &str, links: &mut Links, epoch: Option<u64>) {
+    let Some(master) = sentinel.master_address(name) else {
+        return;
+    };
+    let (ip, port) = split_address(&master);
+    let (epoch, candidate) = match epoch {
+        Some(epoch) => (epoch, sentinel.id()),
+        None => (sentinel.epoch(), "*"),
+    };
+    let epoch = epoch.to_string();
+    for peer in sentinel.peers(name).unwrap_or_default() {
+        let command = [
+            "SENTINEL",
+            "IS-MASTER-DOWN-BY-ADDR",
+            ip,
+            port,
+            &epoch,
+            candidate,
+        ];
+        if let Ok(Value::Array(reply)) = links.request(&peer.address, &command).await
+            && let [
+                Value::Integer(down),
+                Value::BulkString(leader),
+                Value::Integer(voted),
+            ] = &reply[..]
+        {
+            let vote = (leader != "*").then(|| (leader.clone(), *voted as u64));
+            sentinel.agreed(name, &peer.id, *down == 1, vote);
+        }
+    }
+}
+
+/// Promotes the best replica, waiting for it to report being the master before the other
+/// replicas are reconfigured to follow it.
+async fn fail_over(sentinel: &Sentinel, name: &str, links: &mut Links, epoch: u64) {
+    let Some(promoted) = sentinel.best_replica(name) else {
+        eprintln!("no replica of {name} to promote");
+        sentinel.abort_failover(name);
+        return;
+    };
+    let reply = links.request(&promoted, &["REPLICAOF", "NO", "ONE"]).await;
+    if !matches!(reply, Ok(Value::SimpleString(_))) {
+        sentinel.abort_failover(name);
+        return;
+    }
+    let deadline = Instant::now() + sentinel.failover_timeout();
+    loop {
+        if let Ok(Value::BulkString(info)) =
+            links.request(&promoted, &["INFO", "replication"]).await
+            && field(&info, "role") == Some("master")
+        {
+            break;
+        }
+        if Instant::now() >= deadline {
+            sentinel.abort_failover(name);
+            return;
+        }
+        tokio::time::sleep(sentinel.period()).await;
+    }
+
+    let (host, port) = split_address(&promoted);
+    for replica in sentinel.promoted(name, &promoted, epoch) {
+        let _ = links.request(&replica, &["REPLICAOF", host, port]).await;
+    }
+    // The other sentinels follow the promoted replica once they hear of it.
+    publish_hello(sentinel, name, links).await;
+}
+
+/// Hears the hello messages other sentinels publish to the master and its replicas, following
+/// the master once it was failed over.
+async fn listen(sentinel: Arc<Sentinel>, name: String) {
+    let mut subscriptions: HashMap<String, JoinHandle<()>> = HashMap::new();
+    loop {
+        subscriptions.retain(|_, subscription| !subscription.is_finished());
+        for address in instances(&sentinel, &name) {
+            subscriptions.entry(address.clone()).or_insert_with(|| {
+                let sentinel = sentinel.clone();
+                let name = name.clone();
+                tokio::spawn(async move {
+                    let _ = subscribe(&sentinel, &name, &address).await;
+                })
+            });
+        }
+        tokio::time::sleep(sentinel.period()).await;
+    }
+}
+
+async fn subscribe(sentinel: &Sentinel, name: &str, address: &str) -> Result<(), anyhow::Error> {
+    let mut link = tokio::time::timeout(sentinel.period(), Link::connect(address)).await??;
+    link.request(&["SUBSCRIBE", HELLO_CHANNEL]).await?;
+    while instances(sentinel, name)
+        .iter()
+        .any(|instance| instance == address)
+    {
+        let Ok(message) = tokio::time::timeout(sentinel.period(), link.next()).await else {
+            continue;
+        };
+        if let Value::Array(parts) = message?
+            && let [_, _, Value::BulkString(message)] = &parts[..]
+        {
+            sentinel.heard(message);
+        }
+    }
+    Ok(())
+}
+
+/// The addresses of the replicas listed in the INFO replication of a master.
+fn replicas_of(info: &str) -> Vec<String> {
+    info.lines()
+        .filter(|line| line.starts_with("slave") && line.contains(":ip="))
+        .filter_map(|line| {
+            let (_, attributes) = line.split_once(':')?;
+            let attribute = |name: &str| {
+                attributes
+                    .split(',')
+                    .find_map(|attribute| attribute.strip_prefix(name)?.strip_prefix('='))
+            };
+            Some(format!("{}:{}", attribute("ip")?, attribute("port")?))
+        })
+        .collect()
+}
+
+/// The value of a field of INFO.
+fn field<'a>(info: &'a str, name: &str) -> Option<&'a str> {
+    info.lines()
+        .find_map(|line| line.strip_prefix(name)?.strip_prefix(':'))
+}
+
+#[cfg(test)]
+mod specs_for_info {
+    use super::field;
+    use super::replicas_of;
+
+    const INFO: &str = "# Replication\r\nrole:master\r\nconnected_slaves:2\r\n\
+        slave0:ip=127.0.0.1,port=6380,state=online,offset=42\r\n\
+        slave1:ip=10.0.0.2,port=6381,state=wait_bgsave,offset=0\r\n\
+        master_failover_state:no-failover\r\n";
+
+    #[test]
+    fn sut_lists_replicas_reported_by_master() {
+        // Act
+        let actual = replicas_of(INFO);
+
+        // Assert
+        assert_eq!(actual, vec!["127.0.0.1:6380", "10.0.0.2:6381"]);
+    }
+
+    #[rstest::rstest]
+    #[case("role", Some("master"))]
+    #[case("connected_slaves", Some("2"))]
+    #[case("master_host", None)]
+    fn sut_reads_fields(#[case] name: &str, #[case] expected: Option<&str>) {
+        // Act
+        let actual = field(INFO, name);
+
+        // Assert
+        assert_eq!(actual, expected);
+    }
+}
@@ -2,51 +2,234 @@ use anyhow::Result;
 use async_stream::stream;
 use futures::Stream;
 use futures::StreamExt;
+use std::path::Path;
 use std::pin::Pin;
-use std::sync::Arc;
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::Ordering;
 use tokio::io::AsyncRead;
 use tokio::io::AsyncReadExt;
-use tokio::io::AsyncSeekExt;
 use tokio::io::BufReader;
-use tokio::io::SeekFrom;
 use tokio::sync::Mutex;
 
+use crate::clock::Clock;
+use crate::repository::Data;
+use crate::repository::Databases;
 use crate::repository::Entry;
 use crate::repository::Expiry;
-use crate::repository::Repository;
+use crate::repository::SortedSet;
 use crate::repository::TimeUnit;
+use crate::scripting::Libraries;
 
-pub async fn load<R: AsyncRead + AsyncSeekExt + Unpin + Send>(
+/// The opcode of a record holding the code of a function library.
+pub const FUNCTION: u8 = 0xF5;
+const STRING: u8 = 0x00;
+const SORTED_SET: u8 = 0x05;
+
+#[derive(Debug)]
+pub enum Record {
+    /// An entry along with the index of the database it belongs to.
+    Entry(usize, Entry),
+    Library(String),
+}
+
+pub async fn load<R: AsyncRead + Unpin + Send>(
     reader: R,
-    repository: Arc<impl Repository>,
-) {
+    databases: &Databases,
+    libraries: &Libraries,
+    clock: &dyn Clock,
+) -> Result<()> {
     let rdb_file_reader = RdbFileReader::new(reader);
-    let mut entries = rdb_file_reader.entries().await;
-    while let Some(entry) = entries.next().await {
-        if let Some(expiry) = &entry.expiry {
-            if expiry.is_expired() {
+    let mut records = rdb_file_reader.records().await;
+    while let Some(record) = records.next().await {
+        let (database, entry) = match record {
+            Record::Entry(database, entry) => (database, entry),
+            Record::Library(code) => {
+                if let Err(e) = libraries.load(&code, true) {
+                    eprintln!("skipping function library which fails to load: {e}");
+                }
                 continue;
             }
+        };
+        if let Some(expiry) = &entry.expiry
+            && expiry.is_expired(clock.now_in_millis())
+        {
+            continue;
         }
+        let Some(repository) = databases.get(database) else {
+            eprintln!("skipping key of database {database} which is out of range");
+            continue;
+        };
         repository.set(entry).await;
     }
+    if !rdb_file_reader.is_complete() {
+        anyhow::bail!("snapshot is truncated or corrupt");
+    }
+    Ok(())
+}
+
+/// Writes the databases and function libraries as an RDB file, replacing the file at `path`
+/// only once it is complete.
+pub async fn save(
+    path: &Path,
+    databases: &Databases,
+    libraries: &Libraries,
+    clock: &dyn Clock,
+) -> std::io::Result<()> {
+    let bytes = serialize(databases, libraries, clock).await;
+    let temporary = path.with_extension("rdb.tmp");
+    tokio::fs::write(&temporary, bytes).await?;
+    tokio::fs::rename(&temporary, path).await
+}
+
+pub async fn serialize(databases: &Databases, libraries: &Libraries, clock: &dyn Clock) -> Vec<u8> {
+    let mut bytes = b"REDIS0011".to_vec();
+    for (key, value) in [("redis-ver", "7.2.0"), ("redis-bits", "64")] {
+        bytes.push(0xFA);
+        encode_string(&mut bytes, key);
+        encode_string(&mut bytes, value);
+    }
+    for library in libraries.list() {
+        bytes.push(FUNCTION);
+        encode_string(&mut bytes, &library.code);
+    }
+
+    let now = clock.now_in_millis();
+    for (index, repository) in databases.all().into_iter().enumerate() {
+        let entries: Vec<Entry> = repository
+            .entries()
+            .await
+            .into_iter()
+            .filter(|entry| !entry.is_expired(now))
+            .collect();
+        if entries.is_empty() {
+            continue;
+        }
+        bytes.push(0xFE);
+        encode_size(&mut bytes, index);
+        bytes.push(0xFB);
+        encode_size(&mut bytes, entries.len());
+        encode_size(
+            &mut bytes,
+            entries
+                .iter()
+                .filter(|entry| entry.expiry.is_some())
+                .count(),
+        );
+        for entry in entries {
+            encode_entry(&mut bytes, &entry);
+        }
+    }
+
+    bytes.push(0xFF);
+    // A zero checksum tells readers it was not computed, as with `rdbchecksum no`.
+    bytes.extend([0; 8]);
+    bytes
+}
+
+fn encode_entry(bytes: &mut Vec<u8>, entry: &Entry) {
+    if let Some(expiry) = &entry.expiry {
+        bytes.push(0xFC);
+        bytes.extend((expiry.to_millis() as u64).to_le_bytes());
+    }
+    match &entry.value {
+        Data::String(value) => {
+            bytes.push(STRING);
+            encode_string(bytes, &entry.key);
+            encode_string(bytes, value);
+        }
+        Data::SortedSet(sorted_set) => {
+            bytes.push(SORTED_SET);
+            encode_string(bytes, &entry.key);
+            encode_size(bytes, sorted_set.len());
+            for (member, score) in sorted_set.iter() {
+                encode_string(bytes, member);
+                bytes.extend(score.to_le_bytes());
+            }
+        }
+    }
+}
+
+pub fn encode_size(bytes: &mut Vec<u8>, size: usize) {
+    match size {
+        0..0x40 => bytes.push(size as u8),
+        0x40..0x4000 => bytes.extend([0x40 | (size >> 8) as u8, size as u8]),
+        _ => match u32::try_from(size) {
+            Ok(size) => {
+                bytes.push(0x80);
+                bytes.extend(size.to_be_bytes());
+            }
+            Err(_) => {
+                bytes.push(0x81);
+                bytes.extend((size as u64).to_be_bytes());
+            }
+        },
+    }
+}
+
+pub fn encode_string(bytes: &mut Vec<u8>, string: &str) {
+    encode_size(bytes, string.len());
+    bytes.extend(string.as_bytes());
+}
+
+/// Reads a length encoded size off the front of `bytes`.
+pub fn decode_size(bytes: &mut &[u8]) -> Option<usize> {
+    let (&first, rest) = bytes.split_first()?;
+    let (size, length) = match first >> 6 {
+        0b00 => ((first & 0x3F) as usize, 0),
+        0b01 => (
+            (((first & 0x3F) as usize) << 8) | *rest.first()? as usize,
+            1,
+        ),
+        0b10 if first == 0x80 => (
+            u32::from_be_bytes(rest.get(..4)?.try_into().ok()?) as usize,
+            4,
+        ),
+        0b10 if first == 0x81 => (
+            u64::from_be_bytes(rest.get(..8)?.try_into().ok()?) as usize,
+            8,
+        ),
+        _ => return None,
+    };
+    *bytes = &rest[length..];
+    Some(size)
+}
+
+pub fn decode_string(bytes: &mut &[u8]) -> Option<String> {
+    let size = decode_size(bytes)?;
+    let string = bytes.get(..size)?;
+    *bytes = &bytes[size..];
+    Some(String::from_utf8_lossy(string).to_string())
+}
+
+/// CRC-64/Jones, as Redis checksums RDB files and DUMP payloads.
+pub fn crc64(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0, |crc, byte| {
+        (0..8).fold(crc ^ u64::from(*byte), |crc, _| {
+            if crc & 1 != 0 {
+                (crc >> 1) ^ 0x95AC_9329_AC4B_C9B5
+            } else {
+                crc >> 1
+            }
+        })
+    })
 }
 
 struct RdbFileReader<R> {
     reader: Mutex<BufReader<R>>,
+    /// Whether the records were read up to the end of file mark.
+    complete: AtomicBool,
 }
 
-impl<R: AsyncRead + AsyncSeekExt + Unpin + Send> RdbFileReader<R> {
+impl<R: AsyncRead + Unpin + Send> RdbFileReader<R> {
     pub fn new(reader: R) -> Self {
         RdbFileReader {
             reader: Mutex::new(BufReader::new(reader)),
+            complete: AtomicBool::new(false),
         }
     }
 
-    async fn initialize(&self) -> Result<()> {
-        let mut reader = self.reader.lock().await;
-        reader.seek(SeekFrom::Start(0)).await?;
-        Ok(())
+    fn is_complete(&self) -> bool {
+        self.complete.load(Ordering::SeqCst)
     }
 
     async fn header(&self) -> Result<String> {
@@ -54,11 +237,12 @@ impl<R: AsyncRead + AsyncSeekExt + Unpin + Send> RdbFileReader<R> {
         Ok(String::from_utf8_lossy(&buffer).to_string())
     }
 
-    pub async fn entries(&self) -> Pin<Box<dyn Stream<Item = Entry> + Send + '_>> {
-        self.initialize().await.unwrap();
-        self.header().await.unwrap();
-
+    pub async fn records(&self) -> Pin<Box<dyn Stream<Item = Record> + Send + '_>> {
         Box::pin(stream! {
+            if self.header().await.is_err() {
+                return;
+            }
+            let mut database = 0;
             loop {
                 let entry_type = self.read_byte().await;
 
@@ -71,7 +255,10 @@ impl<R: AsyncRead + AsyncSeekExt + Unpin + Send> RdbFileReader<R> {
                     }
                     Ok(0xFE) => {
                         // db selector
-                        let _db = self.read_byte().await;
+                        match self.read_size().await {
+                            Ok(index) => database = index,
+                            Err(_) => break,
+                        }
                         continue;
                     }
                     Ok(0xFB) => {
@@ -80,44 +267,47 @@ impl<R: AsyncRead + AsyncSeekExt + Unpin + Send> RdbFileReader<R> {
                         let _expires_hash_table_size = self.read_size().await;
                         continue;
                     }
-                    Ok(0x00) => {
+                    Ok(FUNCTION) => {
+                        // function library
+                        match self.read_string().await {
+                            Ok(code) => yield Record::Library(code),
+                            Err(_) => break,
+                        }
+                    }
+                    Ok(value_type @ (STRING | SORTED_SET)) => {
                         // entry without expiration
-                        if let (Ok(key), Ok(value)) = (self.read_string().await, self.read_string().await) {
-                            yield Entry {
-                                key,
-                                value,
-                                expiry: None,
-                            };
+                        match self.read_entry(value_type, None).await {
+                            Ok(entry) => yield Record::Entry(database, entry),
+                            Err(_) => break,
                         }
                     }
                     Ok(0xFC) => {
                         // entry with milliseconds expiry
-                        if let (Ok(expiry_millis), Ok(_encoding), Ok(key), Ok(value)) = (self.read_expiry_in_millis().await, self.read_byte().await, self.read_string().await, self.read_string().await) {
-                            yield Entry {
-                                key,
-                                value,
-                                expiry: Some(Expiry {
-                                    epoch: expiry_millis,
-                                    unit: TimeUnit::Millisecond,
-                                }),
-                            };
+                        let expiry = self.read_expiry_in_millis().await;
+                        let value_type = self.read_byte().await;
+                        let (Ok(expiry), Ok(value_type)) = (expiry, value_type) else {
+                            break;
+                        };
+                        match self.read_entry(value_type, Some(expiry)).await {
+                            Ok(entry) => yield Record::Entry(database, entry),
+                            Err(_) => break,
                         }
                     }
                     Ok(0xFD) => {
-                        // entry with seconds expiry
-                        if let (Ok(expiry_secs), Ok(_encoding), Ok(key), Ok(value)) = (self.read_expiry_in_secs().await, self.read_byte().await, self.read_string().await, self.read_string().await) {
-                            yield Entry {
-                                key,
-                                value,
-                                expiry: Some(Expiry {
-                                    epoch: expiry_secs,
-                                    unit: TimeUnit::Millisecond, // read_expiry_in_secs already converts to milliseconds
-                                }),
-                            };
+                        // entry with seconds expiry, read_expiry_in_secs already converts to milliseconds
+                        let expiry = self.read_expiry_in_secs().await;
+                        let value_type = self.read_byte().await;
+                        let (Ok(expiry), Ok(value_type)) = (expiry, value_type) else {
+                            break;
+                        };
+                        match self.read_entry(value_type, Some(expiry)).await {
+                            Ok(entry) => yield Record::Entry(database, entry),
+                            Err(_) => break,
                         }
                     }
                     Ok(0xFF) => {
                         // end of file
+                        self.complete.store(true, Ordering::SeqCst);
                         break;
                     }
                     _ => {
@@ -129,6 +319,34 @@ impl<R: AsyncRead + AsyncSeekExt + Unpin + Send> RdbFileReader<R> {
         })
     }
 
+    async fn read_entry(&self, value_type: u8, expiry_in_millis: Option<u128>) -> Result<Entry> {
+        let key = self.read_string().await?;
+        let value = match value_type {
+            STRING => Data::String(self.read_string().await?),
+            SORTED_SET => Data::SortedSet(self.read_sorted_set().await?),
+            _ => anyhow::bail!("unsupported value type {value_type}"),
+        };
+        Ok(Entry {
+            key: key.into(),
+            value,
+            expiry: expiry_in_millis.map(|epoch| Expiry {
+                epoch,
+                unit: TimeUnit::Millisecond,
+            }),
+        })
+    }
+
+    async fn read_sorted_set(&self) -> Result<SortedSet> {
+        let size = self.read_size().await?;
+        let mut members = Vec::with_capacity(size);
+        for _ in 0..size {
+            let member = self.read_string().await?;
+            let score = self.read_bytes(8).await?;
+            members.push((member, f64::from_le_bytes(score.try_into().unwrap())));
+        }
+        Ok(members.into_iter().collect())
+    }
+
     async fn read_byte(&self) -> Result<u8> {
         let mut buffer = [0u8; 1];
         self.reader.lock().await.read_exact(&mut buffer).await?;
@@ -149,11 +367,12 @@ impl<R: AsyncRead + AsyncSeekExt + Unpin + Send> RdbFileReader<R> {
             0b00 => Ok(remaining_bites as usize),
             0b01 => {
                 let second_bytes = self.read_byte().await?;
-                Ok(((remaining_bites as usize) << 6) + second_bytes as usize)
+                Ok(((remaining_bites as usize) << 8) + second_bytes as usize)
             }
             0b10 => {
-                let next_four_bytes = self.read_bytes(5).await?;
-                Ok(next_four_bytes
+                let count = if remaining_bites == 0x01 { 8 } else { 4 };
+                let next_bytes = self.read_bytes(count).await?;
+                Ok(next_bytes
                     .iter()
                     .fold(0usize, |acc, &b| (acc << 8) | b as usize))
             }
@@ -196,8 +415,19 @@ impl<R: AsyncRead + AsyncSeekExt + Unpin + Send> RdbFileReader<R> {
 mod specs_for_load {
     use futures::StreamExt;
     use std::io::Cursor;
+    use std::sync::Arc;
+
+    use crate::clock::SystemClock;
+    use crate::repository::Data;
+    use crate::repository::Databases;
+    use crate::repository::Entry;
+    use crate::repository::SortedSet;
+    use crate::scripting::Libraries;
 
     use super::RdbFileReader;
+    use super::Record;
+    use super::load;
+    use super::serialize;
 
     #[tokio::test]
     async fn sut_parses_entries_of_rdb_correctly() {
@@ -209,12 +439,95 @@ mod specs_for_load {
         let sut = RdbFileReader::new(cursor);
 
         // Act
-        let entries = sut.entries().await.collect::<Vec<_>>().await;
+        let entries = sut.records().await.collect::<Vec<_>>().await;
 
         // Assert
         insta::assert_debug_snapshot!(entries);
     }
 
+    #[tokio::test]
+    async fn sut_reads_back_serialized_databases_and_libraries() {
+        // Arrange
+        let databases = Databases::in_memory(2, Arc::new(SystemClock));
+        let sorted_set: SortedSet = [("a".to_string(), 1.5), ("b".to_string(), -2.0)]
+            .into_iter()
+            .collect();
+        databases
+            .get(1)
+            .unwrap()
+            .set(Entry {
+                key: "zset".into(),
+                value: Data::SortedSet(sorted_set),
+                expiry: None,
+            })
+            .await;
+        let libraries = Libraries::default();
+        let code = "#!lua name=mylib\nredis.register_function('f', function() return 1 end)";
+        libraries.load(code, false).unwrap();
+        let bytes = serialize(&databases, &libraries, &SystemClock).await;
+        let sut = RdbFileReader::new(Cursor::new(bytes));
+
+        // Act
+        let records = sut.records().await.collect::<Vec<_>>().await;
+
+        // Assert
+        let [Record::Library(library), Record::Entry(1, entry)] = records.as_slice() else {
+            panic!("unexpected records {records:?}");
+        };
+        assert_eq!(library, code);
+        assert_eq!(&*entry.key, "zset");
+        let Data::SortedSet(sorted_set) = &entry.value else {
+            panic!("expected a sorted set but got {:?}", entry.value);
+        };
+        let members: Vec<(String, f64)> = sorted_set
+            .iter()
+            .map(|(member, score)| (member.to_string(), score))
+            .collect();
+        assert_eq!(
+            members,
+            vec![("b".to_string(), -2.0), ("a".to_string(), 1.5)]
+        );
+    }
+
+    #[rstest::rstest]
+    #[case(0)]
+    #[case(5)]
+    #[case(40)]
+    #[case(sample_rdb().len() - 9)]
+    #[tokio::test]
+    async fn sut_fails_to_load_snapshot_which_breaks_off(#[case] length: usize) {
+        // Arrange
+        let databases = Databases::in_memory(1, Arc::new(SystemClock));
+        let libraries = Libraries::default();
+        let data = Cursor::new(&sample_rdb()[..length]);
+
+        // Act
+        let actual = load(data, &databases, &libraries, &SystemClock).await;
+
+        // Assert
+        assert!(actual.is_err());
+    }
+
+    #[tokio::test]
+    async fn sut_loads_snapshot_up_to_its_end_of_file_mark() {
+        // Arrange
+        let databases = Databases::in_memory(1, Arc::new(SystemClock));
+        let libraries = Libraries::default();
+
+        // Act
+        let actual = load(
+            Cursor::new(sample_rdb()),
+            &databases,
+            &libraries,
+            &SystemClock,
+        )
+        .await;
+
+        // Assert
+        assert!(actual.is_ok());
+        assert!(databases.get(0).unwrap().exists("foobar").await);
+    }
+
     fn sample_rdb() -> &'static [u8] {
         &[
             // header, REDIS0011 ...............................................................
@@ -241,3 +554,19 @@ mod specs_for_load {
         ]
     }
 }
+
+#[cfg(test)]
+mod specs_for_crc64 {
+    use super::crc64;
+
+    #[rstest::rstest]
+    #[case(b"", 0)]
+    #[case(b"123456789", 0xe9c6d914c4b8d9ca)]
+    fn sut_computes_checksum_as_redis_does(#[case] bytes: &[u8], #[case] expected: u64) {
+        // Act
+        let actual = crc64(bytes);
+
+        // Assert
+        assert_eq!(actual, expected);
+    }
+}
@@ -0,0 +1,299 @@
+use std::collections::BTreeMap;
+use std::sync::Mutex;
+use std::sync::atomic::AtomicU64;
+use std::sync::atomic::Ordering;
+use std::time::Duration;
+use std::time::Instant;
+use std::time::SystemTime;
+use std::time::UNIX_EPOCH;
+
+use crate::replication::random_id;
+use crate::resp::Value;
+
+/// Commands made of subcommands, which are counted apart like `config|get`.
+const CONTAINERS: [&str; 5] = ["client", "config", "function", "pubsub", "script"];
+
+/// How often a command ran, for how long, and how often it was refused or failed.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct CommandStats {
+    pub calls: u64,
+    pub usec: u64,
+    pub rejected_calls: u64,
+    pub failed_calls: u64,
+}
+
+/// The counters reported by INFO, since the server started.
+pub struct Stats {
+    started: Instant,
+    run_id: String,
+    connections_received: AtomicU64,
+    connected_clients: AtomicU64,
+    commands_processed: AtomicU64,
+    net_input_bytes: AtomicU64,
+    net_output_bytes: AtomicU64,
+    /// Writes since the dataset was last saved.
+    changes: AtomicU64,
+    /// When the dataset was last saved, in seconds since the epoch.
+    last_save: AtomicU64,
+    commands: Mutex<BTreeMap<String, CommandStats>>,
+    /// Error replies by their code, such as `ERR` or `WRONGTYPE`.
+    errors: Mutex<BTreeMap<String, u64>>,
+}
+
+impl Default for Stats {
+    fn default() -> Self {
+        Self {
+            started: Instant::now(),
+            run_id: random_id(),
+            connections_received: AtomicU64::new(0),
+            connected_clients: AtomicU64::new(0),
+            commands_processed: AtomicU64::new(0),
+            net_input_bytes: AtomicU64::new(0),
+            net_output_bytes: AtomicU64::new(0),
+            changes: AtomicU64::new(0),
+            last_save: AtomicU64::new(now_in_seconds()),
+            commands: Mutex::new(BTreeMap::new()),
+            errors: Mutex::new(BTreeMap::new()),
+        }
+    }
+}
+
+impl Stats {
+    pub fn run_id(&self) -> &str {
+        &self.run_id
+    }
+
+    pub fn uptime(&self) -> Duration {
+        self.started.elapsed()
+    }
+
+    pub fn connect(&self) {
+        self.connections_received.fetch_add(1, Ordering::Relaxed);
+        self.connected_clients.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn disconnect(&self) {
+        self.connected_clients.fetch_sub(1, Ordering::Relaxed);
+    }
+
+    pub fn connections_received(&self) -> u64 {
+        self.connections_received.load(Ordering::Relaxed)
+    }
+
+    pub fn connected_clients(&self) -> u64 {
+        self.connected_clients.load(Ordering::Relaxed)
+    }
+
+    pub fn received(&self, bytes: usize) {
+        self.net_input_bytes
+            .fetch_add(bytes as u64, Ordering::Relaxed);
+    }
+
+    pub fn sent(&self, bytes: usize) {
+        self.net_output_bytes
+            .fetch_add(bytes as u64, Ordering::Relaxed);
+    }
+
+    pub fn net_input_bytes(&self) -> u64 {
+        self.net_input_bytes.load(Ordering::Relaxed)
+    }
+
+    pub fn net_output_bytes(&self) -> u64 {
+        self.net_output_bytes.load(Ordering::Relaxed)
+    }
+
+    /// Counts a command which ran for `elapsed` and replied `reply`.
+    pub fn record(&self, request: &Value, elapsed: Duration, reply: &Value) {
+        self.commands_processed.fetch_add(1, Ordering::Relaxed);
+        let mut commands = self.commands.lock().unwrap();
+        let stats = commands.entry(command_name(request)).or_default();
+        stats.calls += 1;
+        stats.usec += elapsed.as_micros() as u64;
+        if matches!(reply, Value::Error(_)) {
+            stats.failed_calls += 1;
+        }
+    }
+
+    /// Counts a command refused before it ran.
+    pub fn reject(&self, request: &Value) {
+        let mut commands = self.commands.lock().unwrap();
+        commands
+            .entry(command_name(request))
+            .or_default()
+            .rejected_calls += 1;
+    }
+
+    pub fn commands_processed(&self) -> u64 {
+        self.commands_processed.load(Ordering::Relaxed)
+    }
+
+    pub fn commands(&self) -> BTreeMap<String, CommandStats> {
+        self.commands.lock().unwrap().clone()
+    }
+
+    /// Counts an error replied to a client by its code.
+    pub fn error(&self, message: &str) {
+        let code = message.split_whitespace().next().unwrap_or_default();
+        *self
+            .errors
+            .lock()
+            .unwrap()
+            .entry(code.to_string())
+            .or_default() += 1;
+    }
+
+    pub fn errors(&self) -> BTreeMap<String, u64> {
+        self.errors.lock().unwrap().clone()
+    }
+
+    /// Counts a write to the dataset.
+    pub fn changed(&self) {
+        self.changes.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn changes(&self) -> u64 {
+        self.changes.load(Ordering::Relaxed)
+    }
+
+    /// Records the dataset was saved, with no change since.
+    pub fn saved(&self) {
+        self.changes.store(0, Ordering::Relaxed);
+        self.last_save.store(now_in_seconds(), Ordering::Relaxed);
+    }
+
+    pub fn last_save(&self) -> u64 {
+        self.last_save.load(Ordering::Relaxed)
+    }
+}
+
+fn now_in_seconds() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |now| now.as_secs())
+}
+
+/// The name a command is counted under, lowercase and with its subcommand if any.
+fn command_name(request: &Value) -> String {
+    let Value::Array(array) = request else {
+        return String::new();
+    };
+    let name = match array.first() {
+        Some(Value::BulkString(name)) => name.to_lowercase(),
+        _ => return String::new(),
+    };
+    match array.get(1) {
+        Some(Value::BulkString(sub)) if CONTAINERS.contains(&name.as_str()) => {
+            format!("{name}|{}", sub.to_lowercase())
+        }
+        _ => name,
+    }
+}
+
+/// The CPU time spent by the process in the kernel and in user space, if the operating system
+/// tells.
+pub fn cpu() -> (Duration, Duration) {
+    // The clock ticks of Linux are always a hundredth of a second to user space.
+    const TICK: Duration = Duration::from_millis(10);
+    let Ok(stat) = std::fs::read_to_string("/proc/self/stat") else {
+        return (Duration::ZERO, Duration::ZERO);
+    };
+    // The process name may contain spaces, but is the only field in parentheses.
+    let fields: Vec<&str> = stat
+        .rsplit_once(')')
+        .map_or("", |(_, fields)| fields)
+        .split_whitespace()
+        .collect();
+    let ticks = |index: usize| {
+        fields
+            .get(index)
+            .and_then(|ticks| ticks.parse::<u32>().ok())
+            .unwrap_or(0)
+    };
+    // utime and stime are the 14th and 15th fields, counting the pid and the name.
+    (TICK * ticks(12), TICK * ticks(11))
+}
+
+#[cfg(test)]
+mod specs_for_stats {
+    use std::time::Duration;
+
+    use crate::resp::Value;
+
+    use super::CommandStats;
+    use super::Stats;
+
+    fn request(arguments: &[&str]) -> Value {
+        Value::Array(
+            arguments
+                .iter()
+                .map(|argument| Value::BulkString(argument.to_string()))
+                .collect(),
+        )
+    }
+
+    #[test]
+    fn sut_counts_calls_by_command_and_subcommand() {
+        // Arrange
+        let stats = Stats::default();
+        let ok = Value::SimpleString("OK".to_string());
+        let error = Value::Error("ERR syntax error".to_string());
+
+        // Act
+        stats.record(
+            &request(&["SET", "foo", "bar"]),
+            Duration::from_micros(3),
+            &ok,
+        );
+        stats.record(
+            &request(&["set", "foo", "bar", "XX"]),
+            Duration::from_micros(5),
+            &error,
+        );
+        stats.record(&request(&["CONFIG", "GET", "dir"]), Duration::ZERO, &ok);
+        stats.reject(&request(&["SET", "foo", "baz"]));
+
+        // Assert
+        let commands = stats.commands();
+        let set = CommandStats {
+            calls: 2,
+            usec: 8,
+            rejected_calls: 1,
+            failed_calls: 1,
+        };
+        assert_eq!(commands["set"], set);
+        assert_eq!(commands["config|get"].calls, 1);
+        assert_eq!(stats.commands_processed(), 3);
+    }
+
+    #[test]
+    fn sut_counts_errors_by_code() {
+        // Arrange
+        let stats = Stats::default();
+
+        // Act
+        stats.error("ERR syntax error");
+        stats.error("WRONGTYPE Operation against a key holding the wrong kind of value");
+        stats.error("ERR unknown command");
+
+        // Assert
+        let errors = stats.errors();
+        assert_eq!(errors["ERR"], 2);
+        assert_eq!(errors["WRONGTYPE"], 1);
+    }
+
+    #[test]
+    fn sut_forgets_changes_once_saved() {
+        // Arrange
+        let stats = Stats::default();
+        stats.changed();
+        stats.changed();
+        let changes = stats.changes();
+
+        // Act
+        stats.saved();
+
+        // Assert
+        assert_eq!(changes, 2);
+        assert_eq!(stats.changes(), 0);
+    }
+}
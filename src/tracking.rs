@@ -0,0 +1,337 @@
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::sync::Weak;
+
+use crate::command::session::Session;
+use crate::resp::Value;
+
+const INVALIDATE_CHANNEL: &str = "__redis__:invalidate";
+
+/// How a client asked to be told about modified keys with CLIENT TRACKING ON.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TrackingOptions {
+    /// The client receiving the invalidations instead of the tracking one.
+    pub redirect: Option<u64>,
+    /// Whether every key matching the prefixes is invalidated, rather than only those read.
+    pub broadcast: bool,
+    pub prefixes: Vec<String>,
+    pub opt_in: bool,
+    pub opt_out: bool,
+    /// Whether keys modified by the client itself are left out.
+    pub no_loop: bool,
+}
+
+struct Tracker {
+    options: TrackingOptions,
+    session: Weak<Session>,
+}
+
+/// The clients with tracking enabled, and which of them read each key, so they can be sent
+/// invalidations once the key is modified.
+#[derive(Default)]
+pub struct Tracking {
+    /// Every connected client by its ID, as invalidations may be redirected to any of them.
+    sessions: Mutex<HashMap<u64, Weak<Session>>>,
+    trackers: Mutex<HashMap<u64, Tracker>>,
+    keys: Mutex<HashMap<String, HashSet<u64>>>,
+}
+
+impl Tracking {
+    pub fn register(&self, session: &Arc<Session>) {
+        self.sessions
+            .lock()
+            .unwrap()
+            .insert(session.id(), Arc::downgrade(session));
+    }
+
+    /// Drops everything about a client which disconnected.
+    pub fn forget(&self, id: u64) {
+        self.sessions.lock().unwrap().remove(&id);
+        self.disable(id);
+    }
+
+    /// How many clients have tracking enabled.
+    pub fn count(&self) -> usize {
+        self.trackers.lock().unwrap().len()
+    }
+
+    pub fn exists(&self, id: u64) -> bool {
+        self.session(id).is_some()
+    }
+
+    pub fn options(&self, id: u64) -> Option<TrackingOptions> {
+        self.trackers
+            .lock()
+            .unwrap()
+            .get(&id)
+            .map(|tracker| tracker.options.clone())
+    }
+
+    /// Enables tracking for the client, or updates its options when already enabled, the
+    /// broadcast prefixes adding up to those given before.
+    pub fn enable(&self, session: &Arc<Session>, mut options: TrackingOptions) {
+        let mut trackers = self.trackers.lock().unwrap();
+        if let Some(tracker) = trackers.get(&session.id()) {
+            let mut prefixes = tracker.options.prefixes.clone();
+            prefixes.append(&mut options.prefixes);
+            options.prefixes = prefixes;
+        }
+        trackers.insert(
+            session.id(),
+            Tracker {
+                options,
+                session: Arc::downgrade(session),
+            },
+        );
+    }
+
+    pub fn disable(&self, id: u64) {
+        self.trackers.lock().unwrap().remove(&id);
+    }
+
+    /// Remembers the key was read by the client, unless it does not track keys this way or
+    /// opted the command out.
+    pub fn remember(&self, session: &Session, key: &str) {
+        let Some(options) = self.options(session.id()) else {
+            return;
+        };
+        let caching = session.caching();
+        if options.broadcast
+            || (options.opt_in && caching != Some(true))
+            || (options.opt_out && caching == Some(false))
+        {
+            return;
+        }
+        self.keys
+            .lock()
+            .unwrap()
+            .entry(key.to_string())
+            .or_default()
+            .insert(session.id());
+    }
+
+    /// Sends an invalidation of the key to the clients which read it since it was last
+    /// modified, and to those broadcasting a prefix of it. `origin` is the client which
+    /// modified the key, if any.
+    pub fn invalidate(&self, key: &str, origin: Option<u64>) {
+        let readers = self.keys.lock().unwrap().remove(key).unwrap_or_default();
+        let trackers = self.trackers.lock().unwrap();
+        for (id, tracker) in trackers.iter() {
+            let options = &tracker.options;
+            let interested = if options.broadcast {
+                options.prefixes.is_empty()
+                    || options
+                        .prefixes
+                        .iter()
+                        .any(|prefix| key.starts_with(prefix.as_str()))
+            } else {
+                readers.contains(id)
+            };
+            if interested && !(options.no_loop && origin == Some(*id)) {
+                self.deliver(
+                    tracker,
+                    Value::Array(vec![Value::BulkString(key.to_string())]),
+                );
+            }
+        }
+    }
+
+    /// Tells every tracking client to drop its whole cache, once the databases are flushed.
+    pub fn invalidate_all(&self) {
+        self.keys.lock().unwrap().clear();
+        for tracker in self.trackers.lock().unwrap().values() {
+            self.deliver(tracker, Value::NullArray);
+        }
+    }
+
+    /// Pushes the invalidation to the client itself when it speaks RESP3, or as a message of
+    /// `__redis__:invalidate` to the client it redirects to, as long as that one subscribed.
+    fn deliver(&self, tracker: &Tracker, keys: Value) {
+        let target = match tracker.options.redirect {
+            Some(redirect) => self.session(redirect),
+            None => tracker.session.upgrade(),
+        };
+        let Some(target) = target else {
+            return;
+        };
+        if target.protocol() == 3 {
+            target.subscriber.push(Value::Push(vec![
+                Value::BulkString("invalidate".to_string()),
+                keys,
+            ]));
+        } else if tracker.options.redirect.is_some() && target.subscriber.is_subscribed() {
+            target.subscriber.push(Value::Array(vec![
+                Value::BulkString("message".to_string()),
+                Value::BulkString(INVALIDATE_CHANNEL.to_string()),
+                keys,
+            ]));
+        }
+    }
+
+    fn session(&self, id: u64) -> Option<Arc<Session>> {
+        self.sessions
+            .lock()
+            .unwrap()
+            .get(&id)
+            .and_then(Weak::upgrade)
+    }
+}
+
+#[cfg(test)]
+mod specs_for_tracking {
+    use std::sync::Arc;
+
+    use crate::command::session::Session;
+    use crate::pubsub::Broker;
+    use crate::pubsub::Scope;
+    use crate::resp::Value;
+
+    use super::Tracking;
+    use super::TrackingOptions;
+
+    fn session(protocol: u8) -> Arc<Session> {
+        let session = Arc::new(Session::new(8));
+        session.set_protocol(protocol);
+        session
+    }
+
+    fn invalidation(keys: Value) -> Value {
+        Value::Push(vec![Value::BulkString("invalidate".to_string()), keys])
+    }
+
+    #[tokio::test]
+    async fn sut_invalidates_keys_read_once() {
+        // Arrange
+        let sut = Tracking::default();
+        let client = session(3);
+        sut.register(&client);
+        sut.enable(&client, TrackingOptions::default());
+        sut.remember(&client, "foo");
+
+        // Act
+        sut.invalidate("foo", None);
+        sut.invalidate("foo", None);
+        sut.invalidate("bar", None);
+
+        // Assert
+        let mut messages = client.subscriber.messages().await;
+        let expected = invalidation(Value::Array(vec![Value::BulkString("foo".to_string())]));
+        assert_eq!(messages.try_recv().unwrap(), expected);
+        assert!(messages.try_recv().is_err());
+    }
+
+    #[rstest::rstest]
+    #[case(vec![], "anything", true)]
+    #[case(vec!["user:".to_string()], "user:1", true)]
+    #[case(vec!["user:".to_string()], "order:1", false)]
+    #[tokio::test]
+    async fn sut_broadcasts_keys_matching_prefixes(
+        #[case] prefixes: Vec<String>,
+        #[case] key: &str,
+        #[case] expected: bool,
+    ) {
+        // Arrange
+        let sut = Tracking::default();
+        let client = session(3);
+        sut.register(&client);
+        let options = TrackingOptions {
+            broadcast: true,
+            prefixes,
+            ..TrackingOptions::default()
+        };
+        sut.enable(&client, options);
+
+        // Act
+        sut.invalidate(key, None);
+
+        // Assert
+        let actual = client.subscriber.messages().await.try_recv().is_ok();
+        assert_eq!(actual, expected);
+    }
+
+    #[tokio::test]
+    async fn sut_leaves_out_keys_modified_by_the_client_with_no_loop() {
+        // Arrange
+        let sut = Tracking::default();
+        let client = session(3);
+        sut.register(&client);
+        let options = TrackingOptions {
+            broadcast: true,
+            no_loop: true,
+            ..TrackingOptions::default()
+        };
+        sut.enable(&client, options);
+
+        // Act
+        sut.invalidate("foo", Some(client.id()));
+
+        // Assert
+        assert!(client.subscriber.messages().await.try_recv().is_err());
+    }
+
+    #[rstest::rstest]
+    #[case(true, None, false)]
+    #[case(true, Some(true), true)]
+    #[case(false, None, true)]
+    #[case(false, Some(false), false)]
+    #[tokio::test]
+    async fn sut_remembers_keys_as_the_client_opted(
+        #[case] opt_in: bool,
+        #[case] caching: Option<bool>,
+        #[case] expected: bool,
+    ) {
+        // Arrange
+        let sut = Tracking::default();
+        let client = session(3);
+        sut.register(&client);
+        let options = TrackingOptions {
+            opt_in,
+            opt_out: !opt_in,
+            ..TrackingOptions::default()
+        };
+        sut.enable(&client, options);
+        if let Some(caching) = caching {
+            client.set_caching(caching);
+        }
+
+        // Act
+        sut.remember(&client, "foo");
+
+        // Assert
+        sut.invalidate("foo", None);
+        let actual = client.subscriber.messages().await.try_recv().is_ok();
+        assert_eq!(actual, expected);
+    }
+
+    #[tokio::test]
+    async fn sut_redirects_invalidations_to_subscribed_client() {
+        // Arrange
+        let sut = Tracking::default();
+        let client = session(2);
+        let target = session(2);
+        sut.register(&client);
+        sut.register(&target);
+        Broker::default().subscribe(Scope::Channel, "__redis__:invalidate", &target.subscriber);
+        let options = TrackingOptions {
+            redirect: Some(target.id()),
+            ..TrackingOptions::default()
+        };
+        sut.enable(&client, options);
+        sut.remember(&client, "foo");
+
+        // Act
+        sut.invalidate_all();
+
+        // Assert
+        let actual = target.subscriber.messages().await.try_recv().unwrap();
+        let expected = Value::Array(vec![
+            Value::BulkString("message".to_string()),
+            Value::BulkString("__redis__:invalidate".to_string()),
+            Value::NullArray,
+        ]);
+        assert_eq!(actual, expected);
+        assert!(client.subscriber.messages().await.try_recv().is_err());
+    }
+}
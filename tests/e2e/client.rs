@@ -68,14 +68,7 @@ impl RedisClient {
     }
 
     pub async fn keys(&self, pattern: &str) -> String {
-        let str = format!(
-            "*2\r\n$4\r\nKEYS\r\n${}\r\n\"{}\"\r\n",
-            pattern.len() + 2,
-            pattern,
-        );
-        let buf = str.as_bytes();
-        self.write_to_stream(buf).await;
-        self.read_from_stream().await
+        self.send(&["KEYS", pattern]).await
     }
 
     pub async fn info_replication(&self) -> String {
@@ -84,6 +77,246 @@ impl RedisClient {
         self.read_from_stream().await
     }
 
+    /// Sends INFO with `sections`, reading its reply however many reads it takes.
+    pub async fn info(&self, sections: &[&str]) -> String {
+        let mut str = format!("*{}\r\n$4\r\nINFO\r\n", sections.len() + 1);
+        for section in sections {
+            str.push_str(&format!("${}\r\n{}\r\n", section.len(), section));
+        }
+        self.write_to_stream(str.as_bytes()).await;
+        let mut reply = self.read_from_stream().await;
+        let (header, _) = reply.split_once("\r\n").unwrap();
+        let size: usize = header.trim_start_matches('$').parse().unwrap();
+        let length = header.len() + size + 4;
+        while reply.len() < length {
+            reply.push_str(&self.read_from_stream().await);
+        }
+        reply
+    }
+
+    pub async fn geoadd(&self, key: &str, members: &[(f64, f64, &str)]) -> String {
+        let coordinates: Vec<(String, String)> = members
+            .iter()
+            .map(|(longitude, latitude, _)| (longitude.to_string(), latitude.to_string()))
+            .collect();
+        let mut args = vec!["GEOADD", key];
+        for ((longitude, latitude), (_, _, member)) in coordinates.iter().zip(members) {
+            args.extend([longitude.as_str(), latitude.as_str(), member]);
+        }
+        self.send(&args).await
+    }
+
+    pub async fn geodist(&self, key: &str, member1: &str, member2: &str, unit: &str) -> String {
+        self.send(&["GEODIST", key, member1, member2, unit]).await
+    }
+
+    pub async fn geopos(&self, key: &str, members: &[&str]) -> String {
+        self.send(&[&["GEOPOS", key], members].concat()).await
+    }
+
+    pub async fn geohash(&self, key: &str, members: &[&str]) -> String {
+        self.send(&[&["GEOHASH", key], members].concat()).await
+    }
+
+    pub async fn geosearch(&self, key: &str, options: &[&str]) -> String {
+        self.send(&[&["GEOSEARCH", key], options].concat()).await
+    }
+
+    pub async fn geosearchstore(
+        &self,
+        destination: &str,
+        source: &str,
+        options: &[&str],
+    ) -> String {
+        self.send(&[&["GEOSEARCHSTORE", destination, source], options].concat())
+            .await
+    }
+
+    pub async fn del(&self, keys: &[&str]) -> String {
+        self.send(&[&["DEL"], keys].concat()).await
+    }
+
+    pub async fn unlink(&self, keys: &[&str]) -> String {
+        self.send(&[&["UNLINK"], keys].concat()).await
+    }
+
+    pub async fn exists(&self, keys: &[&str]) -> String {
+        self.send(&[&["EXISTS"], keys].concat()).await
+    }
+
+    pub async fn key_type(&self, key: &str) -> String {
+        self.send(&["TYPE", key]).await
+    }
+
+    pub async fn rename(&self, key: &str, new_key: &str) -> String {
+        self.send(&["RENAME", key, new_key]).await
+    }
+
+    pub async fn copy(&self, source: &str, destination: &str) -> String {
+        self.send(&["COPY", source, destination]).await
+    }
+
+    pub async fn dbsize(&self) -> String {
+        self.send(&["DBSIZE"]).await
+    }
+
+    pub async fn expire(&self, key: &str, seconds: i64, options: &[&str]) -> String {
+        let seconds = seconds.to_string();
+        self.send(&[&["EXPIRE", key, &seconds], options].concat())
+            .await
+    }
+
+    pub async fn pexpireat(&self, key: &str, epoch: u128) -> String {
+        self.send(&["PEXPIREAT", key, &epoch.to_string()]).await
+    }
+
+    pub async fn ttl(&self, key: &str) -> String {
+        self.send(&["TTL", key]).await
+    }
+
+    pub async fn pexpiretime(&self, key: &str) -> String {
+        self.send(&["PEXPIRETIME", key]).await
+    }
+
+    pub async fn persist(&self, key: &str) -> String {
+        self.send(&["PERSIST", key]).await
+    }
+
+    pub async fn scan(&self, cursor: &str, options: &[&str]) -> String {
+        self.send(&[&["SCAN", cursor], options].concat()).await
+    }
+
+    pub async fn zscan(&self, key: &str, cursor: &str) -> String {
+        self.send(&["ZSCAN", key, cursor]).await
+    }
+
+    pub async fn select(&self, database: usize) -> String {
+        self.send(&["SELECT", &database.to_string()]).await
+    }
+
+    pub async fn swapdb(&self, first: usize, second: usize) -> String {
+        self.send(&["SWAPDB", &first.to_string(), &second.to_string()])
+            .await
+    }
+
+    pub async fn move_key(&self, key: &str, database: usize) -> String {
+        self.send(&["MOVE", key, &database.to_string()]).await
+    }
+
+    pub async fn flushdb(&self) -> String {
+        self.send(&["FLUSHDB"]).await
+    }
+
+    pub async fn flushall(&self, options: &[&str]) -> String {
+        self.send(&[&["FLUSHALL"], options].concat()).await
+    }
+
+    pub async fn multi(&self) -> String {
+        self.send(&["MULTI"]).await
+    }
+
+    pub async fn exec(&self) -> String {
+        self.send(&["EXEC"]).await
+    }
+
+    pub async fn discard(&self) -> String {
+        self.send(&["DISCARD"]).await
+    }
+
+    pub async fn watch(&self, keys: &[&str]) -> String {
+        self.send(&[&["WATCH"], keys].concat()).await
+    }
+
+    pub async fn unwatch(&self) -> String {
+        self.send(&["UNWATCH"]).await
+    }
+
+    pub async fn eval(&self, script: &str, keys: &[&str], args: &[&str]) -> String {
+        let numkeys = keys.len().to_string();
+        self.send(&[&["EVAL", script, &numkeys], keys, args].concat())
+            .await
+    }
+
+    pub async fn evalsha(&self, sha: &str, keys: &[&str], args: &[&str]) -> String {
+        let numkeys = keys.len().to_string();
+        self.send(&[&["EVALSHA", sha, &numkeys], keys, args].concat())
+            .await
+    }
+
+    pub async fn script_load(&self, script: &str) -> String {
+        self.send(&["SCRIPT", "LOAD", script]).await
+    }
+
+    pub async fn script_kill(&self) -> String {
+        self.send(&["SCRIPT", "KILL"]).await
+    }
+
+    pub async fn function_load(&self, code: &str) -> String {
+        self.send(&["FUNCTION", "LOAD", code]).await
+    }
+
+    pub async fn fcall(&self, function: &str, keys: &[&str], args: &[&str]) -> String {
+        let numkeys = keys.len().to_string();
+        self.send(&[&["FCALL", function, &numkeys], keys, args].concat())
+            .await
+    }
+
+    pub async fn fcall_ro(&self, function: &str, keys: &[&str], args: &[&str]) -> String {
+        let numkeys = keys.len().to_string();
+        self.send(&[&["FCALL_RO", function, &numkeys], keys, args].concat())
+            .await
+    }
+
+    pub async fn save(&self) -> String {
+        self.send(&["SAVE"]).await
+    }
+
+    pub async fn subscribe(&self, channels: &[&str]) -> String {
+        self.send(&[&["SUBSCRIBE"], channels].concat()).await
+    }
+
+    pub async fn psubscribe(&self, patterns: &[&str]) -> String {
+        self.send(&[&["PSUBSCRIBE"], patterns].concat()).await
+    }
+
+    pub async fn unsubscribe(&self, channels: &[&str]) -> String {
+        self.send(&[&["UNSUBSCRIBE"], channels].concat()).await
+    }
+
+    pub async fn publish(&self, channel: &str, message: &str) -> String {
+        self.send(&["PUBLISH", channel, message]).await
+    }
+
+    pub async fn pubsub(&self, args: &[&str]) -> String {
+        self.send(&[&["PUBSUB"], args].concat()).await
+    }
+
+    /// Waits for whatever the server pushes next, such as a published message.
+    pub async fn hello(&self, protocol: &str) -> String {
+        self.send(&["HELLO", protocol]).await
+    }
+
+    pub async fn client(&self, args: &[&str]) -> String {
+        self.send(&[&["CLIENT"], args].concat()).await
+    }
+
+    pub async fn receive(&self) -> String {
+        self.read_from_stream().await
+    }
+
+    pub async fn send_raw(&self, args: &[&str]) -> String {
+        self.send(args).await
+    }
+
+    async fn send(&self, args: &[&str]) -> String {
+        let mut str = format!("*{}\r\n", args.len());
+        for arg in args {
+            str.push_str(&format!("${}\r\n{}\r\n", arg.len(), arg));
+        }
+        self.write_to_stream(str.as_bytes()).await;
+        self.read_from_stream().await
+    }
+
     async fn write_to_stream(&self, buf: &[u8]) {
         self.stream.lock().await.write_all(buf).await.unwrap();
     }
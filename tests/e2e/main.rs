@@ -1,10 +1,24 @@
 mod client;
 mod server;
+mod specs_for_cluster;
 mod specs_for_config;
+mod specs_for_databases;
 mod specs_for_echo;
+mod specs_for_expire;
+mod specs_for_functions;
+mod specs_for_geo;
 mod specs_for_get;
 mod specs_for_info;
 mod specs_for_keys;
+mod specs_for_keyspace;
+mod specs_for_notifications;
 mod specs_for_ping;
+mod specs_for_pubsub;
 mod specs_for_rdb;
+mod specs_for_replication;
+mod specs_for_scan;
+mod specs_for_scripting;
+mod specs_for_sentinel;
 mod specs_for_set;
+mod specs_for_tracking;
+mod specs_for_transaction;
@@ -5,8 +5,9 @@ use std::sync::Arc;
 
 use tokio::net::TcpListener;
 
+use codecrafters_redis::clock::SystemClock;
 use codecrafters_redis::config::Config;
-use codecrafters_redis::repository::InMemoryRepository;
+use codecrafters_redis::repository::Databases;
 use codecrafters_redis::runner::run;
 
 pub struct RedisServer {
@@ -19,14 +20,16 @@ impl RedisServer {
         Self::new_with_config(config).await
     }
 
-    pub async fn new_with_config(config: Config) -> Self {
+    pub async fn new_with_config(mut config: Config) -> Self {
         let listener = TcpListener::bind(SocketAddrV4::new(Ipv4Addr::LOCALHOST, 0))
             .await
             .unwrap();
         let address = listener.local_addr().unwrap();
-        let repository = Arc::new(InMemoryRepository::new());
+        config.server.port = address.port() as usize;
+        let clock = Arc::new(SystemClock);
+        let databases = Arc::new(Databases::in_memory(config.server.databases, clock.clone()));
         let config = Arc::new(config);
-        tokio::spawn(run(listener, repository, config));
+        tokio::spawn(run(listener, databases, config, clock));
         Self { address }
     }
 }
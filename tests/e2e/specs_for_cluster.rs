@@ -0,0 +1,147 @@
+use std::time::Duration;
+
+use codecrafters_redis::config::Config;
+use codecrafters_redis::config::Server;
+
+use crate::client::RedisClient;
+use crate::server::RedisServer;
+
+async fn node() -> RedisServer {
+    let config = Config {
+        server: Server {
+            cluster_enabled: true,
+            ..Server::default()
+        },
+        ..Config::default()
+    };
+    RedisServer::new_with_config(config).await
+}
+
+async fn eventually(client: &RedisClient, args: &[&str], expected: &str) -> String {
+    let mut actual = String::new();
+    for _ in 0..50 {
+        actual = client.send_raw(args).await;
+        if actual.contains(expected) {
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(50)).await;
+    }
+    actual
+}
+
+/// Two nodes which met, the first serving the lower half of the slots and the second the upper
+/// half.
+async fn cluster() -> ([RedisServer; 2], [RedisClient; 2]) {
+    let nodes = [node().await, node().await];
+    let clients = [
+        RedisClient::new(nodes[0].address).await,
+        RedisClient::new(nodes[1].address).await,
+    ];
+    clients[0]
+        .send_raw(&["CLUSTER", "ADDSLOTSRANGE", "0", "8191"])
+        .await;
+    clients[1]
+        .send_raw(&["CLUSTER", "ADDSLOTSRANGE", "8192", "16383"])
+        .await;
+    let port = nodes[1].address.port().to_string();
+    clients[0]
+        .send_raw(&["CLUSTER", "MEET", "127.0.0.1", &port])
+        .await;
+    for client in &clients {
+        eventually(client, &["CLUSTER", "INFO"], "cluster_state:ok").await;
+    }
+    (nodes, clients)
+}
+
+#[tokio::test]
+async fn sut_agrees_on_slots_once_nodes_met() {
+    // Arrange
+    let ([first, second], clients) = cluster().await;
+
+    // Act
+    let info = clients[1].send_raw(&["CLUSTER", "INFO"]).await;
+    let slots = clients[1].send_raw(&["CLUSTER", "SLOTS"]).await;
+    let myid = clients[0].send_raw(&["CLUSTER", "MYID"]).await;
+    let keyslot = clients[0].send_raw(&["CLUSTER", "KEYSLOT", "foo"]).await;
+
+    // Assert
+    assert!(info.contains("cluster_state:ok"), "{info}");
+    assert!(info.contains("cluster_known_nodes:2"), "{info}");
+    assert!(info.contains("cluster_size:2"), "{info}");
+    let id = myid.lines().nth(1).unwrap();
+    let (lower, upper) = (first.address.port(), second.address.port());
+    assert!(
+        slots.starts_with(&format!(
+            "*2\r\n*3\r\n:0\r\n:8191\r\n*4\r\n$9\r\n127.0.0.1\r\n:{lower}\r\n$40\r\n{id}\r\n"
+        )),
+        "{slots}"
+    );
+    assert!(
+        slots.contains(&format!(
+            "*3\r\n:8192\r\n:16383\r\n*4\r\n$9\r\n127.0.0.1\r\n:{upper}\r\n"
+        )),
+        "{slots}"
+    );
+    assert_eq!(keyslot, ":12182\r\n");
+}
+
+#[tokio::test]
+async fn sut_redirects_clients_to_the_node_serving_their_keys() {
+    // Arrange
+    let ([_, second], clients) = cluster().await;
+
+    // Act
+    let moved = clients[0].set("foo", "bar", None).await;
+    let served = clients[1].set("foo", "bar", None).await;
+    let crossslot = clients[1].send_raw(&["DEL", "foo", "baz"]).await;
+    let tagged = clients[1].send_raw(&["DEL", "{foo}.a", "{foo}.b"]).await;
+    let count = clients[1]
+        .send_raw(&["CLUSTER", "COUNTKEYSINSLOT", "12182"])
+        .await;
+
+    // Assert
+    let port = second.address.port();
+    assert_eq!(moved, format!("-MOVED 12182 127.0.0.1:{port}\r\n"));
+    assert_eq!(served, "+OK\r\n");
+    assert_eq!(
+        crossslot,
+        "-CROSSSLOT Keys in request don't hash to the same slot\r\n"
+    );
+    assert_eq!(tagged, ":0\r\n");
+    assert_eq!(count, ":1\r\n");
+}
+
+#[tokio::test]
+async fn sut_asks_clients_to_follow_keys_of_migrating_slot() {
+    // Arrange
+    let ([first, second], clients) = cluster().await;
+    let ids = [
+        clients[0].send_raw(&["CLUSTER", "MYID"]).await,
+        clients[1].send_raw(&["CLUSTER", "MYID"]).await,
+    ];
+    let [first_id, second_id] = ids.map(|id| id.lines().nth(1).unwrap().to_string());
+    clients[1].set("foo", "bar", None).await;
+    clients[0]
+        .send_raw(&["CLUSTER", "SETSLOT", "12182", "IMPORTING", &second_id])
+        .await;
+    clients[1]
+        .send_raw(&["CLUSTER", "SETSLOT", "12182", "MIGRATING", &first_id])
+        .await;
+
+    // Act
+    let present = clients[1].get("foo").await;
+    let missing = clients[1].get("{foo}.new").await;
+    let moved = clients[0].set("{foo}.new", "value", None).await;
+    clients[0].send_raw(&["ASKING"]).await;
+    let asked = clients[0].set("{foo}.new", "value", None).await;
+
+    // Assert
+    let port = second.address.port();
+    assert_eq!(present, "$3\r\nbar\r\n");
+    assert_eq!(
+        missing,
+        format!("-ASK 12182 127.0.0.1:{}\r\n", first.address.port())
+    );
+    assert_eq!(moved, format!("-MOVED 12182 127.0.0.1:{port}\r\n"));
+    assert_eq!(asked, "+OK\r\n");
+}
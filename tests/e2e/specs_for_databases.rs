@@ -0,0 +1,120 @@
+use crate::client::RedisClient;
+use crate::server::RedisServer;
+
+#[tokio::test]
+async fn sut_keeps_keys_of_each_database_apart_when_client_sends_select() {
+    // Arrange
+    let server = RedisServer::new().await;
+    let client = RedisClient::new(server.address).await;
+    client.set("foo", "bar", None).await;
+
+    // Act
+    let actual = client.select(1).await;
+
+    // Assert
+    assert_eq!(actual, "+OK\r\n");
+    assert_eq!(client.get("foo").await, "$-1\r\n");
+    client.select(0).await;
+    assert_eq!(client.get("foo").await, "$3\r\nbar\r\n");
+}
+
+#[tokio::test]
+async fn sut_keeps_selected_database_per_connection() {
+    // Arrange
+    let server = RedisServer::new().await;
+    let client = RedisClient::new(server.address).await;
+    let other = RedisClient::new(server.address).await;
+    client.select(1).await;
+
+    // Act
+    client.set("foo", "bar", None).await;
+
+    // Assert
+    assert_eq!(other.get("foo").await, "$-1\r\n");
+}
+
+#[tokio::test]
+async fn sut_responds_error_when_client_selects_database_out_of_range() {
+    // Arrange
+    let server = RedisServer::new().await;
+    let client = RedisClient::new(server.address).await;
+
+    // Act
+    let actual = client.select(16).await;
+
+    // Assert
+    assert_eq!(actual, "-ERR DB index is out of range\r\n");
+}
+
+#[tokio::test]
+async fn sut_moves_key_when_client_sends_move() {
+    // Arrange
+    let server = RedisServer::new().await;
+    let client = RedisClient::new(server.address).await;
+    client.set("foo", "bar", None).await;
+
+    // Act
+    let actual = client.move_key("foo", 2).await;
+
+    // Assert
+    assert_eq!(actual, ":1\r\n");
+    assert_eq!(client.dbsize().await, ":0\r\n");
+    client.select(2).await;
+    assert_eq!(client.get("foo").await, "$3\r\nbar\r\n");
+}
+
+#[tokio::test]
+async fn sut_swaps_databases_for_all_connections_when_client_sends_swapdb() {
+    // Arrange
+    let server = RedisServer::new().await;
+    let client = RedisClient::new(server.address).await;
+    let other = RedisClient::new(server.address).await;
+    client.set("foo", "bar", None).await;
+
+    // Act
+    let actual = client.swapdb(0, 1).await;
+
+    // Assert
+    assert_eq!(actual, "+OK\r\n");
+    assert_eq!(other.get("foo").await, "$-1\r\n");
+    other.select(1).await;
+    assert_eq!(other.get("foo").await, "$3\r\nbar\r\n");
+}
+
+#[tokio::test]
+async fn sut_flushes_only_selected_database_when_client_sends_flushdb() {
+    // Arrange
+    let server = RedisServer::new().await;
+    let client = RedisClient::new(server.address).await;
+    client.set("foo", "bar", None).await;
+    client.select(1).await;
+    client.set("foo", "bar", None).await;
+
+    // Act
+    let actual = client.flushdb().await;
+
+    // Assert
+    assert_eq!(actual, "+OK\r\n");
+    assert_eq!(client.dbsize().await, ":0\r\n");
+    client.select(0).await;
+    assert_eq!(client.dbsize().await, ":1\r\n");
+}
+
+#[tokio::test]
+async fn sut_flushes_every_database_when_client_sends_flushall() {
+    // Arrange
+    let server = RedisServer::new().await;
+    let client = RedisClient::new(server.address).await;
+    client.set("foo", "bar", None).await;
+    client.select(1).await;
+    client.set("foo", "bar", None).await;
+
+    // Act
+    let actual = client.flushall(&["ASYNC"]).await;
+
+    // Assert
+    assert_eq!(actual, "+OK\r\n");
+    assert_eq!(client.dbsize().await, ":0\r\n");
+    client.select(0).await;
+    assert_eq!(client.dbsize().await, ":0\r\n");
+}
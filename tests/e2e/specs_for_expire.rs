@@ -0,0 +1,113 @@
+use fake::Fake;
+use fake::faker::lorem::en::Word;
+
+use crate::client::RedisClient;
+use crate::server::RedisServer;
+
+#[tokio::test]
+async fn sut_sets_time_to_live_when_client_sends_expire() {
+    // Arrange
+    let server = RedisServer::new().await;
+    let client = RedisClient::new(server.address).await;
+    client.set("foo", Word().fake(), None).await;
+
+    // Act
+    let actual = client.expire("foo", 100, &[]).await;
+
+    // Assert
+    assert_eq!(actual, ":1\r\n");
+    assert_eq!(client.ttl("foo").await, ":100\r\n");
+}
+
+#[tokio::test]
+async fn sut_does_not_set_time_to_live_when_condition_is_not_met() {
+    // Arrange
+    let server = RedisServer::new().await;
+    let client = RedisClient::new(server.address).await;
+    client.set("foo", Word().fake(), None).await;
+
+    // Act
+    let actual = client.expire("foo", 100, &["XX"]).await;
+
+    // Assert
+    assert_eq!(actual, ":0\r\n");
+    assert_eq!(client.ttl("foo").await, ":-1\r\n");
+}
+
+#[tokio::test]
+async fn sut_responds_error_when_client_sends_incompatible_options() {
+    // Arrange
+    let server = RedisServer::new().await;
+    let client = RedisClient::new(server.address).await;
+
+    // Act
+    let actual = client.expire("foo", 100, &["GT", "LT"]).await;
+
+    // Assert
+    assert!(actual.starts_with("-ERR"));
+}
+
+#[tokio::test]
+async fn sut_deletes_key_when_expiry_is_in_the_past() {
+    // Arrange
+    let server = RedisServer::new().await;
+    let client = RedisClient::new(server.address).await;
+    client.set("foo", Word().fake(), None).await;
+
+    // Act
+    let actual = client.pexpireat("foo", 1).await;
+
+    // Assert
+    assert_eq!(actual, ":1\r\n");
+    assert_eq!(client.get("foo").await, "$-1\r\n");
+}
+
+#[tokio::test]
+async fn sut_responds_absolute_expiry_when_client_sends_pexpiretime() {
+    // Arrange
+    let server = RedisServer::new().await;
+    let client = RedisClient::new(server.address).await;
+    client.set("foo", Word().fake(), None).await;
+    client.pexpireat("foo", 33177117420000).await;
+
+    // Act
+    let actual = client.pexpiretime("foo").await;
+
+    // Assert
+    assert_eq!(actual, ":33177117420000\r\n");
+}
+
+#[rstest::rstest]
+#[case("foo", ":-1\r\n")]
+#[case("missing", ":-2\r\n")]
+#[tokio::test]
+async fn sut_removes_time_to_live_when_client_sends_persist(
+    #[case] key: &str,
+    #[case] expected: &str,
+) {
+    // Arrange
+    let server = RedisServer::new().await;
+    let client = RedisClient::new(server.address).await;
+    client.set("foo", Word().fake(), Some(100_000)).await;
+
+    // Act
+    client.persist(key).await;
+
+    // Assert
+    assert_eq!(client.ttl(key).await, expected);
+}
+
+#[tokio::test]
+async fn sut_evicts_expired_keys_without_them_being_accessed() {
+    // Arrange
+    let server = RedisServer::new().await;
+    let client = RedisClient::new(server.address).await;
+    client.set("foo", Word().fake(), Some(50)).await;
+    client.set("bar", Word().fake(), None).await;
+
+    // Act
+    tokio::time::sleep(std::time::Duration::from_millis(500)).await;
+
+    // Assert
+    assert_eq!(client.dbsize().await, ":1\r\n");
+}
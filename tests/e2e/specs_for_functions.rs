@@ -0,0 +1,99 @@
+use codecrafters_redis::config::Config;
+use codecrafters_redis::config::RdbConfig;
+use tempfile::tempdir;
+
+use crate::client::RedisClient;
+use crate::server::RedisServer;
+
+const LIBRARY: &str = "#!lua name=counter
+redis.register_function('incr', function(keys)
+    local next = (tonumber(redis.call('GET', keys[1])) or 0) + 1
+    redis.call('SET', keys[1], tostring(next))
+    return next
+end)
+redis.register_function{
+    function_name = 'peek',
+    callback = function(keys) return redis.call('GET', keys[1]) end,
+    flags = {'no-writes'},
+}";
+
+#[tokio::test]
+async fn sut_calls_function_of_loaded_library_when_client_sends_fcall() {
+    // Arrange
+    let server = RedisServer::new().await;
+    let client = RedisClient::new(server.address).await;
+    let loaded = client.function_load(LIBRARY).await;
+
+    // Act
+    let first = client.fcall("incr", &["hits"], &[]).await;
+    let second = client.fcall("incr", &["hits"], &[]).await;
+    let peeked = client.fcall_ro("peek", &["hits"], &[]).await;
+
+    // Assert
+    assert_eq!(loaded, "$7\r\ncounter\r\n");
+    assert_eq!(first, ":1\r\n");
+    assert_eq!(second, ":2\r\n");
+    assert_eq!(peeked, "$1\r\n2\r\n");
+}
+
+#[tokio::test]
+async fn sut_rejects_function_without_no_writes_flag_when_client_sends_fcall_ro() {
+    // Arrange
+    let server = RedisServer::new().await;
+    let client = RedisClient::new(server.address).await;
+    client.function_load(LIBRARY).await;
+
+    // Act
+    let actual = client.fcall_ro("incr", &["hits"], &[]).await;
+
+    // Assert
+    let expected = "-ERR Can not execute a script with write flag using *_ro command.\r\n";
+    assert_eq!(actual, expected);
+    assert_eq!(client.get("hits").await, "$-1\r\n");
+}
+
+#[tokio::test]
+async fn sut_calls_function_queued_in_transaction_when_client_sends_exec() {
+    // Arrange
+    let server = RedisServer::new().await;
+    let client = RedisClient::new(server.address).await;
+    let other = RedisClient::new(server.address).await;
+    client.function_load(LIBRARY).await;
+    client.multi().await;
+    assert_eq!(client.fcall("incr", &["hits"], &[]).await, "+QUEUED\r\n");
+    client.fcall("incr", &["hits"], &[]).await;
+
+    // Act
+    let actual = client.exec().await;
+
+    // Assert
+    assert_eq!(actual, "*2\r\n:1\r\n:2\r\n");
+    assert_eq!(other.ping().await, "+PONG\r\n");
+}
+
+#[tokio::test]
+async fn sut_keeps_functions_and_keys_when_restarted_from_saved_rdb() {
+    // Arrange
+    let rdb_directory = tempdir().unwrap();
+    let config = Config {
+        rdb: Some(RdbConfig {
+            directory: rdb_directory.path().to_string_lossy().to_string(),
+            filename: "dump.rdb".to_string(),
+        }),
+        ..Config::default()
+    };
+    let server = RedisServer::new_with_config(config.clone()).await;
+    let client = RedisClient::new(server.address).await;
+    client.function_load(LIBRARY).await;
+    client.fcall("incr", &["hits"], &[]).await;
+    let saved = client.save().await;
+
+    // Act
+    let restarted = RedisServer::new_with_config(config).await;
+    let client = RedisClient::new(restarted.address).await;
+    let actual = client.fcall("incr", &["hits"], &[]).await;
+
+    // Assert
+    assert_eq!(saved, "+OK\r\n");
+    assert_eq!(actual, ":2\r\n");
+}
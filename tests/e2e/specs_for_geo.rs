@@ -0,0 +1,162 @@
+use crate::client::RedisClient;
+use crate::server::RedisServer;
+
+async fn client_with_sicily() -> RedisClient {
+    let server = RedisServer::new().await;
+    let client = RedisClient::new(server.address).await;
+    client
+        .geoadd(
+            "Sicily",
+            &[
+                (13.361389, 38.115556, "Palermo"),
+                (15.087269, 37.502669, "Catania"),
+            ],
+        )
+        .await;
+    client
+}
+
+#[tokio::test]
+async fn sut_responds_number_of_added_members_when_client_sends_geoadd() {
+    // Arrange
+    let server = RedisServer::new().await;
+    let client = RedisClient::new(server.address).await;
+
+    // Act
+    let actual = client
+        .geoadd(
+            "Sicily",
+            &[
+                (13.361389, 38.115556, "Palermo"),
+                (15.087269, 37.502669, "Catania"),
+            ],
+        )
+        .await;
+
+    // Assert
+    let expected = ":2\r\n";
+    assert_eq!(actual, expected);
+}
+
+#[tokio::test]
+async fn sut_responds_error_when_client_sends_geoadd_with_invalid_coordinate() {
+    // Arrange
+    let server = RedisServer::new().await;
+    let client = RedisClient::new(server.address).await;
+
+    // Act
+    let actual = client.geoadd("Sicily", &[(200.0, 38.0, "Nowhere")]).await;
+
+    // Assert
+    let expected = "-ERR invalid longitude,latitude pair 200.000000,38.000000\r\n";
+    assert_eq!(actual, expected);
+}
+
+#[tokio::test]
+async fn sut_responds_distance_when_client_sends_geodist() {
+    // Arrange
+    let client = client_with_sicily().await;
+
+    // Act
+    let actual = client.geodist("Sicily", "Palermo", "Catania", "km").await;
+
+    // Assert
+    let expected = "$8\r\n166.2742\r\n";
+    assert_eq!(actual, expected);
+}
+
+#[tokio::test]
+async fn sut_responds_positions_when_client_sends_geopos() {
+    // Arrange
+    let client = client_with_sicily().await;
+
+    // Act
+    let actual = client.geopos("Sicily", &["Palermo", "NonExisting"]).await;
+
+    // Assert
+    let expected = "*2\r\n*2\r\n$18\r\n13.361389338970184\r\n$16\r\n38.1155563954963\r\n*-1\r\n";
+    assert_eq!(actual, expected);
+}
+
+#[tokio::test]
+async fn sut_responds_geohash_strings_when_client_sends_geohash() {
+    // Arrange
+    let client = client_with_sicily().await;
+
+    // Act
+    let actual = client.geohash("Sicily", &["Palermo", "Catania"]).await;
+
+    // Assert
+    let expected = "*2\r\n$11\r\nsqc8b49rny0\r\n$11\r\nsqdtr74hyu0\r\n";
+    assert_eq!(actual, expected);
+}
+
+#[tokio::test]
+async fn sut_responds_members_with_distance_when_client_sends_geosearch() {
+    // Arrange
+    let client = client_with_sicily().await;
+
+    // Act
+    let actual = client
+        .geosearch(
+            "Sicily",
+            &[
+                "FROMLONLAT",
+                "15",
+                "37",
+                "BYRADIUS",
+                "200",
+                "km",
+                "ASC",
+                "WITHDIST",
+            ],
+        )
+        .await;
+
+    // Assert
+    let expected =
+        "*2\r\n*2\r\n$7\r\nCatania\r\n$7\r\n56.4413\r\n*2\r\n$7\r\nPalermo\r\n$8\r\n190.4424\r\n";
+    assert_eq!(actual, expected);
+}
+
+#[tokio::test]
+async fn sut_stores_search_result_when_client_sends_geosearchstore() {
+    // Arrange
+    let client = client_with_sicily().await;
+
+    // Act
+    let actual = client
+        .geosearchstore(
+            "nearby",
+            "Sicily",
+            &[
+                "FROMMEMBER",
+                "Palermo",
+                "BYBOX",
+                "400",
+                "400",
+                "km",
+                "COUNT",
+                "1",
+            ],
+        )
+        .await;
+
+    // Assert
+    assert_eq!(actual, ":1\r\n");
+    let stored = client.geopos("nearby", &["Palermo"]).await;
+    assert!(stored.contains("13.361389338970184"));
+}
+
+#[tokio::test]
+async fn sut_responds_wrongtype_error_when_client_gets_geo_key() {
+    // Arrange
+    let client = client_with_sicily().await;
+
+    // Act
+    let actual = client.get("Sicily").await;
+
+    // Assert
+    let expected = "-WRONGTYPE Operation against a key holding the wrong kind of value\r\n";
+    assert_eq!(actual, expected);
+}
@@ -1,6 +1,5 @@
 use codecrafters_redis::config::Config;
 use codecrafters_redis::config::Replication;
-use codecrafters_redis::config::ReplicationMaster;
 use codecrafters_redis::config::ReplicationSlave;
 
 use crate::client::RedisClient;
@@ -12,10 +11,10 @@ async fn sut_responds_replication_role_as_slave_if_replication_is_set() {
     let master_server = RedisServer::new().await;
     let config = Config {
         replication: Replication {
-            master: ReplicationMaster::default(),
             slave: Some(ReplicationSlave {
                 master_address: format!("localhost:{}", master_server.address.port()),
             }),
+            ..Default::default()
         },
         ..Default::default()
     };
@@ -26,8 +25,14 @@ async fn sut_responds_replication_role_as_slave_if_replication_is_set() {
     let actual = client.info_replication().await;
 
     // Assert
-    let expected = "$10\r\nrole:slave\r\n";
-    assert_eq!(actual, expected);
+    let lines: Vec<&str> = actual.split("\r\n").collect();
+    assert_eq!(lines[1], "# Replication");
+    assert_eq!(lines[2], "role:slave");
+    assert_eq!(lines[3], "master_host:localhost");
+    assert_eq!(
+        lines[4],
+        format!("master_port:{}", master_server.address.port())
+    );
 }
 
 #[tokio::test]
@@ -40,6 +45,91 @@ async fn sut_responds_master_related_attributes_if_replication_is_not_set() {
     let actual = client.info_replication().await;
 
     // Assert
-    let expected = "$89\r\nrole:master\r\nmaster_replid:8371b4fb1155b71f4a04d3e1bc3e18c4a990aeeb\r\nmaster_repl_offset:0\r\n";
-    assert_eq!(actual, expected);
+    let lines: Vec<&str> = actual.split("\r\n").collect();
+    assert_eq!(lines[1], "# Replication");
+    assert_eq!(lines[2], "role:master");
+    assert_eq!(lines[3], "connected_slaves:0");
+    assert_eq!(lines[4], "master_failover_state:no-failover");
+    let id = lines[5].strip_prefix("master_replid:").unwrap();
+    assert_eq!(id.len(), 40);
+    assert_eq!(lines[6], format!("master_replid2:{}", "0".repeat(40)));
+    assert_eq!(lines[7], "master_repl_offset:0");
+    assert_eq!(lines[8], "second_repl_offset:-1");
+}
+
+#[tokio::test]
+async fn sut_reports_default_sections_with_counters_of_the_server() {
+    // Arrange
+    let server = RedisServer::new().await;
+    let client = RedisClient::new(server.address).await;
+    let other = RedisClient::new(server.address).await;
+    client.set("foo", "bar", None).await;
+    client.set("baz", "qux", Some(100000)).await;
+    other.send_raw(&["GET"]).await;
+
+    // Act
+    let actual = client.info(&[]).await;
+
+    // Assert
+    for title in [
+        "# Server",
+        "# Clients",
+        "# Memory",
+        "# Persistence",
+        "# Stats",
+        "# Replication",
+        "# CPU",
+        "# Errorstats",
+        "# Cluster",
+        "# Keyspace",
+    ] {
+        assert!(
+            actual.contains(&format!("{title}\r\n")),
+            "{title}: {actual}"
+        );
+    }
+    assert!(!actual.contains("# Commandstats"), "{actual}");
+    assert!(
+        actual.contains(&format!("tcp_port:{}\r\n", server.address.port())),
+        "{actual}"
+    );
+    assert!(actual.contains("connected_clients:2\r\n"), "{actual}");
+    assert!(
+        actual.contains("total_commands_processed:2\r\n"),
+        "{actual}"
+    );
+    assert!(
+        actual.contains("rdb_changes_since_last_save:2\r\n"),
+        "{actual}"
+    );
+    assert!(actual.contains("errorstat_ERR:count=1\r\n"), "{actual}");
+    assert!(actual.contains("db0:keys=2,expires=1\r\n"), "{actual}");
+    assert!(actual.contains("used_memory:"), "{actual}");
+}
+
+#[tokio::test]
+async fn sut_reports_only_sections_asked_for() {
+    // Arrange
+    let server = RedisServer::new().await;
+    let client = RedisClient::new(server.address).await;
+    client.set("foo", "bar", None).await;
+
+    // Act
+    let actual = client.info(&["commandstats", "KEYSPACE"]).await;
+
+    // Assert
+    let lines: Vec<&str> = actual.split("\r\n").collect();
+    assert_eq!(lines[1], "# Commandstats");
+    assert!(
+        lines[2].starts_with("cmdstat_set:calls=1,usec="),
+        "{actual}"
+    );
+    assert!(
+        lines[2].ends_with(",rejected_calls=0,failed_calls=0"),
+        "{actual}"
+    );
+    assert_eq!(
+        &lines[3..],
+        ["", "# Keyspace", "db0:keys=1,expires=0", "", ""]
+    );
 }
@@ -17,7 +17,7 @@ async fn sut_responds_all_keys_when_client_sends_keys_with_asterisk() {
     }
 
     // Act
-    let actual = client.keys("\"*\"").await;
+    let actual = client.keys("*").await;
 
     // Assert
     assert_keys_response(keys.iter().map(|key| key.as_str()).collect(), &actual);
@@ -37,7 +37,7 @@ async fn sut_responds_the_given_key_when_client_sends_keys_without_asterisk() {
     let first_key = keys.first().unwrap();
 
     // Act
-    let actual = client.keys(format!("\"{first_key}\"").as_str()).await;
+    let actual = client.keys(first_key).await;
 
     // Assert
     assert_keys_response(vec![first_key], &actual);
@@ -47,6 +47,11 @@ async fn sut_responds_the_given_key_when_client_sends_keys_without_asterisk() {
 #[case("h*", vec!["hello", "hi", "hps"])]
 #[case("a*e", vec!["arine"])]
 #[case("*s", vec!["redis", "hps"])]
+#[case("h?", vec!["hi"])]
+#[case("h[ei]*", vec!["hello", "hi"])]
+#[case("h[^e]*", vec!["hi", "hps"])]
+#[case("[a-h]*", vec!["hello", "arine", "hi", "hps"])]
+#[case("*e*", vec!["hello", "arine", "redis"])]
 #[tokio::test]
 async fn sut_responds_the_matched_keys_as_asterisk_to_whatever_when_client_sends_keys(
     #[case] pattern: &str,
@@ -70,7 +75,9 @@ async fn sut_responds_the_matched_keys_as_asterisk_to_whatever_when_client_sends
 
 fn keys() -> Vec<String> {
     let n = (3..=10).fake::<usize>();
-    (0..n).map(|_| value()).collect()
+    (0..n)
+        .map(|i| format!("{}:{i}", Word().fake::<&str>()))
+        .collect()
 }
 
 fn value() -> String {
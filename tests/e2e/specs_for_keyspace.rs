@@ -0,0 +1,112 @@
+use fake::Fake;
+use fake::faker::lorem::en::Word;
+
+use crate::client::RedisClient;
+use crate::server::RedisServer;
+
+#[tokio::test]
+async fn sut_deletes_keys_when_client_sends_del() {
+    // Arrange
+    let server = RedisServer::new().await;
+    let client = RedisClient::new(server.address).await;
+    client.set("foo", Word().fake(), None).await;
+    client.set("bar", Word().fake(), None).await;
+
+    // Act
+    let actual = client.del(&["foo", "bar", "baz"]).await;
+
+    // Assert
+    assert_eq!(actual, ":2\r\n");
+    assert_eq!(client.exists(&["foo", "bar"]).await, ":0\r\n");
+}
+
+#[tokio::test]
+async fn sut_unlinks_keys_when_client_sends_unlink() {
+    // Arrange
+    let server = RedisServer::new().await;
+    let client = RedisClient::new(server.address).await;
+    client.set("foo", Word().fake(), None).await;
+
+    // Act
+    let actual = client.unlink(&["foo"]).await;
+
+    // Assert
+    assert_eq!(actual, ":1\r\n");
+    assert_eq!(client.get("foo").await, "$-1\r\n");
+}
+
+#[rstest::rstest]
+#[case("foo", "+string\r\n")]
+#[case("missing", "+none\r\n")]
+#[tokio::test]
+async fn sut_responds_type_when_client_sends_type(#[case] key: &str, #[case] expected: &str) {
+    // Arrange
+    let server = RedisServer::new().await;
+    let client = RedisClient::new(server.address).await;
+    client.set("foo", Word().fake(), None).await;
+
+    // Act
+    let actual = client.key_type(key).await;
+
+    // Assert
+    assert_eq!(actual, expected);
+}
+
+#[tokio::test]
+async fn sut_renames_key_when_client_sends_rename() {
+    // Arrange
+    let server = RedisServer::new().await;
+    let client = RedisClient::new(server.address).await;
+    client.set("foo", "value", None).await;
+
+    // Act
+    let actual = client.rename("foo", "bar").await;
+
+    // Assert
+    assert_eq!(actual, "+OK\r\n");
+    assert_eq!(client.get("bar").await, "$5\r\nvalue\r\n");
+}
+
+#[tokio::test]
+async fn sut_responds_error_when_client_renames_missing_key() {
+    // Arrange
+    let server = RedisServer::new().await;
+    let client = RedisClient::new(server.address).await;
+
+    // Act
+    let actual = client.rename("foo", "bar").await;
+
+    // Assert
+    assert_eq!(actual, "-ERR no such key\r\n");
+}
+
+#[tokio::test]
+async fn sut_copies_key_when_client_sends_copy() {
+    // Arrange
+    let server = RedisServer::new().await;
+    let client = RedisClient::new(server.address).await;
+    client.set("foo", "value", None).await;
+
+    // Act
+    let actual = client.copy("foo", "bar").await;
+
+    // Assert
+    assert_eq!(actual, ":1\r\n");
+    assert_eq!(client.get("foo").await, "$5\r\nvalue\r\n");
+    assert_eq!(client.get("bar").await, "$5\r\nvalue\r\n");
+}
+
+#[tokio::test]
+async fn sut_responds_number_of_keys_when_client_sends_dbsize() {
+    // Arrange
+    let server = RedisServer::new().await;
+    let client = RedisClient::new(server.address).await;
+    client.set("foo", Word().fake(), None).await;
+    client.set("bar", Word().fake(), None).await;
+
+    // Act
+    let actual = client.dbsize().await;
+
+    // Assert
+    assert_eq!(actual, ":2\r\n");
+}
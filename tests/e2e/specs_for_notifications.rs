@@ -0,0 +1,70 @@
+use std::time::Duration;
+
+use codecrafters_redis::config::Config;
+
+use crate::client::RedisClient;
+use crate::server::RedisServer;
+
+async fn server_notifying(flags: &str) -> RedisServer {
+    let mut config = Config::default();
+    config.server.notify_keyspace_events = flags.parse().unwrap();
+    RedisServer::new_with_config(config).await
+}
+
+#[tokio::test]
+async fn sut_publishes_keyevent_when_key_expires() {
+    // Arrange
+    let server = server_notifying("Ex").await;
+    let subscriber = RedisClient::new(server.address).await;
+    let client = RedisClient::new(server.address).await;
+    subscriber.subscribe(&["__keyevent@0__:expired"]).await;
+    client.set("foo", "bar", Some(50)).await;
+
+    // Act
+    let actual = tokio::time::timeout(Duration::from_secs(2), subscriber.receive()).await;
+
+    // Assert
+    let expected = "*3\r\n$7\r\nmessage\r\n$22\r\n__keyevent@0__:expired\r\n$3\r\nfoo\r\n";
+    assert_eq!(actual.unwrap(), expected);
+}
+
+#[tokio::test]
+async fn sut_publishes_keyspace_events_of_mutating_commands() {
+    // Arrange
+    let server = server_notifying("K$g").await;
+    let subscriber = RedisClient::new(server.address).await;
+    let client = RedisClient::new(server.address).await;
+    subscriber.subscribe(&["__keyspace@0__:foo"]).await;
+
+    // Act
+    client.set("foo", "bar", None).await;
+    let set = subscriber.receive().await;
+    client.del(&["foo"]).await;
+    let del = subscriber.receive().await;
+
+    // Assert
+    assert_eq!(
+        set,
+        "*3\r\n$7\r\nmessage\r\n$18\r\n__keyspace@0__:foo\r\n$3\r\nset\r\n"
+    );
+    assert_eq!(
+        del,
+        "*3\r\n$7\r\nmessage\r\n$18\r\n__keyspace@0__:foo\r\n$3\r\ndel\r\n"
+    );
+}
+
+#[tokio::test]
+async fn sut_responds_configured_flags_when_client_sends_config_get() {
+    // Arrange
+    let server = server_notifying("KEA").await;
+    let client = RedisClient::new(server.address).await;
+
+    // Act
+    let actual = client.config_get("notify-keyspace-events").await;
+
+    // Assert
+    assert_eq!(
+        actual,
+        "*2\r\n$22\r\nnotify-keyspace-events\r\n$3\r\nAKE\r\n"
+    );
+}
@@ -0,0 +1,117 @@
+use crate::client::RedisClient;
+use crate::server::RedisServer;
+
+#[tokio::test]
+async fn sut_pushes_published_message_to_channel_subscribers() {
+    // Arrange
+    let server = RedisServer::new().await;
+    let subscriber = RedisClient::new(server.address).await;
+    let publisher = RedisClient::new(server.address).await;
+    let subscribed = subscriber.subscribe(&["news", "sports"]).await;
+
+    // Act
+    let receivers = publisher.publish("news", "hello").await;
+    let actual = subscriber.receive().await;
+
+    // Assert
+    let expected = "*3\r\n$9\r\nsubscribe\r\n$4\r\nnews\r\n:1\r\n\
+                    *3\r\n$9\r\nsubscribe\r\n$6\r\nsports\r\n:2\r\n";
+    assert_eq!(subscribed, expected);
+    assert_eq!(receivers, ":1\r\n");
+    assert_eq!(
+        actual,
+        "*3\r\n$7\r\nmessage\r\n$4\r\nnews\r\n$5\r\nhello\r\n"
+    );
+}
+
+#[tokio::test]
+async fn sut_pushes_published_message_to_pattern_subscribers() {
+    // Arrange
+    let server = RedisServer::new().await;
+    let subscriber = RedisClient::new(server.address).await;
+    let publisher = RedisClient::new(server.address).await;
+    subscriber.psubscribe(&["news.*"]).await;
+
+    // Act
+    let ignored = publisher.publish("weather", "sunny").await;
+    let receivers = publisher.publish("news.tech", "hello").await;
+    let actual = subscriber.receive().await;
+
+    // Assert
+    assert_eq!(ignored, ":0\r\n");
+    assert_eq!(receivers, ":1\r\n");
+    let expected = "*4\r\n$8\r\npmessage\r\n$6\r\nnews.*\r\n$9\r\nnews.tech\r\n$5\r\nhello\r\n";
+    assert_eq!(actual, expected);
+}
+
+#[tokio::test]
+async fn sut_only_allows_subscription_commands_in_subscriber_mode() {
+    // Arrange
+    let server = RedisServer::new().await;
+    let client = RedisClient::new(server.address).await;
+    client.subscribe(&["news"]).await;
+
+    // Act
+    let get = client.get("foo").await;
+    let ping = client.ping().await;
+    let unsubscribed = client.unsubscribe(&[]).await;
+    let after = client.get("foo").await;
+
+    // Assert
+    assert!(get.starts_with("-ERR Can't execute 'get'"), "{get}");
+    assert_eq!(ping, "*2\r\n$4\r\npong\r\n$0\r\n\r\n");
+    assert_eq!(
+        unsubscribed,
+        "*3\r\n$11\r\nunsubscribe\r\n$4\r\nnews\r\n:0\r\n"
+    );
+    assert_eq!(after, "$-1\r\n");
+}
+
+#[tokio::test]
+async fn sut_reports_subscriptions_when_client_sends_pubsub() {
+    // Arrange
+    let server = RedisServer::new().await;
+    let subscriber = RedisClient::new(server.address).await;
+    let client = RedisClient::new(server.address).await;
+    subscriber.subscribe(&["news"]).await;
+    subscriber.psubscribe(&["n*", "s*"]).await;
+
+    // Act
+    let channels = client.pubsub(&["CHANNELS"]).await;
+    let numsub = client.pubsub(&["NUMSUB", "news", "sports"]).await;
+    let numpat = client.pubsub(&["NUMPAT"]).await;
+
+    // Assert
+    assert_eq!(channels, "*1\r\n$4\r\nnews\r\n");
+    assert_eq!(numsub, "*4\r\n$4\r\nnews\r\n:1\r\n$6\r\nsports\r\n:0\r\n");
+    assert_eq!(numpat, ":2\r\n");
+}
+
+#[tokio::test]
+async fn sut_keeps_shard_channels_apart_from_channels() {
+    // Arrange
+    let server = RedisServer::new().await;
+    let subscriber = RedisClient::new(server.address).await;
+    let publisher = RedisClient::new(server.address).await;
+    let subscribed = subscriber.send_raw(&["SSUBSCRIBE", "orders"]).await;
+
+    // Act
+    let classic = publisher.publish("orders", "ignored").await;
+    let sharded = publisher.send_raw(&["SPUBLISH", "orders", "hello"]).await;
+    let actual = subscriber.receive().await;
+
+    // Assert
+    assert_eq!(
+        subscribed,
+        "*3\r\n$10\r\nssubscribe\r\n$6\r\norders\r\n:1\r\n"
+    );
+    assert_eq!(classic, ":0\r\n");
+    assert_eq!(sharded, ":1\r\n");
+    assert_eq!(
+        actual,
+        "*3\r\n$8\r\nsmessage\r\n$6\r\norders\r\n$5\r\nhello\r\n"
+    );
+    let channels = publisher.pubsub(&["SHARDCHANNELS"]).await;
+    assert_eq!(channels, "*1\r\n$6\r\norders\r\n");
+    assert_eq!(publisher.pubsub(&["CHANNELS"]).await, "*0\r\n");
+}
@@ -0,0 +1,419 @@
+use std::time::Duration;
+
+use codecrafters_redis::config::Config;
+use codecrafters_redis::config::DisklessLoad;
+use codecrafters_redis::config::ReplicationSlave;
+use tokio::io::AsyncReadExt;
+use tokio::io::AsyncWriteExt;
+use tokio::net::TcpListener;
+use tokio::net::TcpStream;
+
+use crate::client::RedisClient;
+use crate::server::RedisServer;
+
+async fn exchange(stream: &mut TcpStream, reply: &[u8]) -> String {
+    let mut buf = [0; 512];
+    let read = stream.read(&mut buf).await.unwrap();
+    stream.write_all(reply).await.unwrap();
+    String::from_utf8_lossy(&buf[..read]).to_string()
+}
+
+#[tokio::test]
+async fn sut_loads_snapshot_of_master_and_applies_propagated_commands() {
+    // Arrange
+    let master = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let mut config = Config::default();
+    config.replication.slave = Some(ReplicationSlave {
+        master_address: master.local_addr().unwrap().to_string(),
+    });
+    let replica = RedisServer::new_with_config(config).await;
+    let (mut link, _) = master.accept().await.unwrap();
+    let rdb = b"REDIS0011\xfe\x00\x00\x03foo\x03bar\xff\x00\x00\x00\x00\x00\x00\x00\x00";
+    let mut fullresync = format!(
+        "+FULLRESYNC 8371b4fb1155b71f4a04d3e1bc3e18c4a990aeeb 0\r\n${}\r\n",
+        rdb.len()
+    )
+    .into_bytes();
+    fullresync.extend_from_slice(rdb);
+
+    // Act
+    let ping = exchange(&mut link, b"+PONG\r\n").await;
+    let port = exchange(&mut link, b"+OK\r\n").await;
+    let capa = exchange(&mut link, b"+OK\r\n").await;
+    let psync = exchange(&mut link, &fullresync).await;
+    let set = "*3\r\n$3\r\nSET\r\n$3\r\nbaz\r\n$3\r\nqux\r\n";
+    let getack = "*3\r\n$8\r\nREPLCONF\r\n$6\r\nGETACK\r\n$1\r\n*\r\n";
+    link.write_all(format!("{set}{getack}").as_bytes())
+        .await
+        .unwrap();
+    let ack = exchange(&mut link, b"").await;
+    let client = RedisClient::new(replica.address).await;
+
+    // Assert
+    assert_eq!(ping, "*1\r\n$4\r\nPING\r\n");
+    assert!(port.contains("listening-port"), "{port}");
+    assert!(capa.contains("psync2"), "{capa}");
+    assert_eq!(psync, "*3\r\n$5\r\nPSYNC\r\n$1\r\n?\r\n$2\r\n-1\r\n");
+    assert_eq!(ack, "*3\r\n$8\r\nREPLCONF\r\n$3\r\nACK\r\n$2\r\n31\r\n");
+    assert_eq!(client.get("foo").await, "$3\r\nbar\r\n");
+    assert_eq!(client.get("baz").await, "$3\r\nqux\r\n");
+}
+
+/// A master starting diskless transfers as soon as a replica asks, rather than after a delay.
+async fn master() -> RedisServer {
+    let mut config = Config::default();
+    config.replication.diskless_sync_delay = 0;
+    RedisServer::new_with_config(config).await
+}
+
+async fn eventually(client: &RedisClient, args: &[&str], expected: &str) -> String {
+    let mut actual = String::new();
+    for _ in 0..50 {
+        actual = client.send_raw(args).await;
+        if actual == expected {
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(50)).await;
+    }
+    actual
+}
+
+#[tokio::test]
+async fn sut_streams_snapshot_and_writes_to_replica() {
+    // Arrange
+    let master = master().await;
+    let writer = RedisClient::new(master.address).await;
+    writer.set("foo", "bar", None).await;
+    let mut config = Config::default();
+    config.replication.slave = Some(ReplicationSlave {
+        master_address: master.address.to_string(),
+    });
+    let replica = RedisServer::new_with_config(config).await;
+    let reader = RedisClient::new(replica.address).await;
+    let synced = eventually(&reader, &["GET", "foo"], "$3\r\nbar\r\n").await;
+
+    // Act
+    writer.set("baz", "qux", None).await;
+    writer.send_raw(&["SELECT", "1"]).await;
+    writer.set("one", "1", None).await;
+
+    // Assert
+    assert_eq!(synced, "$3\r\nbar\r\n");
+    let baz = eventually(&reader, &["GET", "baz"], "$3\r\nqux\r\n").await;
+    assert_eq!(baz, "$3\r\nqux\r\n");
+    reader.send_raw(&["SELECT", "1"]).await;
+    let one = eventually(&reader, &["GET", "one"], "$1\r\n1\r\n").await;
+    assert_eq!(one, "$1\r\n1\r\n");
+    let info = writer.info_replication().await;
+    assert!(info.contains("connected_slaves:1"), "{info}");
+    assert!(info.contains("state=online"), "{info}");
+}
+
+async fn read_until(stream: &mut TcpStream, expected: &str) -> String {
+    let mut received = Vec::new();
+    let mut buf = [0; 512];
+    while !String::from_utf8_lossy(&received).contains(expected) {
+        let read = stream.read(&mut buf).await.unwrap();
+        if read == 0 {
+            break;
+        }
+        received.extend_from_slice(&buf[..read]);
+    }
+    String::from_utf8_lossy(&received).to_string()
+}
+
+#[tokio::test]
+async fn sut_sends_writes_missed_by_a_reconnecting_replica() {
+    // Arrange
+    let master = RedisServer::new().await;
+    let writer = RedisClient::new(master.address).await;
+    let mut link = TcpStream::connect(master.address).await.unwrap();
+    link.write_all(b"*3\r\n$5\r\nPSYNC\r\n$1\r\n?\r\n$2\r\n-1\r\n")
+        .await
+        .unwrap();
+    let fullresync = read_until(&mut link, "REDIS").await;
+    let id = fullresync.split(' ').nth(1).unwrap().to_string();
+    drop(link);
+    writer.set("foo", "bar", None).await;
+
+    // Act
+    let mut link = TcpStream::connect(master.address).await.unwrap();
+    let psync = format!("*3\r\n$5\r\nPSYNC\r\n$40\r\n{id}\r\n$1\r\n1\r\n");
+    link.write_all(psync.as_bytes()).await.unwrap();
+    let actual = read_until(&mut link, "bar\r\n").await;
+
+    // Assert
+    let expected = format!(
+        "+CONTINUE {id}\r\n*2\r\n$6\r\nSELECT\r\n$1\r\n0\r\n*3\r\n$3\r\nSET\r\n$3\r\nfoo\r\n$3\r\nbar\r\n"
+    );
+    assert_eq!(actual, expected);
+}
+
+#[tokio::test]
+async fn sut_goes_on_from_its_offset_once_reconnected_to_master() {
+    // Arrange
+    let master = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let mut config = Config::default();
+    config.replication.slave = Some(ReplicationSlave {
+        master_address: master.local_addr().unwrap().to_string(),
+    });
+    let replica = RedisServer::new_with_config(config).await;
+    let id = "8371b4fb1155b71f4a04d3e1bc3e18c4a990aeeb";
+    let rdb = b"REDIS0011\xff\x00\x00\x00\x00\x00\x00\x00\x00";
+    let mut fullresync = format!("+FULLRESYNC {id} 100\r\n${}\r\n", rdb.len()).into_bytes();
+    fullresync.extend_from_slice(rdb);
+    let (mut link, _) = master.accept().await.unwrap();
+    exchange(&mut link, b"+PONG\r\n").await;
+    exchange(&mut link, b"+OK\r\n").await;
+    exchange(&mut link, b"+OK\r\n").await;
+    exchange(&mut link, &fullresync).await;
+    link.write_all(b"*3\r\n$3\r\nSET\r\n$3\r\nfoo\r\n$3\r\nbar\r\n")
+        .await
+        .unwrap();
+    let client = RedisClient::new(replica.address).await;
+    eventually(&client, &["GET", "foo"], "$3\r\nbar\r\n").await;
+    drop(link);
+
+    // Act
+    let (mut link, _) = master.accept().await.unwrap();
+    exchange(&mut link, b"+PONG\r\n").await;
+    exchange(&mut link, b"+OK\r\n").await;
+    exchange(&mut link, b"+OK\r\n").await;
+    let psync = exchange(&mut link, b"+CONTINUE\r\n").await;
+    link.write_all(b"*3\r\n$3\r\nSET\r\n$3\r\nbaz\r\n$3\r\nqux\r\n")
+        .await
+        .unwrap();
+
+    // Assert
+    let expected = format!("*3\r\n$5\r\nPSYNC\r\n$40\r\n{id}\r\n$3\r\n132\r\n");
+    assert_eq!(psync, expected);
+    let baz = eventually(&client, &["GET", "baz"], "$3\r\nqux\r\n").await;
+    assert_eq!(baz, "$3\r\nqux\r\n");
+    assert_eq!(client.get("foo").await, "$3\r\nbar\r\n");
+}
+
+#[tokio::test]
+async fn sut_waits_for_replicas_to_acknowledge_writes() {
+    // Arrange
+    let master = master().await;
+    let writer = RedisClient::new(master.address).await;
+    let alone = writer.send_raw(&["WAIT", "1", "100"]).await;
+    let mut config = Config::default();
+    config.replication.slave = Some(ReplicationSlave {
+        master_address: master.address.to_string(),
+    });
+    let _replica = RedisServer::new_with_config(config).await;
+    eventually(&writer, &["WAIT", "1", "100"], ":1\r\n").await;
+    writer.set("foo", "bar", None).await;
+
+    // Act
+    let actual = writer.send_raw(&["WAIT", "1", "5000"]).await;
+
+    // Assert
+    assert_eq!(alone, ":0\r\n");
+    assert_eq!(actual, ":1\r\n");
+    let waitaof = writer.send_raw(&["WAITAOF", "1", "0", "0"]).await;
+    assert!(
+        waitaof.starts_with("-ERR WAITAOF cannot be used"),
+        "{waitaof}"
+    );
+}
+
+/// Asks for a snapshot as a diskless replica would, returning what was received up to the
+/// mark ending it.
+async fn request_snapshot(master: &RedisServer) -> String {
+    let mut link = TcpStream::connect(master.address).await.unwrap();
+    let capa = "*3\r\n$8\r\nREPLCONF\r\n$4\r\ncapa\r\n$3\r\neof\r\n";
+    let psync = "*3\r\n$5\r\nPSYNC\r\n$1\r\n?\r\n$2\r\n-1\r\n";
+    link.write_all(format!("{capa}{psync}").as_bytes())
+        .await
+        .unwrap();
+    let mut received = Vec::new();
+    let mut buf = [0; 512];
+    loop {
+        let read = link.read(&mut buf).await.unwrap();
+        assert!(read > 0, "connection closed");
+        received.extend_from_slice(&buf[..read]);
+        let text = String::from_utf8_lossy(&received).to_string();
+        let mark = text
+            .find("$EOF:")
+            .and_then(|start| text.get(start + 5..start + 45));
+        if mark.is_some_and(|mark| text.matches(mark).count() == 2) {
+            return text;
+        }
+    }
+}
+
+#[tokio::test]
+async fn sut_streams_one_snapshot_to_replicas_asking_within_the_delay() {
+    // Arrange
+    let mut config = Config::default();
+    config.replication.diskless_sync_delay = 1;
+    let master = RedisServer::new_with_config(config).await;
+    RedisClient::new(master.address)
+        .await
+        .set("foo", "bar", None)
+        .await;
+
+    // Act
+    let (first, second) = tokio::join!(request_snapshot(&master), request_snapshot(&master));
+
+    // Assert
+    assert!(first.contains("$EOF:"), "{first}");
+    assert!(first.contains("foo"), "{first}");
+    assert_eq!(first, second);
+}
+
+#[tokio::test]
+async fn sut_loads_snapshot_streamed_by_a_diskless_master_into_a_fresh_keyspace() {
+    // Arrange
+    let master = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let mut config = Config::default();
+    config.replication.slave = Some(ReplicationSlave {
+        master_address: master.local_addr().unwrap().to_string(),
+    });
+    config.replication.diskless_load = DisklessLoad::Swapdb;
+    let replica = RedisServer::new_with_config(config).await;
+    let mark = "0123456789abcdef0123456789abcdef01234567";
+    let rdb = b"REDIS0011\xfe\x00\x00\x03foo\x03bar\xff\x00\x00\x00\x00\x00\x00\x00\x00";
+    let mut fullresync =
+        format!("+FULLRESYNC 8371b4fb1155b71f4a04d3e1bc3e18c4a990aeeb 0\r\n$EOF:{mark}\r\n")
+            .into_bytes();
+    fullresync.extend_from_slice(rdb);
+    fullresync.extend_from_slice(mark.as_bytes());
+    let (mut link, _) = master.accept().await.unwrap();
+
+    // Act
+    exchange(&mut link, b"+PONG\r\n").await;
+    exchange(&mut link, b"+OK\r\n").await;
+    exchange(&mut link, b"+OK\r\n").await;
+    exchange(&mut link, &fullresync).await;
+    link.write_all(b"*3\r\n$3\r\nSET\r\n$3\r\nbaz\r\n$3\r\nqux\r\n")
+        .await
+        .unwrap();
+    let client = RedisClient::new(replica.address).await;
+
+    // Assert
+    let baz = eventually(&client, &["GET", "baz"], "$3\r\nqux\r\n").await;
+    assert_eq!(baz, "$3\r\nqux\r\n");
+    assert_eq!(client.get("foo").await, "$3\r\nbar\r\n");
+}
+
+async fn eventually_role(client: &RedisClient, role: &str) -> String {
+    let mut info = String::new();
+    for _ in 0..100 {
+        info = client.info_replication().await;
+        if info.contains(&format!("role:{role}")) {
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(50)).await;
+    }
+    info
+}
+
+#[tokio::test]
+async fn sut_follows_master_given_at_runtime_until_promoted() {
+    // Arrange
+    let master = master().await;
+    let writer = RedisClient::new(master.address).await;
+    writer.set("foo", "bar", None).await;
+    let server = RedisServer::new().await;
+    let client = RedisClient::new(server.address).await;
+    let port = master.address.port().to_string();
+
+    // Act
+    let followed = client.send_raw(&["REPLICAOF", "127.0.0.1", &port]).await;
+    let synced = eventually(&client, &["GET", "foo"], "$3\r\nbar\r\n").await;
+    let promoted = client.send_raw(&["REPLICAOF", "NO", "ONE"]).await;
+    writer.set("baz", "qux", None).await;
+    client.set("one", "1", None).await;
+
+    // Assert
+    assert_eq!(followed, "+OK\r\n");
+    assert_eq!(synced, "$3\r\nbar\r\n");
+    assert_eq!(promoted, "+OK\r\n");
+    let info = client.info_replication().await;
+    assert!(info.contains("role:master"), "{info}");
+    assert_eq!(client.get("one").await, "$1\r\n1\r\n");
+    tokio::time::sleep(Duration::from_millis(100)).await;
+    assert_eq!(client.get("baz").await, "$-1\r\n");
+}
+
+#[tokio::test]
+async fn sut_hands_master_role_over_to_replica() {
+    // Arrange
+    let master = master().await;
+    let writer = RedisClient::new(master.address).await;
+    writer.set("foo", "bar", None).await;
+    let mut config = Config::default();
+    config.replication.slave = Some(ReplicationSlave {
+        master_address: master.address.to_string(),
+    });
+    config.replication.diskless_sync_delay = 0;
+    let replica = RedisServer::new_with_config(config).await;
+    let reader = RedisClient::new(replica.address).await;
+    eventually(&reader, &["GET", "foo"], "$3\r\nbar\r\n").await;
+
+    // Act
+    let actual = writer.send_raw(&["FAILOVER", "TIMEOUT", "5000"]).await;
+
+    // Assert
+    assert_eq!(actual, "+OK\r\n");
+    let promoted = eventually_role(&reader, "master").await;
+    assert!(promoted.contains("role:master"), "{promoted}");
+    let demoted = eventually_role(&writer, "slave").await;
+    assert!(demoted.contains("role:slave"), "{demoted}");
+    reader.set("baz", "qux", None).await;
+    let baz = eventually(&writer, &["GET", "baz"], "$3\r\nqux\r\n").await;
+    assert_eq!(baz, "$3\r\nqux\r\n");
+}
+
+#[tokio::test]
+async fn sut_refuses_writes_of_clients_while_applying_those_of_its_master() {
+    // Arrange
+    let master = master().await;
+    let writer = RedisClient::new(master.address).await;
+    let mut config = Config::default();
+    config.replication.diskless_sync_delay = 0;
+    config.replication.slave = Some(ReplicationSlave {
+        master_address: master.address.to_string(),
+    });
+    let replica = RedisServer::new_with_config(config).await;
+    let client = RedisClient::new(replica.address).await;
+
+    // Act
+    let refused = client.send_raw(&["SET", "foo", "baz"]).await;
+    writer.set("foo", "bar", None).await;
+    let applied = eventually(&client, &["GET", "foo"], "$3\r\nbar\r\n").await;
+
+    // Assert
+    assert_eq!(
+        refused,
+        "-READONLY You can't write against a read only replica.\r\n"
+    );
+    assert_eq!(applied, "$3\r\nbar\r\n");
+}
+
+#[tokio::test]
+async fn sut_refuses_stale_data_while_master_is_unreachable() {
+    // Arrange
+    let unreachable = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let master_address = unreachable.local_addr().unwrap().to_string();
+    drop(unreachable);
+    let mut config = Config::default();
+    config.replication.replica_serve_stale_data = false;
+    config.replication.slave = Some(ReplicationSlave { master_address });
+    let replica = RedisServer::new_with_config(config).await;
+    let client = RedisClient::new(replica.address).await;
+
+    // Act
+    let get = client.send_raw(&["GET", "foo"]).await;
+    let ping = client.send_raw(&["PING"]).await;
+
+    // Assert
+    assert_eq!(
+        get,
+        "-MASTERDOWN Link with MASTER is down and replica-serve-stale-data is set to 'no'.\r\n"
+    );
+    assert_eq!(ping, "+PONG\r\n");
+}
@@ -0,0 +1,52 @@
+use fake::Fake;
+use fake::faker::lorem::en::Word;
+
+use crate::client::RedisClient;
+use crate::server::RedisServer;
+
+#[tokio::test]
+async fn sut_responds_all_matching_keys_when_client_sends_scan() {
+    // Arrange
+    let server = RedisServer::new().await;
+    let client = RedisClient::new(server.address).await;
+    client.set("user:1", Word().fake(), None).await;
+    client.set("session:1", Word().fake(), None).await;
+
+    // Act
+    let actual = client.scan("0", &["MATCH", "user:*"]).await;
+
+    // Assert
+    assert_eq!(actual, "*2\r\n$1\r\n0\r\n*1\r\n$6\r\nuser:1\r\n");
+}
+
+#[tokio::test]
+async fn sut_responds_error_when_client_sends_scan_with_invalid_cursor() {
+    // Arrange
+    let server = RedisServer::new().await;
+    let client = RedisClient::new(server.address).await;
+
+    // Act
+    let actual = client.scan("next", &[]).await;
+
+    // Assert
+    assert!(actual.starts_with("-ERR"));
+}
+
+#[tokio::test]
+async fn sut_responds_members_with_scores_when_client_sends_zscan() {
+    // Arrange
+    let server = RedisServer::new().await;
+    let client = RedisClient::new(server.address).await;
+    client
+        .geoadd("Sicily", &[(13.361389, 38.115556, "Palermo")])
+        .await;
+
+    // Act
+    let actual = client.zscan("Sicily", "0").await;
+
+    // Assert
+    assert_eq!(
+        actual,
+        "*2\r\n$1\r\n0\r\n*2\r\n$7\r\nPalermo\r\n$16\r\n3479099956230698\r\n"
+    );
+}
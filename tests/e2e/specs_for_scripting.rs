@@ -0,0 +1,93 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use codecrafters_redis::config::Config;
+
+use crate::client::RedisClient;
+use crate::server::RedisServer;
+
+const RATE_LIMITER: &str = "
+local current = redis.call('GET', KEYS[1])
+if current and tonumber(current) >= tonumber(ARGV[1]) then
+    return 0
+end
+redis.call('SET', KEYS[1], tostring((tonumber(current) or 0) + 1))
+return 1
+";
+
+#[tokio::test]
+async fn sut_runs_script_calling_commands_when_client_sends_eval() {
+    // Arrange
+    let server = RedisServer::new().await;
+    let client = RedisClient::new(server.address).await;
+
+    // Act
+    let mut actual = vec![];
+    for _ in 0..3 {
+        actual.push(client.eval(RATE_LIMITER, &["limit"], &["2"]).await);
+    }
+
+    // Assert
+    assert_eq!(actual, vec![":1\r\n", ":1\r\n", ":0\r\n"]);
+    assert_eq!(client.get("limit").await, "$1\r\n2\r\n");
+}
+
+#[tokio::test]
+async fn sut_runs_loaded_script_when_client_sends_evalsha() {
+    // Arrange
+    let server = RedisServer::new().await;
+    let client = RedisClient::new(server.address).await;
+    let loaded = client.script_load("return ARGV[1]").await;
+    let sha = loaded.split("\r\n").nth(1).unwrap();
+
+    // Act
+    let actual = client.evalsha(sha, &[], &["foo"]).await;
+
+    // Assert
+    assert_eq!(actual, "$3\r\nfoo\r\n");
+}
+
+#[tokio::test]
+async fn sut_responds_busy_until_client_sends_script_kill() {
+    // Arrange
+    let mut config = Config::default();
+    config.server.busy_reply_threshold = 50;
+    let server = RedisServer::new_with_config(config).await;
+    let runner = Arc::new(RedisClient::new(server.address).await);
+    let client = RedisClient::new(server.address).await;
+    let running = tokio::spawn({
+        let runner = runner.clone();
+        async move { runner.eval("while true do end", &[], &[]).await }
+    });
+    tokio::time::sleep(Duration::from_millis(100)).await;
+    assert!(client.ping().await.starts_with("-BUSY"));
+
+    // Act
+    let actual = client.script_kill().await;
+
+    // Assert
+    assert_eq!(actual, "+OK\r\n");
+    assert!(running.await.unwrap().starts_with("-ERR Script killed"));
+    assert_eq!(client.ping().await, "+PONG\r\n");
+}
+
+#[tokio::test]
+async fn sut_runs_script_queued_in_transaction_when_client_sends_exec() {
+    // Arrange
+    let server = RedisServer::new().await;
+    let client = RedisClient::new(server.address).await;
+    let other = RedisClient::new(server.address).await;
+    client.multi().await;
+    assert_eq!(
+        client.eval(RATE_LIMITER, &["limit"], &["2"]).await,
+        "+QUEUED\r\n"
+    );
+    client.get("limit").await;
+
+    // Act
+    let actual = client.exec().await;
+
+    // Assert
+    assert_eq!(actual, "*2\r\n:1\r\n$1\r\n1\r\n");
+    assert_eq!(other.ping().await, "+PONG\r\n");
+}
@@ -0,0 +1,140 @@
+use std::net::SocketAddr;
+use std::time::Duration;
+
+use codecrafters_redis::config::Config;
+use codecrafters_redis::config::Monitor;
+use codecrafters_redis::config::ReplicationSlave;
+use codecrafters_redis::config::SentinelConfig;
+use tokio::net::TcpListener;
+use tokio::net::TcpStream;
+use tokio::task::JoinHandle;
+use tokio::task::JoinSet;
+
+use crate::client::RedisClient;
+use crate::server::RedisServer;
+
+/// Forwards connections to the server until aborted, which makes the server look down to
+/// sentinels monitoring the proxy while it keeps running.
+async fn proxy(server: SocketAddr) -> (SocketAddr, JoinHandle<()>) {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let address = listener.local_addr().unwrap();
+    let handle = tokio::spawn(async move {
+        let mut connections = JoinSet::new();
+        loop {
+            let (mut inbound, _) = listener.accept().await.unwrap();
+            connections.spawn(async move {
+                let mut outbound = TcpStream::connect(server).await.unwrap();
+                let _ = tokio::io::copy_bidirectional(&mut inbound, &mut outbound).await;
+            });
+        }
+    });
+    (address, handle)
+}
+
+async fn sentinel(master: SocketAddr) -> RedisServer {
+    let config = Config {
+        sentinel: Some(SentinelConfig {
+            masters: vec![Monitor {
+                name: "mymaster".to_string(),
+                address: master.to_string(),
+                quorum: 2,
+            }],
+            down_after: 300,
+            failover_timeout: 2000,
+        }),
+        ..Config::default()
+    };
+    RedisServer::new_with_config(config).await
+}
+
+async fn eventually(client: &RedisClient, args: &[&str], expected: &str) -> String {
+    let mut actual = String::new();
+    for _ in 0..100 {
+        actual = client.send_raw(args).await;
+        if actual.contains(expected) {
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(100)).await;
+    }
+    actual
+}
+
+fn address_reply(address: SocketAddr) -> String {
+    let (ip, port) = (address.ip().to_string(), address.port().to_string());
+    format!(
+        "*2\r\n${}\r\n{ip}\r\n${}\r\n{port}\r\n",
+        ip.len(),
+        port.len()
+    )
+}
+
+#[tokio::test]
+async fn sut_answers_address_of_monitored_master_and_refuses_data_commands() {
+    // Arrange
+    let master = RedisServer::new().await;
+    let sentinel = sentinel(master.address).await;
+    let client = RedisClient::new(sentinel.address).await;
+
+    // Act
+    let address = client
+        .send_raw(&["SENTINEL", "GET-MASTER-ADDR-BY-NAME", "mymaster"])
+        .await;
+    let unknown = client
+        .send_raw(&["SENTINEL", "GET-MASTER-ADDR-BY-NAME", "other"])
+        .await;
+    let get = client.get("foo").await;
+
+    // Assert
+    assert_eq!(address, address_reply(master.address));
+    assert_eq!(unknown, "*-1\r\n");
+    assert_eq!(
+        get,
+        "-ERR unknown command 'GET', with args beginning with: 'foo' \r\n"
+    );
+}
+
+#[tokio::test]
+async fn sut_promotes_replica_once_sentinels_agree_master_is_down() {
+    // Arrange
+    let mut config = Config::default();
+    config.replication.diskless_sync_delay = 0;
+    let master = RedisServer::new_with_config(config).await;
+    let (proxy, link) = proxy(master.address).await;
+    let mut config = Config::default();
+    config.replication.slave = Some(ReplicationSlave {
+        master_address: proxy.to_string(),
+    });
+    let replica = RedisServer::new_with_config(config).await;
+    let sentinels = [
+        sentinel(proxy).await,
+        sentinel(proxy).await,
+        sentinel(proxy).await,
+    ];
+    let client = RedisClient::new(sentinels[0].address).await;
+    let replicas = eventually(&client, &["SENTINEL", "REPLICAS", "mymaster"], "slave").await;
+    let peers = eventually(
+        &client,
+        &["SENTINEL", "MASTER", "mymaster"],
+        "num-other-sentinels\r\n$1\r\n2",
+    )
+    .await;
+
+    // Act
+    link.abort();
+
+    // Assert
+    assert!(
+        replicas.contains(&replica.address.port().to_string()),
+        "{replicas}"
+    );
+    assert!(peers.contains("num-other-sentinels\r\n$1\r\n2"), "{peers}");
+    let expected = address_reply(replica.address);
+    for sentinel in &sentinels {
+        let client = RedisClient::new(sentinel.address).await;
+        let args = ["SENTINEL", "GET-MASTER-ADDR-BY-NAME", "mymaster"];
+        assert_eq!(eventually(&client, &args, &expected).await, expected);
+    }
+    let promoted = RedisClient::new(replica.address).await;
+    let info = promoted.info_replication().await;
+    assert!(info.contains("role:master"), "{info}");
+}
@@ -0,0 +1,67 @@
+use crate::client::RedisClient;
+use crate::server::RedisServer;
+
+#[tokio::test]
+async fn sut_pushes_invalidation_of_read_key_over_resp3() {
+    // Arrange
+    let server = RedisServer::new().await;
+    let tracker = RedisClient::new(server.address).await;
+    let writer = RedisClient::new(server.address).await;
+    tracker.hello("3").await;
+    let enabled = tracker.client(&["TRACKING", "ON"]).await;
+    tracker.get("foo").await;
+
+    // Act
+    writer.set("foo", "bar", None).await;
+    let actual = tracker.receive().await;
+
+    // Assert
+    assert_eq!(enabled, "+OK\r\n");
+    assert_eq!(actual, ">2\r\n$10\r\ninvalidate\r\n*1\r\n$3\r\nfoo\r\n");
+}
+
+#[tokio::test]
+async fn sut_redirects_broadcast_invalidations_to_subscribed_client() {
+    // Arrange
+    let server = RedisServer::new().await;
+    let tracker = RedisClient::new(server.address).await;
+    let listener = RedisClient::new(server.address).await;
+    let writer = RedisClient::new(server.address).await;
+    let id = listener.client(&["ID"]).await;
+    let id = id.trim_start_matches(':').trim_end();
+    listener.subscribe(&["__redis__:invalidate"]).await;
+    tracker
+        .client(&["TRACKING", "ON", "REDIRECT", id, "BCAST", "PREFIX", "user:"])
+        .await;
+
+    // Act
+    writer.set("order:1", "bar", None).await;
+    writer.set("user:1", "bar", None).await;
+    let actual = listener.receive().await;
+
+    // Assert
+    let expected = "*3\r\n$7\r\nmessage\r\n$20\r\n__redis__:invalidate\r\n*1\r\n$6\r\nuser:1\r\n";
+    assert_eq!(actual, expected);
+}
+
+#[tokio::test]
+async fn sut_tracks_only_keys_read_after_caching_yes_in_optin_mode() {
+    // Arrange
+    let server = RedisServer::new().await;
+    let tracker = RedisClient::new(server.address).await;
+    let writer = RedisClient::new(server.address).await;
+    tracker.hello("3").await;
+    tracker.client(&["TRACKING", "ON", "OPTIN"]).await;
+    tracker.get("skipped").await;
+    let caching = tracker.client(&["CACHING", "YES"]).await;
+    tracker.get("cached").await;
+
+    // Act
+    writer.set("skipped", "bar", None).await;
+    writer.set("cached", "bar", None).await;
+    let actual = tracker.receive().await;
+
+    // Assert
+    assert_eq!(caching, "+OK\r\n");
+    assert_eq!(actual, ">2\r\n$10\r\ninvalidate\r\n*1\r\n$6\r\ncached\r\n");
+}
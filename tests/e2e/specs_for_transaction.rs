@@ -0,0 +1,107 @@
+use crate::client::RedisClient;
+use crate::server::RedisServer;
+
+#[tokio::test]
+async fn sut_executes_queued_commands_when_client_sends_exec() {
+    // Arrange
+    let server = RedisServer::new().await;
+    let client = RedisClient::new(server.address).await;
+    assert_eq!(client.multi().await, "+OK\r\n");
+    assert_eq!(client.set("foo", "bar", None).await, "+QUEUED\r\n");
+    assert_eq!(client.get("foo").await, "+QUEUED\r\n");
+
+    // Act
+    let actual = client.exec().await;
+
+    // Assert
+    assert_eq!(actual, "*2\r\n+OK\r\n$3\r\nbar\r\n");
+}
+
+#[tokio::test]
+async fn sut_does_not_execute_queued_commands_when_client_sends_discard() {
+    // Arrange
+    let server = RedisServer::new().await;
+    let client = RedisClient::new(server.address).await;
+    client.multi().await;
+    client.set("foo", "bar", None).await;
+
+    // Act
+    let actual = client.discard().await;
+
+    // Assert
+    assert_eq!(actual, "+OK\r\n");
+    assert_eq!(client.get("foo").await, "$-1\r\n");
+}
+
+#[tokio::test]
+async fn sut_aborts_transaction_when_a_command_fails_to_queue() {
+    // Arrange
+    let server = RedisServer::new().await;
+    let client = RedisClient::new(server.address).await;
+    client.multi().await;
+    client.set("foo", "bar", None).await;
+    assert!(
+        client
+            .send_raw(&["NOSUCHCOMMAND"])
+            .await
+            .starts_with("-ERR")
+    );
+
+    // Act
+    let actual = client.exec().await;
+
+    // Assert
+    assert!(actual.starts_with("-EXECABORT"));
+    assert_eq!(client.get("foo").await, "$-1\r\n");
+}
+
+#[tokio::test]
+async fn sut_responds_null_array_when_watched_key_is_modified_by_another_client() {
+    // Arrange
+    let server = RedisServer::new().await;
+    let client = RedisClient::new(server.address).await;
+    let other = RedisClient::new(server.address).await;
+    client.watch(&["foo"]).await;
+    client.multi().await;
+    client.set("foo", "bar", None).await;
+    other.set("foo", "baz", None).await;
+
+    // Act
+    let actual = client.exec().await;
+
+    // Assert
+    assert_eq!(actual, "*-1\r\n");
+    assert_eq!(client.get("foo").await, "$3\r\nbaz\r\n");
+}
+
+#[tokio::test]
+async fn sut_executes_transaction_when_client_unwatches_modified_key() {
+    // Arrange
+    let server = RedisServer::new().await;
+    let client = RedisClient::new(server.address).await;
+    let other = RedisClient::new(server.address).await;
+    client.watch(&["foo"]).await;
+    other.set("foo", "baz", None).await;
+    assert_eq!(client.unwatch().await, "+OK\r\n");
+    client.multi().await;
+    client.set("foo", "bar", None).await;
+
+    // Act
+    let actual = client.exec().await;
+
+    // Assert
+    assert_eq!(actual, "*1\r\n+OK\r\n");
+}
+
+#[tokio::test]
+async fn sut_responds_error_when_client_sends_exec_without_multi() {
+    // Arrange
+    let server = RedisServer::new().await;
+    let client = RedisClient::new(server.address).await;
+
+    // Act
+    let actual = client.exec().await;
+
+    // Assert
+    assert_eq!(actual, "-ERR EXEC without MULTI\r\n");
+}